@@ -0,0 +1,523 @@
+//! Procedural macros for the Sentio Rust SDK
+//!
+//! These are re-exported from `sentio_sdk` and should be used through it.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Ident, ImplItem, ItemImpl, LitStr, Type, parse_macro_input};
+
+/// Derive `SentioEvent` for a struct with named fields
+///
+/// Every field becomes an attribute of the event and a field of its
+/// `EventLogConfig`, typed through its `EventField` implementation. Structs
+/// deriving `SentioEvent` can be nested and are declared as struct fields.
+///
+/// Struct attributes:
+/// - `#[event(name = "Transfer")]` sets the event name, defaults to the struct name
+///
+/// Field attributes:
+/// - `#[event(name = "from")]` renames the attribute
+/// - `#[event(coin = "ETH")]` declares a numeric field as an amount of that coin
+/// - `#[event(distinct_id)]` uses the field as the distinct entity id instead of an attribute
+/// - `#[event(skip)]` leaves the field out of the event
+#[proc_macro_derive(SentioEvent, attributes(event))]
+pub fn derive_sentio_event(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_sentio_event(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct FieldOptions {
+    name: Option<String>,
+    coin: Option<LitStr>,
+    distinct_id: bool,
+    skip: bool,
+}
+
+fn expand_sentio_event(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut event_name = ident.to_string();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("event"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                event_name = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else {
+                Err(meta.error("unsupported event attribute, expected `name`"))
+            }
+        })?;
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "SentioEvent can only be derived for structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "SentioEvent can only be derived for structs",
+            ));
+        }
+    };
+
+    let mut field_configs = Vec::new();
+    let mut attribute_inserts = Vec::new();
+    let mut distinct_id = None;
+
+    for field in fields {
+        let options = parse_field_options(field)?;
+        if options.skip {
+            continue;
+        }
+        let member = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+
+        if options.distinct_id {
+            if distinct_id.is_some() {
+                return Err(syn::Error::new_spanned(
+                    member,
+                    "only one field can be marked as `distinct_id`",
+                ));
+            }
+            distinct_id = Some(quote! {
+                ::std::option::Option::Some(::std::string::ToString::to_string(&self.#member))
+            });
+            continue;
+        }
+
+        let name = options.name.unwrap_or_else(|| member.to_string());
+        match options.coin {
+            Some(coin) => {
+                let coin = coin_variant(&coin)?;
+                field_configs.push(quote! {
+                    ::sentio_sdk::core::event_schema::coin_field(#name, #coin)
+                });
+                attribute_inserts.push(quote! {
+                    if let ::std::option::Option::Some(value) =
+                        ::sentio_sdk::core::EventField::to_attribute_value(&self.#member)
+                    {
+                        attributes.insert(
+                            #name.to_string(),
+                            ::sentio_sdk::core::event_schema::coin_amount(#coin, value),
+                        );
+                    }
+                });
+            }
+            None => {
+                field_configs.push(quote! {
+                    ::sentio_sdk::core::event_schema::field::<#ty>(#name)
+                });
+                attribute_inserts.push(quote! {
+                    if let ::std::option::Option::Some(value) =
+                        ::sentio_sdk::core::EventField::to_attribute_value(&self.#member)
+                    {
+                        attributes.insert(#name.to_string(), value);
+                    }
+                });
+            }
+        }
+    }
+
+    let distinct_id = distinct_id.map(|value| {
+        quote! {
+            fn distinct_id(&self) -> ::std::option::Option<::std::string::String> {
+                #value
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::sentio_sdk::core::SentioEvent for #ident #ty_generics #where_clause {
+            fn event_name() -> &'static str {
+                #event_name
+            }
+
+            fn event_fields() -> ::std::vec::Vec<::sentio_sdk::processor::event_log_config::Field> {
+                ::std::vec![#(#field_configs),*]
+            }
+
+            fn event_attributes(
+                &self,
+            ) -> ::std::collections::HashMap<::std::string::String, ::sentio_sdk::core::AttributeValue> {
+                let mut attributes = ::std::collections::HashMap::new();
+                #(#attribute_inserts)*
+                attributes
+            }
+
+            #distinct_id
+        }
+
+        impl #impl_generics ::sentio_sdk::core::EventField for #ident #ty_generics #where_clause {
+            fn field_type() -> ::sentio_sdk::processor::event_log_config::field::Type {
+                ::sentio_sdk::core::event_schema::struct_type::<Self>()
+            }
+
+            fn to_attribute_value(&self) -> ::std::option::Option<::sentio_sdk::core::AttributeValue> {
+                ::std::option::Option::Some(::sentio_sdk::core::AttributeValue::Object(
+                    ::sentio_sdk::core::SentioEvent::event_attributes(self),
+                ))
+            }
+        }
+    })
+}
+
+fn parse_field_options(field: &syn::Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("event"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                options.name = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("coin") {
+                options.coin = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("distinct_id") {
+                options.distinct_id = true;
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+            } else {
+                return Err(meta.error(
+                    "unsupported event attribute, expected `name`, `coin`, `distinct_id` or `skip`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+    if options.distinct_id && (options.coin.is_some() || options.name.is_some()) {
+        return Err(syn::Error::new_spanned(
+            field,
+            "`distinct_id` fields are not attributes and cannot have a `name` or `coin`",
+        ));
+    }
+    Ok(options)
+}
+
+/// Path of the `CoinId` variant for a proto coin name, e.g. `"USDC"` -> `CoinId::Usdc`
+///
+/// Unknown coins fail to compile on the missing variant.
+fn coin_variant(coin: &LitStr) -> syn::Result<TokenStream2> {
+    let value = coin.value();
+    let valid = value.starts_with(|c: char| c.is_ascii_alphabetic())
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(syn::Error::new_spanned(
+            coin,
+            "expected a coin name such as \"ETH\"",
+        ));
+    }
+    let variant: String = value
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let lower = part.to_ascii_lowercase();
+            let mut chars = lower.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    let variant = Ident::new(&variant, coin.span());
+    Ok(quote! { ::sentio_sdk::common::CoinId::#variant })
+}
+
+/// Declare an Ethereum processor on an impl block
+///
+/// Methods marked with `#[on_event(Name)]` handle the event of the ABI with
+/// that name. For every handler the macro generates an `EventMarker` filtering
+/// on the event topics, named after the processor and the event (e.g.
+/// `Erc20ProcessorTransfer`), and an `EthEventHandler` that decodes the log
+/// with the ABI before calling the method. An inherent `bind(self, server)`
+/// configures all the handlers and binds the processor.
+///
+/// ```rust,ignore
+/// #[sentio_sdk::eth_processor(address = "0x...", network = "1", abi = "abis/erc20.json")]
+/// impl Erc20Processor {
+///     #[on_event(Transfer)]
+///     async fn on_transfer(&self, event: EthEvent, ctx: EthContext) { .. }
+/// }
+///
+/// Erc20Processor.bind(&server);
+/// ```
+///
+/// Arguments:
+/// - `abi` is the path of the JSON ABI, relative to the crate root, required with handlers
+/// - `address` implements `EthProcessor`, leave it out to implement it by hand
+/// - `network` is the chain id, defaults to `"1"`
+/// - `name` is the processor name, defaults to the type name
+#[proc_macro_attribute]
+pub fn eth_processor(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut options = ProcessorOptions::default();
+    let parser = syn::meta::parser(|meta| options.parse(meta));
+    parse_macro_input!(args with parser);
+    let input = parse_macro_input!(input as ItemImpl);
+    expand_eth_processor(options, input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct ProcessorOptions {
+    address: Option<LitStr>,
+    network: Option<LitStr>,
+    abi: Option<LitStr>,
+    name: Option<LitStr>,
+}
+
+impl ProcessorOptions {
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        let slot = if meta.path.is_ident("address") {
+            &mut self.address
+        } else if meta.path.is_ident("network") {
+            &mut self.network
+        } else if meta.path.is_ident("abi") {
+            &mut self.abi
+        } else if meta.path.is_ident("name") {
+            &mut self.name
+        } else {
+            return Err(meta.error(
+                "unsupported eth_processor argument, expected `address`, `network`, `abi` or `name`",
+            ));
+        };
+        *slot = Some(meta.value()?.parse()?);
+        Ok(())
+    }
+}
+
+struct EventHandler {
+    event: Ident,
+    method: Ident,
+}
+
+fn expand_eth_processor(
+    options: ProcessorOptions,
+    mut input: ItemImpl,
+) -> syn::Result<TokenStream2> {
+    if input.trait_.is_some() {
+        return Err(syn::Error::new_spanned(
+            &input.self_ty,
+            "eth_processor must be used on an inherent impl block",
+        ));
+    }
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "eth_processor does not support generic processors",
+        ));
+    }
+    let processor = match input.self_ty.as_ref() {
+        Type::Path(path) if path.qself.is_none() => {
+            path.path.segments.last().expect("type path").ident.clone()
+        }
+        ty => {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "eth_processor must be used on the impl block of a named type",
+            ));
+        }
+    };
+    let self_ty = &input.self_ty;
+
+    let mut handlers = Vec::new();
+    for item in &mut input.items {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
+        let mut event = None;
+        let mut error = None;
+        method.attrs.retain(|attr| {
+            if !attr.path().is_ident("on_event") {
+                return true;
+            }
+            match attr.parse_args::<Ident>() {
+                Ok(_) if event.is_some() => {
+                    error = Some(syn::Error::new_spanned(
+                        attr,
+                        "a method can only handle one event",
+                    ));
+                }
+                Ok(ident) => event = Some(ident),
+                Err(e) => error = Some(e),
+            }
+            false
+        });
+        if let Some(error) = error {
+            return Err(error);
+        }
+        let Some(event) = event else {
+            continue;
+        };
+        if method.sig.asyncness.is_none() {
+            return Err(syn::Error::new_spanned(
+                method.sig.fn_token,
+                "event handlers must be async",
+            ));
+        }
+        handlers.push(EventHandler {
+            event,
+            method: method.sig.ident.clone(),
+        });
+    }
+
+    let abi = match (&options.abi, handlers.first()) {
+        (Some(abi), _) => Some(abi),
+        (None, Some(handler)) => {
+            return Err(syn::Error::new_spanned(
+                &handler.event,
+                "`abi` is required to handle events",
+            ));
+        }
+        (None, None) => None,
+    };
+
+    let mut generated = Vec::new();
+
+    if let Some(abi) = abi {
+        let events = abi_events(abi)?;
+        for handler in &handlers {
+            let name = handler.event.to_string();
+            if !events.contains(&name) {
+                return Err(syn::Error::new_spanned(
+                    &handler.event,
+                    format!("event `{}` is not declared in {}", name, abi.value()),
+                ));
+            }
+        }
+    }
+
+    if let Some(abi) = abi.filter(|_| !handlers.is_empty()) {
+        generated.push(quote! {
+            impl #self_ty {
+                #[doc(hidden)]
+                fn __sentio_abi() -> &'static ::sentio_sdk::eth::ContractAbi {
+                    static ABI: ::std::sync::OnceLock<::sentio_sdk::eth::ContractAbi> =
+                        ::std::sync::OnceLock::new();
+                    ABI.get_or_init(|| {
+                        ::sentio_sdk::eth::ContractAbi::parse(::std::include_str!(
+                            ::std::concat!(::std::env!("CARGO_MANIFEST_DIR"), "/", #abi)
+                        ))
+                        .expect("invalid contract ABI")
+                    })
+                }
+            }
+        });
+    }
+
+    let mut markers = Vec::new();
+    for EventHandler { event, method } in &handlers {
+        let name = event.to_string();
+        let marker = format_ident!("{}{}", processor, event);
+        markers.push(marker.clone());
+        generated.push(quote! {
+            pub struct #marker;
+
+            impl ::sentio_sdk::eth::EventMarker for #marker {
+                fn filter() -> ::std::vec::Vec<::sentio_sdk::eth::eth_processor::EventFilter> {
+                    ::std::vec![<#self_ty>::__sentio_abi()
+                        .event_filter(#name)
+                        .expect(::std::concat!("event ", #name, " is not in the contract ABI"))]
+                }
+            }
+
+            #[::sentio_sdk::async_trait]
+            impl ::sentio_sdk::eth::EthEventHandler<#marker> for #self_ty {
+                async fn on_event(
+                    &self,
+                    event: ::sentio_sdk::eth::eth_processor::EthEvent,
+                    ctx: ::sentio_sdk::eth::context::EthContext,
+                ) {
+                    let event = <#self_ty>::__sentio_abi().decode_event(#name, event);
+                    self.#method(event, ctx).await
+                }
+            }
+        });
+    }
+
+    if let Some(address) = &options.address {
+        let network = options
+            .network
+            .as_ref()
+            .map_or_else(|| "1".to_string(), LitStr::value);
+        let name = options
+            .name
+            .as_ref()
+            .map_or_else(|| processor.to_string(), LitStr::value);
+        generated.push(quote! {
+            impl ::sentio_sdk::eth::eth_processor::EthProcessor for #self_ty {
+                fn address(&self) -> &str {
+                    #address
+                }
+
+                fn chain_id(&self) -> &str {
+                    #network
+                }
+
+                fn name(&self) -> &str {
+                    #name
+                }
+            }
+        });
+    } else if let Some(arg) = options.network.as_ref().or(options.name.as_ref()) {
+        return Err(syn::Error::new_spanned(
+            arg,
+            "`network` and `name` require `address`",
+        ));
+    }
+
+    generated.push(quote! {
+        impl #self_ty {
+            /// Configure the event handlers and bind the processor to `server`
+            pub fn bind<S: ::sentio_sdk::BindableServer>(self, server: &S) {
+                ::sentio_sdk::eth::eth_processor::ConfigurableEthProcessor::new(self)
+                    #(.configure_event::<#markers>(::std::option::Option::None))*
+                    .bind(server)
+            }
+        }
+    });
+
+    Ok(quote! {
+        #input
+        #(#generated)*
+    })
+}
+
+/// Names of the events declared in the ABI file at `path`, relative to the crate root
+///
+/// Accepts a plain ABI array or a build artifact with an `abi` key.
+fn abi_events(path: &LitStr) -> syn::Result<Vec<String>> {
+    let error = |message: String| syn::Error::new_spanned(path, message);
+    let root = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| error("CARGO_MANIFEST_DIR is not set".to_string()))?;
+    let file = std::path::Path::new(&root).join(path.value());
+    let content = std::fs::read_to_string(&file)
+        .map_err(|e| error(format!("failed to read {}: {}", file.display(), e)))?;
+    let value: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| error(format!("failed to parse {}: {}", file.display(), e)))?;
+    let items = value
+        .get("abi")
+        .unwrap_or(&value)
+        .as_array()
+        .ok_or_else(|| error(format!("{} is not a JSON ABI", file.display())))?;
+    Ok(items
+        .iter()
+        .filter(|item| item["type"] == "event")
+        .filter_map(|item| item["name"].as_str().map(str::to_string))
+        .collect())
+}
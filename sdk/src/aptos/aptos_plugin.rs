@@ -0,0 +1,508 @@
+use crate::aptos::aptos_processor::AptosProcessorImpl;
+use crate::aptos::context::AptosContext;
+use crate::aptos::{
+    matches_aptos_type, normalize_aptos_address, AptosCall, AptosEvent, AptosResource,
+    AptosTransaction,
+};
+use crate::core::plugin::FullPlugin;
+use crate::core::{
+    binding_value, dispatch_handler, AsyncPluginProcessor, BaseProcessor, ContractConfigBuilder,
+    HandlerRegister, MetaData, Plugin, PluginRegister,
+};
+use crate::processor::{data, HandlerType};
+use crate::{
+    AccountConfig, ConfigureHandlersResponse, MoveCallFilter, MoveCallHandlerConfig,
+    MoveEventFilter, MoveEventHandlerConfig, MoveResourceChangeConfig, Timestamp,
+};
+use tracing::debug;
+
+#[derive(Default)]
+pub struct AptosPlugin {
+    handler_register: HandlerRegister<HandlerType>,
+    processors: Vec<Box<AptosProcessorImpl>>,
+}
+
+impl Plugin for AptosPlugin {
+    fn name(&self) -> &str {
+        "aptos-plugin"
+    }
+
+    fn handler_types(&self) -> &'static [HandlerType] {
+        &[
+            HandlerType::AptEvent,
+            HandlerType::AptCall,
+            HandlerType::AptResource,
+        ]
+    }
+
+    fn processor_count(&self) -> usize {
+        self.processors.len()
+    }
+
+    fn chain_ids(&self) -> Vec<String> {
+        self.processors
+            .iter()
+            .map(|p| p.chain_id().to_string())
+            .collect()
+    }
+
+    fn configure(&mut self, config: &mut ConfigureHandlersResponse) {
+        debug!("Configuring AptosPlugin handlers for all chains");
+
+        for (processor_idx, processor) in self.processors.iter().enumerate() {
+            let chain_id = processor.chain_id();
+            let address = &processor.options.address;
+            let start_version = processor.options.start_version.unwrap_or_default();
+            let end_version = processor.options.end_version.unwrap_or_default();
+
+            if !processor.event_handlers.is_empty() || !processor.call_handlers.is_empty() {
+                let mut builder = ContractConfigBuilder::new(chain_id, address.clone(), processor.name())
+                    .start_block(start_version)
+                    .end_block(end_version);
+
+                for (handler_idx, handler) in processor.event_handlers.iter().enumerate() {
+                    let handler_id = self.handler_register.register(
+                        chain_id,
+                        HandlerType::AptEvent,
+                        processor_idx,
+                        handler_idx,
+                    );
+                    builder.config_mut().move_event_configs.push(MoveEventHandlerConfig {
+                        filters: vec![MoveEventFilter {
+                            r#type: handler.event_type.clone(),
+                            account: address.clone(),
+                            event_account: String::new(),
+                        }],
+                        handler_id,
+                        fetch_config: handler.options.fetch_config.clone(),
+                        handler_name: handler.options.name.clone().unwrap_or_default(),
+                    });
+                }
+
+                for (handler_idx, handler) in processor.call_handlers.iter().enumerate() {
+                    let handler_id = self.handler_register.register(
+                        chain_id,
+                        HandlerType::AptCall,
+                        processor_idx,
+                        handler_idx,
+                    );
+                    builder.config_mut().move_call_configs.push(MoveCallHandlerConfig {
+                        filters: vec![MoveCallFilter {
+                            function: format!(
+                                "{}::{}",
+                                normalize_aptos_address(address),
+                                handler.function
+                            ),
+                            ..Default::default()
+                        }],
+                        handler_id,
+                        fetch_config: handler.options.fetch_config.clone(),
+                        handler_name: handler.options.name.clone().unwrap_or_default(),
+                    });
+                }
+
+                config.contract_configs.push(builder.build());
+            }
+
+            // Resource changes are tracked per account rather than per contract
+            if !processor.resource_change_handlers.is_empty() {
+                let mut account_config = AccountConfig {
+                    chain_id: chain_id.to_string(),
+                    address: address.clone(),
+                    start_block: start_version,
+                    end_block: end_version,
+                    ..Default::default()
+                };
+                for (handler_idx, handler) in processor.resource_change_handlers.iter().enumerate() {
+                    let handler_id = self.handler_register.register(
+                        chain_id,
+                        HandlerType::AptResource,
+                        processor_idx,
+                        handler_idx,
+                    );
+                    account_config.move_resource_change_configs.push(MoveResourceChangeConfig {
+                        r#type: handler.resource_type.clone(),
+                        include_deleted: handler.options.include_deleted,
+                        handler_id,
+                        handler_name: handler.options.name.clone().unwrap_or_default(),
+                    });
+                }
+                config.account_configs.push(account_config);
+            }
+        }
+    }
+}
+
+impl AptosPlugin {
+    fn metadata(
+        processor: &AptosProcessorImpl,
+        chain_id: &str,
+        version: u64,
+        timestamp_micros: u64,
+        transaction_hash: &str,
+        event_index: i32,
+    ) -> MetaData {
+        MetaData {
+            address: processor.options.address.clone(),
+            contract_name: processor.name().to_string(),
+            chain_id: chain_id.to_string(),
+            block_number: version,
+            timestamp: micros_to_timestamp(timestamp_micros),
+            transaction_hash: transaction_hash.to_string(),
+            transaction_index: 0,
+            log_index: event_index,
+            base_labels: Default::default(),
+        }
+    }
+
+    async fn process_event(&self, data: &crate::DataBinding) -> anyhow::Result<crate::ProcessResult> {
+        let apt_event = match binding_value(data)? {
+            data::Value::AptEvent(apt_event) => apt_event,
+            _ => return Err(anyhow::anyhow!("Expected Aptos event data but got different type")),
+        };
+        let event: AptosEvent = serde_json::from_str(&apt_event.raw_event)
+            .map_err(|e| anyhow::anyhow!("Failed to parse Aptos event: {}", e))?;
+        let transaction = parse_transaction(&apt_event.raw_transaction)?;
+        let version = transaction.version();
+        let timestamp_micros = transaction.timestamp_micros();
+
+        let mut result = crate::ProcessResult::default();
+        for &handler_id in &data.handler_ids {
+            let (processor, handler_idx) = self
+                .handler_register
+                .find_processor(&self.processors, &data.chain_id, handler_id)?;
+            let handler = processor.event_handlers.get(handler_idx).ok_or_else(|| {
+                anyhow::anyhow!("Event handler index {} not found in processor {}", handler_idx, processor.name())
+            })?;
+
+            let metadata = Self::metadata(
+                processor,
+                &data.chain_id,
+                version,
+                timestamp_micros,
+                &transaction.hash,
+                apt_event.event_index,
+            );
+            let handler_result = dispatch_handler(
+                metadata,
+                |collector| {
+                    AptosContext::new(version, transaction.hash.clone(), timestamp_micros)
+                        .with_state_collector(collector)
+                },
+                |ctx| handler.handler.handle_event(event.clone(), ctx),
+            )
+            .await;
+            result = result.merge(handler_result);
+        }
+
+        Ok(result)
+    }
+
+    async fn process_call(&self, data: &crate::DataBinding) -> anyhow::Result<crate::ProcessResult> {
+        let apt_call = match binding_value(data)? {
+            data::Value::AptCall(apt_call) => apt_call,
+            _ => return Err(anyhow::anyhow!("Expected Aptos call data but got different type")),
+        };
+        let transaction = parse_transaction(&apt_call.raw_transaction)?;
+        let payload = transaction
+            .payload
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Aptos transaction {} has no entry function payload", transaction.hash))?;
+        let version = transaction.version();
+        let timestamp_micros = transaction.timestamp_micros();
+
+        let mut result = crate::ProcessResult::default();
+        for &handler_id in &data.handler_ids {
+            let (processor, handler_idx) = self
+                .handler_register
+                .find_processor(&self.processors, &data.chain_id, handler_id)?;
+            let handler = processor.call_handlers.get(handler_idx).ok_or_else(|| {
+                anyhow::anyhow!("Call handler index {} not found in processor {}", handler_idx, processor.name())
+            })?;
+            if !matches_entry_function(&payload.function, &processor.options.address, &handler.function) {
+                debug!("Entry function {} does not match handler {}", payload.function, handler.function);
+                continue;
+            }
+
+            let metadata = Self::metadata(processor, &data.chain_id, version, timestamp_micros, &transaction.hash, 0);
+            let call = AptosCall {
+                payload: payload.clone(),
+                transaction: transaction.clone(),
+            };
+            let handler_result = dispatch_handler(
+                metadata,
+                |collector| {
+                    AptosContext::new(version, transaction.hash.clone(), timestamp_micros)
+                        .with_state_collector(collector)
+                },
+                |ctx| handler.handler.handle_call(call, ctx),
+            )
+            .await;
+            result = result.merge(handler_result);
+        }
+
+        Ok(result)
+    }
+
+    async fn process_resource(&self, data: &crate::DataBinding) -> anyhow::Result<crate::ProcessResult> {
+        let apt_resource = match binding_value(data)? {
+            data::Value::AptResource(apt_resource) => apt_resource,
+            _ => return Err(anyhow::anyhow!("Expected Aptos resource data but got different type")),
+        };
+        let resources = apt_resource
+            .raw_resources
+            .iter()
+            .map(|raw| serde_json::from_str::<AptosResource>(raw))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("Failed to parse Aptos resource: {}", e))?;
+        let version = apt_resource.version as u64;
+        let timestamp_micros = apt_resource.timestamp_micros as u64;
+
+        let mut result = crate::ProcessResult::default();
+        for &handler_id in &data.handler_ids {
+            let (processor, handler_idx) = self
+                .handler_register
+                .find_processor(&self.processors, &data.chain_id, handler_id)?;
+            let handler = processor.resource_change_handlers.get(handler_idx).ok_or_else(|| {
+                anyhow::anyhow!("Resource change handler index {} not found in processor {}", handler_idx, processor.name())
+            })?;
+
+            let matched: Vec<AptosResource> = resources
+                .iter()
+                .filter(|resource| matches_aptos_type(&resource.resource_type, &handler.resource_type))
+                .cloned()
+                .collect();
+            if matched.is_empty() {
+                continue;
+            }
+
+            let metadata = Self::metadata(processor, &data.chain_id, version, timestamp_micros, "", 0);
+            let handler_result = dispatch_handler(
+                metadata,
+                |collector| AptosContext::new(version, "", timestamp_micros).with_state_collector(collector),
+                |ctx| handler.handler.handle_resource_change(matched, ctx),
+            )
+            .await;
+            result = result.merge(handler_result);
+        }
+
+        Ok(result)
+    }
+}
+
+fn parse_transaction(raw_transaction: &str) -> anyhow::Result<AptosTransaction> {
+    if raw_transaction.is_empty() {
+        return Ok(AptosTransaction::default());
+    }
+    serde_json::from_str(raw_transaction)
+        .map_err(|e| anyhow::anyhow!("Failed to parse Aptos transaction: {}", e))
+}
+
+fn micros_to_timestamp(micros: u64) -> Timestamp {
+    Timestamp::from(prost_types::Timestamp {
+        seconds: (micros / 1_000_000) as i64,
+        nanos: ((micros % 1_000_000) * 1_000) as i32,
+    })
+}
+
+/// Whether an entry function `function` (`0x1::coin::transfer`) is `filter`
+/// (`coin::transfer`) of the module account `address`
+pub(crate) fn matches_entry_function(function: &str, address: &str, filter: &str) -> bool {
+    crate::aptos::split_aptos_type(function).is_some_and(|(function_address, name)| {
+        function_address == normalize_aptos_address(address) && name == filter
+    })
+}
+
+#[tonic::async_trait]
+impl AsyncPluginProcessor for AptosPlugin {
+    async fn process_binding(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        debug!(
+            "AptosPlugin processing binding for chain_id: {}, handler_ids: {:?}",
+            data.chain_id, data.handler_ids
+        );
+
+        let handler_type = HandlerType::try_from(data.handler_type)?;
+        match handler_type {
+            HandlerType::AptEvent => self.process_event(data).await,
+            HandlerType::AptCall => self.process_call(data).await,
+            HandlerType::AptResource => self.process_resource(data).await,
+            _ => Err(anyhow::anyhow!(
+                "Unsupported handler type: {:?}",
+                handler_type
+            )),
+        }
+    }
+}
+
+impl FullPlugin for AptosPlugin {}
+
+impl PluginRegister<AptosProcessorImpl> for AptosPlugin {
+    fn register_processor(&mut self, processor: AptosProcessorImpl) -> &mut AptosProcessorImpl {
+        debug!(
+            "Registering processor: {} (chain_id: {})",
+            processor.name(),
+            processor.chain_id()
+        );
+
+        self.processors.push(Box::new(processor));
+        self.processors.last_mut().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aptos::{
+        AptosCallHandler, AptosCallMarker, AptosEventGuid, AptosEventHandler, AptosEventMarker,
+        AptosProcessor, AptosResourceChangeHandler, AptosResourceMarker,
+    };
+    use crate::core::Context;
+    use crate::testing::TestProcessorServer;
+
+    struct CoinProcessor;
+
+    impl AptosProcessor for CoinProcessor {
+        fn address(&self) -> &str {
+            "0x1"
+        }
+
+        fn name(&self) -> &str {
+            "coin"
+        }
+    }
+
+    struct DepositEvent;
+    struct TransferCall;
+    struct CoinStore;
+
+    impl AptosEventMarker for DepositEvent {
+        fn event_type() -> &'static str {
+            "coin::DepositEvent"
+        }
+    }
+
+    impl AptosCallMarker for TransferCall {
+        fn function() -> &'static str {
+            "coin::transfer"
+        }
+    }
+
+    impl AptosResourceMarker for CoinStore {
+        fn resource_type() -> &'static str {
+            "0x1::coin::CoinStore"
+        }
+    }
+
+    #[crate::async_trait]
+    impl AptosEventHandler<DepositEvent> for CoinProcessor {
+        async fn on_event(&self, event: AptosEvent, mut ctx: AptosContext) {
+            assert_eq!(ctx.transaction_hash(), "0xhash");
+            let amount: f64 = event.data["amount"].as_str().unwrap().parse().unwrap();
+            ctx.base_context().gauge("deposit_amount").record(amount, None).await.ok();
+        }
+    }
+
+    #[crate::async_trait]
+    impl AptosCallHandler<TransferCall> for CoinProcessor {
+        async fn on_call(&self, call: AptosCall, mut ctx: AptosContext) {
+            assert_eq!(call.payload.type_arguments, vec!["0x1::aptos_coin::AptosCoin"]);
+            assert_eq!(ctx.version(), 42);
+            ctx.base_context().counter("transfers").add(1.0, None).await.ok();
+        }
+    }
+
+    #[crate::async_trait]
+    impl AptosResourceChangeHandler<CoinStore> for CoinProcessor {
+        async fn on_resource_change(&self, resources: Vec<AptosResource>, mut ctx: AptosContext) {
+            ctx.base_context()
+                .counter("coin_stores")
+                .add(resources.len() as f64, None)
+                .await
+                .ok();
+        }
+    }
+
+    fn transfer_transaction() -> AptosTransaction {
+        AptosTransaction::new(42, "0xhash")
+            .with_sender("0xa11ce")
+            .with_timestamp_micros(1_700_000_000_000_000)
+            .with_entry_function(
+                "0x1::coin::transfer",
+                vec!["0x1::aptos_coin::AptosCoin".to_string()],
+                vec![serde_json::json!("0xb0b"), serde_json::json!("100")],
+            )
+            .with_event(AptosEvent {
+                guid: AptosEventGuid {
+                    creation_number: "2".to_string(),
+                    account_address: "0xb0b".to_string(),
+                },
+                sequence_number: "0".to_string(),
+                event_type: "0x1::coin::DepositEvent".to_string(),
+                data: serde_json::json!({ "amount": "100" }),
+            })
+    }
+
+    #[tokio::test]
+    async fn test_aptos_plugin_configure_and_dispatch() {
+        let mut server = TestProcessorServer::new();
+        CoinProcessor
+            .configure_event::<DepositEvent>(None)
+            .configure_call::<TransferCall>(None)
+            .configure_resource_change::<CoinStore>(None)
+            .from_version(5)
+            .bind(&server);
+        server.start().await.unwrap();
+
+        let config = server.get_config().await;
+        assert_eq!(config.contract_configs.len(), 1);
+        let contract_config = &config.contract_configs[0];
+        assert_eq!(contract_config.start_block, 5);
+        assert_eq!(contract_config.move_event_configs[0].filters[0].r#type, "coin::DepositEvent");
+        // Entry functions are qualified with the module address
+        assert_eq!(
+            contract_config.move_call_configs[0].filters[0].function,
+            format!("{}::coin::transfer", normalize_aptos_address("0x1"))
+        );
+        assert_eq!(config.account_configs.len(), 1);
+        assert_eq!(config.account_configs[0].move_resource_change_configs[0].r#type, "0x1::coin::CoinStore");
+
+        let facet = server.aptos();
+        let result = facet.test_transaction(transfer_transaction(), None).await;
+        assert_eq!(result.first_counter_value("transfers"), Some(1.0));
+        assert_eq!(result.first_gauge_value("deposit_amount"), Some(100.0));
+        assert_eq!(result.gauges[0].metadata.block_number, Some(42));
+
+        // The same function of another module address is not a transfer
+        let other_transfer = AptosTransaction::new(44, "0xother").with_entry_function(
+            "0x2::coin::transfer",
+            vec![],
+            vec![serde_json::json!("0xb0b"), serde_json::json!("100")],
+        );
+        let result = facet.test_transaction(other_transfer, None).await;
+        assert!(result.first_counter_value("transfers").is_none());
+
+        let resources = vec![
+            AptosResource {
+                resource_type: "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>".to_string(),
+                data: serde_json::json!({ "coin": { "value": "10" } }),
+            },
+            AptosResource {
+                resource_type: "0x1::account::Account".to_string(),
+                data: serde_json::Value::Null,
+            },
+        ];
+        let result = facet.test_resources("0x1", resources, 43, 0, None).await;
+        assert_eq!(result.first_counter_value("coin_stores"), Some(1.0));
+        assert_eq!(result.counters[0].metadata.block_number, Some(43));
+    }
+
+    #[test]
+    fn test_matches_entry_function() {
+        assert!(matches_entry_function("0x1::coin::transfer", "0x0001", "coin::transfer"));
+        assert!(!matches_entry_function("0x1::coin::transfer", "0x2", "coin::transfer"));
+        assert!(!matches_entry_function("0x1::aptos_account::transfer", "0x1", "coin::transfer"));
+    }
+}
@@ -0,0 +1,335 @@
+use crate::core::BaseProcessor;
+use crate::aptos::context::AptosContext;
+use crate::aptos::{
+    AptosCall, AptosCallHandler, AptosCallMarker, AptosEvent, AptosEventHandler, AptosEventMarker,
+    AptosResource, AptosResourceChangeHandler, AptosResourceMarker, AptosPlugin, APTOS_MAINNET,
+};
+use crate::MoveFetchConfig;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct AptosBindOptions {
+    /// Module account address
+    pub address: String,
+    /// Optional, if not set, then use aptos mainnet
+    pub network: Option<String>,
+    /// Optional, override default processor name
+    pub name: Option<String>,
+    pub start_version: Option<u64>,
+    pub end_version: Option<u64>,
+}
+
+impl AptosBindOptions {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            network: None,
+            name: None,
+            start_version: None,
+            end_version: None,
+        }
+    }
+
+    pub fn with_network(mut self, network: impl Into<String>) -> Self {
+        self.network = Some(network.into());
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn from_version(mut self, version: u64) -> Self {
+        self.start_version = Some(version);
+        self
+    }
+
+    pub fn to_version(mut self, version: u64) -> Self {
+        self.end_version = Some(version);
+        self
+    }
+}
+
+/// Options for a single Aptos handler
+#[derive(Clone, Default)]
+pub struct AptosHandlerOptions {
+    pub fetch_config: Option<MoveFetchConfig>,
+    /// Include deleted resources, only used by resource change handlers
+    pub include_deleted: bool,
+    pub name: Option<String>,
+}
+
+impl AptosHandlerOptions {
+    pub fn with_fetch_config(mut self, fetch_config: MoveFetchConfig) -> Self {
+        self.fetch_config = Some(fetch_config);
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn include_deleted(mut self) -> Self {
+        self.include_deleted = true;
+        self
+    }
+}
+
+/// A configurable Aptos processor that can register event, call and resource change handlers
+pub struct ConfigurableAptosProcessor<P: AptosProcessor> {
+    processor: Arc<P>,
+    options: AptosBindOptions,
+    event_handlers: Vec<EventHandler>,
+    call_handlers: Vec<CallHandler>,
+    resource_change_handlers: Vec<ResourceChangeHandler>,
+}
+
+impl<P: AptosProcessor> ConfigurableAptosProcessor<P> {
+    /// Create a new configurable processor
+    pub fn new(processor: P) -> Self {
+        let options = AptosBindOptions::new(processor.address())
+            .with_network(processor.chain_id())
+            .with_name(processor.name());
+        Self {
+            processor: Arc::new(processor),
+            options,
+            event_handlers: Vec::new(),
+            call_handlers: Vec::new(),
+            resource_change_handlers: Vec::new(),
+        }
+    }
+
+    /// Handle Move events of type `T` emitted by the processor's account
+    pub fn configure_event<T: AptosEventMarker>(mut self, options: Option<AptosHandlerOptions>) -> Self
+    where
+        P: AptosEventHandler<T>,
+    {
+        let handler: Arc<dyn TypeErasedAptosEventHandler> =
+            Arc::new((Arc::clone(&self.processor), PhantomData::<T>));
+        self.event_handlers.push(EventHandler {
+            handler,
+            event_type: T::event_type().to_string(),
+            options: options.unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Handle calls to the entry function `T` of the processor's account
+    pub fn configure_call<T: AptosCallMarker>(mut self, options: Option<AptosHandlerOptions>) -> Self
+    where
+        P: AptosCallHandler<T>,
+    {
+        let handler: Arc<dyn TypeErasedAptosCallHandler> =
+            Arc::new((Arc::clone(&self.processor), PhantomData::<T>));
+        self.call_handlers.push(CallHandler {
+            handler,
+            function: T::function().to_string(),
+            options: options.unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Handle changes to resources of type `T` under the processor's account
+    pub fn configure_resource_change<T: AptosResourceMarker>(
+        mut self,
+        options: Option<AptosHandlerOptions>,
+    ) -> Self
+    where
+        P: AptosResourceChangeHandler<T>,
+    {
+        let handler: Arc<dyn TypeErasedAptosResourceChangeHandler> =
+            Arc::new((Arc::clone(&self.processor), PhantomData::<T>));
+        self.resource_change_handlers.push(ResourceChangeHandler {
+            handler,
+            resource_type: T::resource_type().to_string(),
+            options: options.unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Start processing from `version`
+    pub fn from_version(mut self, version: u64) -> Self {
+        self.options = self.options.from_version(version);
+        self
+    }
+
+    /// Stop processing after `version`
+    pub fn to_version(mut self, version: u64) -> Self {
+        self.options = self.options.to_version(version);
+        self
+    }
+
+    /// Bind this configured processor to a server
+    pub fn bind<S: crate::BindableServer>(self, server: &S) {
+        let processor_impl = AptosProcessorImpl {
+            options: self.options,
+            event_handlers: self.event_handlers,
+            call_handlers: self.call_handlers,
+            resource_change_handlers: self.resource_change_handlers,
+            _processor: self.processor,
+        };
+
+        server.register_processor::<AptosProcessorImpl, AptosPlugin>(processor_impl);
+    }
+}
+
+/// Core trait that all Aptos processors must implement
+pub trait AptosProcessor: Send + Sync + 'static {
+    /// Get the module account address this processor handles
+    fn address(&self) -> &str;
+
+    /// Get the network, defaults to Aptos mainnet
+    fn chain_id(&self) -> &str {
+        APTOS_MAINNET
+    }
+
+    /// Get the processor name
+    fn name(&self) -> &str;
+
+    fn configure_event<T: AptosEventMarker>(
+        self,
+        options: Option<AptosHandlerOptions>,
+    ) -> ConfigurableAptosProcessor<Self>
+    where
+        Self: Sized + AptosEventHandler<T>,
+    {
+        ConfigurableAptosProcessor::new(self).configure_event::<T>(options)
+    }
+
+    fn configure_call<T: AptosCallMarker>(
+        self,
+        options: Option<AptosHandlerOptions>,
+    ) -> ConfigurableAptosProcessor<Self>
+    where
+        Self: Sized + AptosCallHandler<T>,
+    {
+        ConfigurableAptosProcessor::new(self).configure_call::<T>(options)
+    }
+
+    fn configure_resource_change<T: AptosResourceMarker>(
+        self,
+        options: Option<AptosHandlerOptions>,
+    ) -> ConfigurableAptosProcessor<Self>
+    where
+        Self: Sized + AptosResourceChangeHandler<T>,
+    {
+        ConfigurableAptosProcessor::new(self).configure_resource_change::<T>(options)
+    }
+}
+
+// Type-erased handlers so processors with different markers can share storage
+pub trait TypeErasedAptosEventHandler: Send + Sync {
+    fn handle_event(
+        &self,
+        event: AptosEvent,
+        ctx: AptosContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+}
+
+pub trait TypeErasedAptosCallHandler: Send + Sync {
+    fn handle_call(
+        &self,
+        call: AptosCall,
+        ctx: AptosContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+}
+
+pub trait TypeErasedAptosResourceChangeHandler: Send + Sync {
+    fn handle_resource_change(
+        &self,
+        resources: Vec<AptosResource>,
+        ctx: AptosContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+}
+
+impl<H, T> TypeErasedAptosEventHandler for (H, PhantomData<T>)
+where
+    H: AptosEventHandler<T>,
+    T: AptosEventMarker,
+{
+    fn handle_event(
+        &self,
+        event: AptosEvent,
+        ctx: AptosContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(self.0.on_event(event, ctx))
+    }
+}
+
+impl<H, T> TypeErasedAptosCallHandler for (H, PhantomData<T>)
+where
+    H: AptosCallHandler<T>,
+    T: AptosCallMarker,
+{
+    fn handle_call(
+        &self,
+        call: AptosCall,
+        ctx: AptosContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(self.0.on_call(call, ctx))
+    }
+}
+
+impl<H, T> TypeErasedAptosResourceChangeHandler for (H, PhantomData<T>)
+where
+    H: AptosResourceChangeHandler<T>,
+    T: AptosResourceMarker,
+{
+    fn handle_resource_change(
+        &self,
+        resources: Vec<AptosResource>,
+        ctx: AptosContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(self.0.on_resource_change(resources, ctx))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct EventHandler {
+    pub(crate) handler: Arc<dyn TypeErasedAptosEventHandler>,
+    pub(crate) event_type: String,
+    pub(crate) options: AptosHandlerOptions,
+}
+
+#[derive(Clone)]
+pub(crate) struct CallHandler {
+    pub(crate) handler: Arc<dyn TypeErasedAptosCallHandler>,
+    pub(crate) function: String,
+    pub(crate) options: AptosHandlerOptions,
+}
+
+#[derive(Clone)]
+pub(crate) struct ResourceChangeHandler {
+    pub(crate) handler: Arc<dyn TypeErasedAptosResourceChangeHandler>,
+    pub(crate) resource_type: String,
+    pub(crate) options: AptosHandlerOptions,
+}
+
+// Internal storage for processor data - used by the plugin system
+#[derive(Clone)]
+pub(crate) struct AptosProcessorImpl {
+    pub(crate) options: AptosBindOptions,
+    pub(crate) event_handlers: Vec<EventHandler>,
+    pub(crate) call_handlers: Vec<CallHandler>,
+    pub(crate) resource_change_handlers: Vec<ResourceChangeHandler>,
+    pub(crate) _processor: Arc<dyn AptosProcessor>,
+}
+
+impl BaseProcessor for AptosProcessorImpl {
+    fn chain_id(&self) -> &str {
+        self.options.network.as_deref().unwrap_or(APTOS_MAINNET)
+    }
+
+    fn name(&self) -> &str {
+        self.options.name.as_deref().unwrap_or("aptos-processor")
+    }
+
+    fn handler_count(&self) -> usize {
+        self.event_handlers.len() + self.call_handlers.len() + self.resource_change_handlers.len()
+    }
+}
@@ -0,0 +1,313 @@
+use crate::aptos::context::AptosContext;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Chain id of Aptos mainnet
+pub const APTOS_MAINNET: &str = "aptos_mainnet";
+/// Chain id of Aptos testnet
+pub const APTOS_TESTNET: &str = "aptos_testnet";
+
+/// Normalize an Aptos address to lowercase, `0x`-prefixed, 64 hex characters
+pub fn normalize_aptos_address(address: &str) -> String {
+    let hex = address.trim().trim_start_matches("0x").to_lowercase();
+    format!("0x{:0>64}", hex)
+}
+
+/// Split a fully qualified Move type (`0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>`)
+/// into the normalized account address and the type without address or type
+/// arguments (`coin::CoinStore`)
+pub fn split_aptos_type(move_type: &str) -> Option<(String, String)> {
+    let base = move_type.split('<').next()?;
+    let (address, rest) = base.split_once("::")?;
+    Some((normalize_aptos_address(address), rest.to_string()))
+}
+
+/// Whether `move_type` matches `filter`. A filter without type arguments matches
+/// every instantiation of the type.
+pub fn matches_aptos_type(move_type: &str, filter: &str) -> bool {
+    let (Some(type_base), Some(filter_base)) = (split_aptos_type(move_type), split_aptos_type(filter)) else {
+        return move_type == filter;
+    };
+    if type_base != filter_base {
+        return false;
+    }
+    match filter.split_once('<') {
+        Some((_, filter_args)) => move_type
+            .split_once('<')
+            .is_some_and(|(_, args)| args.replace(' ', "") == filter_args.replace(' ', "")),
+        None => true,
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AptosEventGuid {
+    pub creation_number: String,
+    pub account_address: String,
+}
+
+/// A Move event as returned by the Aptos REST API
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AptosEvent {
+    pub guid: AptosEventGuid,
+    pub sequence_number: String,
+    /// Fully qualified event type, e.g. `0x1::coin::DepositEvent`
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub data: Value,
+}
+
+impl AptosEvent {
+    /// Decode `data` into a user defined type
+    pub fn parse_data<T: DeserializeOwned>(&self) -> anyhow::Result<T> {
+        serde_json::from_value(self.data.clone())
+            .map_err(|e| anyhow::anyhow!("Failed to decode event {}: {}", self.event_type, e))
+    }
+}
+
+/// Entry function payload of a user transaction
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AptosEntryFunctionPayload {
+    #[serde(rename = "type")]
+    pub payload_type: String,
+    /// Fully qualified function, e.g. `0x1::coin::transfer`
+    pub function: String,
+    pub type_arguments: Vec<String>,
+    pub arguments: Vec<Value>,
+}
+
+impl AptosEntryFunctionPayload {
+    /// Normalized module address and `module::function`, the form used in call filters
+    pub fn split_function(&self) -> Option<(String, String)> {
+        split_aptos_type(&self.function)
+    }
+}
+
+/// A user transaction as returned by the Aptos REST API
+///
+/// Only the commonly used fields are typed; write set changes are kept as JSON.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AptosTransaction {
+    #[serde(rename = "type")]
+    pub transaction_type: String,
+    pub version: String,
+    pub hash: String,
+    pub sender: String,
+    pub sequence_number: String,
+    pub success: bool,
+    pub vm_status: String,
+    /// Microseconds since the unix epoch
+    pub timestamp: String,
+    pub payload: Option<AptosEntryFunctionPayload>,
+    pub events: Vec<AptosEvent>,
+    pub changes: Vec<Value>,
+}
+
+impl AptosTransaction {
+    pub fn new(version: u64, hash: impl Into<String>) -> Self {
+        Self {
+            transaction_type: "user_transaction".to_string(),
+            version: version.to_string(),
+            hash: hash.into(),
+            success: true,
+            ..Default::default()
+        }
+    }
+
+    /// Ledger version of the transaction
+    pub fn version(&self) -> u64 {
+        self.version.parse().unwrap_or_default()
+    }
+
+    /// Transaction timestamp in microseconds
+    pub fn timestamp_micros(&self) -> u64 {
+        self.timestamp.parse().unwrap_or_default()
+    }
+
+    pub fn with_sender(mut self, sender: impl Into<String>) -> Self {
+        self.sender = sender.into();
+        self
+    }
+
+    pub fn with_timestamp_micros(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp.to_string();
+        self
+    }
+
+    pub fn with_entry_function(
+        mut self,
+        function: impl Into<String>,
+        type_arguments: Vec<String>,
+        arguments: Vec<Value>,
+    ) -> Self {
+        self.payload = Some(AptosEntryFunctionPayload {
+            payload_type: "entry_function_payload".to_string(),
+            function: function.into(),
+            type_arguments,
+            arguments,
+        });
+        self
+    }
+
+    pub fn with_event(mut self, event: AptosEvent) -> Self {
+        self.events.push(event);
+        self
+    }
+}
+
+/// A Move resource stored under an account
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AptosResource {
+    /// Fully qualified resource type, e.g. `0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>`
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    pub data: Value,
+}
+
+impl AptosResource {
+    /// Decode `data` into a user defined type
+    pub fn parse_data<T: DeserializeOwned>(&self) -> anyhow::Result<T> {
+        serde_json::from_value(self.data.clone())
+            .map_err(|e| anyhow::anyhow!("Failed to decode resource {}: {}", self.resource_type, e))
+    }
+}
+
+/// An entry function call handled by an [`AptosCallHandler`], with its transaction
+#[derive(Debug, Clone)]
+pub struct AptosCall {
+    pub payload: AptosEntryFunctionPayload,
+    pub transaction: AptosTransaction,
+}
+
+/// Marker for an event type handled by an [`AptosEventHandler`]
+pub trait AptosEventMarker: Send + Sync + 'static {
+    /// Event type without the account address, e.g. `coin::DepositEvent`
+    fn event_type() -> &'static str;
+}
+
+/// Marker for an entry function handled by an [`AptosCallHandler`]
+pub trait AptosCallMarker: Send + Sync + 'static {
+    /// Function without the account address, e.g. `coin::transfer`
+    fn function() -> &'static str;
+}
+
+/// Marker for a resource type handled by an [`AptosResourceChangeHandler`]
+pub trait AptosResourceMarker: Send + Sync + 'static {
+    /// Fully qualified resource type, e.g. `0x1::coin::CoinStore`
+    fn resource_type() -> &'static str;
+}
+
+#[crate::async_trait]
+pub trait AptosEventHandler<T: AptosEventMarker>: Send + Sync + 'static {
+    async fn on_event(&self, event: AptosEvent, ctx: AptosContext);
+}
+
+#[crate::async_trait]
+pub trait AptosCallHandler<T: AptosCallMarker>: Send + Sync + 'static {
+    async fn on_call(&self, call: AptosCall, ctx: AptosContext);
+}
+
+#[crate::async_trait]
+pub trait AptosResourceChangeHandler<T: AptosResourceMarker>: Send + Sync + 'static {
+    async fn on_resource_change(&self, resources: Vec<AptosResource>, ctx: AptosContext);
+}
+
+// Implementations for Arc<H> so a shared processor can be registered for several markers
+#[crate::async_trait]
+impl<H, T> AptosEventHandler<T> for std::sync::Arc<H>
+where
+    H: AptosEventHandler<T>,
+    T: AptosEventMarker,
+{
+    async fn on_event(&self, event: AptosEvent, ctx: AptosContext) {
+        self.as_ref().on_event(event, ctx).await
+    }
+}
+
+#[crate::async_trait]
+impl<H, T> AptosCallHandler<T> for std::sync::Arc<H>
+where
+    H: AptosCallHandler<T>,
+    T: AptosCallMarker,
+{
+    async fn on_call(&self, call: AptosCall, ctx: AptosContext) {
+        self.as_ref().on_call(call, ctx).await
+    }
+}
+
+#[crate::async_trait]
+impl<H, T> AptosResourceChangeHandler<T> for std::sync::Arc<H>
+where
+    H: AptosResourceChangeHandler<T>,
+    T: AptosResourceMarker,
+{
+    async fn on_resource_change(&self, resources: Vec<AptosResource>, ctx: AptosContext) {
+        self.as_ref().on_resource_change(resources, ctx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_transaction() {
+        let raw = r#"{
+            "type": "user_transaction",
+            "version": "123456",
+            "hash": "0xabc",
+            "sender": "0x1234",
+            "sequence_number": "7",
+            "success": true,
+            "vm_status": "Executed successfully",
+            "timestamp": "1700000000000000",
+            "payload": {
+                "type": "entry_function_payload",
+                "function": "0x1::coin::transfer",
+                "type_arguments": ["0x1::aptos_coin::AptosCoin"],
+                "arguments": ["0x5678", "100"]
+            },
+            "events": [{
+                "guid": {"creation_number": "2", "account_address": "0x5678"},
+                "sequence_number": "10",
+                "type": "0x1::coin::DepositEvent",
+                "data": {"amount": "100"}
+            }],
+            "changes": [{"type": "write_resource"}],
+            "signature": {"type": "ed25519_signature"}
+        }"#;
+        let transaction: AptosTransaction = serde_json::from_str(raw).unwrap();
+        assert_eq!(transaction.version(), 123456);
+        assert_eq!(transaction.timestamp_micros(), 1_700_000_000_000_000);
+
+        let payload = transaction.payload.as_ref().unwrap();
+        let (address, function) = payload.split_function().unwrap();
+        assert_eq!(address, normalize_aptos_address("0x1"));
+        assert_eq!(function, "coin::transfer");
+
+        #[derive(Deserialize)]
+        struct Deposit {
+            amount: String,
+        }
+        let deposit: Deposit = transaction.events[0].parse_data().unwrap();
+        assert_eq!(deposit.amount, "100");
+    }
+
+    #[test]
+    fn test_matches_aptos_type() {
+        assert!(matches_aptos_type(
+            "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+            "0x1::coin::CoinStore"
+        ));
+        assert!(matches_aptos_type(
+            "0x0000000000000000000000000000000000000000000000000000000000000001::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+            "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>"
+        ));
+        assert!(!matches_aptos_type("0x1::coin::CoinInfo", "0x1::coin::CoinStore"));
+    }
+}
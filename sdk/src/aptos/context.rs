@@ -0,0 +1,55 @@
+use crate::core::{Context, BaseContext, StateCollector};
+
+/// Context passed to Aptos handlers
+#[derive(Clone)]
+pub struct AptosContext {
+    base_context: BaseContext,
+    state_collector: Option<StateCollector>,
+    version: u64,
+    transaction_hash: String,
+    timestamp_micros: u64,
+}
+
+impl AptosContext {
+    /// Create a new AptosContext for the transaction at ledger `version`
+    pub fn new(version: u64, transaction_hash: impl Into<String>, timestamp_micros: u64) -> Self {
+        Self {
+            base_context: BaseContext::new(),
+            state_collector: None,
+            version,
+            transaction_hash: transaction_hash.into(),
+            timestamp_micros,
+        }
+    }
+
+    /// Enable state collection for this context
+    pub fn with_state_collector(mut self, state_collector: StateCollector) -> Self {
+        self.state_collector = Some(state_collector);
+        self
+    }
+
+    /// Ledger version being processed
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Hash of the transaction being processed, empty for resource changes
+    pub fn transaction_hash(&self) -> &str {
+        &self.transaction_hash
+    }
+
+    /// Transaction timestamp in microseconds since the unix epoch
+    pub fn timestamp_micros(&self) -> u64 {
+        self.timestamp_micros
+    }
+}
+
+impl Context for AptosContext {
+    fn base_context(&mut self) -> &mut BaseContext {
+        &mut self.base_context
+    }
+
+    fn state_collector(&self) -> Option<&StateCollector> {
+        self.state_collector.as_ref()
+    }
+}
@@ -0,0 +1,9 @@
+pub mod aptos_processor;
+pub mod aptos_plugin;
+pub mod context;
+mod aptos_types;
+
+pub use aptos_types::*;
+
+pub use aptos_processor::{AptosProcessor, AptosHandlerOptions, AptosBindOptions};
+pub use aptos_plugin::AptosPlugin;
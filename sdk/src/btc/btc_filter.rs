@@ -0,0 +1,475 @@
+//! Transaction filters evaluated by the Sentio driver
+//!
+//! A [`BtcFieldFilter`] is a set of conditions on named fields that must all hold.
+//! A [`BtcTransactionFilter`] matches a transaction when every configured part
+//! matches: the transaction fields, any input and any output.
+//!
+//! Field names available to each part:
+//! - transaction: `txid`, `hash`, `version`, `size`, `vsize`, `weight`, `locktime`
+//! - input: `txid`, `vout`, `is_coinbase`, `script_asm`, `script_hex`, `sequence`
+//! - output and spent output: `value` (in BTC), `n`, `script_asm`, `script_hex`,
+//!   `script_type`, `script_address`
+
+use crate::btc::{BtcInput, BtcOutput, BtcTransaction};
+use crate::entity::serialization::ToRichValue;
+use crate::entity::traits::FilterValue;
+use crate::processor::btc_transaction_filter as proto;
+use crate::{RichValue, RichValueList, rich_value};
+use serde_json::Value;
+
+/// Condition on a single field
+#[derive(Debug, Clone, PartialEq)]
+pub enum BtcCondition {
+    Eq(FilterValue),
+    Ne(FilterValue),
+    Gt(FilterValue),
+    Gte(FilterValue),
+    Lt(FilterValue),
+    Lte(FilterValue),
+    Prefix(String),
+    Contains(String),
+    NotContains(String),
+    In(Vec<FilterValue>),
+}
+
+impl BtcCondition {
+    fn to_proto(&self) -> proto::Condition {
+        let rich = |value: &FilterValue| value.to_rich_value().ok();
+        match self {
+            BtcCondition::Eq(v) => proto::Condition {
+                eq: rich(v),
+                ..Default::default()
+            },
+            BtcCondition::Ne(v) => proto::Condition {
+                ne: rich(v),
+                ..Default::default()
+            },
+            BtcCondition::Gt(v) => proto::Condition {
+                gt: rich(v),
+                ..Default::default()
+            },
+            BtcCondition::Gte(v) => proto::Condition {
+                gte: rich(v),
+                ..Default::default()
+            },
+            BtcCondition::Lt(v) => proto::Condition {
+                lt: rich(v),
+                ..Default::default()
+            },
+            BtcCondition::Lte(v) => proto::Condition {
+                lte: rich(v),
+                ..Default::default()
+            },
+            BtcCondition::Prefix(s) => proto::Condition {
+                prefix: Some(s.clone()),
+                ..Default::default()
+            },
+            BtcCondition::Contains(s) => proto::Condition {
+                contains: Some(s.clone()),
+                ..Default::default()
+            },
+            BtcCondition::NotContains(s) => proto::Condition {
+                not_contains: Some(s.clone()),
+                ..Default::default()
+            },
+            BtcCondition::In(values) => proto::Condition {
+                r#in: Some(RichValueList {
+                    values: values.iter().filter_map(rich).collect(),
+                }),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn from_proto(condition: &proto::Condition) -> Vec<Self> {
+        let value = |v: &Option<RichValue>| v.as_ref().map(filter_value);
+        let mut conditions = Vec::new();
+        conditions.extend(value(&condition.eq).map(BtcCondition::Eq));
+        conditions.extend(value(&condition.ne).map(BtcCondition::Ne));
+        conditions.extend(value(&condition.gt).map(BtcCondition::Gt));
+        conditions.extend(value(&condition.gte).map(BtcCondition::Gte));
+        conditions.extend(value(&condition.lt).map(BtcCondition::Lt));
+        conditions.extend(value(&condition.lte).map(BtcCondition::Lte));
+        conditions.extend(condition.prefix.clone().map(BtcCondition::Prefix));
+        conditions.extend(condition.contains.clone().map(BtcCondition::Contains));
+        conditions.extend(
+            condition
+                .not_contains
+                .clone()
+                .map(BtcCondition::NotContains),
+        );
+        conditions.extend(
+            condition
+                .r#in
+                .as_ref()
+                .map(|list| BtcCondition::In(list.values.iter().map(filter_value).collect())),
+        );
+        conditions
+    }
+
+    /// Evaluate the condition against a JSON field value, missing fields are `null`
+    fn matches(&self, field: &Value) -> bool {
+        use std::cmp::Ordering;
+        match self {
+            BtcCondition::Eq(v) => compare(field, v) == Some(Ordering::Equal),
+            BtcCondition::Ne(v) => compare(field, v) != Some(Ordering::Equal),
+            BtcCondition::Gt(v) => compare(field, v) == Some(Ordering::Greater),
+            BtcCondition::Gte(v) => {
+                matches!(compare(field, v), Some(Ordering::Greater | Ordering::Equal))
+            }
+            BtcCondition::Lt(v) => compare(field, v) == Some(Ordering::Less),
+            BtcCondition::Lte(v) => {
+                matches!(compare(field, v), Some(Ordering::Less | Ordering::Equal))
+            }
+            BtcCondition::Prefix(s) => field.as_str().is_some_and(|f| f.starts_with(s.as_str())),
+            BtcCondition::Contains(s) => field.as_str().is_some_and(|f| f.contains(s.as_str())),
+            BtcCondition::NotContains(s) => !field.as_str().is_some_and(|f| f.contains(s.as_str())),
+            BtcCondition::In(values) => values
+                .iter()
+                .any(|v| compare(field, v) == Some(std::cmp::Ordering::Equal)),
+        }
+    }
+}
+
+fn compare(field: &Value, value: &FilterValue) -> Option<std::cmp::Ordering> {
+    match (field, value) {
+        (Value::String(f), FilterValue::String(v)) => Some(f.as_str().cmp(v.as_str())),
+        (Value::Number(f), FilterValue::Int(v)) => f.as_f64()?.partial_cmp(&(*v as f64)),
+        (Value::Number(f), FilterValue::Float(v)) => f.as_f64()?.partial_cmp(v),
+        (Value::Bool(f), FilterValue::Boolean(v)) => Some(f.cmp(v)),
+        (Value::Null, FilterValue::Null) => Some(std::cmp::Ordering::Equal),
+        _ => None,
+    }
+}
+
+/// Inverse of `FilterValue::to_rich_value` for the values filters are built from
+fn filter_value(value: &RichValue) -> FilterValue {
+    match &value.value {
+        Some(rich_value::Value::StringValue(s)) => FilterValue::String(s.clone()),
+        Some(rich_value::Value::IntValue(i)) => FilterValue::Int(*i as i64),
+        Some(rich_value::Value::Int64Value(i)) => FilterValue::Int(*i),
+        Some(rich_value::Value::FloatValue(f)) => FilterValue::Float(*f),
+        Some(rich_value::Value::BoolValue(b)) => FilterValue::Boolean(*b),
+        Some(rich_value::Value::ListValue(list)) => {
+            FilterValue::List(list.values.iter().map(filter_value).collect())
+        }
+        _ => FilterValue::Null,
+    }
+}
+
+/// Conditions on named fields, all of which must hold
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BtcFieldFilter {
+    pub conditions: Vec<(String, BtcCondition)>,
+}
+
+impl BtcFieldFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn condition(mut self, field: impl Into<String>, condition: BtcCondition) -> Self {
+        self.conditions.push((field.into(), condition));
+        self
+    }
+
+    pub fn eq(self, field: impl Into<String>, value: impl Into<FilterValue>) -> Self {
+        self.condition(field, BtcCondition::Eq(value.into()))
+    }
+
+    pub fn ne(self, field: impl Into<String>, value: impl Into<FilterValue>) -> Self {
+        self.condition(field, BtcCondition::Ne(value.into()))
+    }
+
+    pub fn gt(self, field: impl Into<String>, value: impl Into<FilterValue>) -> Self {
+        self.condition(field, BtcCondition::Gt(value.into()))
+    }
+
+    pub fn gte(self, field: impl Into<String>, value: impl Into<FilterValue>) -> Self {
+        self.condition(field, BtcCondition::Gte(value.into()))
+    }
+
+    pub fn lt(self, field: impl Into<String>, value: impl Into<FilterValue>) -> Self {
+        self.condition(field, BtcCondition::Lt(value.into()))
+    }
+
+    pub fn lte(self, field: impl Into<String>, value: impl Into<FilterValue>) -> Self {
+        self.condition(field, BtcCondition::Lte(value.into()))
+    }
+
+    pub fn prefix(self, field: impl Into<String>, prefix: impl Into<String>) -> Self {
+        self.condition(field, BtcCondition::Prefix(prefix.into()))
+    }
+
+    pub fn contains(self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        self.condition(field, BtcCondition::Contains(value.into()))
+    }
+
+    pub fn not_contains(self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        self.condition(field, BtcCondition::NotContains(value.into()))
+    }
+
+    pub fn is_in<V: Into<FilterValue>>(
+        self,
+        field: impl Into<String>,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        self.condition(
+            field,
+            BtcCondition::In(values.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// Output or spent output paying to `address`
+    pub fn address(address: impl Into<String>) -> Self {
+        Self::new().eq("script_address", address.into())
+    }
+
+    /// Output or spent output with script type `script_type`
+    pub fn script_type(script_type: impl Into<String>) -> Self {
+        Self::new().eq("script_type", script_type.into())
+    }
+
+    fn to_proto(&self) -> proto::Filter {
+        proto::Filter {
+            conditions: self
+                .conditions
+                .iter()
+                .map(|(field, condition)| (field.clone(), condition.to_proto()))
+                .collect(),
+        }
+    }
+
+    fn from_proto(filter: &proto::Filter) -> Self {
+        let mut fields: Vec<_> = filter.conditions.iter().collect();
+        fields.sort_by_key(|(field, _)| field.as_str());
+        Self {
+            conditions: fields
+                .into_iter()
+                .flat_map(|(field, condition)| {
+                    BtcCondition::from_proto(condition)
+                        .into_iter()
+                        .map(|condition| (field.clone(), condition))
+                })
+                .collect(),
+        }
+    }
+
+    pub(crate) fn matches(&self, fields: &Value) -> bool {
+        self.conditions
+            .iter()
+            .all(|(field, condition)| condition.matches(fields.get(field).unwrap_or(&Value::Null)))
+    }
+}
+
+/// Selects the transactions a handler receives
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BtcTransactionFilter {
+    /// Conditions on the transaction itself, any of which may match
+    pub transaction: Vec<BtcFieldFilter>,
+    /// Conditions on inputs, any input matching any filter matches
+    pub input: Vec<BtcFieldFilter>,
+    /// Conditions on the outputs spent by the inputs
+    pub spent_output: Option<BtcFieldFilter>,
+    /// Conditions on outputs, any output matching any filter matches
+    pub output: Vec<BtcFieldFilter>,
+}
+
+impl BtcTransactionFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_transaction(mut self, filter: BtcFieldFilter) -> Self {
+        self.transaction.push(filter);
+        self
+    }
+
+    pub fn with_input(mut self, filter: BtcFieldFilter) -> Self {
+        self.input.push(filter);
+        self
+    }
+
+    pub fn with_spent_output(mut self, filter: BtcFieldFilter) -> Self {
+        self.spent_output = Some(filter);
+        self
+    }
+
+    pub fn with_output(mut self, filter: BtcFieldFilter) -> Self {
+        self.output.push(filter);
+        self
+    }
+
+    /// Transactions paying to `address`
+    pub fn to_address(address: impl Into<String>) -> Self {
+        Self::new().with_output(BtcFieldFilter::address(address))
+    }
+
+    /// Transactions spending from `address`
+    pub fn from_address(address: impl Into<String>) -> Self {
+        Self::new().with_spent_output(BtcFieldFilter::address(address))
+    }
+
+    pub(crate) fn to_proto(&self) -> crate::BtcTransactionFilter {
+        let filters = |filters: &[BtcFieldFilter]| proto::Filters {
+            filters: filters.iter().map(BtcFieldFilter::to_proto).collect(),
+        };
+        let input_filter =
+            (!self.input.is_empty() || self.spent_output.is_some()).then(|| proto::VinFilter {
+                filters: (!self.input.is_empty()).then(|| filters(&self.input)),
+                pre_v_out: self.spent_output.as_ref().map(BtcFieldFilter::to_proto),
+                pre_transaction: None,
+            });
+        let output_filter = (!self.output.is_empty()).then(|| proto::VOutFilter {
+            filters: Some(filters(&self.output)),
+        });
+        crate::BtcTransactionFilter {
+            input_filter: input_filter.map(Box::new),
+            output_filter,
+            filter: self
+                .transaction
+                .iter()
+                .map(BtcFieldFilter::to_proto)
+                .collect(),
+        }
+    }
+
+    /// Rebuild a filter from its configured form
+    pub(crate) fn from_proto(filter: &crate::BtcTransactionFilter) -> Self {
+        let filters = |filters: &Option<proto::Filters>| {
+            filters
+                .iter()
+                .flat_map(|f| f.filters.iter().map(BtcFieldFilter::from_proto))
+                .collect()
+        };
+        Self {
+            transaction: filter
+                .filter
+                .iter()
+                .map(BtcFieldFilter::from_proto)
+                .collect(),
+            input: filter
+                .input_filter
+                .as_ref()
+                .map(|input| filters(&input.filters))
+                .unwrap_or_default(),
+            spent_output: filter
+                .input_filter
+                .as_ref()
+                .and_then(|input| input.pre_v_out.as_ref())
+                .map(BtcFieldFilter::from_proto),
+            output: filter
+                .output_filter
+                .as_ref()
+                .map(|output| filters(&output.filters))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Evaluate the filter locally, as the driver does
+    pub fn matches(&self, transaction: &BtcTransaction) -> bool {
+        fn any_matches(filters: &[BtcFieldFilter], fields: &Value) -> bool {
+            filters.is_empty() || filters.iter().any(|filter| filter.matches(fields))
+        }
+
+        let transaction_fields = transaction.filter_fields();
+        if !any_matches(&self.transaction, &transaction_fields) {
+            return false;
+        }
+        if (!self.input.is_empty() || self.spent_output.is_some())
+            && !transaction
+                .vin
+                .iter()
+                .any(|input| self.input_matches(input))
+        {
+            return false;
+        }
+        if !self.output.is_empty()
+            && !transaction
+                .vout
+                .iter()
+                .any(|output| any_matches(&self.output, &output.filter_fields()))
+        {
+            return false;
+        }
+        true
+    }
+
+    fn input_matches(&self, input: &BtcInput) -> bool {
+        let input_matches = self.input.is_empty()
+            || self
+                .input
+                .iter()
+                .any(|filter| filter.matches(&input.filter_fields()));
+        let spent_output_matches = match &self.spent_output {
+            None => true,
+            Some(filter) => input
+                .pre_vout
+                .as_ref()
+                .map(BtcOutput::filter_fields)
+                .is_some_and(|fields| filter.matches(&fields)),
+        };
+        input_matches && spent_output_matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::btc::{BtcInput, BtcOutput};
+
+    fn transaction() -> BtcTransaction {
+        BtcTransaction::new("ab01")
+            .with_input(
+                BtcInput::spending("cd02", 0)
+                    .with_pre_vout(BtcOutput::to_address("bc1qsender", 200_000)),
+            )
+            .with_output(BtcOutput::to_address("bc1qreceiver", 150_000))
+            .with_output(BtcOutput::to_address("bc1qsender", 49_000))
+    }
+
+    #[test]
+    fn test_matches() {
+        let tx = transaction();
+        assert!(BtcTransactionFilter::new().matches(&tx));
+        assert!(BtcTransactionFilter::to_address("bc1qreceiver").matches(&tx));
+        assert!(!BtcTransactionFilter::to_address("bc1qother").matches(&tx));
+        assert!(BtcTransactionFilter::from_address("bc1qsender").matches(&tx));
+        assert!(!BtcTransactionFilter::from_address("bc1qreceiver").matches(&tx));
+
+        let large_output =
+            BtcTransactionFilter::new().with_output(BtcFieldFilter::new().gte("value", 0.001));
+        assert!(large_output.matches(&tx));
+        let coinbase =
+            BtcTransactionFilter::new().with_input(BtcFieldFilter::new().eq("is_coinbase", true));
+        assert!(!coinbase.matches(&tx));
+        let by_prefix = BtcTransactionFilter::new()
+            .with_transaction(BtcFieldFilter::new().prefix("txid", "ab"))
+            .with_output(BtcFieldFilter::new().is_in("script_address", ["bc1qx", "bc1qreceiver"]));
+        assert!(by_prefix.matches(&tx));
+    }
+
+    #[test]
+    fn test_to_proto() {
+        let filter = BtcTransactionFilter::to_address("bc1qreceiver")
+            .with_spent_output(BtcFieldFilter::script_type("pubkeyhash"));
+        let proto = filter.to_proto();
+        let output = proto.output_filter.unwrap().filters.unwrap();
+        let condition = &output.filters[0].conditions["script_address"];
+        assert_eq!(
+            condition.eq,
+            FilterValue::from("bc1qreceiver").to_rich_value().ok()
+        );
+        let input = proto.input_filter.unwrap();
+        assert!(input.filters.is_none());
+        assert!(
+            input
+                .pre_v_out
+                .unwrap()
+                .conditions
+                .contains_key("script_type")
+        );
+        assert!(proto.filter.is_empty());
+        assert_eq!(BtcTransactionFilter::from_proto(&filter.to_proto()), filter);
+    }
+}
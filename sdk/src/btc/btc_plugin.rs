@@ -0,0 +1,373 @@
+use crate::btc::btc_processor::BtcProcessorImpl;
+use crate::btc::context::BtcContext;
+use crate::btc::{BtcBlock, BtcTransaction};
+use crate::core::conversions::parse_struct;
+use crate::core::plugin::FullPlugin;
+use crate::core::{
+    AsyncPluginProcessor, BaseProcessor, ContractConfigBuilder, HandlerRegister, MetaData, Plugin,
+    PluginRegister, binding_value, dispatch_handler,
+};
+use crate::processor::{HandlerType, data};
+use crate::{
+    BtcTransactionHandlerConfig, ConfigureHandlersResponse, HandleInterval, OnIntervalConfig,
+    Timestamp,
+};
+use tracing::debug;
+
+#[derive(Default)]
+pub struct BtcPlugin {
+    handler_register: HandlerRegister<HandlerType>,
+    processors: Vec<Box<BtcProcessorImpl>>,
+}
+
+impl Plugin for BtcPlugin {
+    fn name(&self) -> &str {
+        "btc-plugin"
+    }
+
+    fn handler_types(&self) -> &'static [HandlerType] {
+        &[HandlerType::BtcTransaction, HandlerType::BtcBlock]
+    }
+
+    fn processor_count(&self) -> usize {
+        self.processors.len()
+    }
+
+    fn chain_ids(&self) -> Vec<String> {
+        self.processors
+            .iter()
+            .map(|p| p.chain_id().to_string())
+            .collect()
+    }
+
+    fn configure(&mut self, config: &mut ConfigureHandlersResponse) {
+        debug!("Configuring BtcPlugin handlers for all chains");
+
+        for (processor_idx, processor) in self.processors.iter().enumerate() {
+            let chain_id = processor.chain_id();
+
+            let mut builder = ContractConfigBuilder::new(
+                chain_id,
+                processor.options.address.clone(),
+                processor.name(),
+            )
+            .start_block(processor.options.start_block.unwrap_or_default())
+            .end_block(processor.options.end_block.unwrap_or_default());
+
+            for (handler_idx, handler) in processor.transaction_handlers.iter().enumerate() {
+                let handler_id = self.handler_register.register(
+                    chain_id,
+                    HandlerType::BtcTransaction,
+                    processor_idx,
+                    handler_idx,
+                );
+                builder
+                    .config_mut()
+                    .btc_transaction_configs
+                    .push(BtcTransactionHandlerConfig {
+                        filters: vec![handler.filter.to_proto()],
+                        handler_id,
+                        handler_name: handler.options.name.clone().unwrap_or_default(),
+                    });
+            }
+
+            for (handler_idx, handler) in processor.block_handlers.iter().enumerate() {
+                let handler_id = self.handler_register.register(
+                    chain_id,
+                    HandlerType::BtcBlock,
+                    processor_idx,
+                    handler_idx,
+                );
+                builder
+                    .config_mut()
+                    .interval_configs
+                    .push(OnIntervalConfig {
+                        handler_id,
+                        slot: handler.interval,
+                        slot_interval: Some(HandleInterval {
+                            recent_interval: handler.interval,
+                            backfill_interval: handler.backfill_interval,
+                        }),
+                        handler_name: handler.options.name.clone().unwrap_or_default(),
+                        ..Default::default()
+                    });
+            }
+
+            config.contract_configs.push(builder.build());
+        }
+    }
+}
+
+impl BtcPlugin {
+    fn metadata(
+        processor: &BtcProcessorImpl,
+        chain_id: &str,
+        block_height: u64,
+        timestamp: &Timestamp,
+        transaction_id: &str,
+    ) -> MetaData {
+        MetaData {
+            address: processor.options.address.clone(),
+            contract_name: processor.name().to_string(),
+            chain_id: chain_id.to_string(),
+            block_number: block_height,
+            timestamp: timestamp.clone(),
+            transaction_hash: transaction_id.to_string(),
+            transaction_index: 0,
+            log_index: 0,
+            base_labels: Default::default(),
+        }
+    }
+
+    async fn process_transaction(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        let btc_transaction = match binding_value(data)? {
+            data::Value::BtcTransaction(btc_transaction) => btc_transaction,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Expected BTC transaction data but got different type"
+                ));
+            }
+        };
+        let transaction: BtcTransaction =
+            parse_struct(btc_transaction.transaction.as_ref(), "BTC", "transaction")?;
+        let timestamp = block_timestamp(btc_transaction.timestamp, transaction.blocktime);
+        let block_height = transaction.block_height();
+
+        let mut result = crate::ProcessResult::default();
+        for &handler_id in &data.handler_ids {
+            let (processor, handler_idx) = self
+                .handler_register
+                .find_processor(&self.processors, &data.chain_id, handler_id)?;
+            let handler = processor
+                .transaction_handlers
+                .get(handler_idx)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Transaction handler index {} not found in processor {}",
+                        handler_idx,
+                        processor.name()
+                    )
+                })?;
+
+            let metadata = Self::metadata(
+                processor,
+                &data.chain_id,
+                block_height,
+                &timestamp,
+                &transaction.txid,
+            );
+            let handler_result = dispatch_handler(
+                metadata,
+                |collector| {
+                    BtcContext::new(block_height, transaction.txid.clone(), timestamp.clone())
+                        .with_state_collector(collector)
+                },
+                |ctx| handler.handler.on_transaction(transaction.clone(), ctx),
+            )
+            .await;
+            result = result.merge(handler_result);
+        }
+
+        Ok(result)
+    }
+
+    async fn process_block(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        let btc_block = match binding_value(data)? {
+            data::Value::BtcBlock(btc_block) => btc_block,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Expected BTC block data but got different type"
+                ));
+            }
+        };
+        let block: BtcBlock = parse_struct(btc_block.block.as_ref(), "BTC", "block")?;
+        let timestamp = block_timestamp(btc_block.timestamp, Some(block.time));
+
+        let mut result = crate::ProcessResult::default();
+        for &handler_id in &data.handler_ids {
+            let (processor, handler_idx) = self
+                .handler_register
+                .find_processor(&self.processors, &data.chain_id, handler_id)?;
+            let handler = processor.block_handlers.get(handler_idx).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Block handler index {} not found in processor {}",
+                    handler_idx,
+                    processor.name()
+                )
+            })?;
+
+            let metadata = Self::metadata(processor, &data.chain_id, block.height, &timestamp, "");
+            let handler_result = dispatch_handler(
+                metadata,
+                |collector| {
+                    BtcContext::new(block.height, "", timestamp.clone())
+                        .with_state_collector(collector)
+                },
+                |ctx| handler.handler.on_block(block.clone(), ctx),
+            )
+            .await;
+            result = result.merge(handler_result);
+        }
+
+        Ok(result)
+    }
+}
+
+/// Binding timestamp, falling back to the block time reported by the node
+fn block_timestamp(
+    timestamp: Option<prost_types::Timestamp>,
+    block_time: Option<u64>,
+) -> Timestamp {
+    let timestamp = timestamp.unwrap_or_else(|| prost_types::Timestamp {
+        seconds: block_time.unwrap_or_default() as i64,
+        nanos: 0,
+    });
+    Timestamp::from(timestamp)
+}
+
+#[tonic::async_trait]
+impl AsyncPluginProcessor for BtcPlugin {
+    async fn process_binding(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        debug!(
+            "BtcPlugin processing binding for chain_id: {}, handler_ids: {:?}",
+            data.chain_id, data.handler_ids
+        );
+
+        let handler_type = HandlerType::try_from(data.handler_type)?;
+        match handler_type {
+            HandlerType::BtcTransaction => self.process_transaction(data).await,
+            HandlerType::BtcBlock => self.process_block(data).await,
+            _ => Err(anyhow::anyhow!(
+                "Unsupported handler type: {:?}",
+                handler_type
+            )),
+        }
+    }
+}
+
+impl FullPlugin for BtcPlugin {}
+
+impl PluginRegister<BtcProcessorImpl> for BtcPlugin {
+    fn register_processor(&mut self, processor: BtcProcessorImpl) -> &mut BtcProcessorImpl {
+        debug!(
+            "Registering processor: {} (chain_id: {})",
+            processor.name(),
+            processor.chain_id()
+        );
+
+        self.processors.push(Box::new(processor));
+        self.processors.last_mut().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::btc::{
+        BtcBlockHandler, BtcFieldFilter, BtcInput, BtcOutput, BtcProcessor, BtcTransactionFilter,
+        BtcTransactionHandler,
+    };
+    use crate::core::Context;
+    use crate::testing::TestProcessorServer;
+
+    const EXCHANGE: &str = "bc1qexchange";
+
+    struct ExchangeProcessor;
+
+    impl BtcProcessor for ExchangeProcessor {
+        fn name(&self) -> &str {
+            "exchange"
+        }
+    }
+
+    #[crate::async_trait]
+    impl BtcTransactionHandler for ExchangeProcessor {
+        async fn on_transaction(&self, transaction: BtcTransaction, mut ctx: BtcContext) {
+            let deposited: u64 = transaction
+                .vout
+                .iter()
+                .filter(|output| output.address() == Some(EXCHANGE))
+                .map(BtcOutput::value_sats)
+                .sum();
+            ctx.base_context()
+                .counter("deposited_sats")
+                .add(deposited as f64, None)
+                .await
+                .ok();
+        }
+    }
+
+    #[crate::async_trait]
+    impl BtcBlockHandler for ExchangeProcessor {
+        async fn on_block(&self, block: BtcBlock, mut ctx: BtcContext) {
+            ctx.base_context()
+                .gauge("block_time")
+                .record(block.time as f64, None)
+                .await
+                .ok();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_btc_plugin_configure_and_dispatch() {
+        let mut server = TestProcessorServer::new();
+        ExchangeProcessor
+            .configure_transaction(
+                BtcTransactionFilter::to_address(EXCHANGE)
+                    .with_output(BtcFieldFilter::address("bc1qhot")),
+                None,
+            )
+            .configure_block_interval(10, 100, None)
+            .from_block(840_000)
+            .bind(&server);
+        server.start().await.unwrap();
+
+        let config = server.get_config().await;
+        assert_eq!(config.contract_configs.len(), 1);
+        let contract_config = &config.contract_configs[0];
+        assert_eq!(contract_config.start_block, 840_000);
+        assert_eq!(contract_config.btc_transaction_configs.len(), 1);
+        let output_filter = contract_config.btc_transaction_configs[0].filters[0]
+            .output_filter
+            .as_ref()
+            .unwrap();
+        assert_eq!(output_filter.filters.as_ref().unwrap().filters.len(), 2);
+        assert_eq!(contract_config.interval_configs.len(), 1);
+
+        let facet = server.btc();
+        let deposit = BtcTransaction::new("ab01")
+            .with_block(840_001, 1_713_571_767)
+            .with_input(
+                BtcInput::spending("cd02", 0)
+                    .with_pre_vout(BtcOutput::to_address("bc1quser", 60_000)),
+            )
+            .with_output(BtcOutput::to_address(EXCHANGE, 50_000))
+            .with_output(BtcOutput::to_address("bc1quser", 9_000));
+        let unrelated = BtcTransaction::new("ef03")
+            .with_block(840_001, 1_713_571_767)
+            .with_output(BtcOutput::to_address("bc1qother", 1_000));
+        let result = facet
+            .test_transactions(vec![deposit, unrelated], None)
+            .await;
+        assert_eq!(result.counters.len(), 1);
+        assert_eq!(result.first_counter_value("deposited_sats"), Some(50_000.0));
+        assert_eq!(result.counters[0].metadata.block_number, Some(840_001));
+
+        let result = facet
+            .test_block(BtcBlock::new("0000beef", 840_010, 1_713_575_000), None)
+            .await;
+        assert_eq!(
+            result.first_gauge_value("block_time"),
+            Some(1_713_575_000.0)
+        );
+    }
+}
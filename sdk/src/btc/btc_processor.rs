@@ -0,0 +1,229 @@
+use crate::btc::{
+    BTC_MAINNET, BtcBlockHandler, BtcPlugin, BtcTransactionFilter, BtcTransactionHandler,
+};
+use crate::core::BaseProcessor;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct BtcBindOptions {
+    /// Optional label for the processor, Bitcoin has no contracts so this defaults to `*`
+    pub address: String,
+    /// Optional, if not set, then use bitcoin mainnet
+    pub network: Option<String>,
+    /// Optional, override default processor name
+    pub name: Option<String>,
+    pub start_block: Option<u64>,
+    pub end_block: Option<u64>,
+}
+
+impl BtcBindOptions {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            network: None,
+            name: None,
+            start_block: None,
+            end_block: None,
+        }
+    }
+
+    pub fn with_network(mut self, network: impl Into<String>) -> Self {
+        self.network = Some(network.into());
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn from_block(mut self, block: u64) -> Self {
+        self.start_block = Some(block);
+        self
+    }
+
+    pub fn to_block(mut self, block: u64) -> Self {
+        self.end_block = Some(block);
+        self
+    }
+}
+
+/// Options for a single Bitcoin handler
+#[derive(Clone, Default)]
+pub struct BtcHandlerOptions {
+    pub name: Option<String>,
+}
+
+impl BtcHandlerOptions {
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+}
+
+/// A configurable Bitcoin processor that can register transaction and block handlers
+pub struct ConfigurableBtcProcessor<P: BtcProcessor> {
+    processor: Arc<P>,
+    options: BtcBindOptions,
+    transaction_handlers: Vec<TransactionHandler>,
+    block_handlers: Vec<BlockHandler>,
+}
+
+impl<P: BtcProcessor> ConfigurableBtcProcessor<P> {
+    /// Create a new configurable processor
+    pub fn new(processor: P) -> Self {
+        let options = BtcBindOptions::new(processor.address())
+            .with_network(processor.chain_id())
+            .with_name(processor.name());
+        Self {
+            processor: Arc::new(processor),
+            options,
+            transaction_handlers: Vec::new(),
+            block_handlers: Vec::new(),
+        }
+    }
+
+    /// Handle transactions matching `filter`
+    pub fn configure_transaction(
+        mut self,
+        filter: BtcTransactionFilter,
+        options: Option<BtcHandlerOptions>,
+    ) -> Self
+    where
+        P: BtcTransactionHandler,
+    {
+        let handler: Arc<dyn BtcTransactionHandler> = self.processor.clone();
+        self.transaction_handlers.push(TransactionHandler {
+            handler,
+            filter,
+            options: options.unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Handle every `interval` blocks, and every `backfill_interval` blocks while
+    /// catching up on history
+    pub fn configure_block_interval(
+        mut self,
+        interval: i32,
+        backfill_interval: i32,
+        options: Option<BtcHandlerOptions>,
+    ) -> Self
+    where
+        P: BtcBlockHandler,
+    {
+        let handler: Arc<dyn BtcBlockHandler> = self.processor.clone();
+        self.block_handlers.push(BlockHandler {
+            handler,
+            interval,
+            backfill_interval,
+            options: options.unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Start processing from `block`
+    pub fn from_block(mut self, block: u64) -> Self {
+        self.options = self.options.from_block(block);
+        self
+    }
+
+    /// Stop processing after `block`
+    pub fn to_block(mut self, block: u64) -> Self {
+        self.options = self.options.to_block(block);
+        self
+    }
+
+    /// Bind this configured processor to a server
+    pub fn bind<S: crate::BindableServer>(self, server: &S) {
+        let processor_impl = BtcProcessorImpl {
+            options: self.options,
+            transaction_handlers: self.transaction_handlers,
+            block_handlers: self.block_handlers,
+            _processor: self.processor,
+        };
+
+        server.register_processor::<BtcProcessorImpl, BtcPlugin>(processor_impl);
+    }
+}
+
+/// Core trait that all Bitcoin processors must implement
+pub trait BtcProcessor: Send + Sync + 'static {
+    /// Get the label reported as the processor address, defaults to `*`
+    fn address(&self) -> &str {
+        "*"
+    }
+
+    /// Get the network, defaults to Bitcoin mainnet
+    fn chain_id(&self) -> &str {
+        BTC_MAINNET
+    }
+
+    /// Get the processor name
+    fn name(&self) -> &str;
+
+    fn configure_transaction(
+        self,
+        filter: BtcTransactionFilter,
+        options: Option<BtcHandlerOptions>,
+    ) -> ConfigurableBtcProcessor<Self>
+    where
+        Self: Sized + BtcTransactionHandler,
+    {
+        ConfigurableBtcProcessor::new(self).configure_transaction(filter, options)
+    }
+
+    fn configure_block_interval(
+        self,
+        interval: i32,
+        backfill_interval: i32,
+        options: Option<BtcHandlerOptions>,
+    ) -> ConfigurableBtcProcessor<Self>
+    where
+        Self: Sized + BtcBlockHandler,
+    {
+        ConfigurableBtcProcessor::new(self).configure_block_interval(
+            interval,
+            backfill_interval,
+            options,
+        )
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct TransactionHandler {
+    pub(crate) handler: Arc<dyn BtcTransactionHandler>,
+    pub(crate) filter: BtcTransactionFilter,
+    pub(crate) options: BtcHandlerOptions,
+}
+
+#[derive(Clone)]
+pub(crate) struct BlockHandler {
+    pub(crate) handler: Arc<dyn BtcBlockHandler>,
+    pub(crate) interval: i32,
+    pub(crate) backfill_interval: i32,
+    pub(crate) options: BtcHandlerOptions,
+}
+
+// Internal storage for processor data - used by the plugin system
+#[derive(Clone)]
+pub(crate) struct BtcProcessorImpl {
+    pub(crate) options: BtcBindOptions,
+    pub(crate) transaction_handlers: Vec<TransactionHandler>,
+    pub(crate) block_handlers: Vec<BlockHandler>,
+    pub(crate) _processor: Arc<dyn BtcProcessor>,
+}
+
+impl BaseProcessor for BtcProcessorImpl {
+    fn chain_id(&self) -> &str {
+        self.options.network.as_deref().unwrap_or(BTC_MAINNET)
+    }
+
+    fn name(&self) -> &str {
+        self.options.name.as_deref().unwrap_or("btc-processor")
+    }
+
+    fn handler_count(&self) -> usize {
+        self.transaction_handlers.len() + self.block_handlers.len()
+    }
+}
@@ -0,0 +1,341 @@
+use crate::btc::context::BtcContext;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Chain id of Bitcoin mainnet
+pub const BTC_MAINNET: &str = "btc_mainnet";
+/// Chain id of Bitcoin testnet
+pub const BTC_TESTNET: &str = "btc_testnet";
+
+/// Satoshis per bitcoin
+pub const SATS_PER_BTC: u64 = 100_000_000;
+
+/// Convert a BTC amount as reported by the node into satoshis
+pub fn btc_to_sats(value: f64) -> u64 {
+    (value * SATS_PER_BTC as f64).round() as u64
+}
+
+/// Convert satoshis into a BTC amount
+pub fn sats_to_btc(sats: u64) -> f64 {
+    sats as f64 / SATS_PER_BTC as f64
+}
+
+/// Script with its disassembly and hex encoding
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BtcScript {
+    pub asm: String,
+    pub hex: String,
+}
+
+/// Locking script of an output
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BtcScriptPubKey {
+    pub asm: String,
+    pub hex: String,
+    /// Script type, e.g. `witness_v0_keyhash`, `pubkeyhash` or `nulldata`
+    #[serde(rename = "type")]
+    pub script_type: String,
+    /// Address the output pays to, if the script has one
+    pub address: Option<String>,
+}
+
+/// Transaction output
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BtcOutput {
+    /// Amount in BTC, see [`value_sats`](Self::value_sats)
+    pub value: f64,
+    pub n: u32,
+    #[serde(rename = "scriptPubKey")]
+    pub script_pub_key: BtcScriptPubKey,
+}
+
+impl BtcOutput {
+    /// Output paying `sats` to `address`
+    pub fn to_address(address: impl Into<String>, sats: u64) -> Self {
+        Self {
+            value: sats_to_btc(sats),
+            script_pub_key: BtcScriptPubKey {
+                script_type: "witness_v0_keyhash".to_string(),
+                address: Some(address.into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    pub fn with_script_type(mut self, script_type: impl Into<String>) -> Self {
+        self.script_pub_key.script_type = script_type.into();
+        self
+    }
+
+    /// Amount in satoshis
+    pub fn value_sats(&self) -> u64 {
+        btc_to_sats(self.value)
+    }
+
+    pub fn address(&self) -> Option<&str> {
+        self.script_pub_key.address.as_deref()
+    }
+
+    /// Flat view used by output filters
+    pub(crate) fn filter_fields(&self) -> Value {
+        serde_json::json!({
+            "value": self.value,
+            "n": self.n,
+            "script_asm": self.script_pub_key.asm,
+            "script_hex": self.script_pub_key.hex,
+            "script_type": self.script_pub_key.script_type,
+            "script_address": self.script_pub_key.address,
+        })
+    }
+}
+
+/// Transaction input, with the output it spends when the driver provides it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BtcInput {
+    /// Spent transaction, unset for coinbase inputs
+    pub txid: Option<String>,
+    /// Index of the spent output
+    pub vout: Option<u32>,
+    /// Coinbase data, only set for coinbase inputs
+    pub coinbase: Option<String>,
+    #[serde(rename = "scriptSig")]
+    pub script_sig: Option<BtcScript>,
+    pub txinwitness: Vec<String>,
+    pub sequence: u64,
+    /// Output spent by this input
+    pub pre_vout: Option<BtcOutput>,
+}
+
+impl BtcInput {
+    /// Input spending output `vout` of `txid`
+    pub fn spending(txid: impl Into<String>, vout: u32) -> Self {
+        Self {
+            txid: Some(txid.into()),
+            vout: Some(vout),
+            sequence: 0xffff_ffff,
+            ..Default::default()
+        }
+    }
+
+    /// Coinbase input of a block reward transaction
+    pub fn coinbase(data: impl Into<String>) -> Self {
+        Self {
+            coinbase: Some(data.into()),
+            sequence: 0xffff_ffff,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_pre_vout(mut self, pre_vout: BtcOutput) -> Self {
+        self.pre_vout = Some(pre_vout);
+        self
+    }
+
+    pub fn is_coinbase(&self) -> bool {
+        self.coinbase.is_some()
+    }
+
+    /// Address of the spent output, if known
+    pub fn address(&self) -> Option<&str> {
+        self.pre_vout.as_ref().and_then(BtcOutput::address)
+    }
+
+    /// Flat view used by input filters
+    pub(crate) fn filter_fields(&self) -> Value {
+        let script_sig = self.script_sig.clone().unwrap_or_default();
+        serde_json::json!({
+            "txid": self.txid,
+            "vout": self.vout,
+            "is_coinbase": self.is_coinbase(),
+            "script_asm": script_sig.asm,
+            "script_hex": script_sig.hex,
+            "sequence": self.sequence,
+        })
+    }
+}
+
+/// A transaction in the verbose format of the Bitcoin Core RPC
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BtcTransaction {
+    pub txid: String,
+    pub hash: String,
+    pub version: i64,
+    pub size: u64,
+    pub vsize: u64,
+    pub weight: u64,
+    pub locktime: u64,
+    pub vin: Vec<BtcInput>,
+    pub vout: Vec<BtcOutput>,
+    pub blockhash: Option<String>,
+    pub blockheight: Option<u64>,
+    /// Block time in seconds since the unix epoch
+    pub blocktime: Option<u64>,
+}
+
+impl BtcTransaction {
+    pub fn new(txid: impl Into<String>) -> Self {
+        let txid = txid.into();
+        Self {
+            hash: txid.clone(),
+            txid,
+            version: 2,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_block(mut self, height: u64, time: u64) -> Self {
+        self.blockheight = Some(height);
+        self.blocktime = Some(time);
+        self
+    }
+
+    pub fn with_input(mut self, input: BtcInput) -> Self {
+        self.vin.push(input);
+        self
+    }
+
+    /// Append an output, numbering it after the existing ones
+    pub fn with_output(mut self, mut output: BtcOutput) -> Self {
+        output.n = self.vout.len() as u32;
+        self.vout.push(output);
+        self
+    }
+
+    pub fn block_height(&self) -> u64 {
+        self.blockheight.unwrap_or_default()
+    }
+
+    pub fn is_coinbase(&self) -> bool {
+        self.vin.first().is_some_and(BtcInput::is_coinbase)
+    }
+
+    /// Sum of all outputs in satoshis
+    pub fn total_output_sats(&self) -> u64 {
+        self.vout.iter().map(BtcOutput::value_sats).sum()
+    }
+
+    /// Sum of all spent outputs in satoshis, `None` unless every input carries its spent output
+    pub fn total_input_sats(&self) -> Option<u64> {
+        self.vin
+            .iter()
+            .map(|input| input.pre_vout.as_ref().map(BtcOutput::value_sats))
+            .sum()
+    }
+
+    /// Fee in satoshis, when the spent outputs are known
+    pub fn fee_sats(&self) -> Option<u64> {
+        if self.is_coinbase() {
+            return Some(0);
+        }
+        self.total_input_sats()
+            .map(|input| input.saturating_sub(self.total_output_sats()))
+    }
+
+    /// Flat view used by transaction filters
+    pub(crate) fn filter_fields(&self) -> Value {
+        serde_json::json!({
+            "txid": self.txid,
+            "hash": self.hash,
+            "version": self.version,
+            "size": self.size,
+            "vsize": self.vsize,
+            "weight": self.weight,
+            "locktime": self.locktime,
+        })
+    }
+}
+
+/// A block in the verbose format of the Bitcoin Core RPC
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BtcBlock {
+    pub hash: String,
+    pub height: u64,
+    /// Block time in seconds since the unix epoch
+    pub time: u64,
+    pub previousblockhash: Option<String>,
+    /// Transactions, either ids or full transactions depending on the fetch verbosity
+    pub tx: Vec<Value>,
+}
+
+impl BtcBlock {
+    pub fn new(hash: impl Into<String>, height: u64, time: u64) -> Self {
+        Self {
+            hash: hash.into(),
+            height,
+            time,
+            ..Default::default()
+        }
+    }
+}
+
+#[crate::async_trait]
+pub trait BtcTransactionHandler: Send + Sync + 'static {
+    async fn on_transaction(&self, transaction: BtcTransaction, ctx: BtcContext);
+}
+
+#[crate::async_trait]
+pub trait BtcBlockHandler: Send + Sync + 'static {
+    async fn on_block(&self, block: BtcBlock, ctx: BtcContext);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_transaction() {
+        let raw = r#"{
+            "txid": "ab01",
+            "hash": "ab01",
+            "version": 2,
+            "size": 222,
+            "vsize": 141,
+            "weight": 561,
+            "locktime": 0,
+            "vin": [{
+                "txid": "cd02",
+                "vout": 1,
+                "scriptSig": {"asm": "", "hex": ""},
+                "txinwitness": ["3044", "02ab"],
+                "sequence": 4294967293,
+                "pre_vout": {"value": 0.0015, "n": 1, "scriptPubKey": {"asm": "0 abcd", "hex": "0014abcd", "type": "witness_v0_keyhash", "address": "bc1qsender"}}
+            }],
+            "vout": [
+                {"value": 0.001, "n": 0, "scriptPubKey": {"asm": "0 ef01", "hex": "0014ef01", "type": "witness_v0_keyhash", "address": "bc1qreceiver"}},
+                {"value": 0.00049, "n": 1, "scriptPubKey": {"asm": "OP_RETURN 6869", "hex": "6a026869", "type": "nulldata"}}
+            ],
+            "blockhash": "0000beef",
+            "blockheight": 840000,
+            "blocktime": 1713571767
+        }"#;
+        let transaction: BtcTransaction = serde_json::from_str(raw).unwrap();
+        assert_eq!(transaction.block_height(), 840000);
+        assert_eq!(transaction.vout[0].value_sats(), 100_000);
+        assert_eq!(transaction.vout[0].address(), Some("bc1qreceiver"));
+        assert_eq!(transaction.vout[1].address(), None);
+        assert_eq!(transaction.vin[0].address(), Some("bc1qsender"));
+        assert_eq!(transaction.total_output_sats(), 149_000);
+        assert_eq!(transaction.fee_sats(), Some(1_000));
+        assert!(!transaction.is_coinbase());
+    }
+
+    #[test]
+    fn test_transaction_builder() {
+        let transaction = BtcTransaction::new("ab01")
+            .with_input(BtcInput::coinbase("03a0bb0d"))
+            .with_output(BtcOutput::to_address("bc1qminer", 312_500_000))
+            .with_output(BtcOutput::to_address("bc1qother", 1));
+        assert!(transaction.is_coinbase());
+        assert_eq!(transaction.fee_sats(), Some(0));
+        assert_eq!(transaction.vout[1].n, 1);
+        assert_eq!(transaction.total_output_sats(), 312_500_001);
+        assert_eq!(transaction.vin[0].txid, None);
+    }
+}
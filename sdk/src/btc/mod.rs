@@ -0,0 +1,11 @@
+pub mod btc_processor;
+pub mod btc_plugin;
+pub mod btc_filter;
+pub mod context;
+mod btc_types;
+
+pub use btc_types::*;
+
+pub use btc_filter::{BtcCondition, BtcFieldFilter, BtcTransactionFilter};
+pub use btc_processor::{BtcProcessor, BtcHandlerOptions, BtcBindOptions};
+pub use btc_plugin::BtcPlugin;
@@ -43,6 +43,7 @@ impl Codegen {
         
         // Register built-in generators
         codegen.register_generator(Box::new(crate::entity::codegen::EntityCodeGenerator::new()));
+        codegen.register_generator(Box::new(crate::solana::AnchorIdlCodeGenerator::new()));
         
         codegen
     }
@@ -0,0 +1,712 @@
+//! Layered configuration for the processor gRPC server
+//!
+//! The effective configuration is resolved in the following order, later layers
+//! overriding earlier ones:
+//!
+//! 1. Built-in defaults (or the config passed to `Server::set_execution_config`)
+//! 2. A TOML or YAML config file (`--config` or `SENTIO_PROCESSOR_CONFIG`)
+//! 3. Environment variables (see [`ServerConfig::apply_env`])
+//! 4. Command line flags (see [`crate::ServerArgs`])
+
+use crate::processor::execution_config::{DecoderWorkerConfig, HandlerOrderInsideTransaction};
+use crate::processor::ExecutionConfig;
+use crate::server::ServerArgs;
+use anyhow::{anyhow, Context as _, Result};
+use serde::{Deserialize, Serialize};
+use tonic::transport::{Certificate, Identity, ServerTlsConfig};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Environment variable pointing at the config file when `--config` is not given
+pub const CONFIG_FILE_ENV: &str = "SENTIO_PROCESSOR_CONFIG";
+
+/// Order in which handlers run inside a single transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandlerOrder {
+    /// Handlers are executed purely by log index
+    #[default]
+    ByLogIndex,
+    /// Handlers are grouped by processor, then executed by log index
+    ByProcessorAndLogIndex,
+}
+
+impl FromStr for HandlerOrder {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "by_log_index" => Ok(HandlerOrder::ByLogIndex),
+            "by_processor_and_log_index" => Ok(HandlerOrder::ByProcessorAndLogIndex),
+            other => Err(anyhow!(
+                "invalid handler order '{}', expected by_log_index or by_processor_and_log_index",
+                other
+            )),
+        }
+    }
+}
+
+impl From<HandlerOrder> for HandlerOrderInsideTransaction {
+    fn from(order: HandlerOrder) -> Self {
+        match order {
+            HandlerOrder::ByLogIndex => HandlerOrderInsideTransaction::ByLogIndex,
+            HandlerOrder::ByProcessorAndLogIndex => {
+                HandlerOrderInsideTransaction::ByProcessorAndLogIndex
+            }
+        }
+    }
+}
+
+/// Settings for the driver-side ABI decoder workers
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AbiDecoderSettings {
+    pub enabled: bool,
+    pub worker_count: Option<i32>,
+    pub skip_when_decode_failed: Option<bool>,
+}
+
+/// gRPC transport settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GrpcSettings {
+    /// Accept gzip compressed requests
+    pub accept_gzip: bool,
+    /// Compress responses with gzip
+    pub send_gzip: bool,
+    /// TCP keepalive in seconds, 0 disables it
+    pub tcp_keepalive_secs: u64,
+    /// HTTP/2 keepalive ping interval in seconds, 0 disables pings
+    pub http2_keepalive_interval_secs: u64,
+    /// HTTP/2 keepalive ping timeout in seconds
+    pub http2_keepalive_timeout_secs: u64,
+    /// Maximum size of a decoded request message in bytes
+    pub max_decoding_message_size: Option<usize>,
+    /// Maximum size of an encoded response message in bytes
+    pub max_encoding_message_size: Option<usize>,
+}
+
+impl Default for GrpcSettings {
+    fn default() -> Self {
+        Self {
+            accept_gzip: true,
+            send_gzip: false,
+            tcp_keepalive_secs: 10,
+            http2_keepalive_interval_secs: 0,
+            http2_keepalive_timeout_secs: 10,
+            max_decoding_message_size: None,
+            max_encoding_message_size: None,
+        }
+    }
+}
+
+/// TLS settings for the gRPC listener.
+///
+/// TLS is enabled when a certificate and key are configured. Setting `client_ca` additionally
+/// requires clients to present a certificate signed by that CA (mTLS).
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TlsSettings {
+    /// PEM encoded server certificate chain
+    pub cert: Option<PathBuf>,
+    /// PEM encoded server private key
+    pub key: Option<PathBuf>,
+    /// PEM encoded CA used to verify client certificates
+    pub client_ca: Option<PathBuf>,
+}
+
+impl TlsSettings {
+    /// Whether any TLS setting was provided
+    pub fn is_enabled(&self) -> bool {
+        self.cert.is_some() || self.key.is_some() || self.client_ca.is_some()
+    }
+
+    /// Load the PEM files into a tonic `ServerTlsConfig`, or `None` when TLS is not configured
+    pub fn server_tls_config(&self) -> Result<Option<ServerTlsConfig>> {
+        if !self.is_enabled() {
+            return Ok(None);
+        }
+        let (cert, key) = match (&self.cert, &self.key) {
+            (Some(cert), Some(key)) => (cert, key),
+            _ => return Err(anyhow!("TLS requires both a certificate and a key")),
+        };
+        let cert = std::fs::read(cert)
+            .with_context(|| format!("Failed to read TLS certificate {}", cert.display()))?;
+        let key = std::fs::read(key)
+            .with_context(|| format!("Failed to read TLS key {}", key.display()))?;
+
+        let mut tls = ServerTlsConfig::new().identity(Identity::from_pem(cert, key));
+        if let Some(ca) = &self.client_ca {
+            let ca = std::fs::read(ca)
+                .with_context(|| format!("Failed to read TLS client CA {}", ca.display()))?;
+            tls = tls.client_ca_root(Certificate::from_pem(ca));
+        }
+        Ok(Some(tls))
+    }
+}
+
+/// Effective configuration of the processor server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    /// Listen on this Unix domain socket instead of host:port
+    pub unix_socket: Option<PathBuf>,
+    /// Record every bindings stream to a file in this directory
+    pub record_dir: Option<PathBuf>,
+    /// Process binding timeout in seconds
+    pub process_binding_timeout: u64,
+    /// Seconds to wait for in-flight bindings on shutdown
+    pub shutdown_timeout: u64,
+    pub sequential: bool,
+    pub force_exact_block_time: bool,
+    pub handler_order_inside_transaction: HandlerOrder,
+    pub skip_start_block_validation: bool,
+    pub rpc_retry_times: i32,
+    pub eth_abi_decoder: Option<AbiDecoderSettings>,
+    pub grpc: GrpcSettings,
+    pub tls: TlsSettings,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: "0.0.0.0".to_string(),
+            port: 4000,
+            unix_socket: None,
+            record_dir: None,
+            process_binding_timeout: 600,
+            shutdown_timeout: 30,
+            sequential: false,
+            force_exact_block_time: false,
+            handler_order_inside_transaction: HandlerOrder::ByLogIndex,
+            skip_start_block_validation: false,
+            rpc_retry_times: 3,
+            eth_abi_decoder: None,
+            grpc: GrpcSettings::default(),
+            tls: TlsSettings::default(),
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Resolve the effective configuration from `base`, the config file, the environment and CLI args
+    pub fn load(base: ServerConfig, args: &ServerArgs) -> Result<Self> {
+        Self::load_with_env(base, args, |key| std::env::var(key).ok())
+    }
+
+    /// Same as [`ServerConfig::load`] but reads environment variables through `env`
+    pub fn load_with_env<F>(base: ServerConfig, args: &ServerArgs, env: F) -> Result<Self>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let config_path = args.config.clone().or_else(|| env(CONFIG_FILE_ENV).map(Into::into));
+
+        let mut config = match config_path {
+            Some(path) => Self::from_file_over(base, &path)?,
+            None => base,
+        };
+        config.apply_env(env)?;
+        config.apply_args(args);
+        Ok(config)
+    }
+
+    /// Read a TOML or YAML file; the format is picked from the file extension
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_file_over(Self::default(), path)
+    }
+
+    /// Read a config file, using `base` for any field the file does not set
+    fn from_file_over(base: ServerConfig, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+
+        // Merge file values on top of the base by going through a generic value tree
+        let mut merged = serde_json::to_value(&base)?;
+        let overlay: serde_json::Value = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => toml::from_str(&content)
+                .with_context(|| format!("Invalid TOML in {}", path.display()))?,
+            Some("yaml") | Some("yml") => serde_yaml::from_str(&content)
+                .with_context(|| format!("Invalid YAML in {}", path.display()))?,
+            _ => {
+                return Err(anyhow!(
+                    "Unsupported config file format '{}', expected .toml, .yaml or .yml",
+                    path.display()
+                ))
+            }
+        };
+        merge_json(&mut merged, overlay);
+
+        serde_json::from_value(merged)
+            .with_context(|| format!("Invalid configuration in {}", path.display()))
+    }
+
+    /// Override fields from environment variables.
+    ///
+    /// Supported variables:
+    /// - listener: `SENTIO_HOST`, `SENTIO_PORT`, `SENTIO_UNIX_SOCKET`, `SENTIO_TLS_CERT`,
+    ///   `SENTIO_TLS_KEY`, `SENTIO_TLS_CLIENT_CA`, `SENTIO_RECORD_DIR`
+    /// - execution: `PROCESS_BINDING_TIMEOUT` (legacy `PROCESS_TIMEOUT_SECS`),
+    ///   `SENTIO_SHUTDOWN_TIMEOUT`, `SENTIO_SEQUENTIAL`, `SENTIO_FORCE_EXACT_BLOCK_TIME`,
+    ///   `SENTIO_HANDLER_ORDER`, `SENTIO_SKIP_START_BLOCK_VALIDATION`, `SENTIO_RPC_RETRY_TIMES`,
+    ///   `SENTIO_ETH_ABI_DECODER_ENABLED`, `SENTIO_ETH_ABI_DECODER_WORKERS`,
+    ///   `SENTIO_ETH_ABI_DECODER_SKIP_FAILED`
+    /// - gRPC: `SENTIO_GRPC_ACCEPT_GZIP`, `GRPC_ENABLE_COMPRESS`, `SENTIO_GRPC_TCP_KEEPALIVE`,
+    ///   `SENTIO_GRPC_HTTP2_KEEPALIVE_INTERVAL`, `SENTIO_GRPC_HTTP2_KEEPALIVE_TIMEOUT`,
+    ///   `SENTIO_GRPC_MAX_DECODING_MESSAGE_SIZE`, `SENTIO_GRPC_MAX_ENCODING_MESSAGE_SIZE`
+    pub fn apply_env<F>(&mut self, env: F) -> Result<()>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let get = |key: &str| env(key).filter(|v| !v.trim().is_empty());
+
+        if let Some(v) = get("SENTIO_HOST") {
+            self.host = v;
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_PORT")? {
+            self.port = v;
+        }
+        if let Some(v) = get("SENTIO_UNIX_SOCKET") {
+            self.unix_socket = Some(v.into());
+        }
+        if let Some(v) = get("SENTIO_RECORD_DIR") {
+            self.record_dir = Some(v.into());
+        }
+        if let Some(v) = get("SENTIO_TLS_CERT") {
+            self.tls.cert = Some(v.into());
+        }
+        if let Some(v) = get("SENTIO_TLS_KEY") {
+            self.tls.key = Some(v.into());
+        }
+        if let Some(v) = get("SENTIO_TLS_CLIENT_CA") {
+            self.tls.client_ca = Some(v.into());
+        }
+        // Legacy variable first so the newer name wins when both are set. Both predate this
+        // module and were always ignored when they did not parse.
+        if let Ok(Some(v)) = parse_env(&get, "PROCESS_TIMEOUT_SECS") {
+            self.process_binding_timeout = v;
+        }
+        if let Ok(Some(v)) = parse_env(&get, "PROCESS_BINDING_TIMEOUT") {
+            self.process_binding_timeout = v;
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_SHUTDOWN_TIMEOUT")? {
+            self.shutdown_timeout = v;
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_SEQUENTIAL")? {
+            self.sequential = v;
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_FORCE_EXACT_BLOCK_TIME")? {
+            self.force_exact_block_time = v;
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_HANDLER_ORDER")? {
+            self.handler_order_inside_transaction = v;
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_SKIP_START_BLOCK_VALIDATION")? {
+            self.skip_start_block_validation = v;
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_RPC_RETRY_TIMES")? {
+            self.rpc_retry_times = v;
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_ETH_ABI_DECODER_ENABLED")? {
+            self.eth_abi_decoder.get_or_insert_with(Default::default).enabled = v;
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_ETH_ABI_DECODER_WORKERS")? {
+            self.eth_abi_decoder.get_or_insert_with(Default::default).worker_count = Some(v);
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_ETH_ABI_DECODER_SKIP_FAILED")? {
+            self.eth_abi_decoder
+                .get_or_insert_with(Default::default)
+                .skip_when_decode_failed = Some(v);
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_GRPC_ACCEPT_GZIP")? {
+            self.grpc.accept_gzip = v;
+        }
+        // Historically only the exact value "true" enabled compression, anything else disables it
+        if let Some(v) = get("GRPC_ENABLE_COMPRESS") {
+            self.grpc.send_gzip = v.trim() == "true";
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_GRPC_TCP_KEEPALIVE")? {
+            self.grpc.tcp_keepalive_secs = v;
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_GRPC_HTTP2_KEEPALIVE_INTERVAL")? {
+            self.grpc.http2_keepalive_interval_secs = v;
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_GRPC_HTTP2_KEEPALIVE_TIMEOUT")? {
+            self.grpc.http2_keepalive_timeout_secs = v;
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_GRPC_MAX_DECODING_MESSAGE_SIZE")? {
+            self.grpc.max_decoding_message_size = Some(v);
+        }
+        if let Some(v) = parse_env(&get, "SENTIO_GRPC_MAX_ENCODING_MESSAGE_SIZE")? {
+            self.grpc.max_encoding_message_size = Some(v);
+        }
+        Ok(())
+    }
+
+    /// Override fields with flags explicitly given on the command line
+    pub fn apply_args(&mut self, args: &ServerArgs) {
+        if let Some(host) = &args.host {
+            self.host = host.clone();
+        }
+        if let Some(port) = args.port {
+            self.port = port;
+        }
+        if let Some(path) = &args.unix_socket {
+            self.unix_socket = Some(path.clone());
+        }
+        if let Some(path) = &args.record_dir {
+            self.record_dir = Some(path.clone());
+        }
+        if let Some(path) = &args.tls_cert {
+            self.tls.cert = Some(path.clone());
+        }
+        if let Some(path) = &args.tls_key {
+            self.tls.key = Some(path.clone());
+        }
+        if let Some(path) = &args.tls_client_ca {
+            self.tls.client_ca = Some(path.clone());
+        }
+        if let Some(timeout) = args.process_binding_timeout {
+            self.process_binding_timeout = timeout;
+        }
+        if let Some(timeout) = args.shutdown_timeout {
+            self.shutdown_timeout = timeout;
+        }
+        if let Some(v) = args.sequential {
+            self.sequential = v;
+        }
+        if let Some(v) = args.force_exact_block_time {
+            self.force_exact_block_time = v;
+        }
+        if let Some(v) = args.handler_order {
+            self.handler_order_inside_transaction = v;
+        }
+        if let Some(v) = args.skip_start_block_validation {
+            self.skip_start_block_validation = v;
+        }
+        if let Some(v) = args.rpc_retry_times {
+            self.rpc_retry_times = v;
+        }
+        if let Some(v) = args.eth_abi_decoder {
+            self.eth_abi_decoder.get_or_insert_with(Default::default).enabled = v;
+        }
+        if let Some(v) = args.eth_abi_decoder_workers {
+            self.eth_abi_decoder.get_or_insert_with(Default::default).worker_count = Some(v);
+        }
+        if let Some(v) = args.accept_gzip {
+            self.grpc.accept_gzip = v;
+        }
+        if let Some(v) = args.send_gzip {
+            self.grpc.send_gzip = v;
+        }
+        if let Some(v) = args.tcp_keepalive {
+            self.grpc.tcp_keepalive_secs = v;
+        }
+        if let Some(v) = args.http2_keepalive_interval {
+            self.grpc.http2_keepalive_interval_secs = v;
+        }
+        if let Some(v) = args.http2_keepalive_timeout {
+            self.grpc.http2_keepalive_timeout_secs = v;
+        }
+        if let Some(v) = args.max_decoding_message_size {
+            self.grpc.max_decoding_message_size = Some(v);
+        }
+        if let Some(v) = args.max_encoding_message_size {
+            self.grpc.max_encoding_message_size = Some(v);
+        }
+    }
+
+    /// Use the fields of an `ExecutionConfig` as the base layer.
+    /// A non-positive `process_binding_timeout` keeps the current value.
+    pub fn apply_execution_config(&mut self, config: &ExecutionConfig) {
+        self.sequential = config.sequential;
+        self.force_exact_block_time = config.force_exact_block_time;
+        self.handler_order_inside_transaction = match config.handler_order_inside_transaction() {
+            HandlerOrderInsideTransaction::ByLogIndex => HandlerOrder::ByLogIndex,
+            HandlerOrderInsideTransaction::ByProcessorAndLogIndex => {
+                HandlerOrder::ByProcessorAndLogIndex
+            }
+        };
+        if config.process_binding_timeout > 0 {
+            self.process_binding_timeout = config.process_binding_timeout as u64;
+        }
+        self.skip_start_block_validation = config.skip_start_block_validation;
+        self.rpc_retry_times = config.rpc_retry_times;
+        self.eth_abi_decoder = config.eth_abi_decoder_config.as_ref().map(|c| AbiDecoderSettings {
+            enabled: c.enabled,
+            worker_count: c.worker_count,
+            skip_when_decode_failed: c.skip_when_decode_failed,
+        });
+    }
+
+    /// Build the `ExecutionConfig` advertised to the driver in `get_config`
+    pub fn execution_config(&self) -> ExecutionConfig {
+        let timeout = if self.process_binding_timeout > 0 {
+            self.process_binding_timeout.min(i32::MAX as u64) as i32
+        } else {
+            ServerConfig::default().process_binding_timeout as i32
+        };
+        ExecutionConfig {
+            sequential: self.sequential,
+            force_exact_block_time: self.force_exact_block_time,
+            handler_order_inside_transaction: HandlerOrderInsideTransaction::from(
+                self.handler_order_inside_transaction,
+            ) as i32,
+            process_binding_timeout: timeout,
+            skip_start_block_validation: self.skip_start_block_validation,
+            rpc_retry_times: self.rpc_retry_times,
+            eth_abi_decoder_config: self.eth_abi_decoder.as_ref().map(|c| DecoderWorkerConfig {
+                enabled: c.enabled,
+                worker_count: c.worker_count,
+                skip_when_decode_failed: c.skip_when_decode_failed,
+            }),
+        }
+    }
+
+    /// Render the configuration as TOML, e.g. for `--print-config`
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+}
+
+fn parse_env<T, G>(get: &G, key: &str) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+    G: Fn(&str) -> Option<String>,
+{
+    match get(key) {
+        Some(raw) => raw
+            .trim()
+            .parse::<T>()
+            .map(Some)
+            .map_err(|e| anyhow!("Invalid value '{}' for {}: {}", raw, key, e)),
+        None => Ok(None),
+    }
+}
+
+/// Recursively overlay `overlay` onto `base`; objects are merged, everything else is replaced
+fn merge_json(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) if existing.is_object() && value.is_object() => {
+                        merge_json(existing, value)
+                    }
+                    _ => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use std::collections::HashMap;
+    use std::io::Write;
+
+    fn args(flags: &[&str]) -> ServerArgs {
+        let mut argv = vec!["sentio-processor"];
+        argv.extend_from_slice(flags);
+        ServerArgs::parse_from(argv)
+    }
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    fn write_config(suffix: &str, content: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn defaults_match_previous_execution_config() {
+        let config = ServerConfig::load_with_env(ServerConfig::default(), &args(&[]), env(&[]))
+            .unwrap();
+        let exec = config.execution_config();
+        assert_eq!(config.port, 4000);
+        assert_eq!(exec.process_binding_timeout, 600);
+        assert_eq!(exec.rpc_retry_times, 3);
+        assert!(!exec.sequential);
+        assert!(exec.eth_abi_decoder_config.is_none());
+    }
+
+    #[test]
+    fn layers_override_in_order() {
+        let file = write_config(
+            ".toml",
+            r#"
+port = 5000
+sequential = true
+rpc_retry_times = 7
+
+[grpc]
+send_gzip = true
+max_decoding_message_size = 1048576
+"#,
+        );
+        let path = file.path().to_str().unwrap();
+
+        // file only
+        let config =
+            ServerConfig::load_with_env(ServerConfig::default(), &args(&["--config", path]), env(&[]))
+                .unwrap();
+        assert_eq!(config.port, 5000);
+        assert!(config.sequential);
+        assert!(config.grpc.send_gzip);
+        assert!(config.grpc.accept_gzip, "unset nested fields keep defaults");
+        assert_eq!(config.grpc.max_decoding_message_size, Some(1048576));
+
+        // env over file
+        let config = ServerConfig::load_with_env(
+            ServerConfig::default(),
+            &args(&["--config", path]),
+            env(&[("SENTIO_PORT", "6000"), ("SENTIO_RPC_RETRY_TIMES", "1")]),
+        )
+        .unwrap();
+        assert_eq!(config.port, 6000);
+        assert_eq!(config.rpc_retry_times, 1);
+
+        // cli over env
+        let config = ServerConfig::load_with_env(
+            ServerConfig::default(),
+            &args(&["--config", path, "--port", "7000", "--sequential", "false"]),
+            env(&[("SENTIO_PORT", "6000")]),
+        )
+        .unwrap();
+        assert_eq!(config.port, 7000);
+        assert!(!config.sequential);
+    }
+
+    #[test]
+    fn yaml_file_and_env_path() {
+        let file = write_config(
+            ".yaml",
+            r#"
+handler_order_inside_transaction: by_processor_and_log_index
+eth_abi_decoder:
+  enabled: true
+  worker_count: 4
+"#,
+        );
+        let config = ServerConfig::load_with_env(
+            ServerConfig::default(),
+            &args(&[]),
+            env(&[(CONFIG_FILE_ENV, file.path().to_str().unwrap())]),
+        )
+        .unwrap();
+        let exec = config.execution_config();
+        assert_eq!(
+            exec.handler_order_inside_transaction,
+            HandlerOrderInsideTransaction::ByProcessorAndLogIndex as i32
+        );
+        let decoder = exec.eth_abi_decoder_config.unwrap();
+        assert!(decoder.enabled);
+        assert_eq!(decoder.worker_count, Some(4));
+    }
+
+    #[test]
+    fn legacy_timeout_env_and_flag_without_value() {
+        let config = ServerConfig::load_with_env(
+            ServerConfig::default(),
+            &args(&["--skip-start-block-validation"]),
+            env(&[("PROCESS_TIMEOUT_SECS", "120"), ("GRPC_ENABLE_COMPRESS", "true")]),
+        )
+        .unwrap();
+        assert_eq!(config.process_binding_timeout, 120);
+        assert!(config.grpc.send_gzip);
+        assert!(config.skip_start_block_validation);
+    }
+
+    #[test]
+    fn invalid_values_are_reported() {
+        let err = ServerConfig::load_with_env(
+            ServerConfig::default(),
+            &args(&[]),
+            env(&[("SENTIO_PORT", "not-a-port")]),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("SENTIO_PORT"));
+
+        let file = write_config(".toml", "unknown_field = 1\n");
+        assert!(ServerConfig::from_file(file.path()).is_err());
+    }
+
+    #[test]
+    fn listener_settings_from_env_and_cli() {
+        let config = ServerConfig::load_with_env(
+            ServerConfig::default(),
+            &args(&["--tls-key", "/etc/sentio/server.key"]),
+            env(&[
+                ("SENTIO_UNIX_SOCKET", "/tmp/processor.sock"),
+                ("SENTIO_TLS_CERT", "/etc/sentio/server.pem"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(config.unix_socket, Some(PathBuf::from("/tmp/processor.sock")));
+        assert_eq!(config.tls.cert, Some(PathBuf::from("/etc/sentio/server.pem")));
+        assert_eq!(config.tls.key, Some(PathBuf::from("/etc/sentio/server.key")));
+
+        let partial = TlsSettings {
+            cert: Some("/etc/sentio/server.pem".into()),
+            ..Default::default()
+        };
+        assert!(partial.server_tls_config().is_err());
+        assert!(TlsSettings::default().server_tls_config().unwrap().is_none());
+    }
+
+    #[test]
+    fn execution_config_is_base_layer() {
+        let mut base = ServerConfig::default();
+        base.apply_execution_config(&ExecutionConfig {
+            sequential: true,
+            process_binding_timeout: 0,
+            rpc_retry_times: 5,
+            ..Default::default()
+        });
+        assert!(base.sequential);
+        assert_eq!(base.process_binding_timeout, 600);
+
+        let config = ServerConfig::load_with_env(
+            base,
+            &args(&["--process-binding-timeout", "120"]),
+            env(&[
+                ("SENTIO_RPC_RETRY_TIMES", "9"),
+                ("SENTIO_ETH_ABI_DECODER_ENABLED", "true"),
+            ]),
+        )
+        .unwrap();
+        assert!(config.sequential);
+        assert_eq!(config.rpc_retry_times, 9);
+        assert_eq!(config.process_binding_timeout, 120);
+        assert!(config.eth_abi_decoder.as_ref().is_some_and(|c| c.enabled));
+        assert!(config.to_toml().unwrap().contains("rpc_retry_times = 9"));
+    }
+
+    #[test]
+    fn grpc_enable_compress_is_lenient() {
+        for (raw, expected) in [("true", true), ("1", false), ("yes", false), ("false", false)] {
+            let config = ServerConfig::load_with_env(
+                ServerConfig::default(),
+                &args(&[]),
+                env(&[("GRPC_ENABLE_COMPRESS", raw)]),
+            )
+            .unwrap();
+            assert_eq!(config.grpc.send_gzip, expected, "GRPC_ENABLE_COMPRESS={}", raw);
+        }
+    }
+}
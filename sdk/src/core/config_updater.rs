@@ -0,0 +1,78 @@
+//! Binding processors from inside handlers
+//!
+//! Plugins cannot be mutated while a binding is processed, so processors bound
+//! through a [`ConfigUpdater`] are queued on the [`RuntimeContext`]. Once the
+//! binding finishes, the [`PluginManager`] moves the queue into its own pending
+//! list and marks the result as `config_updated`. The driver then fetches the
+//! config again, which applies the pending registrations before configuring
+//! the plugins. Handler IDs are stable across configures, so the new config is
+//! a superset of the previous one.
+//!
+//! ```rust,ignore
+//! #[async_trait]
+//! impl EventHandler<PairCreated> for FactoryProcessor {
+//!     async fn on_event(&self, event: PairCreated, ctx: EthContext) {
+//!         PairProcessor::new(event.pair)
+//!             .configure_event::<Swap>(None)
+//!             .bind(&ctx.config_updater());
+//!     }
+//! }
+//! ```
+//!
+//! [`RuntimeContext`]: crate::core::RuntimeContext
+
+use crate::BindableServer;
+use crate::core::plugin::{FullPlugin, PluginRegister};
+use crate::core::{BaseProcessor, PluginError, PluginManager};
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use std::sync::{Arc, Mutex};
+
+/// A processor registration waiting for the next configure
+pub(crate) type PendingRegistration = Box<
+    dyn for<'a> FnOnce(&'a PluginManager) -> BoxFuture<'a, Result<(), PluginError>> + Send,
+>;
+
+/// Registrations queued while processing a single binding
+#[derive(Clone, Default)]
+pub(crate) struct PendingRegistrations(Arc<Mutex<Vec<PendingRegistration>>>);
+
+impl PendingRegistrations {
+    pub(crate) fn push(&self, registration: PendingRegistration) {
+        self.0.lock().unwrap().push(registration);
+    }
+
+    pub(crate) fn take(&self) -> Vec<PendingRegistration> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+/// Server handle for binding processors while the stream is running
+///
+/// Obtained from [`Context::config_updater`](crate::core::Context::config_updater).
+#[derive(Clone)]
+pub struct ConfigUpdater {
+    pending: PendingRegistrations,
+}
+
+impl ConfigUpdater {
+    pub(crate) fn new(pending: PendingRegistrations) -> Self {
+        Self { pending }
+    }
+}
+
+impl BindableServer for ConfigUpdater {
+    fn register_processor<T, P>(&self, processor: T)
+    where
+        T: BaseProcessor + 'static,
+        P: PluginRegister<T> + FullPlugin + Default + 'static,
+    {
+        self.pending.push(Box::new(move |manager: &PluginManager| {
+            manager
+                .update_plugin::<P, _, _>(|plugin| {
+                    plugin.register_processor(processor);
+                })
+                .boxed()
+        }));
+    }
+}
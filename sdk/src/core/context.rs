@@ -1,14 +1,20 @@
-use crate::{processor::TimeseriesResult, ProcessStreamResponseV3, Store, Timestamp};
+use crate::{processor::{EventTrackingResult, ExportResult, TimeseriesResult}, ProcessStreamResponseV3, Store, Timestamp};
 use anyhow::Result;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tonic::Status;
-use tracing::debug;
+use tracing::{debug, warn};
 // Re-export EventLogger trait from event_logger module
 pub use crate::core::event_logger::EventLogger;
 // Re-export metrics types
 pub use crate::core::metrics::{Counter, Gauge, Meter, MetricOptions, NumberValue};
 use crate::entity::store::backend::Backend;
+use crate::core::config_updater::{ConfigUpdater, PendingRegistrations};
+use crate::core::metric_registry::MetricRegistry;
+use crate::core::exporter::{ExportRegistry, Exporter};
+use crate::core::event_tracker::{EventTracker, EventTrackerRegistry};
+use crate::core::named_registry::RecordBuffer;
+use crate::processor::{EventTrackingConfig, ExportConfig};
 
 /// Labels type for metadata - equivalent to TypeScript Labels
 pub type Labels = HashMap<String, String>;
@@ -78,6 +84,24 @@ pub trait Context: Send + Sync {
         self.metadata().log_index
     }
 
+    /// Server handle for binding new processors from inside a handler
+    ///
+    /// Processors bound through it are added once the current binding is
+    /// processed, and the driver is asked to fetch the config again.
+    fn config_updater(&self) -> ConfigUpdater {
+        RUNTIME_CONTEXT.with(|ctx| ConfigUpdater::new(ctx.pending_registrations.clone()))
+    }
+
+    /// Exporter for a declared export, see [`Exporter`]
+    fn exporter(&self, name: &str) -> Exporter {
+        Exporter::new(name)
+    }
+
+    /// Tracker for a declared tracked event, see [`EventTracker`]
+    fn event_tracker(&self, name: &str) -> EventTracker {
+        EventTracker::new(name)
+    }
+
     fn set_config_updated(&mut self, updated: bool) {
         self.base_context().config_updated = updated;
         
@@ -124,20 +148,10 @@ impl BaseContext {
         Counter::new(name)
     }
 
-    /// Create a new pure Counter with options
-    pub fn counter_with_options(&self, name: &str, options: MetricOptions) -> Counter {
-        Counter::with_options(name, options)
-    }
-
     /// Create a new pure Gauge
     pub fn gauge(&self, name: &str) -> Gauge {
         Gauge::new(name)
     }
-
-    /// Create a new pure Gauge with options
-    pub fn gauge_with_options(&self, name: &str, options: MetricOptions) -> Gauge {
-        Gauge::with_options(name, options)
-    }
 }
 
 impl Default for BaseContext {
@@ -156,7 +170,22 @@ pub struct RuntimeContext {
     /// Metadata for this runtime context (Arc for lightweight cloning)
     pub metadata: Arc<MetaData>,
 
-    pub remote_backend: Arc<Backend>
+    pub remote_backend: Arc<Backend>,
+
+    /// Processors bound from handlers while processing this binding
+    pub(crate) pending_registrations: PendingRegistrations,
+
+    /// Declared metrics that recorded values are validated against
+    pub(crate) metric_registry: Option<Arc<MetricRegistry>>,
+
+    /// Metrics and event logs recorded while processing this binding
+    timeseries_results: Arc<Mutex<Vec<TimeseriesResult>>>,
+
+    /// Export payloads emitted while processing this binding
+    export_results: RecordBuffer<ExportConfig, ExportResult>,
+
+    /// Events tracked while processing this binding
+    event_tracking_results: RecordBuffer<EventTrackingConfig, EventTrackingResult>,
  }
 
 impl RuntimeContext {
@@ -170,7 +199,12 @@ impl RuntimeContext {
             tx,
             process_id,
             metadata: Arc::new(metadata),
-            remote_backend: Arc::new(Backend::remote())
+            remote_backend: Arc::new(Backend::remote()),
+            pending_registrations: PendingRegistrations::default(),
+            metric_registry: None,
+            timeseries_results: Arc::default(),
+            export_results: RecordBuffer::default(),
+            event_tracking_results: RecordBuffer::default(),
         }
     }
 
@@ -195,16 +229,47 @@ impl RuntimeContext {
             tx,
             process_id,
             metadata: Arc::new(metadata),
-            remote_backend
+            remote_backend,
+            pending_registrations: PendingRegistrations::default(),
+            metric_registry: None,
+            timeseries_results: Arc::default(),
+            export_results: RecordBuffer::default(),
+            event_tracking_results: RecordBuffer::default(),
         }
     }
 
+    /// Context with empty metadata and an in-memory store, without declared registries
+    #[cfg(test)]
+    pub(crate) fn for_test() -> Self {
+        let (tx, _rx) = tokio::sync::mpsc::channel(1);
+        let backend = Arc::new(Backend::memory(Arc::new(crate::testing::MemoryDatabase::new())));
+        Self::new_with_empty_metadata(tx, 1, backend)
+    }
+
     /// Update the metadata in this runtime context
     pub fn with_metadata(mut self, metadata: MetaData) -> Self {
         self.metadata = Arc::new(metadata);
         self
     }
 
+    /// Validate recorded metrics against the declared ones
+    pub fn with_metric_registry(mut self, metric_registry: Arc<MetricRegistry>) -> Self {
+        self.metric_registry = Some(metric_registry);
+        self
+    }
+
+    /// Validate emitted exports against the declared ones
+    pub fn with_export_registry(mut self, export_registry: Arc<ExportRegistry>) -> Self {
+        self.export_results.set_registry(export_registry);
+        self
+    }
+
+    /// Validate tracked events against the declared trackers
+    pub fn with_event_tracker_registry(mut self, event_tracker_registry: Arc<EventTrackerRegistry>) -> Self {
+        self.event_tracking_results.set_registry(event_tracker_registry);
+        self
+    }
+
     /// Get reference to metadata
     pub fn metadata(&self) -> &MetaData {
         &self.metadata
@@ -225,34 +290,65 @@ impl RuntimeContext {
         }
     }
 
-    /// Emit a TimeseriesResult through the stream
+    /// Buffer a TimeseriesResult for the binding being processed
+    ///
+    /// Results are kept in call order and returned in the binding's
+    /// `ProcessResult`, see [`take_timeseries_results`](Self::take_timeseries_results).
     pub async fn send_timeseries_result(
         &self,
         name: &str,
         mut timeseries_result: TimeseriesResult,
     ) -> Result<()> {
-        use crate::processor::TsRequest;
+        use crate::processor::timeseries_result::TimeseriesType;
+        use crate::processor::MetricType;
+
+        if let Some(registry) = &self.metric_registry {
+            // Event logs aren't declared as metrics
+            let metric_type = match timeseries_result.r#type() {
+                TimeseriesType::Counter => Some(MetricType::Counter),
+                TimeseriesType::Gauge => Some(MetricType::Gauge),
+                TimeseriesType::Event => None,
+            };
+            if let Some(Err(e)) = metric_type.map(|metric_type| registry.validate(name, metric_type)) {
+                warn!("{}", e);
+                return Err(e.into());
+            }
+        }
 
         timeseries_result.metadata = Some(self.to_record_metadata(name));
+        self.timeseries_results.lock().unwrap().push(timeseries_result);
+        Ok(())
+    }
 
-        let ts_request = TsRequest {
-            data: vec![timeseries_result],
-        };
+    /// Take the TimeseriesResults buffered so far, in the order they were recorded
+    pub fn take_timeseries_results(&self) -> Vec<TimeseriesResult> {
+        std::mem::take(&mut *self.timeseries_results.lock().unwrap())
+    }
 
-        // Create ProcessStreamResponseV3 with TsRequest
-        let response = ProcessStreamResponseV3 {
-            process_id: self.process_id,
-            value: Some(crate::processor::process_stream_response_v3::Value::TsRequest(ts_request)),
-        };
+    /// Buffer an ExportResult for the binding being processed
+    pub async fn send_export_result(&self, name: &str, mut export_result: ExportResult) -> Result<()> {
+        export_result.metadata = Some(self.to_record_metadata(name));
+        Ok(self.export_results.push(name, export_result)?)
+    }
 
-        // Send through the channel
-        self.tx
-            .send(Ok(response))
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to send timeseries result: {}", e))?;
+    /// Take the ExportResults buffered so far, in the order they were emitted
+    pub fn take_export_results(&self) -> Vec<ExportResult> {
+        self.export_results.take()
+    }
 
-        debug!("Emitted TimeseriesResult");
-        Ok(())
+    /// Buffer an EventTrackingResult for the binding being processed
+    pub async fn send_event_tracking_result(
+        &self,
+        name: &str,
+        mut event_tracking_result: EventTrackingResult,
+    ) -> Result<()> {
+        event_tracking_result.metadata = Some(self.to_record_metadata(name));
+        Ok(self.event_tracking_results.push(name, event_tracking_result)?)
+    }
+
+    /// Take the EventTrackingResults buffered so far, in the order they were tracked
+    pub fn take_event_tracking_results(&self) -> Vec<EventTrackingResult> {
+        self.event_tracking_results.take()
     }
 
     pub async fn send_db_request(&self,db_request: crate::processor::DbRequest) -> Result<()> {
@@ -0,0 +1,80 @@
+use crate::core::USER_PROCESSOR;
+use crate::{ContractConfig, ContractInfo};
+
+/// Assembles the `ContractConfig` a plugin returns from `Plugin::configure`
+///
+/// Fills in the fields every chain shares (contract info, block range and
+/// processor type); chain-specific handler configs are pushed through
+/// [`config_mut`](Self::config_mut).
+///
+/// ```rust,ignore
+/// let mut builder = ContractConfigBuilder::new("1", "0x...", "MyContract")
+///     .start_block(100);
+/// builder.config_mut().log_configs.push(log_config);
+/// response.contract_configs.push(builder.build());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ContractConfigBuilder {
+    config: ContractConfig,
+}
+
+impl ContractConfigBuilder {
+    /// Start a config for the contract at `address` on `chain_id`
+    pub fn new(
+        chain_id: impl Into<String>,
+        address: impl Into<String>,
+        name: impl Into<String>,
+    ) -> Self {
+        let config = ContractConfig {
+            contract: Some(ContractInfo {
+                address: address.into(),
+                name: name.into(),
+                abi: String::new(),
+                chain_id: chain_id.into(),
+            }),
+            processor_type: USER_PROCESSOR.to_owned(),
+            ..Default::default()
+        };
+        Self { config }
+    }
+
+    /// Set the contract ABI
+    pub fn abi(mut self, abi: impl Into<String>) -> Self {
+        if let Some(contract) = self.config.contract.as_mut() {
+            contract.abi = abi.into();
+        }
+        self
+    }
+
+    /// First block to process (inclusive)
+    pub fn start_block(mut self, block: u64) -> Self {
+        self.config.start_block = block;
+        self
+    }
+
+    /// Last block to process, 0 means no end
+    pub fn end_block(mut self, block: u64) -> Self {
+        self.config.end_block = block;
+        self
+    }
+
+    /// Override the processor type (defaults to `user_processor`)
+    pub fn processor_type(mut self, processor_type: impl Into<String>) -> Self {
+        self.config.processor_type = processor_type.into();
+        self
+    }
+
+    /// Contract info of the config being built
+    pub fn contract(&self) -> &ContractInfo {
+        self.config.contract.as_ref().expect("contract info is always set")
+    }
+
+    /// Mutable access for pushing chain-specific handler configs
+    pub fn config_mut(&mut self) -> &mut ContractConfig {
+        &mut self.config
+    }
+
+    pub fn build(self) -> ContractConfig {
+        self.config
+    }
+}
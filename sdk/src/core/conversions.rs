@@ -1,9 +1,11 @@
 use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
 
 use crate::common::{BigDecimal as ProtoBigDecimal, BigInteger as ProtoBigInteger};
 use crate::entity::types::{BigDecimal, BigInt};
 
-// Centralized numeric <-> protobuf conversions shared across the crate
+// Centralized numeric and JSON <-> protobuf conversions shared across the crate
 
 pub fn bigint_to_proto(value: &BigInt) -> ProtoBigInteger {
     use num_bigint::Sign;
@@ -18,9 +20,10 @@ pub fn proto_to_bigint(proto: &ProtoBigInteger) -> BigInt {
 }
 
 pub fn bigdecimal_to_proto(value: &BigDecimal) -> ProtoBigDecimal {
+    // value = mantissa * 10^exp, while BigDecimal stores mantissa * 10^-scale
     let (mantissa_bigint, scale) = value.as_bigint_and_exponent();
     let proto_mantissa = bigint_to_proto(&mantissa_bigint);
-    ProtoBigDecimal { value: Some(proto_mantissa), exp: scale as i32 }
+    ProtoBigDecimal { value: Some(proto_mantissa), exp: (-scale) as i32 }
 }
 
 pub fn proto_to_bigdecimal(proto: &ProtoBigDecimal) -> Result<BigDecimal> {
@@ -34,3 +37,96 @@ pub fn proto_to_bigdecimal(proto: &ProtoBigDecimal) -> Result<BigDecimal> {
     Ok(BigDecimal::new(mantissa_bigint, scale))
 }
 
+pub fn u256_to_bigint(value: &alloy::primitives::U256) -> BigInt {
+    BigInt::from_bytes_be(num_bigint::Sign::Plus, &value.to_be_bytes::<32>())
+}
+
+pub fn i256_to_bigint(value: &alloy::primitives::I256) -> BigInt {
+    let (sign, abs) = value.into_sign_and_abs();
+    let abs = u256_to_bigint(&abs);
+    if sign.is_negative() { -abs } else { abs }
+}
+
+/// Divide a raw integer amount by `10^decimals` without losing precision
+pub fn scale_decimals(value: &BigInt, decimals: u32) -> BigDecimal {
+    BigDecimal::new(value.clone(), decimals as i64)
+}
+
+/// Convert a protobuf struct into JSON
+pub fn struct_to_json(value: &prost_types::Struct) -> Value {
+    Value::Object(
+        value
+            .fields
+            .iter()
+            .map(|(key, value)| (key.clone(), value_to_json(value)))
+            .collect(),
+    )
+}
+
+/// Deserialize a binding payload given as a protobuf struct, falling back to
+/// the default when the payload is missing
+///
+/// `chain` and `what` name the payload in the error message.
+pub fn parse_struct<T: DeserializeOwned + Default>(
+    value: Option<&prost_types::Struct>,
+    chain: &str,
+    what: &str,
+) -> anyhow::Result<T> {
+    match value {
+        Some(value) => serde_json::from_value(struct_to_json(value))
+            .map_err(|e| anyhow::anyhow!("Failed to parse {} {}: {}", chain, what, e)),
+        None => Ok(T::default()),
+    }
+}
+
+fn value_to_json(value: &prost_types::Value) -> Value {
+    use prost_types::value::Kind;
+    match &value.kind {
+        None | Some(Kind::NullValue(_)) => Value::Null,
+        Some(Kind::NumberValue(n)) => number_to_json(*n),
+        Some(Kind::StringValue(s)) => Value::String(s.clone()),
+        Some(Kind::BoolValue(b)) => Value::Bool(*b),
+        Some(Kind::StructValue(s)) => struct_to_json(s),
+        Some(Kind::ListValue(list)) => Value::Array(list.values.iter().map(value_to_json).collect()),
+    }
+}
+
+/// Struct numbers are all `f64`, integral ones become JSON integers so they
+/// deserialize into integer fields
+fn number_to_json(n: f64) -> Value {
+    if n.fract() == 0.0 && n >= i64::MIN as f64 && n < i64::MAX as f64 {
+        Value::from(n as i64)
+    } else if n.fract() == 0.0 && n >= 0.0 && n < u64::MAX as f64 {
+        Value::from(n as u64)
+    } else {
+        serde_json::Number::from_f64(n).map_or(Value::Null, Value::Number)
+    }
+}
+
+/// Convert JSON into a protobuf struct, `None` unless `value` is an object
+pub fn json_to_struct(value: &Value) -> Option<prost_types::Struct> {
+    let Value::Object(map) = value else {
+        return None;
+    };
+    Some(prost_types::Struct {
+        fields: map
+            .iter()
+            .map(|(key, value)| (key.clone(), json_to_value(value)))
+            .collect(),
+    })
+}
+
+fn json_to_value(value: &Value) -> prost_types::Value {
+    use prost_types::value::Kind;
+    let kind = match value {
+        Value::Null => Kind::NullValue(0),
+        Value::Bool(b) => Kind::BoolValue(*b),
+        Value::Number(n) => Kind::NumberValue(n.as_f64().unwrap_or_default()),
+        Value::String(s) => Kind::StringValue(s.clone()),
+        Value::Array(values) => Kind::ListValue(prost_types::ListValue {
+            values: values.iter().map(json_to_value).collect(),
+        }),
+        Value::Object(_) => Kind::StructValue(json_to_struct(value).unwrap_or_default()),
+    };
+    prost_types::Value { kind: Some(kind) }
+}
@@ -0,0 +1,140 @@
+//! Building blocks for running user handlers from a plugin's `process_binding`
+use crate::core::{MetaData, StateCollector, StateUpdateCollector, RUNTIME_CONTEXT};
+use crate::processor::data;
+use crate::{DataBinding, ProcessResult};
+use std::future::Future;
+
+/// Run a single user handler for a binding.
+///
+/// Creates a [`StateCollector`], builds the handler context from it with
+/// `make_context`, runs `handler` with `RUNTIME_CONTEXT` scoped to `metadata`
+/// (so metrics, event logs and store calls are attributed to this handler) and
+/// returns the collected state updates. Must be called from within
+/// `PluginManager::process`, which installs the outer `RUNTIME_CONTEXT`.
+pub async fn dispatch_handler<C, M, F, Fut>(
+    metadata: MetaData,
+    make_context: M,
+    handler: F,
+) -> ProcessResult
+where
+    M: FnOnce(StateCollector) -> C,
+    F: FnOnce(C) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let (state_collector, state_receiver) = StateCollector::new();
+    let mut update_collector = StateUpdateCollector::new(state_receiver);
+    let context = make_context(state_collector);
+
+    let runtime_ctx = RUNTIME_CONTEXT.get();
+    RUNTIME_CONTEXT
+        .scope(runtime_ctx.with_metadata(metadata), handler(context))
+        .await;
+
+    update_collector.collect_updates()
+}
+
+/// Get the payload of a binding, failing if it is missing
+pub fn binding_value(binding: &DataBinding) -> anyhow::Result<&data::Value> {
+    binding
+        .data
+        .as_ref()
+        .and_then(|d| d.value.as_ref())
+        .ok_or_else(|| anyhow::anyhow!("No data provided in DataBinding"))
+}
+
+impl ProcessResult {
+    /// Merge the result of another handler into this one
+    pub fn merge(mut self, other: ProcessResult) -> Self {
+        // Extend vectors with other's values
+        self.gauges.extend(other.gauges);
+        self.counters.extend(other.counters);
+        #[allow(deprecated)]
+        self.logs.extend(other.logs);
+        self.events.extend(other.events);
+        self.exports.extend(other.exports);
+        self.timeseries_result.extend(other.timeseries_result);
+
+        // Merge states - combine config_updated flags and errors
+        match (self.states.as_mut(), other.states) {
+            (Some(self_state), Some(other_state)) => {
+                // If either has config_updated = true, result should be true
+                self_state.config_updated = self_state.config_updated || other_state.config_updated;
+
+                // Combine errors - if both have errors, concatenate them
+                match (&self_state.error, other_state.error) {
+                    (Some(self_error), Some(other_error)) => {
+                        self_state.error = Some(format!("{}; {}", self_error, other_error));
+                    }
+                    (None, Some(other_error)) => {
+                        self_state.error = Some(other_error);
+                    }
+                    // If self has error and other doesn't, keep self's error
+                    // If neither has error, keep None
+                    _ => {}
+                }
+            }
+            (None, Some(other_state)) => {
+                // If self has no state but other does, use other's state
+                self.states = Some(other_state);
+            }
+            // If other has no state, keep self's state (or None)
+            _ => {}
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::RuntimeContext;
+    use crate::entity::store::backend::Backend;
+    use crate::testing::MemoryDatabase;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_dispatch_scopes_metadata_and_collects_state() {
+        let (tx, _rx) = tokio::sync::mpsc::channel(16);
+        let backend = Arc::new(Backend::memory(Arc::new(MemoryDatabase::new())));
+        let runtime_context = RuntimeContext::new_with_empty_metadata(tx, 1, backend);
+
+        let metadata = MetaData {
+            contract_name: "my-contract".to_string(),
+            block_number: 42,
+            ..Default::default()
+        };
+
+        let result = RUNTIME_CONTEXT
+            .scope(runtime_context, async {
+                dispatch_handler(metadata, |collector| collector, |collector| async move {
+                    let seen = RUNTIME_CONTEXT.with(|ctx| ctx.metadata().clone());
+                    assert_eq!(seen.contract_name, "my-contract");
+                    assert_eq!(seen.block_number, 42);
+                    collector.set_config_updated(true);
+                    collector.report_error("boom".to_string());
+                })
+                .await
+            })
+            .await;
+
+        let states = result.states.expect("state updates should be collected");
+        assert!(states.config_updated);
+        assert_eq!(states.error.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn test_merge_combines_errors() {
+        let a = ProcessResult {
+            states: Some(crate::StateResult { config_updated: false, error: Some("a".into()) }),
+            ..Default::default()
+        };
+        let b = ProcessResult {
+            states: Some(crate::StateResult { config_updated: true, error: Some("b".into()) }),
+            ..Default::default()
+        };
+        let merged = a.merge(b).states.unwrap();
+        assert!(merged.config_updated);
+        assert_eq!(merged.error.as_deref(), Some("a; b"));
+    }
+}
@@ -35,6 +35,13 @@ impl EventLogger {
             .await
     }
 
+    /// Emit a typed event, see [`SentioEvent`](super::SentioEvent)
+    ///
+    /// The schema is reported in get_config only once it is declared with `declare_event`.
+    pub async fn emit_typed<T: super::SentioEvent>(&self, event: &T) -> Result<()> {
+        self.emit(&event.to_event()).await
+    }
+
     /// Convert Event to TimeseriesResult using runtime context metadata
     fn event_to_timeseries_result(&self, event: &Event) -> Result<TimeseriesResult> {
         use crate::processor::timeseries_result::TimeseriesType;
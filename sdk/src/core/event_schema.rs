@@ -0,0 +1,501 @@
+//! Typed events and the event log schemas reported in `get_config`
+//!
+//! Deriving [`SentioEvent`] turns a struct into an [`Event`] whose fields are
+//! known up front:
+//!
+//! ```rust,ignore
+//! #[derive(SentioEvent)]
+//! #[event(name = "Transfer")]
+//! struct TransferLog {
+//!     #[event(distinct_id)]
+//!     from: String,
+//!     to: String,
+//!     #[event(coin = "USDC")]
+//!     amount: BigDecimal,
+//!     block: Block,
+//! }
+//!
+//! #[derive(SentioEvent)]
+//! struct Block {
+//!     number: u64,
+//!     time: Timestamp,
+//! }
+//!
+//! server.declare_event::<TransferLog>();
+//!
+//! // In a handler
+//! EventLogger::new().emit_typed(&transfer).await?;
+//! ```
+
+use crate::common::{CoinId, TokenAmount};
+use crate::core::conversions::bigdecimal_to_proto;
+use crate::core::{AttributeValue, Event};
+use crate::entity::types::{BigDecimal, BigInt, Timestamp};
+use crate::processor::EventLogConfig;
+use crate::processor::event_log_config::{BasicFieldType, Field, StructFieldType, field};
+use alloy::primitives::{Address, FixedBytes, I256, U256};
+use bigdecimal::FromPrimitive;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::RwLock;
+
+/// An event with a fixed schema, usually implemented with `#[derive(SentioEvent)]`
+pub trait SentioEvent {
+    /// Name of the event log
+    fn event_name() -> &'static str;
+
+    /// Schema of the attributes
+    fn event_fields() -> Vec<Field>;
+
+    /// Attribute values, keyed by field name
+    fn event_attributes(&self) -> HashMap<String, AttributeValue>;
+
+    /// Distinct entity id of the event
+    fn distinct_id(&self) -> Option<String> {
+        None
+    }
+
+    fn event_log_config() -> EventLogConfig {
+        EventLogConfig {
+            name: Self::event_name().to_string(),
+            fields: Self::event_fields(),
+        }
+    }
+
+    fn to_event(&self) -> Event {
+        let mut event = Event::name(Self::event_name());
+        if let Some(distinct_id) = self.distinct_id() {
+            event = event.distinct_id(&distinct_id);
+        }
+        for (key, value) in self.event_attributes() {
+            event = event.attr(&key, value);
+        }
+        event
+    }
+}
+
+/// A value that can be used as a field of a [`SentioEvent`]
+pub trait EventField {
+    /// Type declared in the event log schema
+    fn field_type() -> field::Type;
+
+    /// Attribute value, `None` leaves the attribute out of the event
+    fn to_attribute_value(&self) -> Option<AttributeValue>;
+}
+
+/// Schema of a field typed by its [`EventField`] implementation
+pub fn field<T: EventField + ?Sized>(name: &str) -> Field {
+    Field {
+        name: name.to_string(),
+        r#type: Some(T::field_type()),
+    }
+}
+
+/// Schema of a field holding an amount of `coin`
+pub fn coin_field(name: &str, coin: CoinId) -> Field {
+    Field {
+        name: name.to_string(),
+        r#type: Some(field::Type::CoinType(coin as i32)),
+    }
+}
+
+/// Struct type listing the fields of a nested event
+pub fn struct_type<T: SentioEvent + ?Sized>() -> field::Type {
+    field::Type::StructType(StructFieldType {
+        fields: T::event_fields(),
+    })
+}
+
+/// Turn a numeric attribute into an amount of `coin`
+///
+/// Values that are not numbers are kept as they are.
+pub fn coin_amount(coin: CoinId, value: AttributeValue) -> AttributeValue {
+    let amount = match value {
+        AttributeValue::BigDecimal(amount) => amount,
+        AttributeValue::BigInt(amount) => BigDecimal::from(amount),
+        AttributeValue::Integer(amount) => BigDecimal::from(amount),
+        AttributeValue::Number(amount) => match BigDecimal::from_f64(amount) {
+            Some(amount) => amount,
+            None => return AttributeValue::Number(amount),
+        },
+        other => return other,
+    };
+    AttributeValue::Token(TokenAmount {
+        token: coin as i32,
+        amount: Some(bigdecimal_to_proto(&amount)),
+        specified_at: None,
+    })
+}
+
+fn basic(field_type: BasicFieldType) -> field::Type {
+    field::Type::BasicType(field_type as i32)
+}
+
+impl EventField for String {
+    fn field_type() -> field::Type {
+        basic(BasicFieldType::String)
+    }
+
+    fn to_attribute_value(&self) -> Option<AttributeValue> {
+        Some(AttributeValue::String(self.clone()))
+    }
+}
+
+impl EventField for str {
+    fn field_type() -> field::Type {
+        basic(BasicFieldType::String)
+    }
+
+    fn to_attribute_value(&self) -> Option<AttributeValue> {
+        Some(AttributeValue::String(self.to_string()))
+    }
+}
+
+impl EventField for bool {
+    fn field_type() -> field::Type {
+        basic(BasicFieldType::Bool)
+    }
+
+    fn to_attribute_value(&self) -> Option<AttributeValue> {
+        Some(AttributeValue::Boolean(*self))
+    }
+}
+
+impl EventField for f64 {
+    fn field_type() -> field::Type {
+        basic(BasicFieldType::Double)
+    }
+
+    fn to_attribute_value(&self) -> Option<AttributeValue> {
+        Some(AttributeValue::Number(*self))
+    }
+}
+
+impl EventField for f32 {
+    fn field_type() -> field::Type {
+        basic(BasicFieldType::Double)
+    }
+
+    fn to_attribute_value(&self) -> Option<AttributeValue> {
+        Some(AttributeValue::Number(*self as f64))
+    }
+}
+
+// Integers are declared as big integers, like the other SDKs do
+macro_rules! impl_integer_field {
+    ($($ty:ty),*) => {
+        $(
+            impl EventField for $ty {
+                fn field_type() -> field::Type {
+                    basic(BasicFieldType::BigInteger)
+                }
+
+                fn to_attribute_value(&self) -> Option<AttributeValue> {
+                    Some(AttributeValue::Integer(*self as i64))
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_field!(i8, i16, i32, i64, u8, u16, u32);
+
+macro_rules! impl_wide_integer_field {
+    ($($ty:ty),*) => {
+        $(
+            impl EventField for $ty {
+                fn field_type() -> field::Type {
+                    basic(BasicFieldType::BigInteger)
+                }
+
+                fn to_attribute_value(&self) -> Option<AttributeValue> {
+                    Some(AttributeValue::BigInt(BigInt::from(*self)))
+                }
+            }
+        )*
+    };
+}
+
+impl_wide_integer_field!(u64, i128, u128);
+
+impl EventField for BigInt {
+    fn field_type() -> field::Type {
+        basic(BasicFieldType::BigInteger)
+    }
+
+    fn to_attribute_value(&self) -> Option<AttributeValue> {
+        Some(AttributeValue::BigInt(self.clone()))
+    }
+}
+
+impl EventField for BigDecimal {
+    fn field_type() -> field::Type {
+        basic(BasicFieldType::BigDecimal)
+    }
+
+    fn to_attribute_value(&self) -> Option<AttributeValue> {
+        Some(AttributeValue::BigDecimal(self.clone()))
+    }
+}
+
+impl EventField for Timestamp {
+    fn field_type() -> field::Type {
+        basic(BasicFieldType::Timestamp)
+    }
+
+    fn to_attribute_value(&self) -> Option<AttributeValue> {
+        Some(AttributeValue::Timestamp(self.clone()))
+    }
+}
+
+impl EventField for U256 {
+    fn field_type() -> field::Type {
+        basic(BasicFieldType::BigInteger)
+    }
+
+    fn to_attribute_value(&self) -> Option<AttributeValue> {
+        Some((*self).into())
+    }
+}
+
+impl EventField for I256 {
+    fn field_type() -> field::Type {
+        basic(BasicFieldType::BigInteger)
+    }
+
+    fn to_attribute_value(&self) -> Option<AttributeValue> {
+        Some((*self).into())
+    }
+}
+
+impl EventField for Address {
+    fn field_type() -> field::Type {
+        basic(BasicFieldType::String)
+    }
+
+    fn to_attribute_value(&self) -> Option<AttributeValue> {
+        Some((*self).into())
+    }
+}
+
+impl<const N: usize> EventField for FixedBytes<N> {
+    fn field_type() -> field::Type {
+        basic(BasicFieldType::String)
+    }
+
+    fn to_attribute_value(&self) -> Option<AttributeValue> {
+        Some((*self).into())
+    }
+}
+
+impl<T: EventField> EventField for Option<T> {
+    fn field_type() -> field::Type {
+        T::field_type()
+    }
+
+    fn to_attribute_value(&self) -> Option<AttributeValue> {
+        self.as_ref().and_then(T::to_attribute_value)
+    }
+}
+
+impl<T: EventField + ?Sized> EventField for &T {
+    fn field_type() -> field::Type {
+        T::field_type()
+    }
+
+    fn to_attribute_value(&self) -> Option<AttributeValue> {
+        (**self).to_attribute_value()
+    }
+}
+
+/// Registry of the event log schemas declared by the processors
+#[derive(Debug, Default)]
+pub struct EventLogRegistry {
+    configs: RwLock<BTreeMap<String, EventLogConfig>>,
+}
+
+impl EventLogRegistry {
+    /// Declare the schema of a typed event, replacing any schema with the same name
+    pub fn declare<T: SentioEvent>(&self) {
+        self.declare_config(T::event_log_config());
+    }
+
+    /// Declare an event log schema, replacing any schema with the same name
+    pub fn declare_config(&self, config: EventLogConfig) {
+        self.configs
+            .write()
+            .unwrap()
+            .insert(config.name.clone(), config);
+    }
+
+    /// Declared schemas in name order
+    pub fn event_log_configs(&self) -> Vec<EventLogConfig> {
+        self.configs.read().unwrap().values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SentioEvent;
+    use crate::core::conversions::proto_to_bigdecimal;
+    use std::str::FromStr;
+
+    #[derive(SentioEvent)]
+    struct Block {
+        number: u64,
+        time: Timestamp,
+    }
+
+    #[derive(SentioEvent)]
+    #[event(name = "Transfer")]
+    struct TransferLog {
+        #[event(distinct_id)]
+        from: String,
+        #[event(name = "recipient")]
+        to: String,
+        #[event(coin = "USDC")]
+        amount: BigDecimal,
+        fee: f64,
+        memo: Option<String>,
+        confirmed: bool,
+        block: Block,
+        #[event(skip)]
+        #[allow(dead_code)]
+        internal: u32,
+    }
+
+    fn transfer() -> TransferLog {
+        TransferLog {
+            from: "0xaaa".to_string(),
+            to: "0xbbb".to_string(),
+            amount: BigDecimal::from_str("12.5").unwrap(),
+            fee: 0.25,
+            memo: None,
+            confirmed: true,
+            block: Block {
+                number: 100,
+                time: Timestamp::from_timestamp(1_700_000_000, 0).unwrap(),
+            },
+            internal: 7,
+        }
+    }
+
+    #[test]
+    fn test_event_log_config() {
+        let config = TransferLog::event_log_config();
+        assert_eq!(config.name, "Transfer");
+
+        let fields: Vec<_> = config
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), field.r#type.clone().unwrap()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("recipient", basic(BasicFieldType::String)),
+                ("amount", field::Type::CoinType(CoinId::Usdc as i32)),
+                ("fee", basic(BasicFieldType::Double)),
+                ("memo", basic(BasicFieldType::String)),
+                ("confirmed", basic(BasicFieldType::Bool)),
+                (
+                    "block",
+                    field::Type::StructType(StructFieldType {
+                        fields: vec![
+                            Field {
+                                name: "number".to_string(),
+                                r#type: Some(basic(BasicFieldType::BigInteger)),
+                            },
+                            Field {
+                                name: "time".to_string(),
+                                r#type: Some(basic(BasicFieldType::Timestamp)),
+                            },
+                        ],
+                    })
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_event() {
+        let event = transfer().to_event();
+        assert_eq!(event.get_name(), "Transfer");
+        assert_eq!(event.get_distinct_id(), Some("0xaaa"));
+
+        let attributes = event.get_attributes();
+        assert!(!attributes.contains_key("from"));
+        assert!(!attributes.contains_key("memo"));
+        assert!(!attributes.contains_key("internal"));
+        assert!(matches!(&attributes["recipient"], AttributeValue::String(to) if to == "0xbbb"));
+        assert!(matches!(
+            attributes["confirmed"],
+            AttributeValue::Boolean(true)
+        ));
+
+        match &attributes["amount"] {
+            AttributeValue::Token(token) => {
+                assert_eq!(token.token, CoinId::Usdc as i32);
+                let amount = proto_to_bigdecimal(token.amount.as_ref().unwrap()).unwrap();
+                assert_eq!(amount, BigDecimal::from_str("12.5").unwrap());
+            }
+            other => panic!("expected a token amount, got {:?}", other),
+        }
+
+        match &attributes["block"] {
+            AttributeValue::Object(block) => {
+                assert!(
+                    matches!(&block["number"], AttributeValue::BigInt(n) if *n == BigInt::from(100))
+                );
+                assert!(matches!(block["time"], AttributeValue::Timestamp(_)));
+            }
+            other => panic!("expected an object, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_emit_typed() {
+        use crate::core::{EventLogger, RUNTIME_CONTEXT, RuntimeContext};
+        use crate::entity::store::backend::Backend;
+        use crate::testing::MemoryDatabase;
+        use std::sync::Arc;
+
+        let (tx, _rx) = tokio::sync::mpsc::channel(16);
+        let backend = Arc::new(Backend::memory(Arc::new(MemoryDatabase::new())));
+        let runtime_context = RuntimeContext::new_with_empty_metadata(tx, 1, backend);
+
+        RUNTIME_CONTEXT
+            .scope(runtime_context.clone(), async {
+                EventLogger::new().emit_typed(&transfer()).await.unwrap();
+            })
+            .await;
+
+        let results = runtime_context.take_timeseries_results();
+        assert_eq!(results.len(), 1);
+        let fields = &results[0].data.as_ref().unwrap().fields;
+        for key in [
+            "event_name",
+            "distinctEntityId",
+            "recipient",
+            "amount",
+            "block",
+        ] {
+            assert!(fields.contains_key(key), "missing {}", key);
+        }
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry = EventLogRegistry::default();
+        registry.declare::<TransferLog>();
+        registry.declare::<Block>();
+        registry.declare::<TransferLog>();
+
+        let names: Vec<_> = registry
+            .event_log_configs()
+            .into_iter()
+            .map(|config| config.name)
+            .collect();
+        assert_eq!(names, vec!["Block", "Transfer"]);
+    }
+}
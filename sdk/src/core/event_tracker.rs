@@ -0,0 +1,189 @@
+//! Event tracking for unique users, totals and retention
+//!
+//! Trackers are declared with the aggregations to compute, and reported in
+//! `get_config`:
+//!
+//! ```rust,ignore
+//! server.declare_event_tracker(
+//!     "swap",
+//!     EventTrackerOptions {
+//!         distinct_aggregation_by_days: vec![1, 7, 30],
+//!         retention: Some(RetentionConfig { retention_event_name: "swap".into(), days: 30 }),
+//!         ..Default::default()
+//!     },
+//! );
+//!
+//! // In a handler
+//! ctx.event_tracker("swap").track(&trader, [("pool", pool)]).await?;
+//! ```
+
+use crate::LogLevel;
+use crate::common::{RichStruct, RichValue};
+use crate::core::named_registry::{NamedConfig, NamedRegistry};
+use crate::core::{AttributeValue, Event};
+use crate::processor::{
+    EventTrackingConfig, EventTrackingResult, RetentionConfig, TotalPerEntityAggregation,
+};
+use anyhow::Result;
+use derive_builder::Builder;
+use std::collections::HashMap;
+
+/// Aggregations computed for a tracked event
+#[derive(Debug, Clone, Default, Builder)]
+#[builder(default)]
+pub struct EventTrackerOptions {
+    /// Count events per day, defaults to true
+    pub total_by_day: Option<bool>,
+    /// Count unique distinct ids, defaults to true
+    pub unique: Option<bool>,
+    /// Aggregate the number of events per distinct id
+    pub total_per_entity: Option<bool>,
+    /// Windows in days to count unique distinct ids over
+    pub distinct_aggregation_by_days: Vec<i32>,
+    /// Retention of the distinct ids, measured on another event
+    pub retention: Option<RetentionConfig>,
+}
+
+impl EventTrackerOptions {
+    /// Convert to the EventTrackingConfig declared in `get_config`
+    pub fn to_event_tracking_config(&self, name: &str) -> EventTrackingConfig {
+        EventTrackingConfig {
+            event_name: name.to_string(),
+            total_by_day: self.total_by_day.unwrap_or(true),
+            unique: self.unique.unwrap_or(true),
+            total_per_entity: self
+                .total_per_entity
+                .unwrap_or_default()
+                .then_some(TotalPerEntityAggregation {}),
+            distinct_aggregation_by_days: self.distinct_aggregation_by_days.clone(),
+            retention_config: self.retention.clone(),
+        }
+    }
+}
+
+impl NamedConfig for EventTrackingConfig {
+    const KIND: &'static str = "Event tracker";
+    type Options = EventTrackerOptions;
+
+    fn new(name: &str, options: EventTrackerOptions) -> Self {
+        options.to_event_tracking_config(name)
+    }
+}
+
+/// Registry of the event trackers declared by the processors
+pub type EventTrackerRegistry = NamedRegistry<EventTrackingConfig>;
+
+/// Handle for tracking events of a declared tracker
+#[derive(Debug, Clone)]
+pub struct EventTracker {
+    name: String,
+}
+
+impl EventTracker {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Track an event of `distinct_id` with the given attributes
+    pub async fn track<I, K, V>(&self, distinct_id: &str, attributes: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<AttributeValue>,
+    {
+        let event = attributes.into_iter().fold(
+            Event::name(&self.name).distinct_id(distinct_id),
+            |event, (key, value)| event.attr(key.as_ref(), value),
+        );
+        self.track_event(&event).await
+    }
+
+    /// Track an event built with the [`Event`] builder
+    ///
+    /// The event is recorded under the tracker name, with its distinct id,
+    /// severity, message and attributes.
+    pub async fn track_event(&self, event: &Event) -> Result<()> {
+        use super::RUNTIME_CONTEXT;
+
+        let mut fields = HashMap::new();
+        for (key, value) in event.get_attributes() {
+            fields.insert(key.clone(), RichValue::try_from(value)?);
+        }
+        let result = EventTrackingResult {
+            metadata: None,
+            distinct_entity_id: event.get_distinct_id().unwrap_or_default().to_string(),
+            attributes: None,
+            severity: event.get_severity().unwrap_or(LogLevel::Info) as i32,
+            message: event.get_message().unwrap_or_default().to_string(),
+            runtime_info: None,
+            attributes2: Some(RichStruct { fields }),
+            no_metric: false,
+        };
+
+        let ctx = RUNTIME_CONTEXT.try_with(|ctx| ctx.clone())
+            .map_err(|_| anyhow::anyhow!("Runtime context not available - make sure this is called within a processor handler"))?;
+
+        ctx.send_event_tracking_result(&self.name, result).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{RUNTIME_CONTEXT, RuntimeContext};
+
+    #[test]
+    fn test_declare_event_trackers() {
+        let registry = EventTrackerRegistry::default();
+        registry.declare("login", EventTrackerOptions::default());
+        registry.declare(
+            "swap",
+            EventTrackerOptions {
+                unique: Some(false),
+                total_per_entity: Some(true),
+                distinct_aggregation_by_days: vec![1, 7],
+                retention: Some(RetentionConfig {
+                    retention_event_name: "login".to_string(),
+                    days: 30,
+                }),
+                ..Default::default()
+            },
+        );
+
+        let configs = registry.configs();
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[0].event_name, "login");
+        assert!(configs[0].total_by_day && configs[0].unique);
+        assert!(configs[0].total_per_entity.is_none());
+        assert!(!configs[1].unique);
+        assert!(configs[1].total_per_entity.is_some());
+        assert_eq!(configs[1].distinct_aggregation_by_days, vec![1, 7]);
+        assert_eq!(configs[1].retention_config.as_ref().unwrap().days, 30);
+    }
+
+    #[tokio::test]
+    async fn test_track() {
+        let runtime_context = RuntimeContext::for_test();
+        RUNTIME_CONTEXT
+            .scope(
+                runtime_context.clone(),
+                EventTracker::new("swap").track("0xaaa", [("pool", "0xpool")]),
+            )
+            .await
+            .unwrap();
+
+        let results = runtime_context.take_event_tracking_results();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].metadata.as_ref().unwrap().name, "swap");
+        assert_eq!(results[0].distinct_entity_id, "0xaaa");
+        assert_eq!(results[0].severity, LogLevel::Info as i32);
+        let attributes = &results[0].attributes2.as_ref().unwrap().fields;
+        assert!(attributes.contains_key("pool"));
+    }
+}
@@ -4,9 +4,13 @@ use std::collections::HashMap;
 use crate::LogLevel;
 use crate::common::{RichStruct, RichValue, RichValueList, TokenAmount, rich_value};
 use crate::core::conversions::{
-    bigdecimal_to_proto, bigint_to_proto, proto_to_bigdecimal, proto_to_bigint,
+    bigdecimal_to_proto, bigint_to_proto, i256_to_bigint, proto_to_bigdecimal, proto_to_bigint,
+    u256_to_bigint,
 };
 use crate::entity::types::{BigDecimal, BigInt, Bytes, Timestamp};
+use alloy::dyn_abi::DynSolValue;
+use alloy::json_abi::Param;
+use alloy::primitives::{Address, FixedBytes, I256, U256, hex};
 
 /// Attribute value that can be stored in events
 #[derive(Debug, Clone)]
@@ -182,6 +186,100 @@ impl From<BigDecimal> for AttributeValue {
     }
 }
 
+impl From<Address> for AttributeValue {
+    fn from(value: Address) -> Self {
+        AttributeValue::String(value.to_checksum(None))
+    }
+}
+
+impl From<U256> for AttributeValue {
+    fn from(value: U256) -> Self {
+        AttributeValue::BigInt(u256_to_bigint(&value))
+    }
+}
+
+impl From<I256> for AttributeValue {
+    fn from(value: I256) -> Self {
+        AttributeValue::BigInt(i256_to_bigint(&value))
+    }
+}
+
+impl<const N: usize> From<FixedBytes<N>> for AttributeValue {
+    fn from(value: FixedBytes<N>) -> Self {
+        AttributeValue::String(hex::encode_prefixed(value))
+    }
+}
+
+impl From<alloy::primitives::Bytes> for AttributeValue {
+    fn from(value: alloy::primitives::Bytes) -> Self {
+        AttributeValue::Bytes(value.0)
+    }
+}
+
+impl From<&DynSolValue> for AttributeValue {
+    fn from(value: &DynSolValue) -> Self {
+        dyn_sol_value_to_attribute(value, &[])
+    }
+}
+
+impl From<DynSolValue> for AttributeValue {
+    fn from(value: DynSolValue) -> Self {
+        AttributeValue::from(&value)
+    }
+}
+
+/// Convert a decoded ABI value into an attribute
+///
+/// Tuples become objects keyed by the names of their ABI `components`, or
+/// arrays when the components are not known.
+pub fn dyn_sol_value_to_attribute(value: &DynSolValue, components: &[Param]) -> AttributeValue {
+    match value {
+        DynSolValue::Bool(b) => AttributeValue::Boolean(*b),
+        DynSolValue::Int(v, _) => (*v).into(),
+        DynSolValue::Uint(v, _) => (*v).into(),
+        DynSolValue::FixedBytes(word, size) => {
+            AttributeValue::String(hex::encode_prefixed(&word[..*size]))
+        }
+        DynSolValue::Address(address) => (*address).into(),
+        DynSolValue::Function(function) => {
+            AttributeValue::String(hex::encode_prefixed(function.as_slice()))
+        }
+        DynSolValue::Bytes(bytes) => AttributeValue::Bytes(Bytes::from(bytes.clone())),
+        DynSolValue::String(s) => AttributeValue::String(s.clone()),
+        // Components of an array of tuples describe its elements
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => AttributeValue::Array(
+            values
+                .iter()
+                .map(|value| dyn_sol_value_to_attribute(value, components))
+                .collect(),
+        ),
+        DynSolValue::Tuple(values) if values.len() == components.len() => AttributeValue::Object(
+            values
+                .iter()
+                .zip(components)
+                .enumerate()
+                .map(|(i, (value, param))| {
+                    let name = if param.name.is_empty() {
+                        i.to_string()
+                    } else {
+                        param.name.clone()
+                    };
+                    (name, dyn_sol_value_to_attribute(value, &param.components))
+                })
+                .collect(),
+        ),
+        DynSolValue::Tuple(values) => AttributeValue::Array(
+            values
+                .iter()
+                .map(|value| dyn_sol_value_to_attribute(value, &[]))
+                .collect(),
+        ),
+        // EIP-712 structs, only present with alloy's `eip712` feature
+        #[allow(unreachable_patterns)]
+        other => AttributeValue::String(format!("{:?}", other)),
+    }
+}
+
 // BigInt/BigDecimal conversions are provided by core::conversions
 
 // TryFrom conversions between AttributeValue and RichValue, aligned with entity/serialization.rs
@@ -297,3 +395,71 @@ impl TryFrom<&RichValue> for AttributeValue {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_alloy_primitive_conversions() {
+        let address = Address::from_str("0x742d35cc6634c0532925a3b844bc454e4438f44e").unwrap();
+        assert!(matches!(
+            AttributeValue::from(address),
+            AttributeValue::String(s) if s == "0x742d35Cc6634C0532925a3b844Bc454e4438f44e"
+        ));
+
+        let max = U256::MAX;
+        assert!(matches!(
+            AttributeValue::from(max),
+            AttributeValue::BigInt(v) if v.to_string() == max.to_string()
+        ));
+        assert!(matches!(
+            AttributeValue::from(I256::from_str("-5").unwrap()),
+            AttributeValue::BigInt(v) if v == BigInt::from(-5)
+        ));
+        assert!(matches!(
+            AttributeValue::from(FixedBytes::<4>::from([0xde, 0xad, 0xbe, 0xef])),
+            AttributeValue::String(s) if s == "0xdeadbeef"
+        ));
+    }
+
+    #[test]
+    fn test_dyn_sol_value_conversion() {
+        let components: Vec<Param> = serde_json::from_str(
+            r#"[
+                {"name": "owner", "type": "address"},
+                {"name": "", "type": "uint8"}
+            ]"#,
+        )
+        .unwrap();
+        let position = DynSolValue::Tuple(vec![
+            DynSolValue::Address(Address::ZERO),
+            DynSolValue::Uint(U256::from(7), 8),
+        ]);
+
+        match dyn_sol_value_to_attribute(&DynSolValue::Array(vec![position.clone()]), &components) {
+            AttributeValue::Array(items) => match &items[0] {
+                AttributeValue::Object(fields) => {
+                    assert!(
+                        matches!(&fields["owner"], AttributeValue::String(s) if s.starts_with("0x0000"))
+                    );
+                    assert!(
+                        matches!(&fields["1"], AttributeValue::BigInt(v) if *v == BigInt::from(7))
+                    );
+                }
+                other => panic!("expected an object, got {:?}", other),
+            },
+            other => panic!("expected an array, got {:?}", other),
+        }
+
+        // Without components tuples stay positional
+        assert!(
+            matches!(AttributeValue::from(&position), AttributeValue::Array(items) if items.len() == 2)
+        );
+        assert!(matches!(
+            AttributeValue::from(DynSolValue::FixedBytes(FixedBytes::right_padding_from(&[0xab]), 1)),
+            AttributeValue::String(s) if s == "0xab"
+        ));
+    }
+}
@@ -0,0 +1,98 @@
+//! Exports pushed to external destinations such as webhooks
+//!
+//! Each exporter is declared with the channel it delivers to, and reported in
+//! `get_config`:
+//!
+//! ```rust,ignore
+//! server.declare_export("transfers", "webhook-channel-id");
+//!
+//! // In a handler
+//! ctx.exporter("transfers").emit(&json!({ "from": from, "value": value })).await?;
+//! ```
+//!
+//! Payloads are serialized to JSON and returned in the binding's
+//! `ProcessResult` along with the record metadata of the handler.
+
+use crate::core::named_registry::{NamedConfig, NamedRegistry};
+use crate::processor::{ExportConfig, ExportResult};
+use anyhow::Result;
+use serde::Serialize;
+
+impl NamedConfig for ExportConfig {
+    const KIND: &'static str = "Export";
+
+    /// Channel the export delivers to
+    type Options = String;
+
+    fn new(name: &str, channel: String) -> Self {
+        ExportConfig {
+            name: name.to_string(),
+            channel,
+        }
+    }
+}
+
+/// Registry of the exports declared by the processors
+pub type ExportRegistry = NamedRegistry<ExportConfig>;
+
+/// Handle for emitting payloads to a declared export
+#[derive(Debug, Clone)]
+pub struct Exporter {
+    name: String,
+}
+
+impl Exporter {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Serialize `payload` to JSON and emit it using the runtime context
+    pub async fn emit<T: Serialize + ?Sized>(&self, payload: &T) -> Result<()> {
+        use super::RUNTIME_CONTEXT;
+
+        let payload = serde_json::to_string(payload)?;
+        let ctx = RUNTIME_CONTEXT.try_with(|ctx| ctx.clone())
+            .map_err(|_| anyhow::anyhow!("Runtime context not available - make sure this is called within a processor handler"))?;
+
+        ctx.send_export_result(
+            &self.name,
+            ExportResult {
+                metadata: None,
+                payload,
+                runtime_info: None,
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{RUNTIME_CONTEXT, RuntimeContext};
+
+    #[tokio::test]
+    async fn test_emit() {
+        let runtime_context = RuntimeContext::for_test();
+        RUNTIME_CONTEXT
+            .scope(
+                runtime_context.clone(),
+                Exporter::new("transfers")
+                    .emit(&serde_json::json!({ "from": "0xaaa", "value": 10 })),
+            )
+            .await
+            .unwrap();
+
+        let exports = runtime_context.take_export_results();
+        assert_eq!(exports.len(), 1);
+        assert_eq!(exports[0].metadata.as_ref().unwrap().name, "transfers");
+        let payload: serde_json::Value = serde_json::from_str(&exports[0].payload).unwrap();
+        assert_eq!(payload["value"], 10);
+    }
+}
@@ -12,9 +12,15 @@ pub struct HandlerInfo<T> {
 }
 
 /// Registry for managing handler IDs and dispatching
+///
+/// Registration is idempotent: registering the same handler again returns the
+/// ID it was first given, so IDs stay stable when a plugin is reconfigured
+/// after processors were added at runtime.
 pub struct HandlerRegister<T> {
     /// Map from chain_id to list of handlers
     handlers: HashMap<String, Vec<HandlerInfo<T>>>,
+    /// Map from (chain_id, handler_type, processor_idx, handler_idx) to handler ID
+    ids: HashMap<(String, T, usize, usize), i32>,
 }
 
 impl<T> HandlerRegister<T> {
@@ -22,6 +28,7 @@ impl<T> HandlerRegister<T> {
     pub fn new() -> Self {
         Self {
             handlers: HashMap::new(),
+            ids: HashMap::new(),
         }
     }
 
@@ -35,11 +42,18 @@ impl<T> Default for HandlerRegister<T> {
 
 impl<T> HandlerRegister<T>
 where
-    T: Clone + PartialEq + Hash,
+    T: Clone + Eq + Hash,
 {
 
-    /// Register a new handler and return its unique ID (index in the vector)
+    /// Register a handler and return its unique ID (index in the vector)
+    ///
+    /// A handler that is already registered keeps its existing ID.
     pub fn register(&mut self, chain_id: &str, handler_type: T, processor_idx: usize, handler_idx: usize) -> i32 {
+        let key = (chain_id.to_string(), handler_type.clone(), processor_idx, handler_idx);
+        if let Some(&handle_id) = self.ids.get(&key) {
+            return handle_id;
+        }
+
         let chain_handlers = self.handlers.entry(chain_id.to_string()).or_default();
         let handle_id = chain_handlers.len() as i32;
         
@@ -52,6 +66,7 @@ where
         };
         
         chain_handlers.push(handler_info);
+        self.ids.insert(key, handle_id);
         
         handle_id
     }
@@ -71,6 +86,28 @@ where
             .get(handler_id as usize)
     }
 
+    /// Get full handler information, failing with a descriptive error if it is not registered
+    pub fn resolve(&self, chain_id: &str, handler_id: i32) -> anyhow::Result<&HandlerInfo<T>> {
+        self.get_info(chain_id, handler_id).ok_or_else(|| {
+            anyhow::anyhow!("Handler {} not found for chain {}", handler_id, chain_id)
+        })
+    }
+
+    /// Resolve a handler ID to its processor in `processors` and the index of
+    /// the handler within that processor
+    pub fn find_processor<'a, P>(
+        &self,
+        processors: &'a [P],
+        chain_id: &str,
+        handler_id: i32,
+    ) -> anyhow::Result<(&'a P, usize)> {
+        let handler_info = self.resolve(chain_id, handler_id)?;
+        let processor = processors.get(handler_info.processor_idx).ok_or_else(|| {
+            anyhow::anyhow!("Processor index {} not found", handler_info.processor_idx)
+        })?;
+        Ok((processor, handler_info.handler_idx))
+    }
+
     /// Get all handlers for a specific chain ID
     pub fn get_handlers_for_chain(&self, chain_id: &str) -> Vec<(i32, &HandlerInfo<T>)> {
         self.handlers
@@ -112,11 +149,13 @@ where
     /// Clear all registered handlers
     pub fn clear(&mut self) {
         self.handlers.clear();
+        self.ids.clear();
     }
 
     /// Clear all registered handlers for a specific chain
     pub fn clear_chain(&mut self, chain_id: &str) {
         self.handlers.remove(chain_id);
+        self.ids.retain(|(chain, ..), _| chain != chain_id);
     }
 }
 
@@ -261,6 +300,45 @@ mod tests {
         assert_eq!(chain1_handlers.len(), 0);
     }
 
+    #[test]
+    fn test_register_is_idempotent() {
+        let mut register = HandlerRegister::default();
+
+        let id1 = register.register("1", TestHandlerType::Event, 0, 0);
+        let id2 = register.register("1", TestHandlerType::Call, 0, 1);
+
+        // Registering the same handlers again returns the same IDs
+        assert_eq!(register.register("1", TestHandlerType::Event, 0, 0), id1);
+        assert_eq!(register.register("1", TestHandlerType::Call, 0, 1), id2);
+        assert_eq!(register.len(), 2);
+
+        // The same indexes with a different type are a different handler
+        assert_eq!(register.register("1", TestHandlerType::Block, 0, 0), 2);
+    }
+
+    #[test]
+    fn test_reconfigure_with_new_processor_keeps_ids() {
+        let mut register = HandlerRegister::default();
+        let first: Vec<i32> = (0..2)
+            .map(|handler_idx| register.register("1", TestHandlerType::Event, 0, handler_idx))
+            .collect();
+
+        // Reconfigure after a processor was added: old handlers first, then the new one
+        let second: Vec<i32> = [(0, 0), (0, 1), (1, 0)]
+            .into_iter()
+            .map(|(processor_idx, handler_idx)| {
+                register.register("1", TestHandlerType::Event, processor_idx, handler_idx)
+            })
+            .collect();
+        assert_eq!(&second[..2], &first[..]);
+        assert_eq!(second[2], 2);
+        assert_eq!(register.get("1", 2), Some((TestHandlerType::Event, 1, 0)));
+
+        // A cleared chain assigns IDs from scratch
+        register.clear_chain("1");
+        assert_eq!(register.register("1", TestHandlerType::Event, 1, 0), 0);
+    }
+
     #[test]
     fn test_sequence_generation() {
         let mut register = HandlerRegister::default();
@@ -300,4 +378,24 @@ mod tests {
             assert_eq!(handler_idx, i);
         }
     }
+
+    #[test]
+    fn test_find_processor() {
+        let mut register = HandlerRegister::default();
+        let processors = vec!["first", "second"];
+
+        let id1 = register.register("1", TestHandlerType::Event, 1, 3);
+        let id2 = register.register("1", TestHandlerType::Call, 2, 0);
+
+        let (processor, handler_idx) = register.find_processor(&processors, "1", id1).unwrap();
+        assert_eq!(*processor, "second");
+        assert_eq!(handler_idx, 3);
+
+        // Registered handler pointing past the processor list
+        let err = register.find_processor(&processors, "1", id2).unwrap_err();
+        assert!(err.to_string().contains("Processor index 2 not found"));
+
+        // Unknown handler ID
+        assert!(register.find_processor(&processors, "2", id1).is_err());
+    }
 }
\ No newline at end of file
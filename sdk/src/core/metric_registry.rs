@@ -0,0 +1,239 @@
+//! Metric declarations reported in `get_config`
+//!
+//! Processors declare their counters and gauges up front so the driver knows
+//! their unit, description and aggregation before any value is recorded:
+//!
+//! ```rust,ignore
+//! server.declare_counter("transfers", MetricOptions { unit: Some("tx".into()), ..Default::default() })?;
+//! server.declare_gauge("price", MetricOptions::default())?;
+//! ```
+//!
+//! Once at least one metric is declared, recording a metric that was not
+//! declared, or with a different type, fails instead of being sent.
+
+use crate::core::MetricOptions;
+use crate::processor::{MetricConfig, MetricType};
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+/// Errors raised when declaring or recording metrics
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum MetricError {
+    #[error("Metric '{0}' is not declared")]
+    Undeclared(String),
+
+    #[error("Metric '{name}' is declared as {declared:?} but recorded as {recorded:?}")]
+    TypeMismatch {
+        name: String,
+        declared: MetricType,
+        recorded: MetricType,
+    },
+
+    #[error("Metric '{name}' is already declared as {declared:?}")]
+    AlreadyDeclared { name: String, declared: MetricType },
+
+    #[error("Invalid metric value '{value}': {reason}")]
+    InvalidValue { value: String, reason: String },
+}
+
+/// Registry of the metrics declared by the processors
+#[derive(Debug, Default)]
+pub struct MetricRegistry {
+    metrics: RwLock<BTreeMap<String, MetricConfig>>,
+}
+
+impl MetricRegistry {
+    /// Declare a counter, replacing the options of an existing counter with the same name
+    pub fn declare_counter(&self, name: &str, options: MetricOptions) -> Result<(), MetricError> {
+        self.declare(name, MetricType::Counter, options)
+    }
+
+    /// Declare a gauge, replacing the options of an existing gauge with the same name
+    pub fn declare_gauge(&self, name: &str, options: MetricOptions) -> Result<(), MetricError> {
+        self.declare(name, MetricType::Gauge, options)
+    }
+
+    fn declare(
+        &self,
+        name: &str,
+        metric_type: MetricType,
+        options: MetricOptions,
+    ) -> Result<(), MetricError> {
+        let mut metrics = self.metrics.write().unwrap();
+        match metrics.get(name).map(|existing| existing.r#type()) {
+            Some(declared) if declared != metric_type => {
+                return Err(MetricError::AlreadyDeclared {
+                    name: name.to_string(),
+                    declared,
+                });
+            }
+            _ => {}
+        }
+        metrics.insert(
+            name.to_string(),
+            options.to_metric_config(name, metric_type),
+        );
+        Ok(())
+    }
+
+    /// Declared metrics in name order
+    pub fn metric_configs(&self) -> Vec<MetricConfig> {
+        self.metrics.read().unwrap().values().cloned().collect()
+    }
+
+    /// Type of a declared metric
+    pub fn metric_type(&self, name: &str) -> Option<MetricType> {
+        self.metrics
+            .read()
+            .unwrap()
+            .get(name)
+            .map(|config| config.r#type())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.metrics.read().unwrap().is_empty()
+    }
+
+    /// Check that a value of `metric_type` may be recorded for `name`
+    ///
+    /// Everything is allowed while no metric is declared, so processors that
+    /// don't declare their metrics keep working.
+    pub fn validate(&self, name: &str, metric_type: MetricType) -> Result<(), MetricError> {
+        let metrics = self.metrics.read().unwrap();
+        if metrics.is_empty() {
+            return Ok(());
+        }
+        match metrics.get(name) {
+            None => Err(MetricError::Undeclared(name.to_string())),
+            Some(config) if config.r#type() != metric_type => Err(MetricError::TypeMismatch {
+                name: name.to_string(),
+                declared: config.r#type(),
+                recorded: metric_type,
+            }),
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Counter, Gauge, RUNTIME_CONTEXT, RuntimeContext};
+    use crate::entity::store::backend::Backend;
+    use crate::processor::{AggregationConfig, AggregationType};
+    use crate::testing::MemoryDatabase;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_declare_metrics() {
+        let registry = MetricRegistry::default();
+        registry
+            .declare_gauge(
+                "price",
+                MetricOptions {
+                    unit: Some("usd".to_string()),
+                    aggregation_config: Some(AggregationConfig {
+                        interval_in_minutes: vec![60],
+                        types: vec![AggregationType::Avg as i32, AggregationType::Max as i32],
+                        discard_origin: false,
+                    }),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        registry
+            .declare_counter(
+                "transfers",
+                MetricOptions {
+                    description: Some("Number of transfers".to_string()),
+                    sparse: Some(true),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let configs = registry.metric_configs();
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[0].name, "price");
+        assert_eq!(configs[0].r#type(), MetricType::Gauge);
+        assert_eq!(configs[0].unit, "usd");
+        assert_eq!(
+            configs[0]
+                .aggregation_config
+                .as_ref()
+                .unwrap()
+                .interval_in_minutes,
+            vec![60]
+        );
+        assert_eq!(configs[1].r#type(), MetricType::Counter);
+        assert_eq!(configs[1].description, "Number of transfers");
+        assert!(configs[1].sparse);
+        assert!(!configs[1].persistent_between_version);
+
+        // Redeclaring with the same type updates the options, another type is an error
+        registry
+            .declare_counter("transfers", MetricOptions::default())
+            .unwrap();
+        assert_eq!(registry.metric_configs()[1].description, "");
+        assert_eq!(
+            registry.declare_gauge("transfers", MetricOptions::default()),
+            Err(MetricError::AlreadyDeclared {
+                name: "transfers".to_string(),
+                declared: MetricType::Counter,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let registry = MetricRegistry::default();
+        // Nothing declared, nothing checked
+        assert!(registry.validate("anything", MetricType::Gauge).is_ok());
+
+        registry
+            .declare_counter("transfers", MetricOptions::default())
+            .unwrap();
+        assert!(registry.validate("transfers", MetricType::Counter).is_ok());
+        assert_eq!(
+            registry.validate("volume", MetricType::Counter),
+            Err(MetricError::Undeclared("volume".to_string()))
+        );
+        assert_eq!(
+            registry.validate("transfers", MetricType::Gauge),
+            Err(MetricError::TypeMismatch {
+                name: "transfers".to_string(),
+                declared: MetricType::Counter,
+                recorded: MetricType::Gauge,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recording_is_validated() {
+        let registry = Arc::new(MetricRegistry::default());
+        registry
+            .declare_counter("transfers", MetricOptions::default())
+            .unwrap();
+
+        let (tx, _rx) = tokio::sync::mpsc::channel(16);
+        let backend = Arc::new(Backend::memory(Arc::new(MemoryDatabase::new())));
+        let runtime_context =
+            RuntimeContext::new_with_empty_metadata(tx, 1, backend).with_metric_registry(registry);
+
+        RUNTIME_CONTEXT
+            .scope(runtime_context.clone(), async {
+                Counter::new("transfers").add(1, None).await.unwrap();
+                let err = Counter::new("volume").add(1, None).await.unwrap_err();
+                assert!(err.to_string().contains("'volume' is not declared"));
+                let err = Gauge::new("transfers").record(1, None).await.unwrap_err();
+                assert!(
+                    err.to_string()
+                        .contains("declared as Counter but recorded as Gauge")
+                );
+            })
+            .await;
+
+        // Only the valid value is recorded
+        assert_eq!(runtime_context.take_timeseries_results().len(), 1);
+    }
+}
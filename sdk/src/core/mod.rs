@@ -3,11 +3,19 @@ pub mod processor;
 pub mod handler_register;
 pub mod plugin;
 pub mod plugin_manager;
+pub mod dispatch;
+pub mod contract_config;
 pub mod context;
 pub mod event_logger;
 pub mod metrics;
+pub mod metric_registry;
 pub mod event_types;
+pub mod event_schema;
 pub mod conversions;
+pub mod config_updater;
+pub mod exporter;
+pub mod event_tracker;
+pub mod named_registry;
 
 #[cfg(feature = "benchmark")]
 pub mod benchmark;
@@ -30,12 +38,19 @@ pub mod profiling;
 
 pub use processor::{BaseProcessor, TypedProcessor};
 pub use handler_register::{HandlerInfo, HandlerRegister};
-pub use plugin::{Plugin, PluginRegister, AsyncPluginProcessor};
-pub use plugin_manager::PluginManager;
+pub use plugin::{Plugin, PluginRegister, AsyncPluginProcessor, FullPlugin};
+pub use plugin_manager::{PluginManager, PluginError};
+pub use dispatch::{dispatch_handler, binding_value};
+pub use contract_config::ContractConfigBuilder;
 pub use context::{Context, BaseContext, RuntimeContext, RUNTIME_CONTEXT, MetaData, Labels, Meter, Counter, Gauge, MetricOptions, NumberValue, StateCollector, StateUpdateCollector, StateUpdate};
 pub use event_types::{Event, AttributeValue};
 pub use event_logger::EventLogger;
-
-
-
-pub(crate) const USER_PROCESSOR: &str = "user_processor";
+pub use event_schema::{EventField, EventLogRegistry, SentioEvent};
+pub use config_updater::ConfigUpdater;
+pub use metric_registry::{MetricError, MetricRegistry};
+pub use named_registry::{NamedConfig, NamedRegistry, UndeclaredError};
+pub use exporter::{ExportRegistry, Exporter};
+pub use event_tracker::{EventTracker, EventTrackerOptions, EventTrackerRegistry};
+
+/// Processor type reported for user-defined processors
+pub const USER_PROCESSOR: &str = "user_processor";
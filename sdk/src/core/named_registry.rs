@@ -0,0 +1,188 @@
+//! Configs declared by name and the records validated against them
+//!
+//! Exports and event trackers are both declared up front, reported in
+//! `get_config`, and only recorded under a declared name. [`NamedRegistry`]
+//! holds the declarations and [`RecordBuffer`] the records of a binding.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, RwLock};
+use tracing::warn;
+
+/// Config reported in `get_config` for a declared name
+pub trait NamedConfig: Clone {
+    /// What is declared, used in error messages
+    const KIND: &'static str;
+
+    /// Options given when declaring
+    type Options;
+
+    fn new(name: &str, options: Self::Options) -> Self;
+}
+
+/// Error raised when recording under a name that is not declared
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("{kind} '{name}' is not declared")]
+pub struct UndeclaredError {
+    pub kind: &'static str,
+    pub name: String,
+}
+
+/// Registry of the configs declared by the processors
+pub struct NamedRegistry<C> {
+    configs: RwLock<BTreeMap<String, C>>,
+}
+
+impl<C: NamedConfig> NamedRegistry<C> {
+    /// Declare `name`, replacing the options of an existing declaration with the same name
+    pub fn declare(&self, name: &str, options: impl Into<C::Options>) {
+        self.configs
+            .write()
+            .unwrap()
+            .insert(name.to_string(), C::new(name, options.into()));
+    }
+
+    /// Declared configs in name order
+    pub fn configs(&self) -> Vec<C> {
+        self.configs.read().unwrap().values().cloned().collect()
+    }
+
+    /// Check that `name` may be recorded
+    pub fn validate(&self, name: &str) -> Result<(), UndeclaredError> {
+        if self.configs.read().unwrap().contains_key(name) {
+            Ok(())
+        } else {
+            Err(UndeclaredError {
+                kind: C::KIND,
+                name: name.to_string(),
+            })
+        }
+    }
+}
+
+impl<C> Default for NamedRegistry<C> {
+    fn default() -> Self {
+        Self {
+            configs: RwLock::default(),
+        }
+    }
+}
+
+impl<C: fmt::Debug> fmt::Debug for NamedRegistry<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NamedRegistry")
+            .field("configs", &self.configs)
+            .finish()
+    }
+}
+
+/// Records buffered while processing a binding, in the order they were recorded
+///
+/// Clones share the buffer. Once a registry is set, records under a name it
+/// doesn't declare are rejected.
+pub(crate) struct RecordBuffer<C, R> {
+    registry: Option<Arc<NamedRegistry<C>>>,
+    records: Arc<Mutex<Vec<R>>>,
+}
+
+impl<C: NamedConfig, R> RecordBuffer<C, R> {
+    pub(crate) fn set_registry(&mut self, registry: Arc<NamedRegistry<C>>) {
+        self.registry = Some(registry);
+    }
+
+    pub(crate) fn push(&self, name: &str, record: R) -> Result<(), UndeclaredError> {
+        if let Some(registry) = &self.registry
+            && let Err(e) = registry.validate(name)
+        {
+            warn!("{}", e);
+            return Err(e);
+        }
+        self.records.lock().unwrap().push(record);
+        Ok(())
+    }
+
+    pub(crate) fn take(&self) -> Vec<R> {
+        std::mem::take(&mut *self.records.lock().unwrap())
+    }
+}
+
+impl<C, R> Clone for RecordBuffer<C, R> {
+    fn clone(&self) -> Self {
+        Self {
+            registry: self.registry.clone(),
+            records: self.records.clone(),
+        }
+    }
+}
+
+impl<C, R> Default for RecordBuffer<C, R> {
+    fn default() -> Self {
+        Self {
+            registry: None,
+            records: Arc::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Channel {
+        name: String,
+        channel: String,
+    }
+
+    impl NamedConfig for Channel {
+        const KIND: &'static str = "Channel";
+        type Options = String;
+
+        fn new(name: &str, channel: String) -> Self {
+            Self {
+                name: name.to_string(),
+                channel,
+            }
+        }
+    }
+
+    #[test]
+    fn test_declare() {
+        let registry = NamedRegistry::<Channel>::default();
+        registry.declare("transfers", "channel-a");
+        registry.declare("swaps", "channel-b");
+        registry.declare("transfers", "channel-c");
+
+        let configs = registry.configs();
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[0].name, "swaps");
+        assert_eq!(configs[1].channel, "channel-c");
+        assert!(registry.validate("swaps").is_ok());
+        let err = registry.validate("mints").unwrap_err();
+        assert_eq!(err.to_string(), "Channel 'mints' is not declared");
+    }
+
+    #[test]
+    fn test_record_buffer() {
+        let registry = Arc::new(NamedRegistry::<Channel>::default());
+        registry.declare("transfers", "channel-a");
+
+        let mut buffer = RecordBuffer::<Channel, u32>::default();
+        // Nothing is validated until a registry is set
+        buffer.push("mints", 0).unwrap();
+        buffer.set_registry(registry);
+        let shared = buffer.clone();
+        shared.push("transfers", 1).unwrap();
+        buffer.push("transfers", 2).unwrap();
+        assert_eq!(
+            buffer.push("mints", 3),
+            Err(UndeclaredError {
+                kind: "Channel",
+                name: "mints".to_string(),
+            })
+        );
+
+        assert_eq!(shared.take(), vec![0, 1, 2]);
+        assert!(buffer.take().is_empty());
+    }
+}
@@ -1,26 +1,97 @@
+//! Plugin extension API
+//!
+//! A plugin owns every processor for one family of [`HandlerType`]s (for
+//! example all `ETH_*` handlers) and is responsible for two things:
+//!
+//! 1. **Configure** – turn its registered processors into `ContractConfig`s and
+//!    assign handler IDs. Use [`HandlerRegister`](crate::core::HandlerRegister)
+//!    to allocate IDs and [`ContractConfigBuilder`](crate::core::ContractConfigBuilder)
+//!    to assemble the config.
+//! 2. **Dispatch** – given a `DataBinding`, look up each handler ID in the
+//!    register and run the user handler through
+//!    [`dispatch_handler`](crate::core::dispatch_handler), which scopes
+//!    `RUNTIME_CONTEXT` with the binding metadata and collects state updates.
+//!
+//! Processors reach a plugin through [`PluginRegister`], usually from a
+//! `bind()` method that calls `BindableServer::register_processor::<T, P>`.
+//!
+//! ```rust,ignore
+//! use sentio_sdk::core::*;
+//! use sentio_sdk::processor::HandlerType;
+//! use sentio_sdk::{ConfigureHandlersResponse, DataBinding, ProcessResult};
+//!
+//! #[derive(Default)]
+//! struct MyChainPlugin {
+//!     handler_register: HandlerRegister<HandlerType>,
+//!     processors: Vec<MyChainProcessor>,
+//! }
+//!
+//! impl Plugin for MyChainPlugin {
+//!     fn name(&self) -> &str { "my-chain-plugin" }
+//!     fn handler_types(&self) -> &'static [HandlerType] { &[HandlerType::SuiEvent] }
+//!     fn processor_count(&self) -> usize { self.processors.len() }
+//!     fn chain_ids(&self) -> Vec<String> { /* ... */ }
+//!
+//!     fn configure(&mut self, config: &mut ConfigureHandlersResponse) {
+//!         for (processor_idx, processor) in self.processors.iter().enumerate() {
+//!             let mut builder = ContractConfigBuilder::new(processor.chain_id(), processor.address(), processor.name());
+//!             for (handler_idx, handler) in processor.handlers.iter().enumerate() {
+//!                 let handler_id = self.handler_register.register(
+//!                     processor.chain_id(), HandlerType::SuiEvent, processor_idx, handler_idx);
+//!                 builder.config_mut().move_event_configs.push(/* ... */);
+//!             }
+//!             config.contract_configs.push(builder.build());
+//!         }
+//!     }
+//! }
+//!
+//! #[tonic::async_trait]
+//! impl AsyncPluginProcessor for MyChainPlugin {
+//!     async fn process_binding(&self, data: &DataBinding) -> anyhow::Result<ProcessResult> {
+//!         let mut result = ProcessResult::default();
+//!         for &handler_id in &data.handler_ids {
+//!             let (processor, handler_idx) =
+//!                 self.handler_register.find_processor(&self.processors, &data.chain_id, handler_id)?;
+//!             let handler = &processor.handlers[handler_idx];
+//!             result = result.merge(
+//!                 dispatch_handler(metadata, MyContext::with_state_collector, |ctx| handler.handle(event, ctx)).await,
+//!             );
+//!         }
+//!         Ok(result)
+//!     }
+//! }
+//!
+//! impl FullPlugin for MyChainPlugin {}
+//! ```
 use crate::{BaseProcessor, ConfigureHandlersResponse};
 use crate::processor::HandlerType;
 use std::any::Any;
 
 /// Plugin trait that defines the available handler types for a processor
 pub trait Plugin: Send + Sync + Any {
+    /// Unique plugin name, used to identify the plugin in logs and errors
+    fn name(&self) -> &str;
+
     /// Returns an array of all supported handler types for this plugin
     fn handler_types(&self) -> &'static [HandlerType];
-    
+
     /// Get the number of registered processors
     fn processor_count(&self) -> usize;
-    
-    fn chain_ids(&self) -> Vec<String>;
 
-    fn name() -> &'static str where Self: Sized;
+    fn chain_ids(&self) -> Vec<String>;
 
     /// Configure handlers for all processors managed by the plugin
-    /// This method registers all handlers with the plugin's handler register
+    ///
+    /// This is called again whenever processors were bound at runtime. Register
+    /// every handler on each call without clearing the [`HandlerRegister`]
+    /// (crate::core::HandlerRegister): registration is idempotent, so existing
+    /// handlers keep their IDs and only new handlers get new ones.
     fn configure(&mut self, config: &mut ConfigureHandlersResponse);
-    
-    /// Process a data binding request for a specific handler type
+
     /// Returns whether this plugin can handle the given handler type
-    fn can_handle_type(&self, handler_type: HandlerType) -> bool;
+    fn can_handle_type(&self, handler_type: HandlerType) -> bool {
+        self.handler_types().contains(&handler_type)
+    }
 }
 
 /// Async processing trait for plugins - separate from Plugin for dyn compatibility
@@ -37,4 +108,4 @@ pub trait FullPlugin: Plugin + AsyncPluginProcessor {}
 pub trait PluginRegister<T: BaseProcessor + 'static> {
     /// Register a processor with this plugin
     fn register_processor(&mut self, processor: T) -> &mut T;
-} 
+}
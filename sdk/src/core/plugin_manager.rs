@@ -1,76 +1,178 @@
+use crate::core::config_updater::PendingRegistration;
+use crate::core::event_schema::EventLogRegistry;
+use crate::core::exporter::ExportRegistry;
+use crate::core::event_tracker::EventTrackerRegistry;
+use crate::core::metric_registry::MetricRegistry;
 use crate::core::plugin::FullPlugin;
 use crate::core::{RuntimeContext, RUNTIME_CONTEXT};
+use crate::processor::HandlerType;
 use crate::{DataBinding, ProcessResult};
 use dashmap::DashMap;
-use std::sync::RwLock;
+use std::any::TypeId;
+use std::sync::{Arc, Mutex, RwLock};
 use futures::FutureExt;
 
+/// Plugin shared with the bindings it is processing
+///
+/// Bindings hold the read lock while they are processed, configuring takes the
+/// write lock once the in-flight bindings are done.
+pub(crate) type SharedPlugin = Arc<tokio::sync::RwLock<Box<dyn FullPlugin>>>;
+
+/// Errors raised while registering plugins
+#[derive(Debug, thiserror::Error)]
+pub enum PluginError {
+    #[error("Handler type {handler_type:?} claimed by plugin '{plugin}' is already owned by plugin '{owner}'")]
+    HandlerTypeConflict {
+        handler_type: HandlerType,
+        owner: String,
+        plugin: String,
+    },
+
+    #[error("A different plugin named '{0}' is already registered")]
+    NameConflict(String),
+
+    #[error("Plugin '{0}' is processing bindings and can't be modified")]
+    Busy(String),
+}
+
 pub struct PluginManager {
-    pub(crate) plugins: DashMap<String, Box<dyn FullPlugin>>,
-    pub(crate) handler_type_owner: DashMap<crate::processor::HandlerType, String>,
+    pub(crate) plugins: DashMap<String, SharedPlugin>,
+    pub(crate) plugin_types: DashMap<TypeId, String>,
+    pub(crate) handler_type_owner: DashMap<HandlerType, String>,
     pub(crate) gql_schema: RwLock<Option<String>>,
+    /// Processors bound from handlers, registered on the next configure
+    pending_registrations: Mutex<Vec<PendingRegistration>>,
+    /// Metrics declared by the processors, reported in get_config
+    pub(crate) metric_registry: Arc<MetricRegistry>,
+    /// Event log schemas declared by the processors, reported in get_config
+    pub(crate) event_log_registry: EventLogRegistry,
+    /// Exports declared by the processors, reported in get_config
+    pub(crate) export_registry: Arc<ExportRegistry>,
+    /// Event trackers declared by the processors, reported in get_config
+    pub(crate) event_tracker_registry: Arc<EventTrackerRegistry>,
 }
 
 impl PluginManager {
+    /// Register a plugin instance.
+    ///
+    /// Fails without modifying the manager if a plugin of the same type is
+    /// already registered under another name, the name is taken by a different
+    /// plugin type, or one of its handler types is owned by another plugin.
+    pub fn register_plugin<P>(&self, plugin: P) -> Result<(), PluginError>
+    where
+        P: FullPlugin + 'static,
+    {
+        let name = plugin.name().to_string();
+        if self.plugins.contains_key(&name) || self.plugin_types.contains_key(&TypeId::of::<P>()) {
+            return Err(PluginError::NameConflict(name));
+        }
+
+        for handler_type in plugin.handler_types() {
+            if let Some(owner) = self.handler_type_owner.get(handler_type) {
+                return Err(PluginError::HandlerTypeConflict {
+                    handler_type: *handler_type,
+                    owner: owner.value().clone(),
+                    plugin: name,
+                });
+            }
+        }
+
+        for handler_type in plugin.handler_types() {
+            self.handler_type_owner.insert(*handler_type, name.clone());
+        }
+        self.plugin_types.insert(TypeId::of::<P>(), name.clone());
+        self.plugins
+            .insert(name, Arc::new(tokio::sync::RwLock::new(Box::new(plugin))));
+        Ok(())
+    }
+
     /// Get or create a plugin by type. If plugin doesn't exist, create it using Default::default().
-    /// This method performs the registration but returns unit since DashMap references are complex.
-    /// Use get_plugin_typed to retrieve the plugin afterwards.
-    pub fn ensure_plugin<P>(&self)
+    pub fn ensure_plugin<P>(&self) -> Result<(), PluginError>
     where
         P: FullPlugin + Default + 'static,
     {
-        // Create a temporary instance to get the plugin name
-        let name = P::name();
-
-        // Check if plugin already exists, if not insert new one
-        if !self.plugins.contains_key(name) {
-            let temp_plugin = P::default();
-            for handler_type in temp_plugin.handler_types() {
-                if self.handler_type_owner.contains_key(handler_type)
-                    && let Some(existing_owner) = self.handler_type_owner.get(handler_type) {
-                        panic!(
-                            "Handler type {:?} already owned by plugin {}",
-                            handler_type, existing_owner.value()
-                        );
-                    }
-                self.handler_type_owner
-                    .insert(*handler_type, name.to_string());
-            }
-            self.plugins.insert(name.to_string(), Box::new(temp_plugin));
+        if self.plugin_types.contains_key(&TypeId::of::<P>()) {
+            return Ok(());
         }
+        self.register_plugin(P::default())
     }
 
     /// Get or create a plugin by type and execute a closure with mutable access to it
-    pub fn with_plugin_mut<P, F, R>(&self, f: F) -> R
+    ///
+    /// Fails with [`PluginError::Busy`] while the plugin is processing bindings,
+    /// use [`Self::update_plugin`] to wait for them instead.
+    pub fn with_plugin_mut<P, F, R>(&self, f: F) -> Result<R, PluginError>
+    where
+        P: FullPlugin + Default + 'static,
+        F: FnOnce(&mut P) -> R,
+    {
+        let (name, plugin) = self.ensure_shared_plugin::<P>()?;
+        let mut plugin = plugin.try_write().map_err(|_| PluginError::Busy(name))?;
+        Ok(f(Self::downcast_mut::<P>(&mut plugin)))
+    }
+
+    /// Like [`Self::with_plugin_mut`], waiting for the bindings the plugin is processing
+    pub async fn update_plugin<P, F, R>(&self, f: F) -> Result<R, PluginError>
     where
         P: FullPlugin + Default + 'static,
         F: FnOnce(&mut P) -> R,
     {
-        self.ensure_plugin::<P>();
-        let name = P::name();
-        let mut plugin_ref = self.plugins.get_mut(name).expect("Plugin should exist after ensure");
-        let any_plugin = plugin_ref.value_mut().as_mut() as &mut dyn std::any::Any;
-        let typed_plugin = any_plugin
+        let (_, plugin) = self.ensure_shared_plugin::<P>()?;
+        let mut plugin = plugin.write().await;
+        Ok(f(Self::downcast_mut::<P>(&mut plugin)))
+    }
+
+    fn ensure_shared_plugin<P>(&self) -> Result<(String, SharedPlugin), PluginError>
+    where
+        P: FullPlugin + Default + 'static,
+    {
+        self.ensure_plugin::<P>()?;
+        let name = self.plugin_name::<P>().expect("Plugin should exist after ensure");
+        let plugin = self.shared_plugin(&name).expect("Plugin should exist after ensure");
+        Ok((name, plugin))
+    }
+
+    fn downcast_mut<P: FullPlugin + 'static>(plugin: &mut Box<dyn FullPlugin>) -> &mut P {
+        let any_plugin = plugin.as_mut() as &mut dyn std::any::Any;
+        any_plugin
             .downcast_mut::<P>()
-            .expect("Plugin type mismatch");
-        f(typed_plugin)
+            .expect("Plugin type mismatch")
     }
 
-    /// Get the total number of processors across all plugins
-    pub fn total_processor_count(&self) -> usize {
+    /// Plugin registered under `name`, cloned out so no map guard is held while it is locked
+    fn shared_plugin(&self, name: &str) -> Option<SharedPlugin> {
+        self.plugins.get(name).map(|entry| entry.value().clone())
+    }
+
+    /// All registered plugins with their names
+    fn shared_plugins(&self) -> Vec<(String, SharedPlugin)> {
         self.plugins
             .iter()
-            .map(|entry| entry.value().processor_count())
-            .sum()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+
+    /// Name under which a plugin type is registered
+    pub fn plugin_name<P: FullPlugin + 'static>(&self) -> Option<String> {
+        self.plugin_types.get(&TypeId::of::<P>()).map(|entry| entry.value().clone())
+    }
+
+    /// Get the total number of processors across all plugins
+    pub async fn total_processor_count(&self) -> usize {
+        let mut count = 0;
+        for (_, plugin) in self.shared_plugins() {
+            count += plugin.read().await.processor_count();
+        }
+        count
     }
 
     /// Get all chain IDs from all processors across all plugins
-    pub fn get_all_chain_ids(&self) -> Vec<String> {
+    pub async fn get_all_chain_ids(&self) -> Vec<String> {
         let mut chain_ids: std::collections::HashSet<String> = std::collections::HashSet::new();
 
         // Collect chain IDs from all plugins
-        for entry in self.plugins.iter() {
-            entry.value().chain_ids().into_iter().for_each(|chain_id| {
+        for (_, plugin) in self.shared_plugins() {
+            plugin.read().await.chain_ids().into_iter().for_each(|chain_id| {
                 chain_ids.insert(chain_id);
             })
         }
@@ -84,59 +186,82 @@ impl PluginManager {
     }
 
     /// Check if plugin can handle a specific handler type
-    pub fn plugin_can_handle(&self, name: &str, handler_type: crate::processor::HandlerType) -> bool {
-        self.plugins.get(name)
-            .map(|entry| entry.value().can_handle_type(handler_type))
-            .unwrap_or(false)
+    pub async fn plugin_can_handle(&self, name: &str, handler_type: HandlerType) -> bool {
+        match self.shared_plugin(name) {
+            Some(plugin) => plugin.read().await.can_handle_type(handler_type),
+            None => false,
+        }
     }
 
     /// Get a plugin by name with concrete type (read-only access)
-    pub fn with_plugin<P, F, R>(&self, f: F) -> Option<R>
+    pub async fn with_plugin<P, F, R>(&self, f: F) -> Option<R>
     where
         P: FullPlugin + 'static,
         F: FnOnce(&P) -> R,
     {
-        let name = P::name();
-        self.plugins.get(name).and_then(|plugin_ref| {
-            let any_plugin = plugin_ref.value().as_ref() as &dyn std::any::Any;
-            let typed_plugin = any_plugin.downcast_ref::<P>()?;
-            Some(f(typed_plugin))
-        })
+        let name = self.plugin_name::<P>()?;
+        let plugin = self.shared_plugin(&name)?;
+        let plugin = plugin.read().await;
+        let any_plugin = plugin.as_ref() as &dyn std::any::Any;
+        let typed_plugin = any_plugin.downcast_ref::<P>()?;
+        Some(f(typed_plugin))
+    }
+
+    /// Register processors bound from handlers since the last configure
+    ///
+    /// Returns the number of processors registered.
+    pub async fn apply_pending_registrations(&self) -> usize {
+        let pending = std::mem::take(&mut *self.pending_registrations.lock().unwrap());
+        let count = pending.len();
+        for registration in pending {
+            if let Err(e) = registration(self).await {
+                tracing::error!("Failed to register processor bound at runtime: {}", e);
+            }
+        }
+        count
+    }
+
+    /// Whether processors were bound from handlers and not yet configured
+    pub fn has_pending_registrations(&self) -> bool {
+        !self.pending_registrations.lock().unwrap().is_empty()
     }
 
     /// Configure all plugins for a specific chain_id
-    pub fn configure_all_plugins(
+    ///
+    /// Processors bound from handlers are registered first. Each plugin is
+    /// configured once the bindings it is processing are done.
+    pub async fn configure_all_plugins(
         &self,
         response: &mut crate::processor::ConfigureHandlersResponse,
     ) {
-        // First collect the plugin names to avoid borrow checker issues
-        let plugin_names: Vec<String> = self.plugins.iter().map(|entry| entry.key().clone()).collect();
-        
-        for plugin_name in plugin_names {
-            if let Some(mut plugin_entry) = self.plugins.get_mut(&plugin_name) {
-                tracing::debug!("Configuring plugin: {}", plugin_name);
-                plugin_entry.value_mut().configure(response);
-                tracing::debug!(
-                    "Plugin '{}' contributed {} contract configs",
-                    plugin_name,
-                    response.contract_configs.len()
-                );
-            }
+        let registered = self.apply_pending_registrations().await;
+        if registered > 0 {
+            tracing::info!("Registered {} processors bound at runtime", registered);
         }
 
-
+        for (plugin_name, plugin) in self.shared_plugins() {
+            tracing::debug!("Configuring plugin: {}", plugin_name);
+            plugin.write().await.configure(response);
+            tracing::debug!(
+                "Plugin '{}' contributed {} contract configs",
+                plugin_name,
+                response.contract_configs.len()
+            );
+        }
     }
 
     /// Get names of all plugins that can handle a specific handler type
-    pub fn get_plugin_names_for_handler_type(
+    pub async fn get_plugin_names_for_handler_type(
         &self,
-        handler_type: crate::processor::HandlerType,
+        handler_type: HandlerType,
     ) -> Vec<String> {
-        self.plugins
-            .iter()
-            .filter(|entry| entry.value().can_handle_type(handler_type))
-            .map(|entry| entry.key().clone())
-            .collect()
+        let mut names = Vec::new();
+        for (name, plugin) in self.shared_plugins() {
+            if plugin.read().await.can_handle_type(handler_type) {
+                names.push(name);
+            }
+        }
+        names
     }
 
     /// Set the global GraphQL schema to be returned in ProcessConfigResponse
@@ -150,12 +275,32 @@ impl PluginManager {
         self.gql_schema.read().unwrap().clone()
     }
 
+    /// Metrics declared by the processors
+    pub fn metric_registry(&self) -> &MetricRegistry {
+        &self.metric_registry
+    }
+
+    /// Event log schemas declared by the processors
+    pub fn event_log_registry(&self) -> &EventLogRegistry {
+        &self.event_log_registry
+    }
+
+    /// Exports declared by the processors
+    pub fn export_registry(&self) -> &ExportRegistry {
+        &self.export_registry
+    }
+
+    /// Event trackers declared by the processors
+    pub fn event_tracker_registry(&self) -> &EventTrackerRegistry {
+        &self.event_tracker_registry
+    }
+
     pub async fn process(
         &self,
         data: &DataBinding,
         runtime_context: RuntimeContext,
     ) -> anyhow::Result<ProcessResult> {
-        let handler_type = crate::processor::HandlerType::try_from(data.handler_type)?;
+        let handler_type = HandlerType::try_from(data.handler_type)?;
         let plugin_name = self.handler_type_owner.get(&handler_type)
             .map(|entry| entry.value().clone())
             .ok_or_else(|| {
@@ -163,17 +308,49 @@ impl PluginManager {
             })?;
 
         let plugin = self
-            .plugins
-            .get(&plugin_name)
+            .shared_plugin(&plugin_name)
             .ok_or_else(|| anyhow::anyhow!("Plugin not found: {}", plugin_name))?;
 
+        let runtime_context = runtime_context
+            .with_metric_registry(self.metric_registry.clone())
+            .with_export_registry(self.export_registry.clone())
+            .with_event_tracker_registry(self.event_tracker_registry.clone());
+        // Shares the buffers the handlers write to. Plugins can't be mutated while
+        // the binding holds them, so processors bound from handlers are queued and
+        // registered on the next configure
+        let binding_context = runtime_context.clone();
+
+        // Configuring waits until the binding releases the plugin
+        let plugin = plugin.read().await;
         // Catch panics and convert them to errors
         let future = std::panic::AssertUnwindSafe(
-            RUNTIME_CONTEXT.scope(runtime_context, plugin.value().process_binding(data))
+            RUNTIME_CONTEXT.scope(runtime_context, plugin.process_binding(data))
         );
         
         match future.catch_unwind().await {
-            Ok(process_result) => process_result,
+            Ok(Ok(mut process_result)) => {
+                // Metrics, event logs, exports and tracked events are returned with the result instead of one send per value
+                let mut timeseries_results = binding_context.take_timeseries_results();
+                timeseries_results.append(&mut process_result.timeseries_result);
+                process_result.timeseries_result = timeseries_results;
+                let mut exports = binding_context.take_export_results();
+                exports.append(&mut process_result.exports);
+                process_result.exports = exports;
+                let mut events = binding_context.take_event_tracking_results();
+                events.append(&mut process_result.events);
+                process_result.events = events;
+
+                let registrations = binding_context.pending_registrations.take();
+                if !registrations.is_empty() {
+                    self.pending_registrations.lock().unwrap().extend(registrations);
+                    process_result
+                        .states
+                        .get_or_insert_with(Default::default)
+                        .config_updated = true;
+                }
+                Ok(process_result)
+            }
+            Ok(Err(e)) => Err(e),
             Err(panic_payload) => {
                 let panic_message = if let Some(s) = panic_payload.downcast_ref::<&str>() {
                     (*s).to_string()
@@ -192,8 +369,341 @@ impl Default for PluginManager {
     fn default() -> Self {
         Self {
             plugins: DashMap::new(),
+            plugin_types: DashMap::new(),
             handler_type_owner: DashMap::new(),
             gql_schema: RwLock::new(None),
+            pending_registrations: Mutex::new(Vec::new()),
+            metric_registry: Arc::new(MetricRegistry::default()),
+            event_log_registry: EventLogRegistry::default(),
+            export_registry: Arc::new(ExportRegistry::default()),
+            event_tracker_registry: Arc::new(EventTrackerRegistry::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{dispatch_handler, Plugin, AsyncPluginProcessor, BaseContext, BaseProcessor, Context, EventTrackerOptions, MetaData, HandlerRegister, PluginRegister, StateCollector};
+    use crate::entity::store::backend::Backend;
+    use crate::testing::MemoryDatabase;
+    use crate::{BindableServer, ConfigureHandlersResponse};
+
+    /// Handler context of `FakeChainPlugin`
+    struct FakeContext {
+        base_context: BaseContext,
+        state_collector: StateCollector,
+    }
+
+    impl Context for FakeContext {
+        fn base_context(&mut self) -> &mut BaseContext {
+            &mut self.base_context
+        }
+
+        fn state_collector(&self) -> Option<&StateCollector> {
+            Some(&self.state_collector)
+        }
+    }
+
+    /// Minimal third-party style plugin built only on the public building blocks
+    #[derive(Default)]
+    struct FakeChainPlugin {
+        handler_register: HandlerRegister<HandlerType>,
+        processors: Vec<FakeProcessor>,
+    }
+
+    /// Releases the handlers of `wait:` contracts
+    static RELEASE: tokio::sync::Notify = tokio::sync::Notify::const_new();
+
+    /// Reports its contract as an error, binds a processor for `<pool>` if the
+    /// contract is `factory:<pool>`, or waits for [`RELEASE`] if it is `wait:<pool>`
+    struct FakeProcessor {
+        contract: String,
+    }
+
+    impl FakeProcessor {
+        fn new(contract: &str) -> Self {
+            Self { contract: contract.to_string() }
+        }
+
+        fn bind<S: BindableServer>(self, server: &S) {
+            server.register_processor::<FakeProcessor, FakeChainPlugin>(self);
+        }
+    }
+
+    impl BaseProcessor for FakeProcessor {
+        fn chain_id(&self) -> &str {
+            "sui_mainnet"
+        }
+    }
+
+    impl PluginRegister<FakeProcessor> for FakeChainPlugin {
+        fn register_processor(&mut self, processor: FakeProcessor) -> &mut FakeProcessor {
+            self.processors.push(processor);
+            self.processors.last_mut().unwrap()
+        }
+    }
+
+    impl Plugin for FakeChainPlugin {
+        fn name(&self) -> &str {
+            "fake-chain-plugin"
+        }
+
+        fn handler_types(&self) -> &'static [HandlerType] {
+            &[HandlerType::SuiEvent]
+        }
+
+        fn processor_count(&self) -> usize {
+            self.processors.len()
+        }
+
+        fn chain_ids(&self) -> Vec<String> {
+            vec!["sui_mainnet".to_string()]
+        }
+
+        fn configure(&mut self, config: &mut ConfigureHandlersResponse) {
+            for (processor_idx, processor) in self.processors.iter().enumerate() {
+                let contract = &processor.contract;
+                let builder = crate::core::ContractConfigBuilder::new("sui_mainnet", contract, contract);
+                self.handler_register.register("sui_mainnet", HandlerType::SuiEvent, processor_idx, 0);
+                config.contract_configs.push(builder.build());
+            }
+        }
+    }
+
+    #[tonic::async_trait]
+    impl AsyncPluginProcessor for FakeChainPlugin {
+        async fn process_binding(&self, data: &DataBinding) -> anyhow::Result<ProcessResult> {
+            let mut result = ProcessResult::default();
+            for &handler_id in &data.handler_ids {
+                let info = self.handler_register.resolve(&data.chain_id, handler_id)?;
+                let metadata = MetaData {
+                    contract_name: self.processors[info.processor_idx].contract.clone(),
+                    ..Default::default()
+                };
+                let handler_result = dispatch_handler(
+                    metadata,
+                    |state_collector| FakeContext { base_context: BaseContext::new(), state_collector },
+                    |ctx| async move {
+                        let contract = RUNTIME_CONTEXT.with(|ctx| ctx.metadata().contract_name.clone());
+                        match contract.strip_prefix("factory:") {
+                            Some(pool) => FakeProcessor::new(pool).bind(&ctx.config_updater()),
+                            None if contract.starts_with("wait:") => RELEASE.notified().await,
+                            None => {
+                                crate::core::Counter::new("calls").add(1, None).await.unwrap();
+                                ctx.exporter("pools").emit(&contract).await.unwrap();
+                                ctx.event_tracker("calls").track(&contract, [("handler", "fake")]).await.unwrap();
+                                ctx.report_error(contract);
+                            }
+                        }
+                    },
+                )
+                .await;
+                result = result.merge(handler_result);
+            }
+            Ok(result)
+        }
+    }
+
+    impl FullPlugin for FakeChainPlugin {}
+
+    /// Claims a handler type already owned by `FakeChainPlugin`
+    #[derive(Default)]
+    struct ConflictingPlugin;
+
+    impl Plugin for ConflictingPlugin {
+        fn name(&self) -> &str {
+            "conflicting-plugin"
+        }
+
+        fn handler_types(&self) -> &'static [HandlerType] {
+            &[HandlerType::SuiCall, HandlerType::SuiEvent]
+        }
+
+        fn processor_count(&self) -> usize {
+            0
+        }
+
+        fn chain_ids(&self) -> Vec<String> {
+            vec![]
+        }
+
+        fn configure(&mut self, _config: &mut ConfigureHandlersResponse) {}
+    }
+
+    #[tonic::async_trait]
+    impl AsyncPluginProcessor for ConflictingPlugin {
+        async fn process_binding(&self, _data: &DataBinding) -> anyhow::Result<ProcessResult> {
+            Ok(ProcessResult::default())
+        }
+    }
+
+    impl FullPlugin for ConflictingPlugin {}
+
+    #[test]
+    fn test_duplicate_handler_type_is_an_error() {
+        let manager = PluginManager::default();
+        manager.ensure_plugin::<FakeChainPlugin>().unwrap();
+        // Ensuring again is a no-op
+        manager.ensure_plugin::<FakeChainPlugin>().unwrap();
+
+        let err = manager.ensure_plugin::<ConflictingPlugin>().unwrap_err();
+        assert!(matches!(
+            err,
+            PluginError::HandlerTypeConflict { handler_type: HandlerType::SuiEvent, .. }
+        ));
+        // The failed registration must not claim any handler type
+        assert!(!manager.handler_type_owner.contains_key(&HandlerType::SuiCall));
+        assert!(!manager.get_plugin("conflicting-plugin"));
+        assert!(manager.with_plugin_mut::<ConflictingPlugin, _, _>(|_| ()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_custom_plugin_dispatch() {
+        let manager = PluginManager::default();
+        manager
+            .with_plugin_mut::<FakeChainPlugin, _, _>(|plugin| {
+                plugin.register_processor(FakeProcessor::new("0x1::pool"));
+                plugin.register_processor(FakeProcessor::new("0x2::pool"));
+            })
+            .unwrap();
+        manager.export_registry().declare("pools", "pool-channel");
+        manager.event_tracker_registry().declare("calls", EventTrackerOptions::default());
+        assert_eq!(manager.plugin_name::<FakeChainPlugin>().as_deref(), Some("fake-chain-plugin"));
+
+        let mut config = ConfigureHandlersResponse::default();
+        manager.configure_all_plugins(&mut config).await;
+        // Configuring again keeps handler ids stable
+        manager.configure_all_plugins(&mut ConfigureHandlersResponse::default()).await;
+        assert_eq!(config.contract_configs.len(), 2);
+        assert_eq!(config.contract_configs[0].processor_type, crate::core::USER_PROCESSOR);
+        assert_eq!(manager.with_plugin::<FakeChainPlugin, _, _>(|p| p.handler_register.len()).await, Some(2));
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+        let backend = Arc::new(Backend::memory(Arc::new(MemoryDatabase::new())));
+        let runtime_context = RuntimeContext::new_with_empty_metadata(tx, 1, backend);
+        let binding = DataBinding {
+            chain_id: "sui_mainnet".to_string(),
+            handler_type: HandlerType::SuiEvent as i32,
+            handler_ids: vec![1, 0],
+            ..Default::default()
+        };
+
+        let result = manager.process(&binding, runtime_context).await.unwrap();
+        // Metrics are returned with the result, in the order they were recorded
+        let contracts: Vec<_> = result
+            .timeseries_result
+            .iter()
+            .map(|ts| ts.metadata.as_ref().unwrap().contract_name.as_str())
+            .collect();
+        assert_eq!(contracts, vec!["0x2::pool", "0x1::pool"]);
+        let payloads: Vec<_> = result.exports.iter().map(|export| export.payload.as_str()).collect();
+        assert_eq!(payloads, vec!["\"0x2::pool\"", "\"0x1::pool\""]);
+        assert_eq!(result.exports[0].metadata.as_ref().unwrap().name, "pools");
+        let tracked: Vec<_> = result.events.iter().map(|event| event.distinct_entity_id.as_str()).collect();
+        assert_eq!(tracked, vec!["0x2::pool", "0x1::pool"]);
+        assert!(rx.try_recv().is_err());
+        let states = result.states.unwrap();
+        assert_eq!(states.error.as_deref(), Some("0x2::pool; 0x1::pool"));
+    }
+
+    #[tokio::test]
+    async fn test_bind_processor_from_handler() {
+        let manager = PluginManager::default();
+        manager
+            .with_plugin_mut::<FakeChainPlugin, _, _>(|plugin| {
+                plugin.register_processor(FakeProcessor::new("0x1::pool"));
+                plugin.register_processor(FakeProcessor::new("factory:0x3::pool"));
+            })
+            .unwrap();
+        manager.export_registry().declare("pools", "pool-channel");
+        manager.event_tracker_registry().declare("calls", EventTrackerOptions::default());
+        let mut config = ConfigureHandlersResponse::default();
+        manager.configure_all_plugins(&mut config).await;
+        assert_eq!(config.contract_configs.len(), 2);
+
+        let (tx, _rx) = tokio::sync::mpsc::channel(16);
+        let backend = Arc::new(Backend::memory(Arc::new(MemoryDatabase::new())));
+        let binding = DataBinding {
+            chain_id: "sui_mainnet".to_string(),
+            handler_type: HandlerType::SuiEvent as i32,
+            handler_ids: vec![1],
+            ..Default::default()
+        };
+        let runtime_context = RuntimeContext::new_with_empty_metadata(tx.clone(), 1, backend.clone());
+        let result = manager.process(&binding, runtime_context).await.unwrap();
+        assert!(result.states.unwrap().config_updated);
+
+        // The processor is registered when the driver fetches the config again
+        assert!(manager.has_pending_registrations());
+        assert_eq!(manager.total_processor_count().await, 2);
+        let mut updated = ConfigureHandlersResponse::default();
+        manager.configure_all_plugins(&mut updated).await;
+        assert!(!manager.has_pending_registrations());
+        assert_eq!(updated.contract_configs.len(), 3);
+        assert_eq!(updated.contract_configs[..2], config.contract_configs[..]);
+
+        // Existing handler ids are unchanged and the new handler gets the next one
+        let handlers = manager
+            .with_plugin::<FakeChainPlugin, _, _>(|plugin| {
+                (0..3)
+                    .map(|id| plugin.handler_register.get("sui_mainnet", id).map(|(_, processor_idx, _)| processor_idx))
+                    .collect::<Vec<_>>()
+            })
+            .await
+            .unwrap();
+        assert_eq!(handlers, vec![Some(0), Some(1), Some(2)]);
+
+        let binding = DataBinding { handler_ids: vec![2, 0], ..binding };
+        let runtime_context = RuntimeContext::new_with_empty_metadata(tx, 2, backend);
+        let result = manager.process(&binding, runtime_context).await.unwrap();
+        let states = result.states.unwrap();
+        assert!(!states.config_updated);
+        assert_eq!(states.error.as_deref(), Some("0x3::pool; 0x1::pool"));
+    }
+
+    #[tokio::test]
+    async fn test_get_config_while_binding_is_pending() {
+        let manager = Arc::new(PluginManager::default());
+        manager
+            .with_plugin_mut::<FakeChainPlugin, _, _>(|plugin| {
+                plugin.register_processor(FakeProcessor::new("wait:0x1::pool"));
+            })
+            .unwrap();
+        manager.configure_all_plugins(&mut ConfigureHandlersResponse::default()).await;
+
+        let (tx, _rx) = tokio::sync::mpsc::channel(16);
+        let backend = Arc::new(Backend::memory(Arc::new(MemoryDatabase::new())));
+        let runtime_context = RuntimeContext::new_with_empty_metadata(tx, 1, backend);
+        let binding = DataBinding {
+            chain_id: "sui_mainnet".to_string(),
+            handler_type: HandlerType::SuiEvent as i32,
+            handler_ids: vec![0],
+            ..Default::default()
+        };
+        let pending = tokio::spawn({
+            let manager = manager.clone();
+            async move { manager.process(&binding, runtime_context).await }
+        });
+        while manager.with_plugin_mut::<FakeChainPlugin, _, _>(|_| ()).is_ok() {
+            tokio::task::yield_now().await;
+        }
+        assert!(matches!(
+            manager.with_plugin_mut::<FakeChainPlugin, _, _>(|_| ()),
+            Err(PluginError::Busy(_))
+        ));
+
+        // The driver fetches the config again on the same (current thread) runtime
+        let mut config = ConfigureHandlersResponse::default();
+        {
+            let configure = manager.configure_all_plugins(&mut config);
+            tokio::pin!(configure);
+            assert!(futures::poll!(&mut configure).is_pending());
+
+            RELEASE.notify_one();
+            configure.await;
         }
+        assert_eq!(config.contract_configs.len(), 1);
+        assert!(pending.await.unwrap().is_ok());
     }
 }
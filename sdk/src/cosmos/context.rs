@@ -0,0 +1,60 @@
+use crate::Timestamp;
+use crate::core::{BaseContext, Context, StateCollector};
+
+/// Context passed to Cosmos handlers
+#[derive(Clone)]
+pub struct CosmosContext {
+    base_context: BaseContext,
+    state_collector: Option<StateCollector>,
+    block_height: u64,
+    transaction_hash: String,
+    timestamp: Timestamp,
+}
+
+impl CosmosContext {
+    /// Create a new CosmosContext for a transaction included in block `block_height`
+    pub fn new(
+        block_height: u64,
+        transaction_hash: impl Into<String>,
+        timestamp: Timestamp,
+    ) -> Self {
+        Self {
+            base_context: BaseContext::new(),
+            state_collector: None,
+            block_height,
+            transaction_hash: transaction_hash.into(),
+            timestamp,
+        }
+    }
+
+    /// Enable state collection for this context
+    pub fn with_state_collector(mut self, state_collector: StateCollector) -> Self {
+        self.state_collector = Some(state_collector);
+        self
+    }
+
+    /// Height of the block being processed
+    pub fn block_height(&self) -> u64 {
+        self.block_height
+    }
+
+    /// Hash of the transaction being processed
+    pub fn transaction_hash(&self) -> &str {
+        &self.transaction_hash
+    }
+
+    /// Block timestamp
+    pub fn timestamp(&self) -> &Timestamp {
+        &self.timestamp
+    }
+}
+
+impl Context for CosmosContext {
+    fn base_context(&mut self) -> &mut BaseContext {
+        &mut self.base_context
+    }
+
+    fn state_collector(&self) -> Option<&StateCollector> {
+        self.state_collector.as_ref()
+    }
+}
@@ -0,0 +1,324 @@
+use crate::core::conversions::struct_to_json;
+use crate::core::plugin::FullPlugin;
+use crate::core::{
+    AsyncPluginProcessor, BaseProcessor, ContractConfigBuilder, HandlerRegister, MetaData, Plugin,
+    PluginRegister, binding_value, dispatch_handler,
+};
+use crate::cosmos::context::CosmosContext;
+use crate::cosmos::cosmos_processor::CosmosProcessorImpl;
+use crate::cosmos::{CosmosLog, CosmosTransaction};
+use crate::processor::{HandlerType, data};
+use crate::{ConfigureHandlersResponse, CosmosLogHandlerConfig, Timestamp};
+use tracing::debug;
+
+#[derive(Default)]
+pub struct CosmosPlugin {
+    handler_register: HandlerRegister<HandlerType>,
+    processors: Vec<Box<CosmosProcessorImpl>>,
+}
+
+impl Plugin for CosmosPlugin {
+    fn name(&self) -> &str {
+        "cosmos-plugin"
+    }
+
+    fn handler_types(&self) -> &'static [HandlerType] {
+        &[HandlerType::CosmosCall]
+    }
+
+    fn processor_count(&self) -> usize {
+        self.processors.len()
+    }
+
+    fn chain_ids(&self) -> Vec<String> {
+        self.processors
+            .iter()
+            .map(|p| p.chain_id().to_string())
+            .collect()
+    }
+
+    fn configure(&mut self, config: &mut ConfigureHandlersResponse) {
+        debug!("Configuring CosmosPlugin handlers for all chains");
+
+        for (processor_idx, processor) in self.processors.iter().enumerate() {
+            let chain_id = processor.chain_id();
+
+            let mut builder = ContractConfigBuilder::new(
+                chain_id,
+                processor.options.address.clone(),
+                processor.name(),
+            )
+            .start_block(processor.options.start_block.unwrap_or_default())
+            .end_block(processor.options.end_block.unwrap_or_default());
+
+            for (handler_idx, handler) in processor.log_handlers.iter().enumerate() {
+                let handler_id = self.handler_register.register(
+                    chain_id,
+                    HandlerType::CosmosCall,
+                    processor_idx,
+                    handler_idx,
+                );
+                builder
+                    .config_mut()
+                    .cosmos_log_configs
+                    .push(CosmosLogHandlerConfig {
+                        log_filters: handler.log_filters.clone(),
+                        handler_id,
+                        handler_name: handler.options.name.clone().unwrap_or_default(),
+                    });
+            }
+
+            config.contract_configs.push(builder.build());
+        }
+    }
+}
+
+impl CosmosPlugin {
+    async fn process_call(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        let cosmos_call = match binding_value(data)? {
+            data::Value::CosmosCall(cosmos_call) => cosmos_call,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Expected Cosmos call data but got different type"
+                ));
+            }
+        };
+        let transaction: CosmosTransaction = match &cosmos_call.transaction {
+            Some(transaction) => serde_json::from_value(struct_to_json(transaction))
+                .map_err(|e| anyhow::anyhow!("Failed to parse Cosmos transaction: {}", e))?,
+            None => return Err(anyhow::anyhow!("Cosmos call binding has no transaction")),
+        };
+        let timestamp = Timestamp::from(
+            cosmos_call
+                .timestamp
+                .or_else(|| transaction_time(&transaction))
+                .unwrap_or_default(),
+        );
+        let block_height = transaction.height();
+        let events = transaction.message_events();
+
+        let mut result = crate::ProcessResult::default();
+        for &handler_id in &data.handler_ids {
+            let (processor, handler_idx) = self
+                .handler_register
+                .find_processor(&self.processors, &data.chain_id, handler_id)?;
+            let handler = processor.log_handlers.get(handler_idx).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Log handler index {} not found in processor {}",
+                    handler_idx,
+                    processor.name()
+                )
+            })?;
+
+            for (event_index, (msg_index, event)) in events.iter().enumerate() {
+                if !handler
+                    .log_filters
+                    .iter()
+                    .any(|filter| event.matches_filter(filter))
+                {
+                    continue;
+                }
+                let log = CosmosLog {
+                    event: (*event).clone(),
+                    msg_index: *msg_index,
+                    event_index,
+                    transaction: transaction.clone(),
+                };
+                let metadata = MetaData {
+                    address: processor.options.address.clone(),
+                    contract_name: processor.name().to_string(),
+                    chain_id: data.chain_id.clone(),
+                    block_number: block_height,
+                    timestamp: timestamp.clone(),
+                    transaction_hash: transaction.txhash.clone(),
+                    transaction_index: 0,
+                    log_index: event_index as i32,
+                    base_labels: Default::default(),
+                };
+                let handler_result = dispatch_handler(
+                    metadata,
+                    |collector| {
+                        CosmosContext::new(
+                            block_height,
+                            transaction.txhash.clone(),
+                            timestamp.clone(),
+                        )
+                        .with_state_collector(collector)
+                    },
+                    |ctx| handler.handler.handle_log(log, ctx),
+                )
+                .await;
+                result = result.merge(handler_result);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Block time reported in the transaction response
+fn transaction_time(transaction: &CosmosTransaction) -> Option<prost_types::Timestamp> {
+    let time = chrono::DateTime::parse_from_rfc3339(transaction.timestamp.as_deref()?).ok()?;
+    Some(prost_types::Timestamp {
+        seconds: time.timestamp(),
+        nanos: time.timestamp_subsec_nanos() as i32,
+    })
+}
+
+#[tonic::async_trait]
+impl AsyncPluginProcessor for CosmosPlugin {
+    async fn process_binding(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        debug!(
+            "CosmosPlugin processing binding for chain_id: {}, handler_ids: {:?}",
+            data.chain_id, data.handler_ids
+        );
+
+        let handler_type = HandlerType::try_from(data.handler_type)?;
+        match handler_type {
+            HandlerType::CosmosCall => self.process_call(data).await,
+            _ => Err(anyhow::anyhow!(
+                "Unsupported handler type: {:?}",
+                handler_type
+            )),
+        }
+    }
+}
+
+impl FullPlugin for CosmosPlugin {}
+
+impl PluginRegister<CosmosProcessorImpl> for CosmosPlugin {
+    fn register_processor(&mut self, processor: CosmosProcessorImpl) -> &mut CosmosProcessorImpl {
+        debug!(
+            "Registering processor: {} (chain_id: {})",
+            processor.name(),
+            processor.chain_id()
+        );
+
+        self.processors.push(Box::new(processor));
+        self.processors.last_mut().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Context;
+    use crate::cosmos::{CosmosEvent, CosmosLogHandler, CosmosLogMarker, CosmosProcessor};
+    use crate::testing::TestProcessorServer;
+
+    struct BankProcessor;
+
+    impl CosmosProcessor for BankProcessor {
+        fn name(&self) -> &str {
+            "bank"
+        }
+    }
+
+    struct Transfers;
+
+    impl CosmosLogMarker for Transfers {
+        fn log_filters() -> &'static [&'static str] {
+            &["transfer"]
+        }
+    }
+
+    struct MsgSend;
+
+    impl CosmosLogMarker for MsgSend {
+        fn log_filters() -> &'static [&'static str] {
+            &["/cosmos.bank.v1beta1.MsgSend"]
+        }
+    }
+
+    #[crate::async_trait]
+    impl CosmosLogHandler<Transfers> for BankProcessor {
+        async fn on_log(&self, log: CosmosLog, mut ctx: CosmosContext) {
+            let amount: f64 = log
+                .event
+                .attribute("amount")
+                .and_then(|amount| amount.trim_end_matches("inj").parse().ok())
+                .unwrap_or_default();
+            ctx.base_context()
+                .counter("transferred")
+                .add(amount, None)
+                .await
+                .ok();
+        }
+    }
+
+    #[crate::async_trait]
+    impl CosmosLogHandler<MsgSend> for BankProcessor {
+        async fn on_log(&self, log: CosmosLog, mut ctx: CosmosContext) {
+            assert_eq!(log.msg_index, Some(1));
+            ctx.base_context()
+                .counter("sends")
+                .add(1.0, None)
+                .await
+                .ok();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cosmos_plugin_configure_and_dispatch() {
+        let mut server = TestProcessorServer::new();
+        BankProcessor
+            .configure_log::<Transfers>(None)
+            .configure_log::<MsgSend>(None)
+            .from_block(50_000_000)
+            .bind(&server);
+        server.start().await.unwrap();
+
+        let config = server.get_config().await;
+        assert_eq!(config.contract_configs.len(), 1);
+        let contract_config = &config.contract_configs[0];
+        assert_eq!(contract_config.start_block, 50_000_000);
+        assert_eq!(contract_config.cosmos_log_configs.len(), 2);
+        assert_eq!(
+            contract_config.cosmos_log_configs[0].log_filters,
+            vec!["transfer"]
+        );
+
+        let facet = server.cosmos();
+        let transaction = CosmosTransaction::new("ABCD")
+            .with_height(55_000_000)
+            .with_event(
+                0,
+                CosmosEvent::new("transfer").with_attribute("amount", "100inj"),
+            )
+            .with_event(
+                1,
+                CosmosEvent::new("message")
+                    .with_attribute("action", "/cosmos.bank.v1beta1.MsgSend"),
+            )
+            .with_event(
+                1,
+                CosmosEvent::new("transfer").with_attribute("amount", "5inj"),
+            );
+        let unrelated = CosmosTransaction::new("EF01")
+            .with_height(55_000_001)
+            .with_event(
+                0,
+                CosmosEvent::new("wasm").with_attribute("_contract_address", "inj1xyz"),
+            );
+        let result = facet
+            .test_transactions(vec![transaction, unrelated], None)
+            .await;
+
+        let transferred: Vec<f64> = result
+            .counters
+            .iter()
+            .filter(|c| c.name == "transferred")
+            .map(|c| c.value)
+            .collect();
+        assert_eq!(transferred, vec![100.0, 5.0]);
+        assert_eq!(result.first_counter_value("sends"), Some(1.0));
+        assert_eq!(result.counters.len(), 3);
+        assert_eq!(result.counters[0].metadata.block_number, Some(55_000_000));
+    }
+}
@@ -0,0 +1,206 @@
+use crate::core::BaseProcessor;
+use crate::cosmos::context::CosmosContext;
+use crate::cosmos::{
+    CosmosLog, CosmosLogHandler, CosmosLogMarker, CosmosPlugin, INJECTIVE_MAINNET,
+};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct CosmosBindOptions {
+    /// Contract address, `*` to handle events from any contract
+    pub address: String,
+    /// Optional, if not set, then use injective mainnet
+    pub network: Option<String>,
+    /// Optional, override default processor name
+    pub name: Option<String>,
+    pub start_block: Option<u64>,
+    pub end_block: Option<u64>,
+}
+
+impl CosmosBindOptions {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            network: None,
+            name: None,
+            start_block: None,
+            end_block: None,
+        }
+    }
+
+    pub fn with_network(mut self, network: impl Into<String>) -> Self {
+        self.network = Some(network.into());
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn from_block(mut self, block: u64) -> Self {
+        self.start_block = Some(block);
+        self
+    }
+
+    pub fn to_block(mut self, block: u64) -> Self {
+        self.end_block = Some(block);
+        self
+    }
+}
+
+/// Options for a single Cosmos handler
+#[derive(Clone, Default)]
+pub struct CosmosHandlerOptions {
+    pub name: Option<String>,
+}
+
+impl CosmosHandlerOptions {
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+}
+
+/// A configurable Cosmos processor that can register log handlers
+pub struct ConfigurableCosmosProcessor<P: CosmosProcessor> {
+    processor: Arc<P>,
+    options: CosmosBindOptions,
+    log_handlers: Vec<LogHandler>,
+}
+
+impl<P: CosmosProcessor> ConfigurableCosmosProcessor<P> {
+    /// Create a new configurable processor
+    pub fn new(processor: P) -> Self {
+        let options = CosmosBindOptions::new(processor.address())
+            .with_network(processor.chain_id())
+            .with_name(processor.name());
+        Self {
+            processor: Arc::new(processor),
+            options,
+            log_handlers: Vec::new(),
+        }
+    }
+
+    /// Handle events matching the log filters of `T`
+    pub fn configure_log<T: CosmosLogMarker>(
+        mut self,
+        options: Option<CosmosHandlerOptions>,
+    ) -> Self
+    where
+        P: CosmosLogHandler<T>,
+    {
+        let handler: Arc<dyn TypeErasedCosmosLogHandler> =
+            Arc::new((Arc::clone(&self.processor), PhantomData::<T>));
+        self.log_handlers.push(LogHandler {
+            handler,
+            log_filters: T::log_filters().iter().map(|f| f.to_string()).collect(),
+            options: options.unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Start processing from `block`
+    pub fn from_block(mut self, block: u64) -> Self {
+        self.options = self.options.from_block(block);
+        self
+    }
+
+    /// Stop processing after `block`
+    pub fn to_block(mut self, block: u64) -> Self {
+        self.options = self.options.to_block(block);
+        self
+    }
+
+    /// Bind this configured processor to a server
+    pub fn bind<S: crate::BindableServer>(self, server: &S) {
+        let processor_impl = CosmosProcessorImpl {
+            options: self.options,
+            log_handlers: self.log_handlers,
+            _processor: self.processor,
+        };
+
+        server.register_processor::<CosmosProcessorImpl, CosmosPlugin>(processor_impl);
+    }
+}
+
+/// Core trait that all Cosmos processors must implement
+pub trait CosmosProcessor: Send + Sync + 'static {
+    /// Get the contract address this processor handles, defaults to `*`
+    fn address(&self) -> &str {
+        "*"
+    }
+
+    /// Get the network, defaults to Injective mainnet
+    fn chain_id(&self) -> &str {
+        INJECTIVE_MAINNET
+    }
+
+    /// Get the processor name
+    fn name(&self) -> &str;
+
+    fn configure_log<T: CosmosLogMarker>(
+        self,
+        options: Option<CosmosHandlerOptions>,
+    ) -> ConfigurableCosmosProcessor<Self>
+    where
+        Self: Sized + CosmosLogHandler<T>,
+    {
+        ConfigurableCosmosProcessor::new(self).configure_log::<T>(options)
+    }
+}
+
+// Type-erased handler so processors with different markers can share storage
+pub trait TypeErasedCosmosLogHandler: Send + Sync {
+    fn handle_log(
+        &self,
+        log: CosmosLog,
+        ctx: CosmosContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+}
+
+impl<H, T> TypeErasedCosmosLogHandler for (H, PhantomData<T>)
+where
+    H: CosmosLogHandler<T>,
+    T: CosmosLogMarker,
+{
+    fn handle_log(
+        &self,
+        log: CosmosLog,
+        ctx: CosmosContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(self.0.on_log(log, ctx))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct LogHandler {
+    pub(crate) handler: Arc<dyn TypeErasedCosmosLogHandler>,
+    pub(crate) log_filters: Vec<String>,
+    pub(crate) options: CosmosHandlerOptions,
+}
+
+// Internal storage for processor data - used by the plugin system
+#[derive(Clone)]
+pub(crate) struct CosmosProcessorImpl {
+    pub(crate) options: CosmosBindOptions,
+    pub(crate) log_handlers: Vec<LogHandler>,
+    pub(crate) _processor: Arc<dyn CosmosProcessor>,
+}
+
+impl BaseProcessor for CosmosProcessorImpl {
+    fn chain_id(&self) -> &str {
+        self.options.network.as_deref().unwrap_or(INJECTIVE_MAINNET)
+    }
+
+    fn name(&self) -> &str {
+        self.options.name.as_deref().unwrap_or("cosmos-processor")
+    }
+
+    fn handler_count(&self) -> usize {
+        self.log_handlers.len()
+    }
+}
@@ -0,0 +1,220 @@
+use crate::cosmos::context::CosmosContext;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Chain id of Injective mainnet
+pub const INJECTIVE_MAINNET: &str = "injective_mainnet";
+/// Chain id of Injective testnet
+pub const INJECTIVE_TESTNET: &str = "injective_testnet";
+
+/// Event type of the `message` event every Cosmos message emits
+pub const MESSAGE_EVENT: &str = "message";
+
+/// Attribute of a Cosmos event
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CosmosEventAttribute {
+    pub key: String,
+    pub value: String,
+}
+
+/// An event emitted while executing a Cosmos transaction
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CosmosEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub attributes: Vec<CosmosEventAttribute>,
+}
+
+impl CosmosEvent {
+    pub fn new(event_type: impl Into<String>) -> Self {
+        Self {
+            event_type: event_type.into(),
+            attributes: Vec::new(),
+        }
+    }
+
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes.push(CosmosEventAttribute {
+            key: key.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Value of the first attribute named `key`
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.as_str())
+    }
+
+    /// Whether the event matches a log filter
+    ///
+    /// A filter matches events of that type, and `message` events whose `action`
+    /// is that message type, e.g. `/cosmos.bank.v1beta1.MsgSend`.
+    pub fn matches_filter(&self, filter: &str) -> bool {
+        self.event_type == filter
+            || (self.event_type == MESSAGE_EVENT && self.attribute("action") == Some(filter))
+    }
+}
+
+/// Events emitted by one message of a transaction
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CosmosMessageLog {
+    pub msg_index: u32,
+    pub events: Vec<CosmosEvent>,
+}
+
+/// A transaction in the format of the Cosmos `TxResponse`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CosmosTransaction {
+    pub txhash: String,
+    /// Block height, encoded as a string by the Cosmos REST API
+    pub height: String,
+    /// Result code, `0` for success
+    pub code: u32,
+    /// Block time in RFC 3339 format
+    pub timestamp: Option<String>,
+    /// Events grouped by message, empty since Cosmos SDK 0.50
+    pub logs: Vec<CosmosMessageLog>,
+    /// All events of the transaction
+    pub events: Vec<CosmosEvent>,
+    /// Decoded transaction body
+    pub tx: Value,
+}
+
+impl CosmosTransaction {
+    pub fn new(txhash: impl Into<String>) -> Self {
+        Self {
+            txhash: txhash.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_height(mut self, height: u64) -> Self {
+        self.height = height.to_string();
+        self
+    }
+
+    /// Append an event emitted by message `msg_index`
+    pub fn with_event(mut self, msg_index: u32, event: CosmosEvent) -> Self {
+        match self.logs.iter_mut().find(|log| log.msg_index == msg_index) {
+            Some(log) => log.events.push(event),
+            None => self.logs.push(CosmosMessageLog {
+                msg_index,
+                events: vec![event],
+            }),
+        }
+        self
+    }
+
+    pub fn height(&self) -> u64 {
+        self.height.parse().unwrap_or_default()
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.code == 0
+    }
+
+    /// Events with the index of the message emitting them
+    ///
+    /// Uses the per message logs when present, otherwise the flat event list
+    /// with message indexes taken from the `msg_index` attribute.
+    pub fn message_events(&self) -> Vec<(Option<u32>, &CosmosEvent)> {
+        if self.logs.is_empty() {
+            self.events
+                .iter()
+                .map(|event| {
+                    (
+                        event.attribute("msg_index").and_then(|i| i.parse().ok()),
+                        event,
+                    )
+                })
+                .collect()
+        } else {
+            self.logs
+                .iter()
+                .flat_map(|log| log.events.iter().map(|event| (Some(log.msg_index), event)))
+                .collect()
+        }
+    }
+}
+
+/// An event matching the log filters of a [`CosmosLogHandler`]
+#[derive(Debug, Clone)]
+pub struct CosmosLog {
+    pub event: CosmosEvent,
+    /// Index of the message emitting the event, if known
+    pub msg_index: Option<u32>,
+    /// Index of the event within the transaction
+    pub event_index: usize,
+    pub transaction: CosmosTransaction,
+}
+
+/// Marker for the events handled by a [`CosmosLogHandler`]
+pub trait CosmosLogMarker: Send + Sync + 'static {
+    /// Event types or message types, e.g. `wasm` or `/cosmos.bank.v1beta1.MsgSend`
+    fn log_filters() -> &'static [&'static str];
+}
+
+#[crate::async_trait]
+pub trait CosmosLogHandler<T: CosmosLogMarker>: Send + Sync + 'static {
+    async fn on_log(&self, log: CosmosLog, ctx: CosmosContext);
+}
+
+// Implementation for Arc<H> so a shared processor can be registered for several markers
+#[crate::async_trait]
+impl<H, T> CosmosLogHandler<T> for std::sync::Arc<H>
+where
+    H: CosmosLogHandler<T>,
+    T: CosmosLogMarker,
+{
+    async fn on_log(&self, log: CosmosLog, ctx: CosmosContext) {
+        self.as_ref().on_log(log, ctx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_transaction() {
+        let raw = r#"{
+            "height": "55000000",
+            "txhash": "ABCD",
+            "code": 0,
+            "timestamp": "2024-01-01T00:00:00Z",
+            "logs": [],
+            "events": [
+                {"type": "message", "attributes": [
+                    {"key": "action", "value": "/cosmos.bank.v1beta1.MsgSend", "index": true},
+                    {"key": "msg_index", "value": "0", "index": true}
+                ]},
+                {"type": "transfer", "attributes": [
+                    {"key": "amount", "value": "100inj", "index": true},
+                    {"key": "msg_index", "value": "0", "index": true}
+                ]},
+                {"type": "tx", "attributes": [{"key": "fee", "value": "1inj", "index": true}]}
+            ],
+            "tx": {"body": {"messages": []}}
+        }"#;
+        let transaction: CosmosTransaction = serde_json::from_str(raw).unwrap();
+        assert_eq!(transaction.height(), 55_000_000);
+        assert!(transaction.is_success());
+
+        let events = transaction.message_events();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[1].0, Some(0));
+        assert_eq!(events[1].1.attribute("amount"), Some("100inj"));
+        assert_eq!(events[2].0, None);
+        assert!(events[0].1.matches_filter("/cosmos.bank.v1beta1.MsgSend"));
+        assert!(events[1].1.matches_filter("transfer"));
+        assert!(!events[2].1.matches_filter("transfer"));
+    }
+}
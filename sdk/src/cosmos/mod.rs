@@ -0,0 +1,9 @@
+pub mod context;
+pub mod cosmos_plugin;
+pub mod cosmos_processor;
+mod cosmos_types;
+
+pub use cosmos_types::*;
+
+pub use cosmos_plugin::CosmosPlugin;
+pub use cosmos_processor::{CosmosBindOptions, CosmosHandlerOptions, CosmosProcessor};
@@ -3,6 +3,7 @@
 use crate::codegen::{CodeGenerator, CodegenResult};
 use crate::entity::schema::parser::SchemaParser;
 use crate::entity::schema::{EntitySchema, EntityType, FieldDefinition, FieldType};
+use crate::entity::types::ScalarType;
 use anyhow::{Context, Result};
 use convert_case::{Case, Casing};
 use rust_codegen::{Field, Function, Impl, Scope, Struct, Type};
@@ -41,6 +42,9 @@ impl EntityCodeGenerator {
         // Generate Entity trait implementation
         self.generate_entity_trait_impl(&mut scope, entity)?;
 
+        // Generate typed field references
+        self.generate_fields_module(&mut scope, entity);
+
         // Generate helper methods implementation
         self.generate_helper_impl(&mut scope, entity, schema)?;
 
@@ -58,6 +62,7 @@ impl EntityCodeGenerator {
             if let Some(target_type) = field.base_type().get_object_name() {
                 // Import the referenced entity type from the entities module
                 scope.import("crate::entities", target_type);
+                scope.import("crate::entities", &self.fields_module(target_type));
             }
         }
 
@@ -205,11 +210,14 @@ impl EntityCodeGenerator {
         // Determine ID type
         let id_type = if entity.is_timeseries() { "i64" } else { "ID" };
 
-        // Add NAME constant manually since rust-codegen doesn't support associate_const
-        // We need to add it inside the impl block as raw content
+        // Add NAME and FIELDS constants manually since rust-codegen doesn't support associate_const
+        // We need to add them inside the impl block as raw content
         let impl_code = format!(
-            "impl Entity for {} {{\n    type Id = {};\n    const NAME: &'static str = \"{}\";\n\n    fn id(&self) -> &Self::Id {{\n        &self.id\n    }}\n}}",
-            entity.name, id_type, entity.name
+            "impl Entity for {} {{\n    type Id = {};\n    const NAME: &'static str = \"{}\";\n    const FIELDS: &'static [FieldSchema] = &[\n{}    ];\n\n    fn id(&self) -> &Self::Id {{\n        &self.id\n    }}\n}}",
+            entity.name,
+            id_type,
+            entity.name,
+            self.field_schemas(entity)
         );
 
         scope.raw(&impl_code);
@@ -217,6 +225,79 @@ impl EntityCodeGenerator {
         Ok(())
     }
 
+    /// Stored fields of the entity in name order, derived fields only exist as getters
+    fn stored_fields<'a>(&self, entity: &'a EntityType) -> Vec<(&'a String, &'a FieldDefinition)> {
+        let mut fields: Vec<_> = entity
+            .fields
+            .iter()
+            .filter(|(_, field)| !field.has_directive("derivedFrom"))
+            .collect();
+        fields.sort_by(|a, b| a.0.cmp(b.0));
+        fields
+    }
+
+    /// Scalar stored for a field, relations are stored as the IDs of the referenced entities
+    fn stored_scalar(&self, field: &FieldDefinition) -> ScalarType {
+        match field.base_type() {
+            FieldType::Scalar(scalar) => scalar.clone(),
+            _ => ScalarType::ID,
+        }
+    }
+
+    /// `FieldSchema` entries of the stored fields, in name order
+    fn field_schemas(&self, entity: &EntityType) -> String {
+        self.stored_fields(entity)
+            .into_iter()
+            .map(|(name, field)| {
+                format!(
+                    "        FieldSchema::new(\"{}\", ScalarType::{:?}, {}, {}),\n",
+                    name,
+                    self.stored_scalar(field),
+                    field.field_type.is_list(),
+                    field.field_type.is_optional()
+                )
+            })
+            .collect()
+    }
+
+    /// Name of the module holding the typed fields of an entity
+    fn fields_module(&self, entity_name: &str) -> String {
+        format!("{}_fields", self.to_snake_case(entity_name))
+    }
+
+    /// Path of the typed field constant for `field_name` of `entity_name`
+    fn field_const(&self, entity_name: &str, field_name: &str) -> String {
+        format!(
+            "{}::{}",
+            self.fields_module(entity_name),
+            field_name.to_case(Case::Constant)
+        )
+    }
+
+    /// Generate the module of typed field references used in queries
+    ///
+    /// Values are typed by the scalar of the field, or of its elements for list fields.
+    fn generate_fields_module(&self, scope: &mut Scope, entity: &EntityType) {
+        let mut module = format!(
+            "/// Typed fields of [`{0}`] for queries\npub mod {1} {{\n    use super::*;\n\n",
+            entity.name,
+            self.fields_module(&entity.name)
+        );
+        for (name, field) in self.stored_fields(entity) {
+            module.push_str(&format!(
+                "    pub const {}: EntityField<{}, {}> = EntityField::new(\"{}\");\n",
+                name.to_case(Case::Constant),
+                entity.name,
+                self.stored_scalar(field).rust_type(),
+                name
+            ));
+        }
+        module.push('}');
+
+        scope.raw(&module);
+        scope.raw("");
+    }
+
     /// Generate helper methods implementation
     fn generate_helper_impl(
         &self,
@@ -248,7 +329,7 @@ impl EntityCodeGenerator {
         impl_block: &mut Impl,
         field_name: &str,
         field: &FieldDefinition,
-        _schema: &EntitySchema,
+        schema: &EntitySchema,
     ) -> Result<()> {
         if let Some(target_type) = field.base_type().get_object_name() {
             // Determine if this is a list or single relation
@@ -271,17 +352,33 @@ impl EntityCodeGenerator {
             // Generate query implementation based on @derivedFrom directive
             if let Some(derived_directive) = field.get_directive("derivedFrom") {
                 if let Some(derived_field) = derived_directive.get_string_arg("field") {
+                    // A list relation on the target holds many IDs, one of which is ours
+                    let target_is_list = schema
+                        .get_entity(target_type)
+                        .and_then(|target| target.fields.get(derived_field))
+                        .is_some_and(|target_field| target_field.field_type.is_list());
+                    let filter = if target_is_list {
+                        format!(
+                            "where_has_any({}, vec![self.id.clone()])",
+                            self.field_const(target_type, derived_field)
+                        )
+                    } else {
+                        format!(
+                            "where_eq({}, self.id.clone())",
+                            self.field_const(target_type, derived_field)
+                        )
+                    };
                     if is_list {
                         // Many relations derived field (case 2) - using Entity Query API
                         getter.line(format!(
-                            "Ok({}::find().where_eq(\"{}\", self.id.clone()).list().await?)",
-                            target_type, derived_field
+                            "Ok({}::find().{}.list().await?)",
+                            target_type, filter
                         ));
                     } else {
                         // Single relation derived field (case 4) - using Entity Query API
                         getter.line(format!(
-                            "Ok({}::find().where_eq(\"{}\", self.id.clone()).first().await?)",
-                            target_type, derived_field
+                            "Ok({}::find().{}.first().await?)",
+                            target_type, filter
                         ));
                     }
                 } else {
@@ -427,6 +524,9 @@ impl EntityCodeGenerator {
         // Generate Entity trait implementation
         self.generate_entity_trait_impl(&mut scope, entity)?;
 
+        // Generate typed field references
+        self.generate_fields_module(&mut scope, entity);
+
         // Generate helper methods implementation
         self.generate_helper_impl(&mut scope, entity, schema)?;
 
@@ -542,6 +642,15 @@ mod tests {
         assert!(code.contains("name: String") || code.contains("name :String"));
         assert!(code.contains("impl Entity for User"));
         assert!(code.contains("NAME") && code.contains("User"));
+        assert!(code.contains(
+            "FieldSchema::new(\"id\", ScalarType::ID, false, false),\n        FieldSchema::new(\"name\", ScalarType::String, false, false),"
+        ));
+        assert!(code.contains("pub mod user_fields {"));
+        assert!(
+            code.contains(
+                "pub const NAME: EntityField<User, String> = EntityField::new(\"name\");"
+            )
+        );
     }
 
     #[test]
@@ -777,9 +886,9 @@ mod tests {
         // Test Case 2: Many relations derived field
         assert!(code.contains("pub async fn followers"));
         assert!(code.contains("EntityResult<Vec<User>>"));
-        assert!(
-            code.contains("User::find().where_eq(\"following\", self.id.clone()).list().await")
-        );
+        assert!(code.contains(
+            "User::find().where_eq(user_fields::FOLLOWING, self.id.clone()).list().await"
+        ));
 
         // Test Case 3: Single relation (optional) stored as ID
         assert!(code.contains("owner_id: Option<ID>"));
@@ -790,11 +899,9 @@ mod tests {
         // Test Case 4: Single relation derived
         assert!(code.contains("pub async fn manager"));
         assert!(code.contains("EntityResult<Option<User>>"));
-        assert!(
-            code.contains(
-                "User::find().where_eq(\"managedAccount\", self.id.clone()).first().await"
-            )
-        );
+        assert!(code.contains(
+            "User::find().where_eq(user_fields::MANAGED_ACCOUNT, self.id.clone()).first().await"
+        ));
 
         // Test imports (they might be in different format)
         assert!(code.contains("User") && (code.contains("use") || code.contains("import")));
@@ -802,4 +909,46 @@ mod tests {
 
         println!("Generated relations entity code:\n{}", code);
     }
+
+    #[test]
+    fn test_derived_field_from_list_relation() {
+        let id_field = || {
+            FieldDefinition::new(
+                "id".to_string(),
+                FieldType::NonNull(Box::new(FieldType::Scalar(ScalarType::ID))),
+            )
+        };
+        let user_list = || {
+            FieldType::NonNull(Box::new(FieldType::List(Box::new(FieldType::NonNull(
+                Box::new(FieldType::Object("User".to_string())),
+            )))))
+        };
+        let mut schema = EntitySchema::new();
+
+        // type User @entity { id: ID!, following: [User!]!, followers: [User!]! @derivedFrom(field: "following") }
+        let mut user_entity = EntityType::new("User".to_string());
+        user_entity.add_field("id".to_string(), id_field());
+        user_entity.add_field(
+            "following".to_string(),
+            FieldDefinition::new("following".to_string(), user_list()),
+        );
+        let mut followers_field = FieldDefinition::new("followers".to_string(), user_list());
+        let mut derived_directive = Directive::new("derivedFrom".to_string());
+        derived_directive.add_argument(
+            "field".to_string(),
+            DirectiveArg::String("following".to_string()),
+        );
+        followers_field.add_directive(derived_directive);
+        user_entity.add_field("followers".to_string(), followers_field);
+        user_entity.add_directive(Directive::new("entity".to_string()));
+        schema.add_entity("User".to_string(), user_entity.clone());
+
+        let generator = EntityCodeGenerator::new();
+        let code = generator.generate_entity(&user_entity, &schema).unwrap();
+
+        assert!(code.contains(
+            "User::find().where_has_any(user_fields::FOLLOWING, vec![self.id.clone()]).list().await"
+        ));
+        assert!(!code.contains("where_eq(user_fields::FOLLOWING"));
+    }
 }
@@ -13,9 +13,14 @@ pub mod types;
 
 // Re-export commonly used types and traits
 pub use serialization::{FromRichValue, ToRichValue, from_rich_struct, to_rich_struct};
-pub use store::{Store, StoreContext};
-pub use traits::{Entity, EntityId, EntityStore, Filter, ListOptions, QueryBuilder};
-pub use types::{BigDecimal, BigInt, Bytes, EntityError, EntityResult, ID, Int8, Timestamp};
+pub use store::{EntityUpdate, Store, StoreContext, UpdateField};
+pub use traits::{
+    Entity, EntityField, EntityId, EntityStore, FieldFor, FieldRef, FieldSchema, Filter,
+    FilterValue, ListOptions, Page, QueryBuilder,
+};
+pub use types::{
+    BigDecimal, BigInt, Bytes, EntityError, EntityResult, ID, Int8, ScalarType, Timestamp,
+};
 
 // Re-export schema types
 pub use schema::{Directive, EntitySchema, EntityType, FieldType};
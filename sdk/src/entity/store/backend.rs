@@ -2,8 +2,8 @@
 
 use crate::core::RUNTIME_CONTEXT;
 use crate::core::benchmark;
-use crate::db_request::{DbDelete, DbFilter, DbGet, DbList, DbUpsert, Op};
-use crate::{DbRequest, db_response};
+use crate::db_request::{DbDelete, DbFilter, DbGet, DbList, DbUpdate, DbUpsert, Op};
+use crate::{DbRequest, DbResponse, EntityUpdateData, db_response};
 use anyhow::Result;
 use async_trait::async_trait;
 use std::sync::atomic::AtomicU64;
@@ -11,6 +11,14 @@ use tracing::warn;
 use crate::testing::MemoryDatabase;
 use dashmap::DashMap;
 
+/// One page of entities returned by [`StorageBackend::list`]
+#[derive(Debug, Clone, Default)]
+pub struct ListResponse {
+    pub value: Option<db_response::Value>,
+    /// Cursor of the next page, `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
 /// Trait for storage backends
 #[async_trait]
 pub trait StorageBackend: Send + Sync {
@@ -20,21 +28,33 @@ pub trait StorageBackend: Send + Sync {
     /// Delete a value by table and key
     async fn delete(&self, table: Vec<String>, ids: Vec<String>) -> Result<()>;
 
-    /// List all values in a table (with optional filtering)
+    /// List a page of values in a table (with optional filtering)
     async fn list(
         &self,
         table: &str,
         filters: Vec<DbFilter>,
         cursor: String,
         page_size: Option<u32>,
-    ) -> Result<Option<db_response::Value>>;
+    ) -> Result<ListResponse>;
 
     async fn upsert(&self, table: Vec<String>, id: Vec<String>, data: Vec<crate::common::RichStruct>) -> Result<()> ;
+
+    /// Apply partial updates to existing values, each one atomically
+    ///
+    /// Backends without partial updates keep the default, which fails.
+    async fn update(
+        &self,
+        _table: Vec<String>,
+        _id: Vec<String>,
+        _data: Vec<EntityUpdateData>,
+    ) -> Result<()> {
+        Err(anyhow::anyhow!("Partial updates are not supported by this backend"))
+    }
 }
 
 pub struct RemoteBackend {
     op_counter: AtomicU64,
-    promises: DashMap<u64, async_promise::Resolve<Option<db_response::Value>>>,
+    promises: DashMap<u64, async_promise::Resolve<DbResponse>>,
 }
 
 impl RemoteBackend {
@@ -53,15 +73,29 @@ impl Default for RemoteBackend {
 }
 
 impl RemoteBackend {
-    pub(crate) fn receive_db_result(&self, db_result: crate::processor::DbResponse) {
+    pub(crate) fn receive_db_result(&self, db_result: DbResponse) {
         let op_id = db_result.op_id;
         if let Some((_key, resolver)) = self.promises.remove(&op_id) {
-            resolver.into_resolve(db_result.value);
+            resolver.into_resolve(db_result);
         } else {
             warn!("Received db result for unknown op id: {}", op_id);
         }
     }
 
+    /// Resolve every pending request with an error so awaiting handlers fail instead of hanging
+    pub(crate) fn fail_pending(&self, reason: &str) {
+        let op_ids: Vec<u64> = self.promises.iter().map(|entry| *entry.key()).collect();
+        for op_id in op_ids {
+            if let Some((_key, resolver)) = self.promises.remove(&op_id) {
+                resolver.into_resolve(DbResponse {
+                    op_id,
+                    value: Some(db_response::Value::Error(reason.to_string())),
+                    next_cursor: None,
+                });
+            }
+        }
+    }
+
     fn new_request(&self, op: Op) -> DbRequest {
         let op_id = self
             .op_counter
@@ -71,10 +105,10 @@ impl RemoteBackend {
             op: Some(op),
         }
     }
-    async fn send_async(&self, request: DbRequest) -> Result<Option<db_response::Value>> {
+    async fn send_async(&self, request: DbRequest) -> Result<DbResponse> {
         let op_id = request.op_id;
         let start = std::time::Instant::now();
-        let (resolve, promise) = async_promise::channel::<Option<db_response::Value>>();
+        let (resolve, promise) = async_promise::channel::<DbResponse>();
         self.promises.insert(op_id, resolve);
 
         if let Err(e) = self.send(request).await {
@@ -86,10 +120,17 @@ impl RemoteBackend {
         let result = promise.wait().await;
 
         benchmark::record_db_time(start.elapsed());
-        if let Some(ret) = result {
-            return Ok(ret.clone());
+        match result {
+            Some(DbResponse {
+                value: Some(db_response::Value::Error(err)),
+                ..
+            }) => Err(anyhow::anyhow!("Database error: {}", err)),
+            Some(response) => Ok(response.clone()),
+            None => Ok(DbResponse {
+                op_id,
+                ..Default::default()
+            }),
         }
-        Ok(None)
     }
 
     async fn send(&self, req: DbRequest) -> Result<()> {
@@ -107,7 +148,7 @@ impl StorageBackend for RemoteBackend {
             id: id.to_string(),
         });
         let req = self.new_request(op);
-        self.send_async(req).await
+        Ok(self.send_async(req).await?.value)
     }
 
     async fn delete(&self, table: Vec<String>, ids: Vec<String>) -> Result<()> {
@@ -126,7 +167,7 @@ impl StorageBackend for RemoteBackend {
         filters: Vec<DbFilter>,
         cursor: String,
         page_size: Option<u32>,
-    ) -> Result<Option<db_response::Value>> {
+    ) -> Result<ListResponse> {
         let op = Op::List(DbList {
             entity: table.to_string(),
             filters,
@@ -134,7 +175,11 @@ impl StorageBackend for RemoteBackend {
             page_size,
         });
         let req = self.new_request(op);
-        self.send_async(req).await
+        let response = self.send_async(req).await?;
+        Ok(ListResponse {
+            value: response.value,
+            next_cursor: response.next_cursor.filter(|cursor| !cursor.is_empty()),
+        })
     }
 
     async fn upsert(&self, table: Vec<String>, id: Vec<String>, data: Vec<crate::common::RichStruct>) -> Result<()> {
@@ -148,6 +193,22 @@ impl StorageBackend for RemoteBackend {
         self.send_async(req).await?;
         Ok(())
     }
+
+    async fn update(
+        &self,
+        table: Vec<String>,
+        id: Vec<String>,
+        data: Vec<EntityUpdateData>,
+    ) -> Result<()> {
+        let op = Op::Update(DbUpdate {
+            entity: table,
+            id,
+            entity_data: data,
+        });
+        let req = self.new_request(op);
+        self.send_async(req).await?;
+        Ok(())
+    }
 }
 
 /// Backend that can be either remote or in-memory (for tests)
@@ -161,12 +222,19 @@ impl Backend {
 
     pub fn memory(db: std::sync::Arc<MemoryDatabase>) -> Self { Self::Memory(db) }
 
-    pub fn receive_db_result(&self, db_result: crate::processor::DbResponse) {
+    pub fn receive_db_result(&self, db_result: DbResponse) {
         if let Backend::Remote(remote) = self {
             remote.receive_db_result(db_result)
         }
     }
 
+    /// Fail all in-flight remote requests, e.g. when the server is shutting down
+    pub fn fail_pending(&self, reason: &str) {
+        if let Backend::Remote(remote) = self {
+            remote.fail_pending(reason)
+        }
+    }
+
     pub fn reset(&self) {
         if let Backend::Remote(remote) = self {
             if remote.promises.len() > 0 {
@@ -203,9 +271,11 @@ impl StorageBackend for Backend {
         filters: Vec<DbFilter>,
         cursor: String,
         page_size: Option<u32>,
-    ) -> Result<Option<db_response::Value>> {
+    ) -> Result<ListResponse> {
         match self {
-            Backend::Remote(remote) => remote.list(table, filters, cursor, page_size).await,
+            Backend::Remote(remote) => {
+                remote.list(table, filters, cursor, page_size).await
+            }
             Backend::Memory(db) => {
                 db.list(table, filters, cursor, page_size).await
             }
@@ -220,6 +290,18 @@ impl StorageBackend for Backend {
             }
         }
     }
+
+    async fn update(
+        &self,
+        table: Vec<String>,
+        id: Vec<String>,
+        data: Vec<EntityUpdateData>,
+    ) -> Result<()> {
+        match self {
+            Backend::Remote(remote) => remote.update(table, id, data).await,
+            Backend::Memory(db) => db.update(table, id, data).await,
+        }
+    }
 }
 
 impl Default for Backend {
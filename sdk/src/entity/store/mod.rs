@@ -3,7 +3,9 @@
 pub mod store;
 pub mod context;
 pub mod backend;
+pub mod update;
 
 pub use store::{Store, StoreImpl};
 pub use context::StoreContext;
-pub use backend::StorageBackend;
\ No newline at end of file
+pub use backend::StorageBackend;
+pub use update::{EntityUpdate, UpdateField};
\ No newline at end of file
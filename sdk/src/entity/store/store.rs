@@ -2,11 +2,15 @@
 
 use crate::db_request::DbFilter;
 use crate::entity::ToRichValue;
-use crate::entity::store::StorageBackend;
-use crate::entity::traits::{Entity, EntityId, EntityStore, Filter, ListOptions};
+use crate::entity::store::{EntityUpdate, StorageBackend};
+use crate::entity::traits::{
+    Entity, EntityId, EntityStore, Filter, ListOptions, Page,
+};
 use crate::{RichValueList, db_response};
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
+use futures::stream::{self, BoxStream};
+use futures::{StreamExt, TryStreamExt};
 use std::sync::Arc;
 
 /// Store implementation that uses a storage backend
@@ -27,6 +31,42 @@ impl<B: StorageBackend> StoreImpl<B> {
         Self { backend }
     }
 
+    /// Stream the entities of every page, starting at the cursor of `options`
+    ///
+    /// `options.limit` is the size of each page.
+    pub fn stream<T: Entity>(&self, options: ListOptions<T>) -> BoxStream<'static, Result<T>>
+    where
+        B: 'static,
+    {
+        let store = Self::from_arc(self.backend.clone());
+        stream::try_unfold(Some((store, options)), |state| async move {
+            let Some((store, mut options)) = state else {
+                return Ok::<_, anyhow::Error>(None);
+            };
+            let page = store.list_page(options.clone()).await?;
+            // Stop on an empty page as well, so a backend repeating its cursor cannot loop forever
+            let next = match page.next_cursor {
+                Some(cursor) if !page.items.is_empty() => {
+                    options.cursor = Some(cursor);
+                    Some((store, options))
+                }
+                _ => None,
+            };
+            Ok(Some((page.items, next)))
+        })
+        .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+
+    /// Start a partial update of the existing entity `id`
+    ///
+    /// Unlike `upsert`, the update doesn't need the current entity, and numeric fields can be
+    /// incremented without a racy read-modify-write.
+    pub fn update<T: Entity>(&self, id: &T::Id) -> EntityUpdate<T, B> {
+        EntityUpdate::new(self.backend.clone(), id.as_string())
+    }
+
 
 
     /// Convert db_response::Value directly to entity
@@ -151,13 +191,14 @@ impl<B: StorageBackend> EntityStore for StoreImpl<B> {
         self.backend.delete(tables, ids).await
     }
 
-    async fn list<T: Entity>(&self, options: ListOptions<T>) -> Result<Vec<T>>
+    async fn list_page<T: Entity>(&self, options: ListOptions<T>) -> Result<Page<T>>
     where
         T: for<'de> serde::Deserialize<'de> + serde::Serialize,
     {
          let mut filters = vec![];
 
         for f in options.filters {
+            f.validate()?;
             let value = f.value.to_rich_value()?;
             let filter = DbFilter {
                 field: f.field,
@@ -178,13 +219,14 @@ impl<B: StorageBackend> EntityStore for StoreImpl<B> {
                 options.limit,
             )
             .await?;
-        match response {
-            Some(db_value) => {
-                let entities = Self::db_value_to_entities::<T>(db_value)?;
-                Ok(entities)
-            }
-            _ => Ok(vec![]),
-        }
+        let items = match response.value {
+            Some(db_value) => Self::db_value_to_entities::<T>(db_value)?,
+            _ => vec![],
+        };
+        Ok(Page {
+            items,
+            next_cursor: response.next_cursor,
+        })
     }
 }
 
@@ -212,4 +254,303 @@ impl<B: StorageBackend + Default> Default for StoreImpl<B> {
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use crate::entity::traits::FieldSchema;
+    use crate::entity::{EntityError, ScalarType};
+    use crate::testing::MemoryDatabase;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Pool {
+        id: String,
+        tvl: i64,
+        symbol: String,
+        tags: Vec<String>,
+        owner: Option<String>,
+    }
+
+    impl Entity for Pool {
+        type Id = String;
+        const NAME: &'static str = "Pool";
+        const FIELDS: &'static [FieldSchema] = &[
+            FieldSchema::new("id", ScalarType::ID, false, false),
+            FieldSchema::new("owner", ScalarType::String, false, true),
+            FieldSchema::new("symbol", ScalarType::String, false, false),
+            FieldSchema::new("tags", ScalarType::String, true, false),
+            FieldSchema::new("tvl", ScalarType::Int8, false, false),
+        ];
+
+        fn id(&self) -> &Self::Id {
+            &self.id
+        }
+    }
+
+    async fn pool_store() -> StoreImpl<MemoryDatabase> {
+        let store = StoreImpl::new(MemoryDatabase::new());
+        let pools: Vec<Pool> = [
+            ("a", 30, "WETH/USDC", vec!["stable", "eth"], None),
+            ("b", 10, "WBTC/USDC", vec!["stable", "btc"], Some("0xaaa")),
+            ("c", 20, "WETH/WBTC", vec!["eth", "btc"], None),
+            ("d", 10, "DAI/USDC", vec!["stable"], Some("0xbbb")),
+        ]
+        .into_iter()
+        .map(|(id, tvl, symbol, tags, owner)| Pool {
+            id: id.to_string(),
+            tvl,
+            symbol: symbol.to_string(),
+            tags: tags.into_iter().map(str::to_string).collect(),
+            owner: owner.map(str::to_string),
+        })
+        .collect();
+        store.upsert_many(&pools).await.unwrap();
+        store
+    }
+
+    async fn filtered(store: &StoreImpl<MemoryDatabase>, filter: Filter<Pool>) -> Vec<String> {
+        let options = ListOptions {
+            filters: vec![filter],
+            ..Default::default()
+        };
+        let pools = store.list(options).await.unwrap();
+        pools.into_iter().map(|pool| pool.id).collect()
+    }
+
+    fn ids(pools: &[Pool]) -> Vec<&str> {
+        pools.iter().map(|pool| pool.id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_list_page() {
+        let store = pool_store().await;
+        let options = ListOptions::<Pool> {
+            limit: Some(3),
+            ..Default::default()
+        };
+
+        let page = store.list_page(options.clone()).await.unwrap();
+        assert_eq!(ids(&page.items), ["a", "b", "c"]);
+        assert!(page.has_next());
+
+        let next = store
+            .list_page(ListOptions {
+                cursor: page.next_cursor,
+                ..options
+            })
+            .await
+            .unwrap();
+        assert_eq!(ids(&next.items), ["d"]);
+        assert!(!next.has_next());
+    }
+
+    #[tokio::test]
+    async fn test_stream_all_pages() {
+        let store = pool_store().await;
+        let options = ListOptions::<Pool> {
+            filters: vec![Filter::gt("tvl", 10)],
+            limit: Some(1),
+            ..Default::default()
+        };
+
+        let pools: Vec<Pool> = store.stream(options).try_collect().await.unwrap();
+        assert_eq!(ids(&pools), ["a", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_filter_operators() {
+        let store = pool_store().await;
+
+        assert_eq!(filtered(&store, Filter::eq("tvl", 10)).await, ["b", "d"]);
+        assert_eq!(filtered(&store, Filter::ne("tvl", 10)).await, ["a", "c"]);
+        assert_eq!(filtered(&store, Filter::gt("tvl", 10)).await, ["a", "c"]);
+        assert_eq!(filtered(&store, Filter::gte("tvl", 20)).await, ["a", "c"]);
+        assert_eq!(filtered(&store, Filter::lt("tvl", 20)).await, ["b", "d"]);
+        assert_eq!(
+            filtered(&store, Filter::lte("tvl", 20)).await,
+            ["b", "c", "d"]
+        );
+        assert_eq!(
+            filtered(&store, Filter::in_("id", vec!["a", "d"])).await,
+            ["a", "d"]
+        );
+        assert_eq!(
+            filtered(&store, Filter::not_in("id", vec!["a", "d"])).await,
+            ["b", "c"]
+        );
+        assert_eq!(
+            filtered(&store, Filter::like("symbol", "W%/USDC")).await,
+            ["a", "b"]
+        );
+        assert_eq!(
+            filtered(&store, Filter::not_like("symbol", "%USDC")).await,
+            ["c"]
+        );
+        assert_eq!(
+            filtered(&store, Filter::like("symbol", "DAI/USD_")).await,
+            ["d"]
+        );
+        assert_eq!(
+            filtered(&store, Filter::has_all("tags", vec!["stable", "eth"])).await,
+            ["a"]
+        );
+        assert_eq!(
+            filtered(&store, Filter::has_any("tags", vec!["btc"])).await,
+            ["b", "c"]
+        );
+        assert_eq!(filtered(&store, Filter::is_null("owner")).await, ["a", "c"]);
+        assert_eq!(
+            filtered(&store, Filter::is_not_null("owner")).await,
+            ["b", "d"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_filters_checked_against_schema() {
+        let store = pool_store().await;
+        let error = |filter: Filter<Pool>| filter.validate().unwrap_err();
+
+        assert!(
+            Filter::<Pool>::has_any("tags", vec!["eth"])
+                .validate()
+                .is_ok()
+        );
+        assert!(matches!(
+            error(Filter::eq("name", "x")),
+            EntityError::Validation { .. }
+        ));
+        assert!(matches!(
+            error(Filter::gt("tvl", "10")),
+            EntityError::TypeConversion { .. }
+        ));
+        assert!(matches!(
+            error(Filter::is_null("tvl")),
+            EntityError::Validation { .. }
+        ));
+        assert!(matches!(
+            error(Filter::like("tvl", "1%")),
+            EntityError::Validation { .. }
+        ));
+        assert!(matches!(
+            error(Filter::has_all("symbol", vec!["WETH"])),
+            EntityError::Validation { .. }
+        ));
+
+        let options = ListOptions::<Pool> {
+            filters: vec![Filter::eq("tvl", true)],
+            ..Default::default()
+        };
+        let err = store.list(options).await.unwrap_err();
+        assert!(err.to_string().contains("expected Int8, got Boolean"));
+    }
+
+    mod pool_fields {
+        use super::*;
+        use crate::entity::{EntityField, Int8};
+
+        pub const OWNER: EntityField<Pool, String> = EntityField::new("owner");
+        pub const SYMBOL: EntityField<Pool, String> = EntityField::new("symbol");
+        pub const TAGS: EntityField<Pool, String> = EntityField::new("tags");
+        pub const TVL: EntityField<Pool, Int8> = EntityField::new("tvl");
+    }
+
+    #[tokio::test]
+    async fn test_typed_fields() {
+        let store = pool_store().await;
+
+        assert_eq!(
+            filtered(&store, Filter::eq(pool_fields::TVL, 10)).await,
+            ["b", "d"]
+        );
+        assert_eq!(
+            filtered(&store, Filter::like(pool_fields::SYMBOL, "WETH/%")).await,
+            ["a", "c"]
+        );
+        assert_eq!(
+            filtered(&store, Filter::has_any(pool_fields::TAGS, vec!["btc"])).await,
+            ["b", "c"]
+        );
+        assert_eq!(
+            filtered(&store, Filter::is_not_null(pool_fields::OWNER)).await,
+            ["b", "d"]
+        );
+    }
+
+    async fn pool(store: &StoreImpl<MemoryDatabase>, id: &str) -> Pool {
+        store.get::<Pool>(&id.to_string()).await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_update() {
+        let store = pool_store().await;
+        let id = "b".to_string();
+
+        store
+            .update::<Pool>(&id)
+            .add(pool_fields::TVL, 5)
+            .set(pool_fields::SYMBOL, "WBTC/USDT")
+            .set_null(pool_fields::OWNER)
+            .execute()
+            .await
+            .unwrap();
+        store
+            .update::<Pool>(&id)
+            .multiply("tvl", 3i64)
+            .execute()
+            .await
+            .unwrap();
+
+        let updated = pool(&store, "b").await;
+        assert_eq!(updated.tvl, 45);
+        assert_eq!(updated.symbol, "WBTC/USDT");
+        assert_eq!(updated.owner, None);
+        assert_eq!(updated.tags, ["stable", "btc"]);
+
+        // Concurrent increments are not lost
+        let updates = (0..10).map(|_| store.update::<Pool>(&id).add(pool_fields::TVL, 1).execute());
+        for result in futures::future::join_all(updates).await {
+            result.unwrap();
+        }
+        assert_eq!(pool(&store, "b").await.tvl, 55);
+    }
+
+    #[tokio::test]
+    async fn test_update_errors() {
+        let store = pool_store().await;
+        let id = "a".to_string();
+
+        let error = |update: EntityUpdate<Pool, MemoryDatabase>| async move {
+            update.execute().await.unwrap_err().to_string()
+        };
+        assert!(
+            error(store.update::<Pool>(&id).add(pool_fields::SYMBOL, "x"))
+                .await
+                .contains("ADD only applies to numeric fields")
+        );
+        assert!(
+            error(store.update::<Pool>(&id).set_null(pool_fields::TVL))
+                .await
+                .contains("field is not nullable")
+        );
+        assert!(
+            error(store.update::<Pool>(&id).set("name", "x"))
+                .await
+                .contains("unknown field")
+        );
+        assert!(
+            error(store.update::<Pool>(&id).set("tvl", "x"))
+                .await
+                .contains("expected Int8, got String")
+        );
+        assert!(
+            error(store.update::<Pool>(&id).set("tags", "eth"))
+                .await
+                .contains("SET does not apply to a String list field")
+        );
+        assert!(
+            error(store.update::<Pool>(&"z".to_string()).add(pool_fields::TVL, 1))
+                .await
+                .contains("missing entity")
+        );
+        assert_eq!(pool(&store, "a").await.tvl, 30);
+    }
+}
@@ -0,0 +1,187 @@
+//! Partial entity updates applied atomically by the store
+
+use crate::RichValue;
+use crate::entity::store::StorageBackend;
+use crate::entity::traits::{Entity, EntityField, FieldRef, FilterValue};
+use crate::entity::types::ScalarType;
+use crate::entity::{EntityError, FromRichValue, ToRichValue};
+use crate::processor::EntityUpdateData;
+use crate::processor::entity_update_data::{FieldValue, Operator};
+use crate::rich_value::Value;
+use anyhow::Result;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Field of entity `T` that can be updated with values of type `V`
+///
+/// Typed fields convert the value into their own type first, so it is stored the way
+/// the entity deserializes it.
+pub trait UpdateField<T: Entity, V>: FieldRef<T> {
+    fn rich_value(value: V) -> Result<RichValue>;
+}
+
+impl<T: Entity, V: ToRichValue> UpdateField<T, V> for &str {
+    fn rich_value(value: V) -> Result<RichValue> {
+        value.to_rich_value()
+    }
+}
+
+impl<T: Entity, F: ToRichValue, V: Into<F>> UpdateField<T, V> for EntityField<T, F> {
+    fn rich_value(value: V) -> Result<RichValue> {
+        value.into().to_rich_value()
+    }
+}
+
+/// Partial update of an entity, created with [`StoreImpl::update`](super::StoreImpl::update)
+///
+/// ```rust,ignore
+/// store
+///     .update::<Pool>(&pool.id)
+///     .add(pool_fields::VOLUME, amount)
+///     .set(pool_fields::LAST_SEEN, timestamp)
+///     .execute()
+///     .await?;
+/// ```
+///
+/// The operations are applied by the store in a single request, so concurrent handlers
+/// incrementing the same field don't overwrite each other. Fields that are not updated
+/// keep their latest value, and a later operation on a field replaces an earlier one.
+#[must_use = "updates are only applied by `execute`"]
+pub struct EntityUpdate<T: Entity, B: StorageBackend> {
+    backend: Arc<B>,
+    id: String,
+    fields: Vec<(String, Operator, Result<RichValue>)>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Entity, B: StorageBackend> EntityUpdate<T, B> {
+    pub(crate) fn new(backend: Arc<B>, id: String) -> Self {
+        Self {
+            backend,
+            id,
+            fields: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Set the field to `value`
+    pub fn set<F, V>(self, field: F, value: V) -> Self
+    where
+        F: UpdateField<T, V>,
+    {
+        self.push(field.field_name(), Operator::Set, F::rich_value(value))
+    }
+
+    /// Set a nullable field to null
+    pub fn set_null(self, field: impl FieldRef<T>) -> Self {
+        let null = RichValue {
+            value: Some(Value::NullValue(0)),
+        };
+        self.push(field.field_name(), Operator::Set, Ok(null))
+    }
+
+    /// Add `value` to a numeric field
+    pub fn add<F, V>(self, field: F, value: V) -> Self
+    where
+        F: UpdateField<T, V>,
+    {
+        self.push(field.field_name(), Operator::Add, F::rich_value(value))
+    }
+
+    /// Multiply a numeric field by `value`
+    pub fn multiply<F, V>(self, field: F, value: V) -> Self
+    where
+        F: UpdateField<T, V>,
+    {
+        self.push(field.field_name(), Operator::Multiply, F::rich_value(value))
+    }
+
+    fn push(mut self, field: &str, op: Operator, value: Result<RichValue>) -> Self {
+        self.fields.push((field.to_string(), op, value));
+        self
+    }
+
+    /// Send the update to the store
+    pub async fn execute(self) -> Result<()> {
+        if self.fields.is_empty() {
+            return Ok(());
+        }
+
+        let mut data = EntityUpdateData::default();
+        for (field, op, value) in self.fields {
+            let value = value?;
+            Self::validate(&field, op, &value)?;
+            data.fields.insert(
+                field,
+                FieldValue {
+                    value: Some(value),
+                    op: op as i32,
+                },
+            );
+        }
+
+        self.backend
+            .update(vec![T::NAME.to_string()], vec![self.id], vec![data])
+            .await
+    }
+
+    /// Check an operation against the schema of `T`, entities without a schema are not checked
+    fn validate(field: &str, op: Operator, value: &RichValue) -> Result<(), EntityError> {
+        if T::FIELDS.is_empty() {
+            return Ok(());
+        }
+        let invalid = |reason: &str| EntityError::validation(T::NAME, field, reason);
+        let schema = T::FIELDS
+            .iter()
+            .find(|schema| schema.name == field)
+            .ok_or_else(|| invalid("unknown field"))?;
+
+        if field == "id" {
+            return Err(invalid("the id cannot be updated"));
+        }
+        let value = FilterValue::from_rich_value(value).map_err(|e| invalid(&e.to_string()))?;
+        let check = |value: &FilterValue| {
+            if schema.accepts(value) {
+                Ok(())
+            } else {
+                Err(EntityError::type_conversion(
+                    T::NAME,
+                    field,
+                    schema.scalar.to_string(),
+                    value.type_name(),
+                ))
+            }
+        };
+
+        match (op, &value) {
+            (Operator::Add | Operator::Multiply, _)
+                if schema.list
+                    || !matches!(
+                        schema.scalar,
+                        ScalarType::Int
+                            | ScalarType::Int8
+                            | ScalarType::Float
+                            | ScalarType::BigInt
+                            | ScalarType::BigDecimal
+                    ) =>
+            {
+                Err(invalid(&format!(
+                    "{} only applies to numeric fields",
+                    op.as_str_name()
+                )))
+            }
+            (Operator::Set, FilterValue::Null) if schema.nullable => Ok(()),
+            (Operator::Set, FilterValue::Null) => Err(invalid("field is not nullable")),
+            (Operator::Set, FilterValue::List(values)) if schema.list => {
+                values.iter().try_for_each(check)
+            }
+            (_, value) if !schema.list => check(value),
+            (_, value) => Err(invalid(&format!(
+                "{} does not apply to a {} list field with a {} value",
+                op.as_str_name(),
+                schema.scalar,
+                value.type_name()
+            ))),
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! Core traits for the entity framework
 
 use crate::db_request::DbOperator;
-use crate::entity::types::ID;
+use crate::entity::types::{ID, ScalarType};
 use crate::entity::*;
 use crate::rich_value::Value;
 use crate::{RichValue, RichValueList};
@@ -21,6 +21,11 @@ pub trait Entity:
     /// The entity name (used as table/collection name)
     const NAME: &'static str;
 
+    /// Fields declared in the schema, used to type-check filters
+    ///
+    /// Empty for entities without a schema, whose filters are not checked.
+    const FIELDS: &'static [FieldSchema] = &[];
+
     /// Get the entity's primary key
     fn id(&self) -> &Self::Id;
     
@@ -202,10 +207,131 @@ pub trait EntityStore: Send + Sync {
 
     /// List entities with optional filtering
     async fn list<T: Entity>(&self, options: ListOptions<T>) -> Result<Vec<T>>
+    where
+        T: for<'de> serde::Deserialize<'de> + serde::Serialize,
+    {
+        Ok(self.list_page(options).await?.items)
+    }
+
+    /// List a page of entities along with the cursor of the next page
+    async fn list_page<T: Entity>(&self, options: ListOptions<T>) -> Result<Page<T>>
     where
         T: for<'de> serde::Deserialize<'de> + serde::Serialize;
 }
 
+/// Schema of an entity field, generated from `schema.graphql`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSchema {
+    /// Stored field name
+    pub name: &'static str,
+    /// Scalar type, relations are stored as `ID`
+    pub scalar: ScalarType,
+    pub list: bool,
+    pub nullable: bool,
+}
+
+impl FieldSchema {
+    pub const fn new(name: &'static str, scalar: ScalarType, list: bool, nullable: bool) -> Self {
+        Self {
+            name,
+            scalar,
+            list,
+            nullable,
+        }
+    }
+
+    /// Whether a value of this field, or an element of a list field, can be compared to `value`
+    pub fn accepts(&self, value: &FilterValue) -> bool {
+        matches!(
+            (&self.scalar, value),
+            (ScalarType::ID, FilterValue::String(_) | FilterValue::Int(_))
+                | (ScalarType::String, FilterValue::String(_))
+                | (ScalarType::Int | ScalarType::Int8, FilterValue::Int(_))
+                | (
+                    ScalarType::Float,
+                    FilterValue::Float(_) | FilterValue::Int(_)
+                )
+                | (ScalarType::Boolean, FilterValue::Boolean(_))
+                | (
+                    ScalarType::BigInt,
+                    FilterValue::BigInt(_) | FilterValue::Int(_)
+                )
+                | (
+                    ScalarType::BigDecimal,
+                    FilterValue::BigDecimal(_)
+                        | FilterValue::BigInt(_)
+                        | FilterValue::Int(_)
+                        | FilterValue::Float(_)
+                )
+                | (ScalarType::Timestamp, FilterValue::Timestamp(_))
+                | (ScalarType::Bytes, FilterValue::Bytes(_))
+        )
+    }
+}
+
+/// Typed reference to a field of entity `T` holding values of type `V`
+///
+/// Generated for every entity in a `<entity>_fields` module, e.g. `transfer_fields::FROM`,
+/// so that queries on renamed fields or with mismatched values fail to compile.
+pub struct EntityField<T: Entity, V> {
+    name: &'static str,
+    _phantom: std::marker::PhantomData<fn() -> (T, V)>,
+}
+
+impl<T: Entity, V> EntityField<T, V> {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Field name in `schema.graphql`
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T: Entity, V> Clone for EntityField<T, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Entity, V> Copy for EntityField<T, V> {}
+
+impl<T: Entity, V> Debug for EntityField<T, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", T::NAME, self.name)
+    }
+}
+
+/// Field of entity `T`, either a typed [`EntityField`] or a field name
+pub trait FieldRef<T: Entity> {
+    fn field_name(&self) -> &str;
+}
+
+impl<T: Entity> FieldRef<T> for &str {
+    fn field_name(&self) -> &str {
+        self
+    }
+}
+
+impl<T: Entity, V> FieldRef<T> for EntityField<T, V> {
+    fn field_name(&self) -> &str {
+        self.name
+    }
+}
+
+/// Field of entity `T` that can be compared to values of type `V`
+///
+/// Field names accept any value, typed fields only values converting into their type.
+pub trait FieldFor<T: Entity, V>: FieldRef<T> {}
+
+impl<T: Entity, V> FieldFor<T, V> for &str {}
+
+impl<T: Entity, F, V: Into<F>> FieldFor<T, V> for EntityField<T, F> {}
+
 /// Query filter for entity operations
 #[derive(Debug, Clone)]
 pub struct Filter<T: Entity> {
@@ -225,60 +351,227 @@ impl<T: Entity> Filter<T> {
         }
     }
 
-    pub fn eq<V>(field: &str, value: V) -> Self
+    pub fn eq<F, V>(field: F, value: V) -> Self
     where
+        F: FieldFor<T, V>,
         V: Into<FilterValue>,
     {
-        Self::new(field.to_string(), FilterOperator::Eq, value.into())
+        Self::new(
+            field.field_name().to_string(),
+            FilterOperator::Eq,
+            value.into(),
+        )
     }
 
-    pub fn ne<V>(field: &str, value: V) -> Self
+    pub fn ne<F, V>(field: F, value: V) -> Self
     where
+        F: FieldFor<T, V>,
         V: Into<FilterValue>,
     {
-        Self::new(field.to_string(), FilterOperator::Ne, value.into())
+        Self::new(
+            field.field_name().to_string(),
+            FilterOperator::Ne,
+            value.into(),
+        )
     }
 
-    pub fn gt<V>(field: &str, value: V) -> Self
+    pub fn gt<F, V>(field: F, value: V) -> Self
     where
+        F: FieldFor<T, V>,
         V: Into<FilterValue>,
     {
-        Self::new(field.to_string(), FilterOperator::Gt, value.into())
+        Self::new(
+            field.field_name().to_string(),
+            FilterOperator::Gt,
+            value.into(),
+        )
     }
 
-    pub fn gte<V>(field: &str, value: V) -> Self
+    pub fn gte<F, V>(field: F, value: V) -> Self
     where
+        F: FieldFor<T, V>,
         V: Into<FilterValue>,
     {
-        Self::new(field.to_string(), FilterOperator::Ge, value.into())
+        Self::new(
+            field.field_name().to_string(),
+            FilterOperator::Ge,
+            value.into(),
+        )
     }
 
-    pub fn lt<V>(field: &str, value: V) -> Self
+    pub fn lt<F, V>(field: F, value: V) -> Self
     where
+        F: FieldFor<T, V>,
         V: Into<FilterValue>,
     {
-        Self::new(field.to_string(), FilterOperator::Le, value.into())
+        Self::new(
+            field.field_name().to_string(),
+            FilterOperator::Lt,
+            value.into(),
+        )
     }
 
-    pub fn lte<V>(field: &str, value: V) -> Self
+    pub fn lte<F, V>(field: F, value: V) -> Self
     where
+        F: FieldFor<T, V>,
         V: Into<FilterValue>,
     {
-        Self::new(field.to_string(), FilterOperator::Le, value.into())
+        Self::new(
+            field.field_name().to_string(),
+            FilterOperator::Le,
+            value.into(),
+        )
     }
 
     /// Create an IN filter for matching any value in the provided array
-    pub fn in_<V>(field: &str, values: Vec<V>) -> Self
+    pub fn in_<F, V>(field: F, values: Vec<V>) -> Self
     where
+        F: FieldFor<T, V>,
         V: Into<FilterValue>,
     {
-        let filter_values: Vec<FilterValue> = values.into_iter().map(|v| v.into()).collect();
         Self::new(
-            field.to_string(),
+            field.field_name().to_string(),
             FilterOperator::In,
-            FilterValue::List(filter_values),
+            Self::list(values),
+        )
+    }
+
+    /// Create a NOT IN filter for matching none of the values in the provided array
+    pub fn not_in<F, V>(field: F, values: Vec<V>) -> Self
+    where
+        F: FieldFor<T, V>,
+        V: Into<FilterValue>,
+    {
+        Self::new(
+            field.field_name().to_string(),
+            FilterOperator::NotIn,
+            Self::list(values),
+        )
+    }
+
+    /// Create a LIKE filter, `%` matches any sequence of characters and `_` a single one
+    pub fn like<'a>(field: impl FieldFor<T, &'a str>, pattern: &'a str) -> Self {
+        Self::new(
+            field.field_name().to_string(),
+            FilterOperator::Like,
+            pattern.into(),
+        )
+    }
+
+    pub fn not_like<'a>(field: impl FieldFor<T, &'a str>, pattern: &'a str) -> Self {
+        Self::new(
+            field.field_name().to_string(),
+            FilterOperator::NotLike,
+            pattern.into(),
         )
     }
+
+    /// Create a filter matching list fields containing all the provided values
+    pub fn has_all<F, V>(field: F, values: Vec<V>) -> Self
+    where
+        F: FieldFor<T, V>,
+        V: Into<FilterValue>,
+    {
+        Self::new(
+            field.field_name().to_string(),
+            FilterOperator::HasAll,
+            Self::list(values),
+        )
+    }
+
+    /// Create a filter matching list fields containing any of the provided values
+    pub fn has_any<F, V>(field: F, values: Vec<V>) -> Self
+    where
+        F: FieldFor<T, V>,
+        V: Into<FilterValue>,
+    {
+        Self::new(
+            field.field_name().to_string(),
+            FilterOperator::HasAny,
+            Self::list(values),
+        )
+    }
+
+    pub fn is_null(field: impl FieldRef<T>) -> Self {
+        Self::new(
+            field.field_name().to_string(),
+            FilterOperator::Eq,
+            FilterValue::Null,
+        )
+    }
+
+    pub fn is_not_null(field: impl FieldRef<T>) -> Self {
+        Self::new(
+            field.field_name().to_string(),
+            FilterOperator::Ne,
+            FilterValue::Null,
+        )
+    }
+
+    fn list<V: Into<FilterValue>>(values: Vec<V>) -> FilterValue {
+        FilterValue::List(values.into_iter().map(|v| v.into()).collect())
+    }
+
+    /// Check the field, operator and value against the schema of `T`
+    ///
+    /// Filters of entities without a schema are not checked.
+    pub fn validate(&self) -> Result<(), EntityError> {
+        use DbOperator::*;
+
+        if T::FIELDS.is_empty() {
+            return Ok(());
+        }
+        let invalid = |reason: String| EntityError::Validation {
+            entity_type: T::NAME.to_string(),
+            field: self.field.clone(),
+            reason,
+        };
+        let schema = T::FIELDS
+            .iter()
+            .find(|schema| schema.name == self.field)
+            .ok_or_else(|| invalid("unknown field".to_string()))?;
+        let check = |value: &FilterValue| {
+            if schema.accepts(value) {
+                Ok(())
+            } else {
+                Err(EntityError::TypeConversion {
+                    entity_type: T::NAME.to_string(),
+                    field: self.field.clone(),
+                    expected: schema.scalar.to_string(),
+                    actual: value.type_name().to_string(),
+                })
+            }
+        };
+
+        match (self.operator, &self.value) {
+            (Eq | Ne, FilterValue::Null) if schema.nullable => Ok(()),
+            (Eq | Ne, FilterValue::Null) => Err(invalid("field is not nullable".to_string())),
+            (Eq | Ne, FilterValue::List(values)) if schema.list => {
+                values.iter().try_for_each(check)
+            }
+            (Eq | Ne | Gt | Ge | Lt | Le, value) if !schema.list && *value != FilterValue::Null => {
+                check(value)
+            }
+            (In | NotIn, FilterValue::List(values)) if !schema.list => {
+                values.iter().try_for_each(check)
+            }
+            (HasAll | HasAny, FilterValue::List(values)) if schema.list => {
+                values.iter().try_for_each(check)
+            }
+            (Like | NotLike, FilterValue::String(_))
+                if !schema.list && matches!(schema.scalar, ScalarType::ID | ScalarType::String) =>
+            {
+                Ok(())
+            }
+            (operator, value) => Err(invalid(format!(
+                "{} does not apply to a {}{} field with a {} value",
+                operator.as_str_name(),
+                schema.scalar,
+                if schema.list { " list" } else { "" },
+                value.type_name()
+            ))),
+        }
+    }
 }
 
 pub type FilterOperator = DbOperator;
@@ -290,10 +583,31 @@ pub enum FilterValue {
     Int(i64),
     Float(f64),
     Boolean(bool),
+    BigInt(BigInt),
+    BigDecimal(BigDecimal),
+    Timestamp(Timestamp),
+    Bytes(Bytes),
     Null,
     List(Vec<FilterValue>),
 }
 
+impl FilterValue {
+    pub(crate) fn type_name(&self) -> &'static str {
+        match self {
+            FilterValue::String(_) => "String",
+            FilterValue::Int(_) => "Int",
+            FilterValue::Float(_) => "Float",
+            FilterValue::Boolean(_) => "Boolean",
+            FilterValue::BigInt(_) => "BigInt",
+            FilterValue::BigDecimal(_) => "BigDecimal",
+            FilterValue::Timestamp(_) => "Timestamp",
+            FilterValue::Bytes(_) => "Bytes",
+            FilterValue::Null => "null",
+            FilterValue::List(_) => "list",
+        }
+    }
+}
+
 impl ToRichValue for FilterValue {
     fn to_rich_value(&self) -> Result<RichValue> {
         match self {
@@ -301,6 +615,10 @@ impl ToRichValue for FilterValue {
             FilterValue::Int(i) => i.to_rich_value(),
             FilterValue::Float(f) => f.to_rich_value(),
             FilterValue::Boolean(b) => b.to_rich_value(),
+            FilterValue::BigInt(i) => i.to_rich_value(),
+            FilterValue::BigDecimal(d) => d.to_rich_value(),
+            FilterValue::Timestamp(t) => t.to_rich_value(),
+            FilterValue::Bytes(b) => b.to_rich_value(),
             FilterValue::Null => Ok(RichValue {
                 value: Some(Value::NullValue(0)),
             }),
@@ -316,6 +634,37 @@ impl ToRichValue for FilterValue {
     }
 }
 
+impl FromRichValue for FilterValue {
+    fn from_rich_value(value: &RichValue) -> Result<Self> {
+        Ok(match &value.value {
+            None | Some(Value::NullValue(_)) => FilterValue::Null,
+            Some(Value::IntValue(_) | Value::Int64Value(_)) => {
+                FilterValue::Int(i64::from_rich_value(value)?)
+            }
+            Some(Value::FloatValue(f)) => FilterValue::Float(*f),
+            Some(Value::BoolValue(b)) => FilterValue::Boolean(*b),
+            Some(Value::StringValue(s)) => FilterValue::String(s.clone()),
+            Some(Value::BytesValue(_)) => FilterValue::Bytes(Bytes::from_rich_value(value)?),
+            Some(Value::TimestampValue(_)) => {
+                FilterValue::Timestamp(Timestamp::from_rich_value(value)?)
+            }
+            Some(Value::BigintValue(_)) => FilterValue::BigInt(BigInt::from_rich_value(value)?),
+            Some(Value::BigdecimalValue(_)) => {
+                FilterValue::BigDecimal(BigDecimal::from_rich_value(value)?)
+            }
+            Some(Value::ListValue(list)) => FilterValue::List(
+                list.values
+                    .iter()
+                    .map(FilterValue::from_rich_value)
+                    .collect::<Result<_>>()?,
+            ),
+            Some(Value::StructValue(_) | Value::TokenValue(_)) => {
+                return Err(anyhow::anyhow!("Unsupported field value: {:?}", value));
+            }
+        })
+    }
+}
+
 impl From<String> for FilterValue {
     fn from(s: String) -> Self {
         FilterValue::String(s)
@@ -328,6 +677,12 @@ impl From<&str> for FilterValue {
     }
 }
 
+impl From<i32> for FilterValue {
+    fn from(i: i32) -> Self {
+        FilterValue::Int(i as i64)
+    }
+}
+
 impl From<i64> for FilterValue {
     fn from(i: i64) -> Self {
         FilterValue::Int(i)
@@ -346,17 +701,56 @@ impl From<bool> for FilterValue {
     }
 }
 
+impl From<BigInt> for FilterValue {
+    fn from(i: BigInt) -> Self {
+        FilterValue::BigInt(i)
+    }
+}
+
+impl From<BigDecimal> for FilterValue {
+    fn from(d: BigDecimal) -> Self {
+        FilterValue::BigDecimal(d)
+    }
+}
+
+impl From<Timestamp> for FilterValue {
+    fn from(t: Timestamp) -> Self {
+        FilterValue::Timestamp(t)
+    }
+}
+
+impl From<Bytes> for FilterValue {
+    fn from(b: Bytes) -> Self {
+        FilterValue::Bytes(b)
+    }
+}
+
 impl From<ID> for FilterValue {
     fn from(id: ID) -> Self {
         FilterValue::String(id.to_string())
     }
 }
 
+/// A page of entities and the cursor of the next page
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// `None` when this is the last page
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    pub fn has_next(&self) -> bool {
+        self.next_cursor.is_some()
+    }
+}
+
 /// Options for listing entities
 #[derive(Debug, Clone)]
 pub struct ListOptions<T: Entity> {
     pub filters: Vec<Filter<T>>,
     pub cursor: Option<String>,
+    /// Maximum number of entities per page
     pub limit: Option<u32>,
 }
 
@@ -403,8 +797,9 @@ impl<T: Entity> QueryBuilder<T> {
     }
 
     /// Add an equality filter
-    pub fn where_eq<V>(mut self, field: &str, value: V) -> Self
+    pub fn where_eq<F, V>(mut self, field: F, value: V) -> Self
     where
+        F: FieldFor<T, V>,
         V: Into<FilterValue>,
     {
         self.filters.push(Filter::eq(field, value));
@@ -412,8 +807,9 @@ impl<T: Entity> QueryBuilder<T> {
     }
 
     /// Add a not-equal filter
-    pub fn where_ne<V>(mut self, field: &str, value: V) -> Self
+    pub fn where_ne<F, V>(mut self, field: F, value: V) -> Self
     where
+        F: FieldFor<T, V>,
         V: Into<FilterValue>,
     {
         self.filters.push(Filter::ne(field, value));
@@ -421,8 +817,9 @@ impl<T: Entity> QueryBuilder<T> {
     }
 
     /// Add a greater-than filter
-    pub fn where_gt<V>(mut self, field: &str, value: V) -> Self
+    pub fn where_gt<F, V>(mut self, field: F, value: V) -> Self
     where
+        F: FieldFor<T, V>,
         V: Into<FilterValue>,
     {
         self.filters.push(Filter::gt(field, value));
@@ -430,8 +827,9 @@ impl<T: Entity> QueryBuilder<T> {
     }
 
     /// Add a greater-than-or-equal filter
-    pub fn where_gte<V>(mut self, field: &str, value: V) -> Self
+    pub fn where_gte<F, V>(mut self, field: F, value: V) -> Self
     where
+        F: FieldFor<T, V>,
         V: Into<FilterValue>,
     {
         self.filters.push(Filter::gte(field, value));
@@ -439,8 +837,9 @@ impl<T: Entity> QueryBuilder<T> {
     }
 
     /// Add a less-than filter
-    pub fn where_lt<V>(mut self, field: &str, value: V) -> Self
+    pub fn where_lt<F, V>(mut self, field: F, value: V) -> Self
     where
+        F: FieldFor<T, V>,
         V: Into<FilterValue>,
     {
         self.filters.push(Filter::lt(field, value));
@@ -448,8 +847,9 @@ impl<T: Entity> QueryBuilder<T> {
     }
 
     /// Add a less-than-or-equal filter
-    pub fn where_lte<V>(mut self, field: &str, value: V) -> Self
+    pub fn where_lte<F, V>(mut self, field: F, value: V) -> Self
     where
+        F: FieldFor<T, V>,
         V: Into<FilterValue>,
     {
         self.filters.push(Filter::lte(field, value));
@@ -457,21 +857,80 @@ impl<T: Entity> QueryBuilder<T> {
     }
 
     /// Add an IN filter (field matches any of the provided values)
-    pub fn where_in<V>(mut self, field: &str, values: Vec<V>) -> Self
+    pub fn where_in<F, V>(mut self, field: F, values: Vec<V>) -> Self
     where
+        F: FieldFor<T, V>,
         V: Into<FilterValue>,
     {
         self.filters.push(Filter::in_(field, values));
         self
     }
 
+    /// Add a NOT IN filter (field matches none of the provided values)
+    pub fn where_not_in<F, V>(mut self, field: F, values: Vec<V>) -> Self
+    where
+        F: FieldFor<T, V>,
+        V: Into<FilterValue>,
+    {
+        self.filters.push(Filter::not_in(field, values));
+        self
+    }
+
+    /// Add a LIKE filter, `%` matches any sequence of characters and `_` a single one
+    pub fn where_like<'a>(mut self, field: impl FieldFor<T, &'a str>, pattern: &'a str) -> Self {
+        self.filters.push(Filter::like(field, pattern));
+        self
+    }
+
+    /// Add a NOT LIKE filter
+    pub fn where_not_like<'a>(
+        mut self,
+        field: impl FieldFor<T, &'a str>,
+        pattern: &'a str,
+    ) -> Self {
+        self.filters.push(Filter::not_like(field, pattern));
+        self
+    }
+
+    /// Add a filter on a list field containing all of the provided values
+    pub fn where_has_all<F, V>(mut self, field: F, values: Vec<V>) -> Self
+    where
+        F: FieldFor<T, V>,
+        V: Into<FilterValue>,
+    {
+        self.filters.push(Filter::has_all(field, values));
+        self
+    }
+
+    /// Add a filter on a list field containing any of the provided values
+    pub fn where_has_any<F, V>(mut self, field: F, values: Vec<V>) -> Self
+    where
+        F: FieldFor<T, V>,
+        V: Into<FilterValue>,
+    {
+        self.filters.push(Filter::has_any(field, values));
+        self
+    }
+
+    /// Add a filter matching entities where the field is null
+    pub fn where_null(mut self, field: impl FieldRef<T>) -> Self {
+        self.filters.push(Filter::is_null(field));
+        self
+    }
+
+    /// Add a filter matching entities where the field is not null
+    pub fn where_not_null(mut self, field: impl FieldRef<T>) -> Self {
+        self.filters.push(Filter::is_not_null(field));
+        self
+    }
+
     /// Add a custom filter
     pub fn where_filter(mut self, filter: Filter<T>) -> Self {
         self.filters.push(filter);
         self
     }
 
-    /// Set the maximum number of results to return
+    /// Set the maximum number of results to return, per page when streaming
     pub fn limit(mut self, limit: u32) -> Self {
         self.limit = Some(limit);
         self
@@ -483,18 +942,38 @@ impl<T: Entity> QueryBuilder<T> {
         self
     }
 
-    /// Execute the query and return the results
-    pub async fn list(self) -> Result<Vec<T>> {
-        use crate::entity::Store;
-        let store = Store::from_current_context().await?;
-
-        let options = ListOptions {
+    fn into_options(self) -> ListOptions<T> {
+        ListOptions {
             filters: self.filters,
             limit: self.limit,
             cursor: self.cursor,
-        };
+        }
+    }
 
-        store.list(options).await
+    /// Execute the query and return the results
+    pub async fn list(self) -> Result<Vec<T>> {
+        Ok(self.page().await?.items)
+    }
+
+    /// Execute the query and return a page of results with the cursor of the next page
+    pub async fn page(self) -> Result<Page<T>> {
+        use crate::entity::Store;
+        let store = Store::from_current_context().await?;
+        store.list_page(self.into_options()).await
+    }
+
+    /// Stream the results of every page, starting at the query cursor
+    ///
+    /// ```rust,ignore
+    /// let mut pools = Pool::find().where_gt("tvl", 0).stream().await?;
+    /// while let Some(pool) = pools.try_next().await? {
+    ///     // ...
+    /// }
+    /// ```
+    pub async fn stream(self) -> Result<futures::stream::BoxStream<'static, Result<T>>> {
+        use crate::entity::Store;
+        let store = Store::from_current_context().await?;
+        Ok(store.stream(self.into_options()))
     }
 
     /// Execute the query and return the first result (if any)
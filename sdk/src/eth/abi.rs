@@ -0,0 +1,123 @@
+//! Contract ABIs used by processors generated with `#[eth_processor]`
+
+use crate::eth::eth_processor::{EthEvent, EventFilter};
+use alloy::json_abi::{Event as JsonEvent, JsonAbi};
+use alloy::primitives::hex;
+use anyhow::Result;
+use tracing::debug;
+
+/// Parsed contract ABI
+#[derive(Debug, Clone)]
+pub struct ContractAbi {
+    abi: JsonAbi,
+}
+
+impl ContractAbi {
+    /// Parse a JSON ABI, either a plain array or a build artifact with an `abi` key
+    pub fn parse(json: &str) -> Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let abi = match value {
+            serde_json::Value::Object(mut artifact) => artifact
+                .remove("abi")
+                .ok_or_else(|| anyhow::anyhow!("ABI artifact has no `abi` key"))?,
+            value => value,
+        };
+        Ok(Self {
+            abi: serde_json::from_value(abi)?,
+        })
+    }
+
+    pub fn abi(&self) -> &JsonAbi {
+        &self.abi
+    }
+
+    /// First overload of the event `name`
+    pub fn event(&self, name: &str) -> Option<&JsonEvent> {
+        self.abi.event(name)?.first()
+    }
+
+    /// Filter matching every non-anonymous overload of the event `name`
+    pub fn event_filter(&self, name: &str) -> Option<EventFilter> {
+        let topics = self
+            .abi
+            .event(name)?
+            .iter()
+            .filter(|event| !event.anonymous)
+            .map(|event| hex::encode_prefixed(event.selector()))
+            .collect::<Vec<_>>();
+        if topics.is_empty() {
+            return None;
+        }
+        Some(EventFilter {
+            address: None,
+            address_type: None,
+            topics,
+        })
+    }
+
+    /// Decode `event` with the overload of `name` matching its topic0
+    ///
+    /// The event is returned undecoded if no overload matches or decoding fails.
+    pub fn decode_event(&self, name: &str, event: EthEvent) -> EthEvent {
+        let Some(topic0) = event.log.topics().first() else {
+            return event;
+        };
+        let json_event = self
+            .abi
+            .event(name)
+            .and_then(|events| events.iter().find(|e| e.selector() == *topic0));
+        match json_event.map(|json_event| event.decode(json_event)) {
+            Some(Ok(decoded)) => decoded,
+            Some(Err(e)) => {
+                debug!("Failed to decode event {}: {}", name, e);
+                event
+            }
+            None => {
+                debug!("No ABI overload of {} matches topic {}", name, topic0);
+                event
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{addresses, mock_transfer_log};
+
+    const ERC20_ABI: &str = include_str!("tests/abis/erc20.json");
+    const TRANSFER_TOPIC: &str =
+        "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+    #[test]
+    fn test_parse_artifact() {
+        let artifact = format!(r#"{{"contractName":"ERC20","abi":{}}}"#, ERC20_ABI);
+        let abi = ContractAbi::parse(&artifact).unwrap();
+        assert!(abi.event("Transfer").is_some());
+        assert!(ContractAbi::parse(r#"{"contractName":"ERC20"}"#).is_err());
+    }
+
+    #[test]
+    fn test_event_filter_and_decode() {
+        let abi = ContractAbi::parse(ERC20_ABI).unwrap();
+        assert_eq!(
+            abi.event_filter("Transfer").unwrap().topics,
+            vec![TRANSFER_TOPIC]
+        );
+        assert!(abi.event_filter("Mint").is_none());
+
+        let log = mock_transfer_log(
+            addresses::TEST_CONTRACT,
+            addresses::ZERO,
+            addresses::TEST_ADDRESS_1,
+            "1000",
+        );
+        let event = EthEvent { log, decoded: None };
+        assert!(
+            abi.decode_event("Transfer", event.clone())
+                .decoded
+                .is_some()
+        );
+        assert!(abi.decode_event("Approval", event).decoded.is_none());
+    }
+}
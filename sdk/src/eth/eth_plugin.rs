@@ -1,13 +1,13 @@
 use crate::core::plugin::FullPlugin;
 use crate::core::{
-    AsyncPluginProcessor, BaseProcessor, HandlerRegister, Plugin, PluginRegister,
-    StateCollector, StateUpdateCollector, RUNTIME_CONTEXT,
+    binding_value, dispatch_handler, AsyncPluginProcessor, BaseProcessor, ContractConfigBuilder,
+    HandlerRegister, Plugin, PluginRegister,
 };
 use crate::eth::eth_processor::{EthProcessorImpl, EthEvent, TimeOrBlock};
 use crate::eth::ParsedEthData;
 use crate::log_filter::AddressOrType;
 use crate::processor::HandlerType;
-use crate::{ConfigureHandlersResponse, ContractConfig, ContractInfo, LogFilter, LogHandlerConfig, Timestamp, Topic};
+use crate::{ConfigureHandlersResponse, LogFilter, LogHandlerConfig, Timestamp, Topic};
 use anyhow;
 use tracing::debug;
 
@@ -19,6 +19,10 @@ pub struct EthPlugin {
 
 
 impl Plugin for EthPlugin {
+    fn name(&self) -> &str {
+        "eth-plugin"
+    }
+
     fn handler_types(&self) -> &'static [HandlerType] {
         &[
             HandlerType::EthLog,
@@ -39,33 +43,23 @@ impl Plugin for EthPlugin {
             .collect()
     }
 
-    fn name() -> &'static str
-    where
-        Self: Sized,
-    {
-        "eth-plugin"
-    }
-
     fn configure(&mut self, config: &mut ConfigureHandlersResponse) {
         debug!("Configuring EthPlugin handlers for all chains");
 
         for (processor_idx, processor) in self.processors.iter().enumerate() {
             let processor_chain_id = processor.chain_id();
 
-            let mut contract_config = ContractConfig::default();
-            contract_config.processor_type = crate::core::USER_PROCESSOR.to_owned();
+            let mut builder = ContractConfigBuilder::new(
+                processor_chain_id,
+                processor.options.address.clone(),
+                processor.name(),
+            );
             if let Some(TimeOrBlock::Block(block)) = processor.options.start {
-                contract_config.start_block = block;
+                builder = builder.start_block(block);
             }
             if let Some(TimeOrBlock::Block(block)) = processor.options.end {
-                contract_config.end_block = block;
+                builder = builder.end_block(block);
             }
-            contract_config.contract = Some(ContractInfo {
-                address: processor.options.address.clone(),
-                name: processor.name().to_string(),
-                abi: "".to_owned(),
-                chain_id: processor_chain_id.to_string(),
-            });
 
             debug!(
                 "Registering handlers for processor '{}' (chain_id: {})",
@@ -93,36 +87,24 @@ impl Plugin for EthPlugin {
                 } else {
                     for filter in handler.filters.iter() {
                         let mut log_filter = LogFilter::default();
-                        if let Some(contract) = &contract_config.contract {
-                            let mut address = &contract.address;
-                            if let Some(addr) = &filter.address {
-                                address = addr
-                            }
-                            log_filter.address_or_type =
-                                Some(AddressOrType::Address(address.clone()));
-                        }
+                        let address = filter
+                            .address
+                            .as_ref()
+                            .unwrap_or(&builder.contract().address);
+                        log_filter.address_or_type =
+                            Some(AddressOrType::Address(address.clone()));
                         log_filter.topics.push(Topic {
                             hashes: filter.topics.clone(),
                         });
                         log_config.filters.push(log_filter);
                     }
                 }
-                contract_config.log_configs.push(log_config);
+                builder.config_mut().log_configs.push(log_config);
             }
 
-            config.contract_configs.push(contract_config);
+            config.contract_configs.push(builder.build());
         }
     }
-
-    fn can_handle_type(&self, handler_type: HandlerType) -> bool {
-        matches!(
-            handler_type,
-            HandlerType::EthLog
-                | HandlerType::EthBlock
-                | HandlerType::EthTrace
-                | HandlerType::EthTransaction
-        )
-    }
 }
 
 impl EthPlugin {
@@ -131,33 +113,22 @@ impl EthPlugin {
         chain_id: &str,
         handler_id: i32,
     ) -> anyhow::Result<(&EthProcessorImpl, &crate::eth::eth_processor::EventHandler)> {
-        // Look up the handler information
-        let handler_info = self
+        // Get the processor and event handler
+        let (processor, handler_idx) = self
             .handler_register
-            .get_info(chain_id, handler_id)
-            .ok_or_else(|| {
-                anyhow::anyhow!("Handler {} not found for chain {}", handler_id, chain_id)
-            })?;
-
-        let processor_idx = handler_info.processor_idx;
-        let handler_idx = handler_info.handler_idx;
+            .find_processor(&self.processors, chain_id, handler_id)?;
 
         debug!(
-            "Found handler - processor_idx: {}, handler_idx: {}",
-            processor_idx, handler_idx
+            "Found handler - processor: {}, handler_idx: {}",
+            processor.name(),
+            handler_idx
         );
 
-        // Get the processor and event handler
-        let processor = self
-            .processors
-            .get(processor_idx)
-            .ok_or_else(|| anyhow::anyhow!("Processor index {} not found", processor_idx))?;
-
         let event_handler = processor.event_handlers.get(handler_idx).ok_or_else(|| {
             anyhow::anyhow!(
                 "Event handler index {} not found in processor {}",
                 handler_idx,
-                processor_idx
+                processor.name()
             )
         })?;
 
@@ -171,16 +142,13 @@ impl EthPlugin {
         debug!("Processing ETH log for chain_id: {}", data.chain_id);
 
         // Extract ETH log data
-        let eth_log_data = match &data.data {
-            Some(d) => match &d.value {
-                Some(crate::processor::data::Value::EthLog(log_data)) => log_data,
-                _ => {
-                    return Err(anyhow::anyhow!(
-                        "Expected ETH log data but got different type"
-                    ))
-                }
-            },
-            None => return Err(anyhow::anyhow!("No data provided in DataBinding")),
+        let eth_log_data = match binding_value(data)? {
+            crate::processor::data::Value::EthLog(log_data) => log_data,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Expected ETH log data but got different type"
+                ))
+            }
         };
         let timestamp = eth_log_data.timestamp.unwrap_or_default();
         
@@ -213,35 +181,20 @@ impl EthPlugin {
                     // todo decode log
                 }
 
-                // Create state collector for this handler execution
-                let (state_collector, state_receiver) = StateCollector::new();
-                let mut update_collector = StateUpdateCollector::new(state_receiver);
-
-                // Create context with state collector
-                let context =
-                    crate::eth::context::EthContext::with_state_collector(state_collector);
-                
                 // Extract metadata from all available parsed data sources
                 let mut metadata = parsed_data.extract_metadata(
                     data.chain_id.clone(),
                     processor.name().to_string()
                 );
                 metadata.timestamp = Timestamp::from(timestamp);
-                
-
-
-                let runtime_ctx = RUNTIME_CONTEXT.get();
 
                 // Execute the user handler with owned context using trait method
-                RUNTIME_CONTEXT
-                    .scope(
-                        runtime_ctx.with_metadata(metadata),
-                        event_handler.handler.handle_event(event, context),
-                    )
-                    .await;
-
-                // Collect state updates that occurred during handler execution
-                let state_result = update_collector.collect_updates();
+                let state_result = dispatch_handler(
+                    metadata,
+                    crate::eth::context::EthContext::with_state_collector,
+                    |context| event_handler.handler.handle_event(event, context),
+                )
+                .await;
                 result = result.merge(state_result)
             } else {
                 debug!("No log found for processor: {}", processor.name());
@@ -252,48 +205,6 @@ impl EthPlugin {
     }
 }
 
-impl crate::ProcessResult {
-    fn merge(mut self, other: crate::ProcessResult) -> Self {
-        // Extend vectors with other's values
-        self.gauges.extend(other.gauges);
-        self.counters.extend(other.counters);
-        #[allow(deprecated)]
-        self.logs.extend(other.logs);
-        self.events.extend(other.events);
-        self.exports.extend(other.exports);
-        self.timeseries_result.extend(other.timeseries_result);
-
-        // Merge states - combine config_updated flags and errors
-        match (self.states.as_mut(), other.states) {
-            (Some(self_state), Some(other_state)) => {
-                // If either has config_updated = true, result should be true
-                self_state.config_updated = self_state.config_updated || other_state.config_updated;
-
-                // Combine errors - if both have errors, concatenate them
-                match (&self_state.error, other_state.error) {
-                    (Some(self_error), Some(other_error)) => {
-                        self_state.error = Some(format!("{}; {}", self_error, other_error));
-                    }
-                    (None, Some(other_error)) => {
-                        self_state.error = Some(other_error);
-                    }
-                    // If self has error and other doesn't, keep self's error
-                    // If neither has error, keep None
-                    _ => {}
-                }
-            }
-            (None, Some(other_state)) => {
-                // If self has no state but other does, use other's state
-                self.states = Some(other_state);
-            }
-            // If other has no state, keep self's state (or None)
-            _ => {}
-        }
-
-        self
-    }
-}
-
 #[tonic::async_trait]
 impl AsyncPluginProcessor for EthPlugin {
     async fn process_binding(
@@ -428,9 +339,9 @@ mod tests {
         let registered_count = plugin.handler_register.len();
         assert!(registered_count > 0, "Should have registered some handlers");
 
-        // We no longer filter by chain; ensure configure is idempotent
+        // Configure is idempotent: existing handlers keep their ids
         let prev = plugin.handler_register.len();
-        plugin.configure(&mut config);
-        assert!(plugin.handler_register.len() >= prev);
+        plugin.configure(&mut ConfigureHandlersResponse::default());
+        assert_eq!(plugin.handler_register.len(), prev);
     }
 }
@@ -1,4 +1,5 @@
 use crate::core::BaseProcessor;
+use crate::core::event_types::{Event, dyn_sol_value_to_attribute};
 use crate::eth::{EthEventHandler, EventMarker};
 use crate::{AddressType, EthFetchConfig, EthPlugin};
 use alloy::dyn_abi::{DecodedEvent, DynSolEvent};
@@ -108,6 +109,32 @@ impl EthEvent {
     }
 
 
+    /// Build an [`Event`] named after the ABI event, with every param as an attribute
+    ///
+    /// Uses the decoded data if present and decodes the log otherwise. Unnamed
+    /// params are named `arg<index>`.
+    pub fn to_event(&self, json_event: &JsonEvent) -> Result<Event> {
+        let decoded_event;
+        let decoded = match &self.decoded {
+            Some(decoded) => decoded,
+            None => {
+                decoded_event = self.decode(json_event)?;
+                decoded_event.decoded.as_ref().expect("decode populates the decoded event")
+            }
+        };
+
+        let mut indexed = decoded.indexed.iter();
+        let mut body = decoded.body.iter();
+        let mut event = Event::name(&json_event.name);
+        for (i, input) in json_event.inputs.iter().enumerate() {
+            let value = if input.indexed { indexed.next() } else { body.next() }
+                .ok_or_else(|| anyhow::anyhow!("Decoded log has no value for param {} of {}", i, json_event.name))?;
+            let name = if input.name.is_empty() { format!("arg{}", i) } else { input.name.clone() };
+            event = event.attr(&name, dyn_sol_value_to_attribute(value, &input.components));
+        }
+        Ok(event)
+    }
+
     /// Internal method to decode a log using a pre-parsed `JsonEvent`
     fn parse_log_with_json(&self, json_event: &JsonEvent) -> Result<DecodedEvent> {
         // Convert JsonEvent inputs to DynSolTypes for dynamic decoding
@@ -444,4 +471,51 @@ mod tests {
             other => panic!("unexpected type for body[0]: {:?}", other),
         }
     }
+
+    #[test]
+    fn test_to_event() {
+        use crate::core::AttributeValue;
+
+        let json_event: JsonEvent = serde_json::from_str(
+            r#"{
+                "type": "event",
+                "name": "Swap",
+                "anonymous": false,
+                "inputs": [
+                    {"name": "pool", "type": "address", "indexed": true},
+                    {"name": "", "type": "bool", "indexed": false},
+                    {"name": "amounts", "type": "tuple", "indexed": false, "components": [
+                        {"name": "amountIn", "type": "uint256"},
+                        {"name": "amountOut", "type": "uint256"}
+                    ]}
+                ]
+            }"#,
+        )
+        .unwrap();
+        // Already decoded events are not decoded again
+        let eth_event = EthEvent {
+            log: Log::default(),
+            decoded: Some(DecodedEvent {
+                selector: None,
+                indexed: vec![DynSolValue::Address(Address::ZERO)],
+                body: vec![
+                    DynSolValue::Bool(true),
+                    DynSolValue::Tuple(vec![
+                        DynSolValue::Uint(U256::from(5), 256),
+                        DynSolValue::Uint(U256::from(7), 256),
+                    ]),
+                ],
+            }),
+        };
+
+        let event = eth_event.to_event(&json_event).unwrap();
+        assert_eq!(event.get_name(), "Swap");
+        let attributes = event.get_attributes();
+        assert!(matches!(&attributes["pool"], AttributeValue::String(s) if *s == Address::ZERO.to_checksum(None)));
+        assert!(matches!(attributes["arg1"], AttributeValue::Boolean(true)));
+        let AttributeValue::Object(amounts) = &attributes["amounts"] else {
+            panic!("expected an object for the tuple param");
+        };
+        assert!(matches!(&amounts["amountOut"], AttributeValue::BigInt(v) if v.to_string() == "7"));
+    }
 }
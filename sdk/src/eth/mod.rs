@@ -2,13 +2,15 @@ pub mod eth_processor;
 pub mod handler_type;
 pub mod eth_plugin;
 pub mod context;
+pub mod abi;
 mod eth_types;
-mod tests;
+pub(crate) mod tests;
 
 pub use eth_types::*;
 
 pub use handler_type::EthHandlerType;
 pub use eth_plugin::EthPlugin;
+pub use abi::ContractAbi;
 
 // Re-export alloy Log for convenient access via crate::eth::Log
 pub use alloy::rpc::types::Log;
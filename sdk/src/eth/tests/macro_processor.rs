@@ -0,0 +1,47 @@
+//! ERC20 processor declared with the `eth_processor` attribute macro
+
+#![cfg(test)]
+
+use crate::core::Context;
+use crate::core::event_types::dyn_sol_value_to_attribute;
+use crate::eth::context::EthContext;
+use crate::eth::eth_processor::EthEvent;
+
+/// Counts transfers and approvals, and logs the decoded transfer value
+///
+/// Deployed at `addresses::TEST_CONTRACT`.
+pub struct MacroErc20Processor;
+
+#[crate::eth_processor(
+    address = "0x1E4EDE388cbc9F4b5c79681B7f94d36a11ABEBC9",
+    network = "1",
+    abi = "src/eth/tests/abis/erc20.json",
+    name = "MacroToken"
+)]
+impl MacroErc20Processor {
+    #[on_event(Transfer)]
+    async fn on_transfer(&self, event: EthEvent, mut ctx: EthContext) {
+        let Some(decoded) = &event.decoded else {
+            return;
+        };
+        ctx.base_context()
+            .counter("transfers")
+            .add(1.0, None)
+            .await
+            .ok();
+        if let Some(value) = decoded.body.first() {
+            let event = crate::core::Event::name("transfer")
+                .attr("value", dyn_sol_value_to_attribute(value, &[]));
+            ctx.base_context().event_logger().emit(&event).await.ok();
+        }
+    }
+
+    #[on_event(Approval)]
+    async fn on_approval(&self, _event: EthEvent, mut ctx: EthContext) {
+        ctx.base_context()
+            .counter("approvals")
+            .add(1.0, None)
+            .await
+            .ok();
+    }
+}
@@ -4,6 +4,7 @@
 //! Ethereum processors with simulated blockchain data.
 
 pub mod test_processor;
+pub mod macro_processor;
 
 
 #[cfg(test)]
@@ -146,6 +147,44 @@ mod tests {
         assert_eq!(total_metrics, 3, "Expected exactly 3 total metrics (1 counter + 1 gauge + 1 event)");
     }
 
+    /// Test a processor declared with the `eth_processor` attribute macro
+    #[tokio::test]
+    async fn test_macro_processor() {
+        use macro_processor::{MacroErc20Processor, MacroErc20ProcessorTransfer};
+
+        assert_eq!(MacroErc20Processor.address(), addresses::TEST_CONTRACT);
+        assert_eq!(MacroErc20Processor.chain_id(), "1");
+        assert_eq!(MacroErc20Processor.name(), "MacroToken");
+        assert_eq!(
+            MacroErc20ProcessorTransfer::filter()[0].topics,
+            vec!["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"]
+        );
+
+        let mut server = TestProcessorServer::new();
+        MacroErc20Processor.bind(&server);
+        server.start().await.expect("Failed to start test server");
+
+        let config = server.get_config().await;
+        assert_eq!(config.contract_configs.len(), 1);
+        assert_eq!(config.contract_configs[0].log_configs.len(), 2);
+
+        let transfer_log = mock_transfer_log(
+            addresses::TEST_CONTRACT,
+            addresses::ZERO,
+            addresses::TEST_ADDRESS_1,
+            "1000",
+        );
+        let result = server.eth().test_log(transfer_log, Some(chain_ids::ETHEREUM)).await;
+
+        assert_eq!(result.first_counter_value("transfers"), Some(1.0));
+        assert_eq!(result.first_counter_value("approvals"), None);
+        let event = result.first_event("transfer").expect("Expected decoded transfer event");
+        assert!(matches!(
+            event.attributes.get("value"),
+            Some(AttributeValue::BigInt(value)) if value.to_string() == "1000"
+        ));
+    }
+
     /// Example test showing how to test block interval handlers
     #[tokio::test] 
     async fn test_block_interval_handler() {
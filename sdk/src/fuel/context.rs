@@ -0,0 +1,56 @@
+use crate::core::{Context, BaseContext, StateCollector};
+use crate::Timestamp;
+
+/// Context passed to Fuel handlers
+#[derive(Clone)]
+pub struct FuelContext {
+    base_context: BaseContext,
+    state_collector: Option<StateCollector>,
+    block_height: u64,
+    transaction_id: String,
+    timestamp: Timestamp,
+}
+
+impl FuelContext {
+    /// Create a new FuelContext for a transaction included in block `block_height`
+    pub fn new(block_height: u64, transaction_id: impl Into<String>, timestamp: Timestamp) -> Self {
+        Self {
+            base_context: BaseContext::new(),
+            state_collector: None,
+            block_height,
+            transaction_id: transaction_id.into(),
+            timestamp,
+        }
+    }
+
+    /// Enable state collection for this context
+    pub fn with_state_collector(mut self, state_collector: StateCollector) -> Self {
+        self.state_collector = Some(state_collector);
+        self
+    }
+
+    /// Height of the block being processed
+    pub fn block_height(&self) -> u64 {
+        self.block_height
+    }
+
+    /// Id of the transaction being processed, empty for block handlers
+    pub fn transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+
+    /// Block timestamp
+    pub fn timestamp(&self) -> &Timestamp {
+        &self.timestamp
+    }
+}
+
+impl Context for FuelContext {
+    fn base_context(&mut self) -> &mut BaseContext {
+        &mut self.base_context
+    }
+
+    fn state_collector(&self) -> Option<&StateCollector> {
+        self.state_collector.as_ref()
+    }
+}
@@ -0,0 +1,527 @@
+use crate::core::conversions::parse_struct;
+use crate::core::plugin::FullPlugin;
+use crate::core::{
+    AsyncPluginProcessor, BaseProcessor, ContractConfigBuilder, HandlerRegister, MetaData, Plugin,
+    PluginRegister, binding_value, dispatch_handler,
+};
+use crate::fuel::context::FuelContext;
+use crate::fuel::fuel_processor::{FuelProcessorImpl, ReceiptHandlerKind};
+use crate::fuel::{
+    FuelBlock, FuelLog, FuelReceipt, FuelTransaction, FuelTransfer, FuelTransferFilter,
+    normalize_fuel_address,
+};
+use crate::processor::{HandlerType, data, fuel_receipt_handler_config};
+use crate::{
+    ConfigureHandlersResponse, FuelReceiptHandlerConfig, FuelTransactionHandlerConfig,
+    HandleInterval, OnIntervalConfig, Timestamp,
+};
+use tracing::debug;
+
+#[derive(Default)]
+pub struct FuelPlugin {
+    handler_register: HandlerRegister<HandlerType>,
+    processors: Vec<Box<FuelProcessorImpl>>,
+}
+
+impl Plugin for FuelPlugin {
+    fn name(&self) -> &str {
+        "fuel-plugin"
+    }
+
+    fn handler_types(&self) -> &'static [HandlerType] {
+        &[
+            HandlerType::FuelReceipt,
+            HandlerType::FuelTransaction,
+            HandlerType::FuelBlock,
+        ]
+    }
+
+    fn processor_count(&self) -> usize {
+        self.processors.len()
+    }
+
+    fn chain_ids(&self) -> Vec<String> {
+        self.processors
+            .iter()
+            .map(|p| p.chain_id().to_string())
+            .collect()
+    }
+
+    fn configure(&mut self, config: &mut ConfigureHandlersResponse) {
+        debug!("Configuring FuelPlugin handlers for all chains");
+
+        for (processor_idx, processor) in self.processors.iter().enumerate() {
+            let chain_id = processor.chain_id();
+
+            let mut builder = ContractConfigBuilder::new(
+                chain_id,
+                processor.options.address.clone(),
+                processor.name(),
+            )
+            .start_block(processor.options.start_block.unwrap_or_default())
+            .end_block(processor.options.end_block.unwrap_or_default());
+
+            for (handler_idx, handler) in processor.receipt_handlers.iter().enumerate() {
+                let handler_id = self.handler_register.register(
+                    chain_id,
+                    HandlerType::FuelReceipt,
+                    processor_idx,
+                    handler_idx,
+                );
+                let receipt_filter = match &handler.kind {
+                    ReceiptHandlerKind::Log { log_ids, .. } => {
+                        fuel_receipt_handler_config::ReceiptFilter::Log(
+                            fuel_receipt_handler_config::Log {
+                                log_ids: log_ids.clone(),
+                            },
+                        )
+                    }
+                    ReceiptHandlerKind::Transfer { filter, .. } => {
+                        fuel_receipt_handler_config::ReceiptFilter::Transfer(
+                            fuel_receipt_handler_config::Transfer {
+                                asset_id: filter.asset_id.clone().unwrap_or_default(),
+                                from: filter.from.clone().unwrap_or_default(),
+                                to: filter.to.clone().unwrap_or_default(),
+                            },
+                        )
+                    }
+                };
+                builder
+                    .config_mut()
+                    .fuel_receipt_configs
+                    .push(FuelReceiptHandlerConfig {
+                        receipt_filter: Some(receipt_filter),
+                        handler_id,
+                        handler_name: handler.options.name.clone().unwrap_or_default(),
+                    });
+            }
+
+            for (handler_idx, handler) in processor.transaction_handlers.iter().enumerate() {
+                let handler_id = self.handler_register.register(
+                    chain_id,
+                    HandlerType::FuelTransaction,
+                    processor_idx,
+                    handler_idx,
+                );
+                builder
+                    .config_mut()
+                    .fuel_transaction_configs
+                    .push(FuelTransactionHandlerConfig {
+                        handler_id,
+                        handler_name: handler.options.name.clone().unwrap_or_default(),
+                    });
+            }
+
+            for (handler_idx, handler) in processor.block_handlers.iter().enumerate() {
+                let handler_id = self.handler_register.register(
+                    chain_id,
+                    HandlerType::FuelBlock,
+                    processor_idx,
+                    handler_idx,
+                );
+                builder.config_mut().interval_configs.push(OnIntervalConfig {
+                    handler_id,
+                    slot: handler.interval,
+                    slot_interval: Some(HandleInterval {
+                        recent_interval: handler.interval,
+                        backfill_interval: handler.backfill_interval,
+                    }),
+                    handler_name: handler.options.name.clone().unwrap_or_default(),
+                    ..Default::default()
+                });
+            }
+
+            config.contract_configs.push(builder.build());
+        }
+    }
+}
+
+impl FuelPlugin {
+    fn metadata(
+        processor: &FuelProcessorImpl,
+        chain_id: &str,
+        block_height: u64,
+        timestamp: &Timestamp,
+        transaction_id: &str,
+        receipt_index: i32,
+    ) -> MetaData {
+        MetaData {
+            address: processor.options.address.clone(),
+            contract_name: processor.name().to_string(),
+            chain_id: chain_id.to_string(),
+            block_number: block_height,
+            timestamp: timestamp.clone(),
+            transaction_hash: transaction_id.to_string(),
+            transaction_index: 0,
+            log_index: receipt_index,
+            base_labels: Default::default(),
+        }
+    }
+
+    async fn process_receipt(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        let fuel_receipt = match binding_value(data)? {
+            data::Value::FuelLog(fuel_receipt) => fuel_receipt,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Expected Fuel receipt data but got different type"
+                ));
+            }
+        };
+        let transaction: FuelTransaction =
+            parse_struct(fuel_receipt.transaction.as_ref(), "Fuel", "transaction")?;
+        let receipt_index = fuel_receipt.receipt_index as usize;
+        let receipt = transaction
+            .receipts()
+            .get(receipt_index)
+            .cloned()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Receipt index {} out of range in transaction {}",
+                    receipt_index,
+                    transaction.id
+                )
+            })?;
+        let timestamp = Timestamp::from(fuel_receipt.timestamp.unwrap_or_default());
+        let block_height = transaction.block_height();
+
+        let mut result = crate::ProcessResult::default();
+        for &handler_id in &data.handler_ids {
+            let (processor, handler_idx) = self
+                .handler_register
+                .find_processor(&self.processors, &data.chain_id, handler_id)?;
+            let handler = processor.receipt_handlers.get(handler_idx).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Receipt handler index {} not found in processor {}",
+                    handler_idx,
+                    processor.name()
+                )
+            })?;
+
+            let metadata = Self::metadata(
+                processor,
+                &data.chain_id,
+                block_height,
+                &timestamp,
+                &transaction.id,
+                receipt_index as i32,
+            );
+            let make_context = |collector| {
+                FuelContext::new(block_height, transaction.id.clone(), timestamp.clone())
+                    .with_state_collector(collector)
+            };
+            let handler_result = match &handler.kind {
+                ReceiptHandlerKind::Log { handler, .. } => {
+                    let log = FuelLog {
+                        log_id: receipt.log_id().unwrap_or_default().to_string(),
+                        receipt: receipt.clone(),
+                        receipt_index,
+                        transaction: transaction.clone(),
+                    };
+                    dispatch_handler(metadata, make_context, |ctx| handler.handle_log(log, ctx)).await
+                }
+                ReceiptHandlerKind::Transfer { handler, .. } => {
+                    let Some(transfer) =
+                        FuelTransfer::from_receipt(&receipt, receipt_index, transaction.clone())
+                    else {
+                        continue;
+                    };
+                    dispatch_handler(metadata, make_context, |ctx| {
+                        handler.on_transfer(transfer, ctx)
+                    })
+                    .await
+                }
+            };
+            result = result.merge(handler_result);
+        }
+
+        Ok(result)
+    }
+
+    async fn process_transaction(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        let fuel_transaction = match binding_value(data)? {
+            data::Value::FuelTransaction(fuel_transaction) => fuel_transaction,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Expected Fuel transaction data but got different type"
+                ));
+            }
+        };
+        let transaction: FuelTransaction =
+            parse_struct(fuel_transaction.transaction.as_ref(), "Fuel", "transaction")?;
+        let timestamp = Timestamp::from(fuel_transaction.timestamp.unwrap_or_default());
+        let block_height = transaction.block_height();
+
+        let mut result = crate::ProcessResult::default();
+        for &handler_id in &data.handler_ids {
+            let (processor, handler_idx) = self
+                .handler_register
+                .find_processor(&self.processors, &data.chain_id, handler_id)?;
+            let handler = processor
+                .transaction_handlers
+                .get(handler_idx)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Transaction handler index {} not found in processor {}",
+                        handler_idx,
+                        processor.name()
+                    )
+                })?;
+
+            let metadata = Self::metadata(
+                processor,
+                &data.chain_id,
+                block_height,
+                &timestamp,
+                &transaction.id,
+                0,
+            );
+            let handler_result = dispatch_handler(
+                metadata,
+                |collector| {
+                    FuelContext::new(block_height, transaction.id.clone(), timestamp.clone())
+                        .with_state_collector(collector)
+                },
+                |ctx| handler.handler.on_transaction(transaction.clone(), ctx),
+            )
+            .await;
+            result = result.merge(handler_result);
+        }
+
+        Ok(result)
+    }
+
+    async fn process_block(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        let fuel_block = match binding_value(data)? {
+            data::Value::FuelBlock(fuel_block) => fuel_block,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Expected Fuel block data but got different type"
+                ));
+            }
+        };
+        let block: FuelBlock = parse_struct(fuel_block.block.as_ref(), "Fuel", "block")?;
+        let timestamp = Timestamp::from(fuel_block.timestamp.unwrap_or_default());
+
+        let mut result = crate::ProcessResult::default();
+        for &handler_id in &data.handler_ids {
+            let (processor, handler_idx) = self
+                .handler_register
+                .find_processor(&self.processors, &data.chain_id, handler_id)?;
+            let handler = processor.block_handlers.get(handler_idx).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Block handler index {} not found in processor {}",
+                    handler_idx,
+                    processor.name()
+                )
+            })?;
+
+            let metadata =
+                Self::metadata(processor, &data.chain_id, block.height(), &timestamp, "", 0);
+            let handler_result = dispatch_handler(
+                metadata,
+                |collector| {
+                    FuelContext::new(block.height(), "", timestamp.clone())
+                        .with_state_collector(collector)
+                },
+                |ctx| handler.handler.on_block(block.clone(), ctx),
+            )
+            .await;
+            result = result.merge(handler_result);
+        }
+
+        Ok(result)
+    }
+}
+
+/// Whether `receipt` of a transaction calling `contract` matches a receipt handler config
+pub(crate) fn matches_receipt_config(
+    config: &FuelReceiptHandlerConfig,
+    contract: &str,
+    receipt: &FuelReceipt,
+) -> bool {
+    match &config.receipt_filter {
+        Some(fuel_receipt_handler_config::ReceiptFilter::Log(log)) => {
+            receipt.id.as_deref().map(normalize_fuel_address) == Some(normalize_fuel_address(contract))
+                && receipt
+                    .log_id()
+                    .is_some_and(|log_id| log.log_ids.iter().any(|id| id == log_id))
+        }
+        Some(fuel_receipt_handler_config::ReceiptFilter::Transfer(transfer)) => FuelTransferFilter {
+            asset_id: Some(transfer.asset_id.clone()),
+            from: Some(transfer.from.clone()),
+            to: Some(transfer.to.clone()),
+        }
+        .matches(receipt),
+        None => false,
+    }
+}
+
+#[tonic::async_trait]
+impl AsyncPluginProcessor for FuelPlugin {
+    async fn process_binding(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        debug!(
+            "FuelPlugin processing binding for chain_id: {}, handler_ids: {:?}",
+            data.chain_id, data.handler_ids
+        );
+
+        let handler_type = HandlerType::try_from(data.handler_type)?;
+        match handler_type {
+            HandlerType::FuelReceipt => self.process_receipt(data).await,
+            HandlerType::FuelTransaction => self.process_transaction(data).await,
+            HandlerType::FuelBlock => self.process_block(data).await,
+            _ => Err(anyhow::anyhow!(
+                "Unsupported handler type: {:?}",
+                handler_type
+            )),
+        }
+    }
+}
+
+impl FullPlugin for FuelPlugin {}
+
+impl PluginRegister<FuelProcessorImpl> for FuelPlugin {
+    fn register_processor(&mut self, processor: FuelProcessorImpl) -> &mut FuelProcessorImpl {
+        debug!(
+            "Registering processor: {} (chain_id: {})",
+            processor.name(),
+            processor.chain_id()
+        );
+
+        self.processors.push(Box::new(processor));
+        self.processors.last_mut().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Context;
+    use crate::fuel::{
+        FuelBlockHandler, FuelLogHandler, FuelLogMarker, FuelProcessor, FuelTransactionHandler,
+        FuelTransferHandler,
+    };
+    use crate::testing::TestProcessorServer;
+
+    const CONTRACT: &str = "0x2e40f2b244b98ed6b8204b3de0156c6961f98525c8162f80162fcf53eebd90e7";
+    const ASSET: &str = "0xf8f8b6283d7fa5b672b530cbb84fcccb4ff8dc40f8176ef4544ddb1f1952ad07";
+
+    struct AmmProcessor;
+
+    impl FuelProcessor for AmmProcessor {
+        fn address(&self) -> &str {
+            CONTRACT
+        }
+
+        fn name(&self) -> &str {
+            "amm"
+        }
+    }
+
+    struct SwapLog;
+
+    impl FuelLogMarker for SwapLog {
+        fn log_ids() -> &'static [&'static str] {
+            &["1515152261580153489"]
+        }
+    }
+
+    #[crate::async_trait]
+    impl FuelLogHandler<SwapLog> for AmmProcessor {
+        async fn on_log(&self, log: FuelLog, mut ctx: FuelContext) {
+            assert_eq!(ctx.transaction_id(), "0x7a");
+            let amount = u64::from_be_bytes(log.data().unwrap().try_into().unwrap());
+            ctx.base_context().gauge("swap_amount").record(amount as f64, None).await.ok();
+        }
+    }
+
+    #[crate::async_trait]
+    impl FuelTransferHandler for AmmProcessor {
+        async fn on_transfer(&self, transfer: FuelTransfer, mut ctx: FuelContext) {
+            ctx.base_context()
+                .counter("transferred")
+                .add(transfer.amount as f64, None)
+                .await
+                .ok();
+        }
+    }
+
+    #[crate::async_trait]
+    impl FuelTransactionHandler for AmmProcessor {
+        async fn on_transaction(&self, _transaction: FuelTransaction, mut ctx: FuelContext) {
+            ctx.base_context().counter("transactions").add(1.0, None).await.ok();
+        }
+    }
+
+    #[crate::async_trait]
+    impl FuelBlockHandler for AmmProcessor {
+        async fn on_block(&self, block: FuelBlock, mut ctx: FuelContext) {
+            ctx.base_context()
+                .gauge("block_height")
+                .record(block.height() as f64, None)
+                .await
+                .ok();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fuel_plugin_configure_and_dispatch() {
+        let mut server = TestProcessorServer::new();
+        AmmProcessor
+            .configure_log::<SwapLog>(None)
+            .configure_transfer(FuelTransferFilter::new().asset_id(ASSET), None)
+            .configure_transaction(None)
+            .configure_block_interval(100, 1000, None)
+            .from_block(10)
+            .bind(&server);
+        server.start().await.unwrap();
+
+        let config = server.get_config().await;
+        assert_eq!(config.contract_configs.len(), 1);
+        let contract_config = &config.contract_configs[0];
+        assert_eq!(contract_config.start_block, 10);
+        assert_eq!(contract_config.fuel_receipt_configs.len(), 2);
+        assert_eq!(contract_config.fuel_transaction_configs.len(), 1);
+        assert_eq!(
+            contract_config.interval_configs[0].slot_interval,
+            Some(HandleInterval {
+                recent_interval: 100,
+                backfill_interval: 1000,
+            })
+        );
+
+        let facet = server.fuel();
+        let transaction = FuelTransaction::new("0x7a")
+            .with_block_height(1200)
+            .with_log(CONTRACT, "1515152261580153489", &42u64.to_be_bytes())
+            .with_log(CONTRACT, "999", &[0])
+            .with_transfer(CONTRACT, "0x01", ASSET, 300)
+            .with_transfer(CONTRACT, "0x01", "0xbeef", 5);
+        let result = facet.test_transaction(transaction, None).await;
+        assert_eq!(result.first_counter_value("transactions"), Some(1.0));
+        assert_eq!(result.first_gauge_value("swap_amount"), Some(42.0));
+        assert_eq!(result.gauges.len(), 1);
+        let transferred: Vec<f64> = result
+            .counters
+            .iter()
+            .filter(|c| c.name == "transferred")
+            .map(|c| c.value)
+            .collect();
+        assert_eq!(transferred, vec![300.0]);
+        assert_eq!(result.gauges[0].metadata.block_number, Some(1200));
+
+        let result = facet.test_block(FuelBlock::new("0xb10c", 1300), None).await;
+        assert_eq!(result.first_gauge_value("block_height"), Some(1300.0));
+    }
+}
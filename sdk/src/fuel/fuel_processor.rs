@@ -0,0 +1,311 @@
+use crate::core::BaseProcessor;
+use crate::fuel::context::FuelContext;
+use crate::fuel::{
+    FuelBlockHandler, FuelLog, FuelLogHandler, FuelLogMarker, FuelPlugin, FuelTransactionHandler,
+    FuelTransferFilter, FuelTransferHandler, FUEL_MAINNET,
+};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct FuelBindOptions {
+    /// Contract id
+    pub address: String,
+    /// Optional, if not set, then use fuel mainnet
+    pub network: Option<String>,
+    /// Optional, override default processor name
+    pub name: Option<String>,
+    pub start_block: Option<u64>,
+    pub end_block: Option<u64>,
+}
+
+impl FuelBindOptions {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            network: None,
+            name: None,
+            start_block: None,
+            end_block: None,
+        }
+    }
+
+    pub fn with_network(mut self, network: impl Into<String>) -> Self {
+        self.network = Some(network.into());
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn from_block(mut self, block: u64) -> Self {
+        self.start_block = Some(block);
+        self
+    }
+
+    pub fn to_block(mut self, block: u64) -> Self {
+        self.end_block = Some(block);
+        self
+    }
+}
+
+/// Options for a single Fuel handler
+#[derive(Clone, Default)]
+pub struct FuelHandlerOptions {
+    pub name: Option<String>,
+}
+
+impl FuelHandlerOptions {
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+}
+
+/// A configurable Fuel processor that can register receipt, transaction and block handlers
+pub struct ConfigurableFuelProcessor<P: FuelProcessor> {
+    processor: Arc<P>,
+    options: FuelBindOptions,
+    receipt_handlers: Vec<ReceiptHandler>,
+    transaction_handlers: Vec<TransactionHandler>,
+    block_handlers: Vec<BlockHandler>,
+}
+
+impl<P: FuelProcessor> ConfigurableFuelProcessor<P> {
+    /// Create a new configurable processor
+    pub fn new(processor: P) -> Self {
+        let options = FuelBindOptions::new(processor.address())
+            .with_network(processor.chain_id())
+            .with_name(processor.name());
+        Self {
+            processor: Arc::new(processor),
+            options,
+            receipt_handlers: Vec::new(),
+            transaction_handlers: Vec::new(),
+            block_handlers: Vec::new(),
+        }
+    }
+
+    /// Handle logs with the ids of `T` emitted by the processor's contract
+    pub fn configure_log<T: FuelLogMarker>(mut self, options: Option<FuelHandlerOptions>) -> Self
+    where
+        P: FuelLogHandler<T>,
+    {
+        let handler: Arc<dyn TypeErasedFuelLogHandler> =
+            Arc::new((Arc::clone(&self.processor), PhantomData::<T>));
+        self.receipt_handlers.push(ReceiptHandler {
+            kind: ReceiptHandlerKind::Log {
+                handler,
+                log_ids: T::log_ids().iter().map(|id| id.to_string()).collect(),
+            },
+            options: options.unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Handle asset transfers matching `filter`
+    pub fn configure_transfer(
+        mut self,
+        filter: FuelTransferFilter,
+        options: Option<FuelHandlerOptions>,
+    ) -> Self
+    where
+        P: FuelTransferHandler,
+    {
+        let handler: Arc<dyn FuelTransferHandler> = self.processor.clone();
+        self.receipt_handlers.push(ReceiptHandler {
+            kind: ReceiptHandlerKind::Transfer { handler, filter },
+            options: options.unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Handle every transaction calling the processor's contract
+    pub fn configure_transaction(mut self, options: Option<FuelHandlerOptions>) -> Self
+    where
+        P: FuelTransactionHandler,
+    {
+        let handler: Arc<dyn FuelTransactionHandler> = self.processor.clone();
+        self.transaction_handlers.push(TransactionHandler {
+            handler,
+            options: options.unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Handle every `interval` blocks, and every `backfill_interval` blocks while
+    /// catching up on history
+    pub fn configure_block_interval(
+        mut self,
+        interval: i32,
+        backfill_interval: i32,
+        options: Option<FuelHandlerOptions>,
+    ) -> Self
+    where
+        P: FuelBlockHandler,
+    {
+        let handler: Arc<dyn FuelBlockHandler> = self.processor.clone();
+        self.block_handlers.push(BlockHandler {
+            handler,
+            interval,
+            backfill_interval,
+            options: options.unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Start processing from `block`
+    pub fn from_block(mut self, block: u64) -> Self {
+        self.options = self.options.from_block(block);
+        self
+    }
+
+    /// Stop processing after `block`
+    pub fn to_block(mut self, block: u64) -> Self {
+        self.options = self.options.to_block(block);
+        self
+    }
+
+    /// Bind this configured processor to a server
+    pub fn bind<S: crate::BindableServer>(self, server: &S) {
+        let processor_impl = FuelProcessorImpl {
+            options: self.options,
+            receipt_handlers: self.receipt_handlers,
+            transaction_handlers: self.transaction_handlers,
+            block_handlers: self.block_handlers,
+            _processor: self.processor,
+        };
+
+        server.register_processor::<FuelProcessorImpl, FuelPlugin>(processor_impl);
+    }
+}
+
+/// Core trait that all Fuel processors must implement
+pub trait FuelProcessor: Send + Sync + 'static {
+    /// Get the contract id this processor handles
+    fn address(&self) -> &str;
+
+    /// Get the network, defaults to Fuel mainnet
+    fn chain_id(&self) -> &str {
+        FUEL_MAINNET
+    }
+
+    /// Get the processor name
+    fn name(&self) -> &str;
+
+    fn configure_log<T: FuelLogMarker>(
+        self,
+        options: Option<FuelHandlerOptions>,
+    ) -> ConfigurableFuelProcessor<Self>
+    where
+        Self: Sized + FuelLogHandler<T>,
+    {
+        ConfigurableFuelProcessor::new(self).configure_log::<T>(options)
+    }
+
+    fn configure_transfer(
+        self,
+        filter: FuelTransferFilter,
+        options: Option<FuelHandlerOptions>,
+    ) -> ConfigurableFuelProcessor<Self>
+    where
+        Self: Sized + FuelTransferHandler,
+    {
+        ConfigurableFuelProcessor::new(self).configure_transfer(filter, options)
+    }
+
+    fn configure_transaction(self, options: Option<FuelHandlerOptions>) -> ConfigurableFuelProcessor<Self>
+    where
+        Self: Sized + FuelTransactionHandler,
+    {
+        ConfigurableFuelProcessor::new(self).configure_transaction(options)
+    }
+
+    fn configure_block_interval(
+        self,
+        interval: i32,
+        backfill_interval: i32,
+        options: Option<FuelHandlerOptions>,
+    ) -> ConfigurableFuelProcessor<Self>
+    where
+        Self: Sized + FuelBlockHandler,
+    {
+        ConfigurableFuelProcessor::new(self).configure_block_interval(interval, backfill_interval, options)
+    }
+}
+
+// Type-erased handler so processors with different markers can share storage
+pub trait TypeErasedFuelLogHandler: Send + Sync {
+    fn handle_log(&self, log: FuelLog, ctx: FuelContext) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+}
+
+impl<H, T> TypeErasedFuelLogHandler for (H, PhantomData<T>)
+where
+    H: FuelLogHandler<T>,
+    T: FuelLogMarker,
+{
+    fn handle_log(&self, log: FuelLog, ctx: FuelContext) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(self.0.on_log(log, ctx))
+    }
+}
+
+/// Receipt handlers share one handler id space, as both are `FuelReceiptHandlerConfig`s
+#[derive(Clone)]
+pub(crate) enum ReceiptHandlerKind {
+    Log {
+        handler: Arc<dyn TypeErasedFuelLogHandler>,
+        log_ids: Vec<String>,
+    },
+    Transfer {
+        handler: Arc<dyn FuelTransferHandler>,
+        filter: FuelTransferFilter,
+    },
+}
+
+#[derive(Clone)]
+pub(crate) struct ReceiptHandler {
+    pub(crate) kind: ReceiptHandlerKind,
+    pub(crate) options: FuelHandlerOptions,
+}
+
+#[derive(Clone)]
+pub(crate) struct TransactionHandler {
+    pub(crate) handler: Arc<dyn FuelTransactionHandler>,
+    pub(crate) options: FuelHandlerOptions,
+}
+
+#[derive(Clone)]
+pub(crate) struct BlockHandler {
+    pub(crate) handler: Arc<dyn FuelBlockHandler>,
+    pub(crate) interval: i32,
+    pub(crate) backfill_interval: i32,
+    pub(crate) options: FuelHandlerOptions,
+}
+
+// Internal storage for processor data - used by the plugin system
+#[derive(Clone)]
+pub(crate) struct FuelProcessorImpl {
+    pub(crate) options: FuelBindOptions,
+    pub(crate) receipt_handlers: Vec<ReceiptHandler>,
+    pub(crate) transaction_handlers: Vec<TransactionHandler>,
+    pub(crate) block_handlers: Vec<BlockHandler>,
+    pub(crate) _processor: Arc<dyn FuelProcessor>,
+}
+
+impl BaseProcessor for FuelProcessorImpl {
+    fn chain_id(&self) -> &str {
+        self.options.network.as_deref().unwrap_or(FUEL_MAINNET)
+    }
+
+    fn name(&self) -> &str {
+        self.options.name.as_deref().unwrap_or("fuel-processor")
+    }
+
+    fn handler_count(&self) -> usize {
+        self.receipt_handlers.len() + self.transaction_handlers.len() + self.block_handlers.len()
+    }
+}
@@ -0,0 +1,370 @@
+use crate::fuel::context::FuelContext;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Chain id of Fuel mainnet
+pub const FUEL_MAINNET: &str = "fuel_mainnet";
+/// Chain id of Fuel testnet
+pub const FUEL_TESTNET: &str = "fuel_testnet";
+
+/// Receipt types as reported by the Fuel GraphQL API
+pub const RECEIPT_LOG: &str = "LOG";
+pub const RECEIPT_LOG_DATA: &str = "LOG_DATA";
+pub const RECEIPT_TRANSFER: &str = "TRANSFER";
+pub const RECEIPT_TRANSFER_OUT: &str = "TRANSFER_OUT";
+
+/// Normalize a Fuel address, contract or asset id to lowercase, `0x`-prefixed, 64 hex characters
+pub fn normalize_fuel_address(address: &str) -> String {
+    let hex = address.trim().trim_start_matches("0x").to_lowercase();
+    format!("0x{:0>64}", hex)
+}
+
+/// A transaction receipt as returned by the Fuel GraphQL API
+///
+/// Which fields are set depends on `receipt_type`; numeric values are kept as the
+/// decimal strings the API returns.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FuelReceipt {
+    pub receipt_type: String,
+    /// Contract that produced the receipt
+    pub id: Option<String>,
+    /// Receiving contract of `TRANSFER` and `CALL` receipts
+    pub to: Option<String>,
+    /// Receiving address of `TRANSFER_OUT` receipts
+    pub to_address: Option<String>,
+    pub amount: Option<String>,
+    pub asset_id: Option<String>,
+    pub ra: Option<String>,
+    /// Log id of `LOG` and `LOG_DATA` receipts
+    pub rb: Option<String>,
+    pub rc: Option<String>,
+    pub rd: Option<String>,
+    pub ptr: Option<String>,
+    pub len: Option<String>,
+    pub digest: Option<String>,
+    /// Hex encoded payload of `LOG_DATA` and `RETURN_DATA` receipts
+    pub data: Option<String>,
+    pub pc: Option<String>,
+    pub is: Option<String>,
+    pub result: Option<String>,
+    pub gas_used: Option<String>,
+}
+
+impl FuelReceipt {
+    /// Log id of a `LOG` or `LOG_DATA` receipt
+    pub fn log_id(&self) -> Option<&str> {
+        match self.receipt_type.as_str() {
+            RECEIPT_LOG | RECEIPT_LOG_DATA => self.rb.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a `TRANSFER` or `TRANSFER_OUT` receipt
+    pub fn is_transfer(&self) -> bool {
+        matches!(self.receipt_type.as_str(), RECEIPT_TRANSFER | RECEIPT_TRANSFER_OUT)
+    }
+
+    /// Payload bytes of a `LOG_DATA` receipt
+    pub fn data_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        let data = self.data.as_deref().unwrap_or_default();
+        hex::decode(data.trim_start_matches("0x"))
+            .map_err(|e| anyhow::anyhow!("Failed to decode receipt data: {}", e))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FuelBlockRef {
+    pub id: String,
+    pub height: String,
+}
+
+/// Execution status of a transaction
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FuelTransactionStatus {
+    /// `SuccessStatus` or `FailureStatus`
+    #[serde(rename = "type")]
+    pub status_type: String,
+    /// Block time as a TAI64 timestamp string
+    pub time: String,
+    pub block: Option<FuelBlockRef>,
+    pub receipts: Vec<FuelReceipt>,
+    pub reason: Option<String>,
+}
+
+/// A transaction as returned by the Fuel GraphQL API
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FuelTransaction {
+    pub id: String,
+    pub block_number: Option<String>,
+    pub status: FuelTransactionStatus,
+    pub raw_payload: Option<String>,
+}
+
+impl FuelTransaction {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            status: FuelTransactionStatus {
+                status_type: "SuccessStatus".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Height of the block including this transaction
+    pub fn block_height(&self) -> u64 {
+        self.block_number
+            .as_deref()
+            .or(self.status.block.as_ref().map(|block| block.height.as_str()))
+            .and_then(|height| height.parse().ok())
+            .unwrap_or_default()
+    }
+
+    pub fn receipts(&self) -> &[FuelReceipt] {
+        &self.status.receipts
+    }
+
+    /// Whether the transaction executed successfully
+    pub fn is_success(&self) -> bool {
+        self.status.status_type == "SuccessStatus"
+    }
+
+    pub fn with_block_height(mut self, height: u64) -> Self {
+        self.block_number = Some(height.to_string());
+        self
+    }
+
+    pub fn with_receipt(mut self, receipt: FuelReceipt) -> Self {
+        self.status.receipts.push(receipt);
+        self
+    }
+
+    /// Append a `LOG_DATA` receipt emitted by `contract_id`
+    pub fn with_log(self, contract_id: &str, log_id: &str, data: &[u8]) -> Self {
+        self.with_receipt(FuelReceipt {
+            receipt_type: RECEIPT_LOG_DATA.to_string(),
+            id: Some(contract_id.to_string()),
+            rb: Some(log_id.to_string()),
+            len: Some(data.len().to_string()),
+            data: Some(format!("0x{}", hex::encode(data))),
+            ..Default::default()
+        })
+    }
+
+    /// Append a `TRANSFER` receipt from contract `from` to contract `to`
+    pub fn with_transfer(self, from: &str, to: &str, asset_id: &str, amount: u64) -> Self {
+        self.with_receipt(FuelReceipt {
+            receipt_type: RECEIPT_TRANSFER.to_string(),
+            id: Some(from.to_string()),
+            to: Some(to.to_string()),
+            asset_id: Some(asset_id.to_string()),
+            amount: Some(amount.to_string()),
+            ..Default::default()
+        })
+    }
+}
+
+/// A block as returned by the Fuel GraphQL API
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FuelBlock {
+    pub id: String,
+    pub height: String,
+    pub header: Value,
+}
+
+impl FuelBlock {
+    pub fn new(id: impl Into<String>, height: u64) -> Self {
+        Self {
+            id: id.into(),
+            height: height.to_string(),
+            header: Value::Null,
+        }
+    }
+
+    pub fn height(&self) -> u64 {
+        self.height.parse().unwrap_or_default()
+    }
+}
+
+/// A `LOG` or `LOG_DATA` receipt handled by a [`FuelLogHandler`]
+#[derive(Debug, Clone)]
+pub struct FuelLog {
+    pub log_id: String,
+    pub receipt: FuelReceipt,
+    pub receipt_index: usize,
+    pub transaction: FuelTransaction,
+}
+
+impl FuelLog {
+    /// Payload bytes, to be decoded with the contract's ABI
+    pub fn data(&self) -> anyhow::Result<Vec<u8>> {
+        self.receipt.data_bytes()
+    }
+}
+
+/// A `TRANSFER` or `TRANSFER_OUT` receipt handled by a [`FuelTransferHandler`]
+#[derive(Debug, Clone)]
+pub struct FuelTransfer {
+    pub asset_id: String,
+    /// Sending contract
+    pub from: String,
+    /// Receiving contract or address
+    pub to: String,
+    pub amount: u64,
+    pub receipt_index: usize,
+    pub transaction: FuelTransaction,
+}
+
+impl FuelTransfer {
+    /// Build from a transfer receipt, `None` for other receipt types
+    pub fn from_receipt(receipt: &FuelReceipt, receipt_index: usize, transaction: FuelTransaction) -> Option<Self> {
+        if !receipt.is_transfer() {
+            return None;
+        }
+        Some(Self {
+            asset_id: receipt.asset_id.clone().unwrap_or_default(),
+            from: receipt.id.clone().unwrap_or_default(),
+            to: receipt.to.clone().or_else(|| receipt.to_address.clone()).unwrap_or_default(),
+            amount: receipt.amount.as_deref().and_then(|a| a.parse().ok()).unwrap_or_default(),
+            receipt_index,
+            transaction,
+        })
+    }
+}
+
+/// Filter of a transfer handler, unset fields match anything
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FuelTransferFilter {
+    pub asset_id: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+impl FuelTransferFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn asset_id(mut self, asset_id: impl Into<String>) -> Self {
+        self.asset_id = Some(asset_id.into());
+        self
+    }
+
+    pub fn from(mut self, from: impl Into<String>) -> Self {
+        self.from = Some(from.into());
+        self
+    }
+
+    pub fn to(mut self, to: impl Into<String>) -> Self {
+        self.to = Some(to.into());
+        self
+    }
+
+    /// Whether a transfer receipt matches this filter
+    pub fn matches(&self, receipt: &FuelReceipt) -> bool {
+        fn field_matches(filter: &Option<String>, value: Option<&String>) -> bool {
+            match filter.as_deref() {
+                None | Some("") => true,
+                Some(filter) => value.is_some_and(|v| normalize_fuel_address(v) == normalize_fuel_address(filter)),
+            }
+        }
+        receipt.is_transfer()
+            && field_matches(&self.asset_id, receipt.asset_id.as_ref())
+            && field_matches(&self.from, receipt.id.as_ref())
+            && field_matches(&self.to, receipt.to.as_ref().or(receipt.to_address.as_ref()))
+    }
+}
+
+/// Marker for a log type handled by a [`FuelLogHandler`]
+pub trait FuelLogMarker: Send + Sync + 'static {
+    /// Log ids from the contract ABI (`loggedTypes[].logId`)
+    fn log_ids() -> &'static [&'static str];
+}
+
+#[crate::async_trait]
+pub trait FuelLogHandler<T: FuelLogMarker>: Send + Sync + 'static {
+    async fn on_log(&self, log: FuelLog, ctx: FuelContext);
+}
+
+#[crate::async_trait]
+pub trait FuelTransferHandler: Send + Sync + 'static {
+    async fn on_transfer(&self, transfer: FuelTransfer, ctx: FuelContext);
+}
+
+#[crate::async_trait]
+pub trait FuelTransactionHandler: Send + Sync + 'static {
+    async fn on_transaction(&self, transaction: FuelTransaction, ctx: FuelContext);
+}
+
+#[crate::async_trait]
+pub trait FuelBlockHandler: Send + Sync + 'static {
+    async fn on_block(&self, block: FuelBlock, ctx: FuelContext);
+}
+
+// Implementation for Arc<H> so a shared processor can be registered for several markers
+#[crate::async_trait]
+impl<H, T> FuelLogHandler<T> for std::sync::Arc<H>
+where
+    H: FuelLogHandler<T>,
+    T: FuelLogMarker,
+{
+    async fn on_log(&self, log: FuelLog, ctx: FuelContext) {
+        self.as_ref().on_log(log, ctx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_transaction() {
+        let raw = r#"{
+            "id": "0xabc",
+            "blockNumber": "1200",
+            "status": {
+                "type": "SuccessStatus",
+                "time": "4611686020108779339",
+                "block": {"id": "0xb10c", "height": "1200"},
+                "receipts": [
+                    {"receiptType": "LOG_DATA", "id": "0x01", "rb": "1515152261580153489", "data": "0x0102", "len": "2"},
+                    {"receiptType": "TRANSFER_OUT", "id": "0x01", "toAddress": "0x02", "assetId": "0x03", "amount": "500"}
+                ]
+            }
+        }"#;
+        let transaction: FuelTransaction = serde_json::from_str(raw).unwrap();
+        assert_eq!(transaction.block_height(), 1200);
+        assert!(transaction.is_success());
+
+        let log = &transaction.receipts()[0];
+        assert_eq!(log.log_id(), Some("1515152261580153489"));
+        assert_eq!(log.data_bytes().unwrap(), vec![1, 2]);
+
+        let transfer = FuelTransfer::from_receipt(&transaction.receipts()[1], 1, transaction.clone()).unwrap();
+        assert_eq!(transfer.to, "0x02");
+        assert_eq!(transfer.amount, 500);
+        assert!(FuelTransfer::from_receipt(log, 0, transaction.clone()).is_none());
+    }
+
+    #[test]
+    fn test_transfer_filter() {
+        let receipt = FuelReceipt {
+            receipt_type: RECEIPT_TRANSFER.to_string(),
+            id: Some("0x01".to_string()),
+            to: Some("0x02".to_string()),
+            asset_id: Some("0xAA".to_string()),
+            amount: Some("1".to_string()),
+            ..Default::default()
+        };
+        assert!(FuelTransferFilter::new().matches(&receipt));
+        assert!(FuelTransferFilter::new().asset_id("0xaa").from("0x1").matches(&receipt));
+        assert!(!FuelTransferFilter::new().to("0x01").matches(&receipt));
+        assert!(!FuelTransferFilter::new().matches(&FuelReceipt::default()));
+    }
+}
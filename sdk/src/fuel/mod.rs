@@ -0,0 +1,9 @@
+pub mod fuel_processor;
+pub mod fuel_plugin;
+pub mod context;
+mod fuel_types;
+
+pub use fuel_types::*;
+
+pub use fuel_processor::{FuelProcessor, FuelHandlerOptions, FuelBindOptions};
+pub use fuel_plugin::FuelPlugin;
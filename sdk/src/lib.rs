@@ -1,9 +1,22 @@
+// Lets the derive macros refer to `::sentio_sdk` from inside this crate
+extern crate self as sentio_sdk;
+
+pub mod aptos;
+pub mod btc;
 pub mod codegen;
+pub mod config;
 pub mod core;
+pub mod cosmos;
 pub mod entity;
 pub mod eth;
+pub mod fuel;
+pub mod recording;
+pub mod runner;
 pub mod server;
 pub mod service;
+pub mod solana;
+pub mod starknet;
+pub mod sui;
 pub mod testing;
 
 #[cfg(test)]
@@ -20,10 +33,19 @@ pub mod processor {
 
 // Re-export commonly used types for convenience
 pub use common::*;
-pub use core::{BaseProcessor, Plugin};
+pub use core::{BaseProcessor, Plugin, SentioEvent};
+pub use sentio_sdk_macros::{SentioEvent, eth_processor};
+pub use aptos::AptosPlugin;
+pub use btc::BtcPlugin;
+pub use cosmos::CosmosPlugin;
 pub use eth::EthPlugin;
+pub use fuel::FuelPlugin;
+pub use solana::SolanaPlugin;
+pub use starknet::StarknetPlugin;
+pub use sui::SuiPlugin;
 pub use processor::HandlerType;
 pub use processor::*;
+pub use config::ServerConfig;
 pub use server::{Server, ServerArgs};
 pub use service::ProcessorService;
 
@@ -35,8 +57,8 @@ pub use testing::{TestEnvironment, TestProcessorServer};
 
 // Re-export entity framework components
 pub use entity::{
-    BigDecimal, BigInt, Bytes, Entity, EntityError, EntityId, EntityResult, EntityStore, Filter,
-    ID, Int8, ListOptions, QueryBuilder, Store, Timestamp,
+    BigDecimal, BigInt, Bytes, Entity, EntityError, EntityField, EntityId, EntityResult,
+    EntityStore, Filter, ID, Int8, ListOptions, Page, QueryBuilder, Store, Timestamp,
 };
 
 // Re-export codegen components for build scripts
@@ -0,0 +1,436 @@
+//! Recording and replay of `process_bindings_stream` sessions
+//!
+//! When recording is enabled (`--record-dir` / `SENTIO_RECORD_DIR`), every stream opened by the
+//! driver is written to its own file in the record directory. Each file is a sequence of
+//! length-delimited [`RecordedMessage`]s holding the `ProcessStreamRequest`s received from the
+//! driver and the `ProcessStreamResponseV3`s sent back, including DB request/result round trips.
+//!
+//! [`replay`] feeds a recording back through a [`ProcessorService`], playing the driver's role:
+//! bindings are re-sent, DB requests are answered from the recorded DB results, and the produced
+//! responses are diffed against the recorded ones.
+
+use crate::processor::{
+    process_stream_request, process_stream_response_v3, DbRequest, DbResponse,
+    ProcessStreamRequest, ProcessStreamResponseV3,
+};
+use crate::service::ProcessorService;
+use anyhow::{anyhow, Context as _, Result};
+use prost::Message;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio_stream::StreamExt;
+use tonic::Status;
+use tracing::{debug, warn};
+
+/// A single message of a recorded stream session
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RecordedMessage {
+    /// Position of the message within the session
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    /// Microseconds since the stream was opened
+    #[prost(uint64, tag = "2")]
+    pub elapsed_micros: u64,
+    #[prost(oneof = "recorded_message::Message", tags = "3, 4")]
+    pub message: Option<recorded_message::Message>,
+}
+
+pub mod recorded_message {
+    /// Direction and payload of a recorded message
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Message {
+        /// Received from the driver
+        #[prost(message, tag = "3")]
+        Request(crate::processor::ProcessStreamRequest),
+        /// Sent to the driver
+        #[prost(message, tag = "4")]
+        Response(crate::processor::ProcessStreamResponseV3),
+    }
+}
+
+/// Writes one stream session to a length-delimited protobuf file
+pub struct StreamRecorder {
+    path: PathBuf,
+    writer: Mutex<std::io::BufWriter<std::fs::File>>,
+    sequence: AtomicU64,
+    started: Instant,
+}
+
+impl StreamRecorder {
+    /// Create a recorder writing to `path`, truncating any existing file
+    pub fn create(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let file = std::fs::File::create(&path)
+            .with_context(|| format!("Failed to create recording {}", path.display()))?;
+        Ok(Self {
+            path,
+            writer: Mutex::new(std::io::BufWriter::new(file)),
+            sequence: AtomicU64::new(0),
+            started: Instant::now(),
+        })
+    }
+
+    /// Create a recorder for a new stream inside `dir`
+    pub fn create_in(dir: &Path, stream_id: i32) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create record directory {}", dir.display()))?;
+        let millis = chrono::Utc::now().timestamp_millis();
+        Self::create(dir.join(format!("stream-{}-{}.binpb", millis, stream_id)))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record_request(&self, request: &ProcessStreamRequest) {
+        self.write(recorded_message::Message::Request(request.clone()));
+    }
+
+    pub fn record_response(&self, response: &ProcessStreamResponseV3) {
+        self.write(recorded_message::Message::Response(response.clone()));
+    }
+
+    fn write(&self, message: recorded_message::Message) {
+        let mut writer = self.writer.lock().unwrap();
+        // Assign the sequence under the lock so file order matches sequence order
+        let record = RecordedMessage {
+            sequence: self.sequence.fetch_add(1, Ordering::SeqCst),
+            elapsed_micros: self.started.elapsed().as_micros() as u64,
+            message: Some(message),
+        };
+        // Flush every message so the recording survives a crash of the processor
+        let result = writer
+            .write_all(&record.encode_length_delimited_to_vec())
+            .and_then(|_| writer.flush());
+        if let Err(e) = result {
+            warn!("Failed to write recording {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Read all messages of a recording
+pub fn read_recording(path: impl AsRef<Path>) -> Result<Vec<RecordedMessage>> {
+    let path = path.as_ref();
+    let content = std::fs::read(path)
+        .with_context(|| format!("Failed to read recording {}", path.display()))?;
+    let mut buf = content.as_slice();
+    let mut messages = Vec::new();
+    while !buf.is_empty() {
+        let message = RecordedMessage::decode_length_delimited(&mut buf).with_context(|| {
+            format!("Corrupt message #{} in {}", messages.len(), path.display())
+        })?;
+        messages.push(message);
+    }
+    Ok(messages)
+}
+
+/// A difference between a recorded and a replayed response
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseMismatch {
+    pub process_id: i32,
+    /// Position of the response among the responses for `process_id`
+    pub index: usize,
+    pub recorded: Option<ProcessStreamResponseV3>,
+    pub replayed: Option<ProcessStreamResponseV3>,
+}
+
+/// Outcome of replaying a recording
+#[derive(Debug, Clone, Default)]
+pub struct ReplayReport {
+    /// Number of bindings sent to the service
+    pub bindings: usize,
+    /// DB requests issued during replay that have no recorded counterpart
+    pub unmatched_db_requests: Vec<DbRequest>,
+    pub mismatches: Vec<ResponseMismatch>,
+}
+
+impl ReplayReport {
+    /// Whether the replay produced exactly the recorded responses
+    pub fn is_match(&self) -> bool {
+        self.unmatched_db_requests.is_empty() && self.mismatches.is_empty()
+    }
+}
+
+/// Options for [`replay_with`]
+#[derive(Debug, Clone)]
+pub struct ReplayOptions {
+    /// Maximum time to wait for all bindings to finish
+    pub timeout: Duration,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Replay a recording through `service` with default options
+pub async fn replay(service: &ProcessorService, path: impl AsRef<Path>) -> Result<ReplayReport> {
+    replay_with(service, path, ReplayOptions::default()).await
+}
+
+/// Replay a recording through `service` and diff the responses against the recording
+pub async fn replay_with(
+    service: &ProcessorService,
+    path: impl AsRef<Path>,
+    options: ReplayOptions,
+) -> Result<ReplayReport> {
+    let messages = read_recording(path)?;
+
+    let mut bindings = Vec::new();
+    let mut recorded_results: HashMap<u64, DbResponse> = HashMap::new();
+    let mut recorded_responses: BTreeMap<i32, Vec<ProcessStreamResponseV3>> = BTreeMap::new();
+    let mut recorded_db_requests = Vec::new();
+    for message in messages {
+        match message.message {
+            Some(recorded_message::Message::Request(request)) => match request.value {
+                Some(process_stream_request::Value::Binding(_)) => bindings.push(request),
+                Some(process_stream_request::Value::DbResult(result)) => {
+                    recorded_results.insert(result.op_id, result);
+                }
+                _ => {}
+            },
+            Some(recorded_message::Message::Response(response)) => {
+                if let Some(process_stream_response_v3::Value::DbRequest(request)) = &response.value
+                {
+                    recorded_db_requests.push((response.process_id, request.clone()));
+                }
+                recorded_responses
+                    .entry(response.process_id)
+                    .or_default()
+                    .push(response);
+            }
+            None => {}
+        }
+    }
+
+    let mut report = ReplayReport {
+        bindings: bindings.len(),
+        ..Default::default()
+    };
+
+    let (inbound_tx, inbound_rx) =
+        tokio::sync::mpsc::channel::<Result<ProcessStreamRequest, Status>>(1000);
+    let mut outbound =
+        service.open_stream(
+            tokio_stream::wrappers::ReceiverStream::new(inbound_rx),
+            crate::core::benchmark::new_stream_id(),
+            None,
+        );
+    for binding in bindings {
+        inbound_tx
+            .send(Ok(binding))
+            .await
+            .map_err(|_| anyhow!("Replay stream closed before all bindings were sent"))?;
+    }
+
+    let mut replayed_responses: BTreeMap<i32, Vec<ProcessStreamResponseV3>> = BTreeMap::new();
+    let mut pending_results = report.bindings;
+    let deadline = tokio::time::Instant::now() + options.timeout;
+    while pending_results > 0 {
+        let next = tokio::time::timeout_at(deadline, outbound.next())
+            .await
+            .map_err(|_| {
+                anyhow!("Replay timed out with {} binding(s) unfinished", pending_results)
+            })?;
+        let response = match next {
+            Some(Ok(response)) => response,
+            Some(Err(status)) => return Err(anyhow!("Replay stream failed: {}", status)),
+            None => break,
+        };
+
+        match &response.value {
+            Some(process_stream_response_v3::Value::Result(_)) => pending_results -= 1,
+            Some(process_stream_response_v3::Value::DbRequest(request)) => {
+                let answer = take_recorded_answer(
+                    response.process_id,
+                    request,
+                    &mut recorded_db_requests,
+                    &mut recorded_results,
+                );
+                let answer = match answer {
+                    Some(result) => result,
+                    None => {
+                        debug!("No recorded DB result for op {}", request.op_id);
+                        report.unmatched_db_requests.push(request.clone());
+                        DbResponse {
+                            op_id: request.op_id,
+                            value: Some(crate::processor::db_response::Value::Error(
+                                "no recorded result for this request".to_string(),
+                            )),
+                            next_cursor: None,
+                        }
+                    }
+                };
+                let request = ProcessStreamRequest {
+                    process_id: response.process_id,
+                    value: Some(process_stream_request::Value::DbResult(answer)),
+                };
+                let _ = inbound_tx.send(Ok(request)).await;
+            }
+            _ => {}
+        }
+        replayed_responses
+            .entry(response.process_id)
+            .or_default()
+            .push(response);
+    }
+
+    report.mismatches = diff_responses(&recorded_responses, &replayed_responses);
+    Ok(report)
+}
+
+/// Find the recorded DB request equal to `request` (ignoring op ids) and return its recorded
+/// result, re-addressed to the op id of the replayed request
+fn take_recorded_answer(
+    process_id: i32,
+    request: &DbRequest,
+    recorded_requests: &mut Vec<(i32, DbRequest)>,
+    recorded_results: &mut HashMap<u64, DbResponse>,
+) -> Option<DbResponse> {
+    let position = recorded_requests
+        .iter()
+        .position(|(pid, recorded)| *pid == process_id && recorded.op == request.op)?;
+    let (_, recorded) = recorded_requests.remove(position);
+    let mut result = recorded_results.remove(&recorded.op_id)?;
+    result.op_id = request.op_id;
+    Some(result)
+}
+
+/// Compare responses per process id, in order, with DB op ids normalized
+fn diff_responses(
+    recorded: &BTreeMap<i32, Vec<ProcessStreamResponseV3>>,
+    replayed: &BTreeMap<i32, Vec<ProcessStreamResponseV3>>,
+) -> Vec<ResponseMismatch> {
+    let empty = Vec::new();
+    let mut process_ids: Vec<i32> = recorded.keys().chain(replayed.keys()).copied().collect();
+    process_ids.sort_unstable();
+    process_ids.dedup();
+
+    let mut mismatches = Vec::new();
+    for process_id in process_ids {
+        let expected = recorded.get(&process_id).unwrap_or(&empty);
+        let actual = replayed.get(&process_id).unwrap_or(&empty);
+        for index in 0..expected.len().max(actual.len()) {
+            let recorded = expected.get(index).cloned();
+            let replayed = actual.get(index).cloned();
+            if recorded.clone().map(normalize) != replayed.clone().map(normalize) {
+                mismatches.push(ResponseMismatch {
+                    process_id,
+                    index,
+                    recorded,
+                    replayed,
+                });
+            }
+        }
+    }
+    mismatches
+}
+
+fn normalize(mut response: ProcessStreamResponseV3) -> ProcessStreamResponseV3 {
+    if let Some(process_stream_response_v3::Value::DbRequest(request)) = &mut response.value {
+        request.op_id = 0;
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processor::{db_request, DataBinding, HandlerType, ProcessResult};
+
+    fn binding_request(process_id: i32) -> ProcessStreamRequest {
+        ProcessStreamRequest {
+            process_id,
+            value: Some(process_stream_request::Value::Binding(DataBinding {
+                data: None,
+                handler_type: HandlerType::EthLog as i32,
+                handler_ids: vec![0],
+                chain_id: "1".to_string(),
+            })),
+        }
+    }
+
+    #[test]
+    fn recorder_round_trips_messages() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = StreamRecorder::create_in(dir.path(), 7).unwrap();
+        recorder.record_request(&binding_request(1));
+        recorder.record_response(&ProcessStreamResponseV3 {
+            process_id: 1,
+            value: Some(process_stream_response_v3::Value::Result(ProcessResult::default())),
+        });
+
+        let messages = read_recording(recorder.path()).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].sequence, 0);
+        assert_eq!(messages[1].sequence, 1);
+        assert!(matches!(
+            messages[0].message,
+            Some(recorded_message::Message::Request(_))
+        ));
+        assert!(matches!(
+            messages[1].message,
+            Some(recorded_message::Message::Response(_))
+        ));
+    }
+
+    #[test]
+    fn recorded_answers_are_matched_by_content() {
+        let get = |op_id| DbRequest {
+            op_id,
+            op: Some(db_request::Op::Get(db_request::DbGet {
+                entity: "Transfer".to_string(),
+                id: "1".to_string(),
+            })),
+        };
+        let mut requests = vec![(1, get(5))];
+        let mut results = HashMap::from([(
+            5,
+            DbResponse {
+                op_id: 5,
+                value: None,
+                next_cursor: Some("next".to_string()),
+            },
+        )]);
+
+        assert!(take_recorded_answer(2, &get(0), &mut requests, &mut results).is_none());
+        let answer = take_recorded_answer(1, &get(42), &mut requests, &mut results).unwrap();
+        assert_eq!(answer.op_id, 42);
+        assert_eq!(answer.next_cursor.as_deref(), Some("next"));
+        assert!(requests.is_empty());
+    }
+
+    #[tokio::test]
+    async fn replay_reports_diverging_responses() {
+        // Recorded a successful result, but no plugin handles the binding on replay
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = StreamRecorder::create_in(dir.path(), 1).unwrap();
+        recorder.record_request(&binding_request(3));
+        recorder.record_response(&ProcessStreamResponseV3 {
+            process_id: 3,
+            value: Some(process_stream_response_v3::Value::Result(ProcessResult::default())),
+        });
+
+        let service = ProcessorService::new();
+        let report = replay(&service, recorder.path()).await.unwrap();
+        assert_eq!(report.bindings, 1);
+        assert!(!report.is_match());
+        assert_eq!(report.mismatches.len(), 1);
+        assert_eq!(report.mismatches[0].process_id, 3);
+        assert!(report.mismatches[0].replayed.is_some());
+
+        // Replaying the replayed output against itself matches
+        let recorder = StreamRecorder::create_in(dir.path(), 2).unwrap();
+        recorder.record_request(&binding_request(3));
+        recorder.record_response(report.mismatches[0].replayed.as_ref().unwrap());
+        let report = replay(&service, recorder.path()).await.unwrap();
+        assert!(report.is_match(), "{:?}", report);
+    }
+}
@@ -0,0 +1,794 @@
+//! Offline runner that replays recorded bindings without the Sentio driver
+//!
+//! The runner loads a file of bindings, feeds them through the registered plugins with an
+//! in-memory entity store and writes the produced counters, gauges, events and entities as JSON.
+//! This makes it possible to reproduce a production run locally.
+//!
+//! Two input formats are supported:
+//!
+//! - Protobuf (`.pb`, `.bin`): length-delimited `DataBinding` messages, exactly as sent by the driver
+//! - JSON lines (`.jsonl`, `.ndjson`, `.json`): one [`BindingRecord`] per line, holding a raw alloy
+//!   log or transaction plus its block timestamp. Handler ids are resolved from the processor
+//!   configuration when they are not given.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use sentio_sdk::runner::Runner;
+//!
+//! fn main() {
+//!     let runner = Runner::new();
+//!     MyProcessor::new().bind(&runner);
+//!     // sentio-runner bindings.jsonl --output report.json
+//!     runner.start();
+//! }
+//! ```
+
+use crate::common::{rich_value, RichStruct, RichValue};
+use crate::core::conversions::{proto_to_bigdecimal, proto_to_bigint};
+use crate::core::{AttributeValue, PluginManager, RuntimeContext};
+use crate::entity::store::backend::Backend;
+use crate::testing::{MemoryDatabase, TestMetadata, TestResult};
+use crate::{data, ConfigureHandlersResponse, Data, DataBinding, HandlerType};
+use anyhow::{anyhow, Context as _, Result};
+use clap::{Parser, ValueEnum};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+/// Format of a binding file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InputFormat {
+    /// One JSON [`BindingRecord`] per line
+    Jsonl,
+    /// Length-delimited protobuf `DataBinding` messages
+    Protobuf,
+}
+
+impl InputFormat {
+    /// Guess the format from the file extension
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("jsonl") | Some("ndjson") | Some("json") => Ok(InputFormat::Jsonl),
+            Some("pb") | Some("bin") | Some("binpb") => Ok(InputFormat::Protobuf),
+            _ => Err(anyhow!("Cannot infer input format of {}, pass --format", path.display())),
+        }
+    }
+}
+
+/// Command line arguments for the offline runner
+#[derive(Parser, Debug, Clone)]
+#[command(name = "sentio-runner")]
+#[command(about = "Replay recorded bindings through a Sentio processor")]
+pub struct RunnerArgs {
+    /// File with the bindings to replay
+    pub input: PathBuf,
+
+    /// Input format, inferred from the file extension by default
+    #[arg(long, value_enum)]
+    pub format: Option<InputFormat>,
+
+    /// Where to write the JSON report (stdout by default)
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Chain id for JSON records that don't specify one
+    #[arg(long, default_value = "1")]
+    pub chain_id: String,
+
+    /// Stop at the first binding that fails
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub fail_fast: bool,
+
+    /// Enable debug/verbose logging
+    #[arg(short, long, action = clap::ArgAction::SetTrue)]
+    pub debug: bool,
+}
+
+/// Block timestamp of a JSON record, as unix seconds or RFC 3339
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum RecordTimestamp {
+    Seconds(i64),
+    Rfc3339(String),
+}
+
+impl RecordTimestamp {
+    fn to_proto(&self) -> Result<prost_types::Timestamp> {
+        let time = match self {
+            RecordTimestamp::Seconds(secs) => chrono::DateTime::from_timestamp(*secs, 0)
+                .ok_or_else(|| anyhow!("Timestamp out of range: {}", secs))?,
+            RecordTimestamp::Rfc3339(s) => chrono::DateTime::parse_from_rfc3339(s)
+                .with_context(|| format!("Invalid timestamp '{}'", s))?
+                .to_utc(),
+        };
+        Ok(prost_types::Timestamp {
+            seconds: time.timestamp(),
+            nanos: time.timestamp_subsec_nanos() as i32,
+        })
+    }
+}
+
+/// One line of a JSON lines binding file
+///
+/// Objects use the JSON-RPC shape that alloy serializes, e.g. a `log` as returned by `eth_getLogs`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BindingRecord {
+    /// Chain id, defaults to the runner's chain id
+    pub chain_id: Option<String>,
+    /// Handler type name (e.g. `ETH_LOG`), inferred from the payload when omitted
+    pub handler_type: Option<String>,
+    /// Handler ids to invoke, resolved from the processor config when omitted
+    pub handler_ids: Option<Vec<i32>>,
+    /// Block timestamp; falls back to `block.timestamp`
+    pub timestamp: Option<RecordTimestamp>,
+    pub log: Option<serde_json::Value>,
+    pub transaction: Option<serde_json::Value>,
+    pub transaction_receipt: Option<serde_json::Value>,
+    pub block: Option<serde_json::Value>,
+    pub trace: Option<serde_json::Value>,
+}
+
+impl BindingRecord {
+    /// Convert the record to a `DataBinding`, resolving handler ids against `config`
+    pub fn into_binding(
+        self,
+        default_chain_id: &str,
+        config: &ConfigureHandlersResponse,
+    ) -> Result<DataBinding> {
+        let chain_id = self
+            .chain_id
+            .clone()
+            .unwrap_or_else(|| default_chain_id.to_string());
+        let handler_type = match &self.handler_type {
+            Some(name) => HandlerType::from_str_name(&name.to_ascii_uppercase())
+                .ok_or_else(|| anyhow!("Unknown handler type '{}'", name))?,
+            None if self.log.is_some() => HandlerType::EthLog,
+            None if self.transaction.is_some() => HandlerType::EthTransaction,
+            None => return Err(anyhow!("Record has neither a log nor a transaction")),
+        };
+        let timestamp = self.block_timestamp()?;
+        let to_string = |v: &Option<serde_json::Value>| v.as_ref().map(|v| v.to_string());
+
+        let (value, handler_ids) = match handler_type {
+            HandlerType::EthLog => {
+                let log = self
+                    .log
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("ETH_LOG record requires a log"))?;
+                let handler_ids = match &self.handler_ids {
+                    Some(ids) => ids.clone(),
+                    None => {
+                        let parsed: alloy::rpc::types::Log = serde_json::from_value(log.clone())
+                            .context("Invalid log in record")?;
+                        eth_log_handler_ids(config, &parsed, &chain_id)
+                    }
+                };
+                let eth_log = data::EthLog {
+                    timestamp,
+                    raw_log: log.to_string(),
+                    raw_transaction: to_string(&self.transaction),
+                    raw_transaction_receipt: to_string(&self.transaction_receipt),
+                    raw_block: to_string(&self.block),
+                    ..Default::default()
+                };
+                (data::Value::EthLog(eth_log), handler_ids)
+            }
+            HandlerType::EthTransaction => {
+                let transaction = self
+                    .transaction
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("ETH_TRANSACTION record requires a transaction"))?;
+                let handler_ids = self.handler_ids.clone().ok_or_else(|| {
+                    anyhow!("ETH_TRANSACTION record requires explicit handler_ids")
+                })?;
+                let eth_transaction = data::EthTransaction {
+                    timestamp,
+                    raw_transaction: transaction.to_string(),
+                    raw_transaction_receipt: to_string(&self.transaction_receipt),
+                    raw_block: to_string(&self.block),
+                    raw_trace: to_string(&self.trace),
+                    ..Default::default()
+                };
+                (data::Value::EthTransaction(eth_transaction), handler_ids)
+            }
+            other => {
+                return Err(anyhow!(
+                    "{:?} bindings are only supported in the protobuf input format",
+                    other
+                ))
+            }
+        };
+
+        Ok(DataBinding {
+            data: Some(Data { value: Some(value) }),
+            handler_type: handler_type as i32,
+            handler_ids,
+            chain_id,
+        })
+    }
+
+    fn block_timestamp(&self) -> Result<Option<prost_types::Timestamp>> {
+        if let Some(ts) = &self.timestamp {
+            return ts.to_proto().map(Some);
+        }
+        // JSON-RPC blocks carry the timestamp as a hex quantity
+        let from_block = self
+            .block
+            .as_ref()
+            .and_then(|b| b.get("timestamp"))
+            .and_then(|t| t.as_str())
+            .and_then(|t| i64::from_str_radix(t.trim_start_matches("0x"), 16).ok());
+        from_block
+            .map(|secs| RecordTimestamp::Seconds(secs).to_proto())
+            .transpose()
+    }
+}
+
+/// Handler ids of all log handlers whose contract and topic filters match `log`
+fn eth_log_handler_ids(
+    config: &ConfigureHandlersResponse,
+    log: &alloy::rpc::types::Log,
+    chain_id: &str,
+) -> Vec<i32> {
+    let log_address = format!("{:?}", log.address()).to_lowercase();
+    let topics: Vec<String> = log
+        .topics()
+        .iter()
+        .map(|t| format!("{:?}", t).to_lowercase())
+        .collect();
+
+    let mut handler_ids = Vec::new();
+    for contract_config in &config.contract_configs {
+        let Some(contract) = &contract_config.contract else {
+            continue;
+        };
+        if contract.chain_id != chain_id {
+            continue;
+        }
+        let address = contract.address.to_lowercase();
+        if !(address.is_empty() || address == "*" || address == log_address) {
+            continue;
+        }
+        for log_config in &contract_config.log_configs {
+            let matched = log_config.filters.iter().any(|filter| {
+                filter.topics.iter().enumerate().all(|(idx, topic)| {
+                    topic.hashes.is_empty()
+                        || topics
+                            .get(idx)
+                            .is_some_and(|t| topic.hashes.iter().any(|h| h.to_lowercase() == *t))
+                })
+            });
+            if matched {
+                handler_ids.push(log_config.handler_id);
+            }
+        }
+    }
+    handler_ids
+}
+
+/// Read length-delimited protobuf `DataBinding`s
+pub fn read_protobuf_bindings(path: &Path) -> Result<Vec<DataBinding>> {
+    let content =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut buf = content.as_slice();
+    let mut bindings = Vec::new();
+    while !buf.is_empty() {
+        let binding = DataBinding::decode_length_delimited(&mut buf).with_context(|| {
+            format!("Invalid binding #{} in {}", bindings.len(), path.display())
+        })?;
+        bindings.push(binding);
+    }
+    Ok(bindings)
+}
+
+/// Read JSON lines [`BindingRecord`]s, skipping blank lines
+pub fn read_jsonl_records(path: &Path) -> Result<Vec<BindingRecord>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Invalid record on line {} of {}", idx + 1, path.display()))
+        })
+        .collect()
+}
+
+/// A binding that could not be processed
+#[derive(Debug, Clone, Serialize)]
+pub struct BindingFailure {
+    pub index: usize,
+    pub handler_type: String,
+    pub error: String,
+}
+
+/// A counter or gauge value emitted during the run
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricRecord {
+    pub name: String,
+    pub value: f64,
+    pub labels: BTreeMap<String, String>,
+    pub contract_name: Option<String>,
+    pub block_number: Option<u64>,
+}
+
+/// An event log emitted during the run
+#[derive(Debug, Clone, Serialize)]
+pub struct EventRecord {
+    pub name: String,
+    pub attributes: BTreeMap<String, serde_json::Value>,
+    pub contract_name: Option<String>,
+    pub block_number: Option<u64>,
+}
+
+/// Everything produced by a run, serialized as the runner's JSON output
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RunReport {
+    /// Number of bindings that were processed
+    pub bindings: usize,
+    pub failures: Vec<BindingFailure>,
+    pub counters: Vec<MetricRecord>,
+    pub gauges: Vec<MetricRecord>,
+    pub events: Vec<EventRecord>,
+    /// Entities by table, each with its `id` and fields
+    pub entities: BTreeMap<String, Vec<serde_json::Value>>,
+}
+
+impl RunReport {
+    /// Write the report as pretty printed JSON to `path`, or stdout when `None`
+    pub fn write_json(&self, path: Option<&Path>) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        match path {
+            Some(path) => std::fs::write(path, json + "\n")
+                .with_context(|| format!("Failed to write report to {}", path.display())),
+            None => {
+                println!("{}", json);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Offline processor runner backed by an in-memory entity store
+pub struct Runner {
+    /// Entities written by the processors during the run
+    pub db: Arc<MemoryDatabase>,
+    pub(crate) plugin_manager: Arc<PluginManager>,
+    config: OnceLock<ConfigureHandlersResponse>,
+    args: Option<RunnerArgs>,
+}
+
+impl Default for Runner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Runner {
+    pub fn new() -> Self {
+        Self {
+            db: Arc::new(MemoryDatabase::new()),
+            plugin_manager: Arc::new(PluginManager::default()),
+            config: OnceLock::new(),
+            args: None,
+        }
+    }
+
+    /// Use the given arguments instead of parsing the command line in [`Runner::start`]
+    pub fn with_args(mut self, args: RunnerArgs) -> Self {
+        self.args = Some(args);
+        self
+    }
+
+    /// Processor configuration; plugins are configured once, on first use
+    pub fn config(&self) -> &ConfigureHandlersResponse {
+        self.config.get_or_init(|| {
+            let mut response = ConfigureHandlersResponse {
+                contract_configs: vec![],
+                account_configs: vec![],
+            };
+            // Nothing is processed before the config, so the plugins are not locked
+            futures::executor::block_on(self.plugin_manager.configure_all_plugins(&mut response));
+            response
+        })
+    }
+
+    /// Parse command line arguments, replay the input file and write the report (blocking)
+    /// Exits the process with an error code if the run fails
+    pub fn start(self) {
+        let args = self.args.clone().unwrap_or_else(RunnerArgs::parse);
+        crate::server::Server::init_logging(args.debug);
+
+        let result = tokio::runtime::Runtime::new()
+            .map_err(anyhow::Error::from)
+            .and_then(|rt| rt.block_on(self.run(&args)));
+        match result {
+            Ok(report) if report.failures.is_empty() => {}
+            Ok(report) => {
+                error!("{} of {} bindings failed", report.failures.len(), report.bindings);
+                std::process::exit(2);
+            }
+            Err(e) => {
+                error!("Runner failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    /// Replay the input described by `args` and write the report
+    pub async fn run(&self, args: &RunnerArgs) -> Result<RunReport> {
+        let bindings = self.load_bindings(&args.input, args.format, &args.chain_id)?;
+        info!("Replaying {} bindings from {}", bindings.len(), args.input.display());
+
+        let report = self.run_bindings_inner(bindings, args.fail_fast).await?;
+        report.write_json(args.output.as_deref())?;
+        Ok(report)
+    }
+
+    /// Load bindings from a file
+    pub fn load_bindings(
+        &self,
+        path: &Path,
+        format: Option<InputFormat>,
+        default_chain_id: &str,
+    ) -> Result<Vec<DataBinding>> {
+        let format = match format {
+            Some(format) => format,
+            None => InputFormat::from_path(path)?,
+        };
+        match format {
+            InputFormat::Protobuf => read_protobuf_bindings(path),
+            InputFormat::Jsonl => read_jsonl_records(path)?
+                .into_iter()
+                .enumerate()
+                .map(|(idx, record)| {
+                    record
+                        .into_binding(default_chain_id, self.config())
+                        .with_context(|| format!("Record #{}", idx))
+                })
+                .collect(),
+        }
+    }
+
+    /// Process alloy logs with their block timestamps (unix seconds)
+    pub async fn run_logs(
+        &self,
+        logs: Vec<(alloy::rpc::types::Log, i64)>,
+        chain_id: &str,
+    ) -> Result<RunReport> {
+        let bindings = logs
+            .into_iter()
+            .map(|(log, timestamp)| {
+                let record = BindingRecord {
+                    log: Some(serde_json::to_value(&log)?),
+                    timestamp: Some(RecordTimestamp::Seconds(timestamp)),
+                    ..Default::default()
+                };
+                record.into_binding(chain_id, self.config())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.run_bindings(bindings).await
+    }
+
+    /// Process bindings in order; failures are recorded in the report and don't stop the run
+    pub async fn run_bindings(&self, bindings: Vec<DataBinding>) -> Result<RunReport> {
+        self.run_bindings_inner(bindings, false).await
+    }
+
+    async fn run_bindings_inner(
+        &self,
+        bindings: Vec<DataBinding>,
+        fail_fast: bool,
+    ) -> Result<RunReport> {
+        // Make sure handlers are registered before the first binding
+        self.config();
+
+        let mut collected = TestResult::new();
+        let mut report = RunReport::default();
+        for (index, binding) in bindings.iter().enumerate() {
+            report.bindings += 1;
+            if let Err(e) = self.process_binding(index, binding, &mut collected).await {
+                let handler_type = HandlerType::try_from(binding.handler_type)
+                    .map(|t| t.as_str_name().to_string())
+                    .unwrap_or_else(|_| binding.handler_type.to_string());
+                warn!("Binding #{} ({}) failed: {:#}", index, handler_type, e);
+                if fail_fast {
+                    return Err(e.context(format!("Binding #{} failed", index)));
+                }
+                report.failures.push(BindingFailure {
+                    index,
+                    handler_type,
+                    error: format!("{:#}", e),
+                });
+            }
+        }
+
+        report.counters = collected
+            .counters
+            .iter()
+            .map(|c| metric_record(&c.name, c.value, &c.labels, &c.metadata))
+            .collect();
+        report.gauges = collected
+            .gauges
+            .iter()
+            .map(|g| metric_record(&g.name, g.value, &g.labels, &g.metadata))
+            .collect();
+        report.events = collected
+            .events
+            .iter()
+            .map(|e| EventRecord {
+                name: e.name.clone(),
+                attributes: e
+                    .attributes
+                    .iter()
+                    .map(|(k, v)| (k.clone(), attribute_to_json(v)))
+                    .collect(),
+                contract_name: e.metadata.contract_name.clone(),
+                block_number: e.metadata.block_number,
+            })
+            .collect();
+        report.entities = self.dump_entities().await;
+        Ok(report)
+    }
+
+    async fn process_binding(
+        &self,
+        index: usize,
+        binding: &DataBinding,
+        collected: &mut TestResult,
+    ) -> Result<()> {
+        let (tx, mut rx) = mpsc::channel(1024);
+        let backend = Arc::new(Backend::memory(self.db.clone()));
+        let runtime_context = RuntimeContext::new_with_empty_metadata(tx, index as i32, backend);
+
+        // Drain the channel while processing so large handlers can't fill it up
+        let process = self.plugin_manager.process(binding, runtime_context);
+        tokio::pin!(process);
+        let result = loop {
+            tokio::select! {
+                result = &mut process => break result,
+                Some(msg) = rx.recv() => {
+                    if let Ok(response) = msg {
+                        collected.collect_response(response);
+                    }
+                }
+            }
+        };
+        while let Ok(msg) = rx.try_recv() {
+            if let Ok(response) = msg {
+                collected.collect_response(response);
+            }
+        }
+
+        let result = result?;
+        if result.states.as_ref().is_some_and(|states| states.config_updated) {
+            // Register processors bound by the handlers so later bindings can reach them
+            self.plugin_manager
+                .configure_all_plugins(&mut ConfigureHandlersResponse::default())
+                .await;
+        }
+        for ts in result.timeseries_result {
+            collected.collect_timeseries_result(ts);
+        }
+        Ok(())
+    }
+
+    async fn dump_entities(&self) -> BTreeMap<String, Vec<serde_json::Value>> {
+        let mut entities = BTreeMap::new();
+        for table in self.db.get_table_names().await {
+            let mut rows = self.db.list_table_entities(&table).await;
+            rows.sort_by(|a, b| a.entity.cmp(&b.entity));
+            let rows = rows
+                .into_iter()
+                .map(|entity| {
+                    let mut object = serde_json::Map::new();
+                    object.insert("id".to_string(), entity.entity.into());
+                    if let Some(serde_json::Value::Object(fields)) =
+                        entity.data.as_ref().map(rich_struct_to_json)
+                    {
+                        object.extend(fields);
+                    }
+                    serde_json::Value::Object(object)
+                })
+                .collect();
+            entities.insert(table, rows);
+        }
+        entities
+    }
+}
+
+impl crate::BindableServer for Runner {
+    fn register_processor<T, P>(&self, processor: T)
+    where
+        T: crate::core::BaseProcessor + 'static,
+        P: crate::core::plugin::PluginRegister<T>
+            + crate::core::plugin::FullPlugin
+            + Default
+            + 'static,
+    {
+        self.plugin_manager
+            .with_plugin_mut::<P, _, _>(|plugin| {
+                plugin.register_processor(processor);
+            })
+            .expect("Failed to register processor");
+    }
+}
+
+fn metric_record(
+    name: &str,
+    value: f64,
+    labels: &std::collections::HashMap<String, String>,
+    metadata: &TestMetadata,
+) -> MetricRecord {
+    MetricRecord {
+        name: name.to_string(),
+        value,
+        labels: labels.clone().into_iter().collect(),
+        contract_name: metadata.contract_name.clone(),
+        block_number: metadata.block_number,
+    }
+}
+
+fn attribute_to_json(value: &AttributeValue) -> serde_json::Value {
+    match RichValue::try_from(value) {
+        Ok(rich) => rich_value_to_json(&rich),
+        Err(_) => serde_json::Value::Null,
+    }
+}
+
+fn rich_struct_to_json(value: &RichStruct) -> serde_json::Value {
+    let fields: BTreeMap<&String, serde_json::Value> = value
+        .fields
+        .iter()
+        .map(|(k, v)| (k, rich_value_to_json(v)))
+        .collect();
+    serde_json::to_value(fields).unwrap_or_default()
+}
+
+/// JSON view of a `RichValue`; big numbers are rendered as decimal strings to stay exact
+fn rich_value_to_json(value: &RichValue) -> serde_json::Value {
+    use serde_json::Value as Json;
+
+    let Some(value) = &value.value else {
+        return Json::Null;
+    };
+    match value {
+        rich_value::Value::NullValue(_) => Json::Null,
+        rich_value::Value::IntValue(v) => (*v).into(),
+        rich_value::Value::Int64Value(v) => (*v).into(),
+        rich_value::Value::FloatValue(v) => (*v).into(),
+        rich_value::Value::BytesValue(v) => format!("0x{}", hex::encode(v)).into(),
+        rich_value::Value::BoolValue(v) => (*v).into(),
+        rich_value::Value::StringValue(v) => v.clone().into(),
+        rich_value::Value::TimestampValue(ts) => {
+            chrono::DateTime::from_timestamp(ts.seconds, ts.nanos.max(0) as u32)
+                .map(|t| Json::String(t.to_rfc3339()))
+                .unwrap_or(Json::Null)
+        }
+        rich_value::Value::BigintValue(v) => proto_to_bigint(v).to_string().into(),
+        rich_value::Value::BigdecimalValue(v) => proto_to_bigdecimal(v)
+            .map(|d| Json::String(d.to_string()))
+            .unwrap_or(Json::Null),
+        rich_value::Value::ListValue(list) => {
+            Json::Array(list.values.iter().map(rich_value_to_json).collect())
+        }
+        rich_value::Value::StructValue(s) => rich_struct_to_json(s),
+        rich_value::Value::TokenValue(token) => serde_json::json!({
+            "token": crate::common::CoinId::try_from(token.token)
+                .map(|c| c.as_str_name())
+                .unwrap_or("UNKNOWN"),
+            "amount": token
+                .amount
+                .as_ref()
+                .and_then(|a| proto_to_bigdecimal(a).ok())
+                .map(|d| d.to_string()),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::eth_processor::EthProcessor;
+    use crate::eth::tests::test_processor::{TestErc20Processor, TransferEvent};
+    use crate::testing::{addresses, mock_transfer_log};
+    use std::io::Write;
+
+    fn runner_with_erc20() -> Runner {
+        let runner = Runner::new();
+        TestErc20Processor::new(addresses::TEST_CONTRACT, "TestToken")
+            .configure_event::<TransferEvent>(None)
+            .bind(&runner);
+        runner
+    }
+
+    fn transfer_log() -> alloy::rpc::types::Log {
+        mock_transfer_log(
+            addresses::TEST_CONTRACT,
+            addresses::ZERO,
+            addresses::TEST_ADDRESS_1,
+            "1000000000000000000",
+        )
+    }
+
+    #[tokio::test]
+    async fn replays_jsonl_logs() {
+        let runner = runner_with_erc20();
+
+        let mut file = tempfile::Builder::new().suffix(".jsonl").tempfile().unwrap();
+        let log = serde_json::to_string(&transfer_log()).unwrap();
+        writeln!(file, r#"{{"log": {}, "timestamp": 1700000000}}"#, log).unwrap();
+        writeln!(file).unwrap();
+        writeln!(file, r#"{{"log": {}, "timestamp": "2023-11-14T22:13:20Z"}}"#, log).unwrap();
+
+        let bindings = runner.load_bindings(file.path(), None, "1").unwrap();
+        assert_eq!(bindings.len(), 2);
+        assert_eq!(bindings[0].handler_ids, vec![0]);
+
+        let report = runner.run_bindings(bindings).await.unwrap();
+        assert_eq!(report.bindings, 2);
+        assert!(report.failures.is_empty(), "{:?}", report.failures);
+        assert_eq!(report.counters.len(), 2);
+        assert_eq!(report.counters[0].name, "transfers");
+        assert_eq!(report.events.len(), 2);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["events"][0]["name"], "transfer");
+    }
+
+    #[tokio::test]
+    async fn replays_protobuf_bindings() {
+        let runner = runner_with_erc20();
+        let logs = vec![(transfer_log(), 1_700_000_000)];
+        let expected = runner.run_logs(logs.clone(), "1").await.unwrap();
+
+        // Round trip the same binding through the length-delimited protobuf format
+        let record = BindingRecord {
+            log: Some(serde_json::to_value(&logs[0].0).unwrap()),
+            timestamp: Some(RecordTimestamp::Seconds(logs[0].1)),
+            ..Default::default()
+        };
+        let binding = record.into_binding("1", runner.config()).unwrap();
+        let mut file = tempfile::Builder::new().suffix(".pb").tempfile().unwrap();
+        file.write_all(&binding.encode_length_delimited_to_vec()).unwrap();
+
+        let bindings = runner.load_bindings(file.path(), None, "1").unwrap();
+        assert_eq!(bindings, vec![binding]);
+        let report = runner.run_bindings(bindings).await.unwrap();
+        assert_eq!(report.counters.len(), expected.counters.len());
+        assert_eq!(report.gauges.len(), expected.gauges.len());
+    }
+
+    #[tokio::test]
+    async fn failures_are_reported_per_binding() {
+        let runner = runner_with_erc20();
+        let bad = DataBinding {
+            data: None,
+            handler_type: HandlerType::SuiEvent as i32,
+            handler_ids: vec![0],
+            chain_id: "1".to_string(),
+        };
+        let report = runner.run_bindings(vec![bad]).await.unwrap();
+        assert_eq!(report.bindings, 1);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].handler_type, "SUI_EVENT");
+    }
+
+    #[test]
+    fn unmatched_logs_resolve_to_no_handlers() {
+        let runner = runner_with_erc20();
+        let mut log = transfer_log();
+        log.inner.address = addresses::TEST_ADDRESS_1.parse().unwrap();
+        assert!(eth_log_handler_ids(runner.config(), &log, "1").is_empty());
+        assert_eq!(eth_log_handler_ids(runner.config(), &transfer_log(), "1"), vec![0]);
+        assert!(eth_log_handler_ids(runner.config(), &transfer_log(), "10").is_empty());
+    }
+}
@@ -1,31 +1,127 @@
 use crate::processor::processor_v3_server::ProcessorV3Server as TonicProcessorV3Server;
+use crate::config::{HandlerOrder, ServerConfig};
 use crate::service::ProcessorService;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::Parser;
+use std::future::Future;
 use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tonic::transport::Server as TonicServer;
+use tonic_health::ServingStatus;
 use tracing::{debug, error, info};
 
 /// Command line arguments for the Sentio server
+///
+/// Every setting flag is optional; flags given here override the config file and environment
+/// variables (see [`ServerConfig`]).
 #[derive(Parser, Debug, Clone)]
 #[command(name = "sentio-processor")]
 #[command(about = "Sentio Processor gRPC Server")]
 pub struct ServerArgs {
-    /// Port to listen on
-    #[arg(short, long, default_value = "4000")]
-    pub port: u16,
+    /// Path to a TOML or YAML config file. Also via env SENTIO_PROCESSOR_CONFIG
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
+    /// Print the effective configuration as TOML and exit
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub print_config: bool,
+
+    /// Port to listen on (default 4000)
+    #[arg(short, long)]
+    pub port: Option<u16>,
 
     /// Enable debug/verbose logging
     #[arg(short, long, action = clap::ArgAction::SetTrue)]
     pub debug: bool,
 
-    /// Host address to bind to
-    #[arg(long, default_value = "0.0.0.0")]
-    pub host: String,
+    /// Host address to bind to (default 0.0.0.0)
+    #[arg(long)]
+    pub host: Option<String>,
+
+    /// Listen on a Unix domain socket at this path instead of host:port
+    #[arg(long)]
+    pub unix_socket: Option<PathBuf>,
+
+    /// Record every bindings stream to a file in this directory, for later replay
+    #[arg(long)]
+    pub record_dir: Option<PathBuf>,
+
+    /// PEM certificate chain; enables TLS together with --tls-key
+    #[arg(long)]
+    pub tls_cert: Option<PathBuf>,
+
+    /// PEM private key for --tls-cert
+    #[arg(long)]
+    pub tls_key: Option<PathBuf>,
+
+    /// PEM CA bundle used to verify client certificates (enables mTLS)
+    #[arg(long)]
+    pub tls_client_ca: Option<PathBuf>,
 
     /// Process binding timeout in seconds (default 600). Also via env PROCESS_BINDING_TIMEOUT or legacy PROCESS_TIMEOUT_SECS
-    #[arg(long, default_value = "600")]
-    pub process_binding_timeout: u64,
+    #[arg(long)]
+    pub process_binding_timeout: Option<u64>,
+
+    /// Seconds to wait for in-flight bindings to finish after SIGTERM/SIGINT before closing streams (default 30)
+    #[arg(long)]
+    pub shutdown_timeout: Option<u64>,
+
+    /// Process bindings one at a time
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    pub sequential: Option<bool>,
+
+    /// Use the exact block time instead of an estimate
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    pub force_exact_block_time: Option<bool>,
+
+    /// Handler order inside a transaction: by_log_index or by_processor_and_log_index
+    #[arg(long)]
+    pub handler_order: Option<HandlerOrder>,
+
+    /// Skip validating processor start blocks
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    pub skip_start_block_validation: Option<bool>,
+
+    /// Number of RPC retries performed by the driver (default 3)
+    #[arg(long)]
+    pub rpc_retry_times: Option<i32>,
+
+    /// Let the driver decode eth logs with ABI decoder workers
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    pub eth_abi_decoder: Option<bool>,
+
+    /// Number of ABI decoder workers
+    #[arg(long)]
+    pub eth_abi_decoder_workers: Option<i32>,
+
+    /// Accept gzip compressed requests (default true)
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    pub accept_gzip: Option<bool>,
+
+    /// Gzip compress responses. Also via env GRPC_ENABLE_COMPRESS
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    pub send_gzip: Option<bool>,
+
+    /// TCP keepalive in seconds, 0 disables it (default 10)
+    #[arg(long)]
+    pub tcp_keepalive: Option<u64>,
+
+    /// HTTP/2 keepalive ping interval in seconds, 0 disables pings
+    #[arg(long)]
+    pub http2_keepalive_interval: Option<u64>,
+
+    /// HTTP/2 keepalive ping timeout in seconds (default 10)
+    #[arg(long)]
+    pub http2_keepalive_timeout: Option<u64>,
+
+    /// Maximum decoded request message size in bytes
+    #[arg(long)]
+    pub max_decoding_message_size: Option<usize>,
+
+    /// Maximum encoded response message size in bytes
+    #[arg(long)]
+    pub max_encoding_message_size: Option<usize>,
 
     /// Port for profiling HTTP server
     #[cfg(feature = "profiling")]
@@ -72,14 +168,49 @@ impl Server {
         self.service.set_gql_schema(schema);
     }
 
-    /// Configure execution settings. If `process_binding_timeout` is 0, the value from CLI/env/default is used.
+    /// Declare a counter, reported to the driver with its unit, description and aggregation
+    pub fn declare_counter(
+        &self,
+        name: &str,
+        options: crate::core::MetricOptions,
+    ) -> Result<(), crate::core::MetricError> {
+        self.service.declare_counter(name, options)
+    }
+
+    /// Declare a gauge, reported to the driver with its unit, description and aggregation
+    pub fn declare_gauge(
+        &self,
+        name: &str,
+        options: crate::core::MetricOptions,
+    ) -> Result<(), crate::core::MetricError> {
+        self.service.declare_gauge(name, options)
+    }
+
+    /// Declare the schema of an event derived with `#[derive(SentioEvent)]`, so the driver
+    /// knows its field types before any event is emitted
+    pub fn declare_event<T: crate::core::SentioEvent>(&self) {
+        self.service.declare_event::<T>();
+    }
+
+    /// Declare an export delivered to `channel`; emitting to an undeclared export fails
+    pub fn declare_export(&self, name: &str, channel: &str) {
+        self.service.declare_export(name, channel);
+    }
+
+    /// Declare an event tracker with the unique, total and retention aggregations to compute
+    pub fn declare_event_tracker(&self, name: &str, options: crate::core::EventTrackerOptions) {
+        self.service.declare_event_tracker(name, options);
+    }
+
+    /// Configure execution settings. These act as the base layer of the configuration: the config
+    /// file, environment and CLI override them. If `process_binding_timeout` is 0, the default is used.
     pub fn set_execution_config(&mut self, config: crate::processor::ExecutionConfig) {
         self.execution_config = Some(config);
     }
 
     /// Initialize logging based on debug flag
     /// Gracefully handles cases where a global subscriber is already initialized
-    fn init_logging(debug: bool) {
+    pub(crate) fn init_logging(debug: bool) {
         let level = if debug { "debug" } else { "info" };
 
         let result = tracing_subscriber::fmt()
@@ -139,15 +270,20 @@ impl Server {
         // Initialize logging
         Self::init_logging(args.debug);
 
-        // Initialize benchmark reporter (requires Tokio runtime; safe here)
-        crate::core::benchmark::init_if_enabled();
-
-        // execution_config will be constructed below before serving
+        // Resolve the effective configuration (base < file < env < cli)
+        let mut base = ServerConfig::default();
+        if let Some(cfg) = &self.execution_config {
+            base.apply_execution_config(cfg);
+        }
+        let config = ServerConfig::load(base, &args)?;
 
-        let addr: SocketAddr = format!("{}:{}", args.host, args.port).parse()?;
+        if args.print_config {
+            print!("{}", config.to_toml()?);
+            return Ok(());
+        }
 
-        info!("🚀 Starting Sentio Processor server on {}", addr);
-        debug!("Server configuration: {:?}", args);
+        // Initialize benchmark reporter (requires Tokio runtime; safe here)
+        crate::core::benchmark::init_if_enabled();
 
         #[cfg(feature = "profiling")]
         {
@@ -167,64 +303,156 @@ impl Server {
             });
         }
 
-        // Construct execution config once (override > env > cli > default)
-        let default_timeout = 600i32;
-        let env_timeout = std::env::var("PROCESS_BINDING_TIMEOUT")
-            .ok()
-            .and_then(|s| s.parse::<i32>().ok())
-            .or_else(|| {
-                std::env::var("PROCESS_TIMEOUT_SECS")
-                    .ok()
-                    .and_then(|s| s.parse::<i32>().ok())
-            });
-        let cli_timeout = args.process_binding_timeout as i32;
-        let selected_timeout = env_timeout.unwrap_or(cli_timeout);
-        let selected_timeout = if selected_timeout > 0 {
-            selected_timeout
-        } else {
-            default_timeout
-        };
-        let exec_cfg = if let Some(mut cfg) = self.execution_config.clone() {
-            if cfg.process_binding_timeout <= 0 {
-                cfg.process_binding_timeout = selected_timeout;
-            }
-            cfg
-        } else {
-            crate::processor::ExecutionConfig {
-                sequential: false,
-                force_exact_block_time: false,
-                handler_order_inside_transaction: 0,
-                process_binding_timeout: selected_timeout,
-                skip_start_block_validation: false,
-                rpc_retry_times: 3,
-                eth_abi_decoder_config: None,
-            }
-        };
+        self.serve_with_shutdown(config, shutdown_signal()).await
+    }
 
-        let service = ProcessorService::new_with_plugin_and_config(
+    /// Serve with an already resolved configuration until `signal` resolves.
+    /// In-flight bindings are then drained for up to `config.shutdown_timeout` seconds.
+    pub async fn serve_with_shutdown<F>(self, config: ServerConfig, signal: F) -> Result<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        debug!("Server configuration: {:?}", config);
+
+        // Health service reports NOT_SERVING until the driver has fetched the config
+        let (health_reporter, health_service) = tonic_health::server::health_reporter();
+        health_reporter
+            .set_service_status("", ServingStatus::NotServing)
+            .await;
+        health_reporter
+            .set_not_serving::<TonicProcessorV3Server<ProcessorService>>()
+            .await;
+
+        let mut service = ProcessorService::new_with_plugin_and_config(
             self.service.plugin_manager.clone(),
-            exec_cfg,
-        );
+            config.execution_config(),
+        )
+        .with_health_reporter(health_reporter);
+        if let Some(dir) = &config.record_dir {
+            info!("Recording bindings streams to {}", dir.display());
+            service = service.with_recording(dir.clone());
+        }
 
-        let mut server = TonicProcessorV3Server::new(service)
-            .accept_compressed(tonic::codec::CompressionEncoding::Gzip);
-        if std::env::var("GRPC_ENABLE_COMPRESS").is_ok()
-            && std::env::var("GRPC_ENABLE_COMPRESS")? == "true"
-        {
+        let drain_timeout = Duration::from_secs(config.shutdown_timeout);
+        let shutdown_service = service.clone();
+        let shutdown = async move {
+            signal.await;
+            info!("Shutdown signal received, draining in-flight bindings");
+            shutdown_service.shutdown(drain_timeout).await;
+        };
+
+        let grpc = &config.grpc;
+        let mut server = TonicProcessorV3Server::new(service);
+        if grpc.accept_gzip {
+            server = server.accept_compressed(tonic::codec::CompressionEncoding::Gzip);
+        }
+        if grpc.send_gzip {
             server = server.send_compressed(tonic::codec::CompressionEncoding::Gzip);
         }
+        if let Some(limit) = grpc.max_decoding_message_size {
+            server = server.max_decoding_message_size(limit);
+        }
+        if let Some(limit) = grpc.max_encoding_message_size {
+            server = server.max_encoding_message_size(limit);
+        }
 
-        TonicServer::builder()
-            .tcp_keepalive(Some(std::time::Duration::from_secs(10)))
-            .http2_keepalive_timeout(Some(std::time::Duration::from_secs(10)))
-            .add_service(server)
-            .serve(addr)
-            .await?;
+        let mut builder = TonicServer::builder()
+            .tcp_keepalive(non_zero_secs(grpc.tcp_keepalive_secs))
+            .http2_keepalive_interval(non_zero_secs(grpc.http2_keepalive_interval_secs))
+            .http2_keepalive_timeout(non_zero_secs(grpc.http2_keepalive_timeout_secs));
+        let tls = config.tls.server_tls_config()?;
+        let scheme = if tls.is_some() { "https" } else { "http" };
+        if let Some(tls) = tls {
+            builder = builder.tls_config(tls)?;
+        }
+        let router = builder.add_service(health_service).add_service(server);
+
+        match &config.unix_socket {
+            Some(path) => {
+                let incoming = bind_unix_socket(path)?;
+                info!(
+                    "🚀 Starting Sentio Processor server on unix://{} ({})",
+                    path.display(),
+                    scheme
+                );
+                let result = router.serve_with_incoming_shutdown(incoming, shutdown).await;
+                if let Err(e) = std::fs::remove_file(path) {
+                    debug!("Failed to remove socket {}: {}", path.display(), e);
+                }
+                result?;
+            }
+            None => {
+                let addr: SocketAddr = format!("{}:{}", config.host, config.port).parse()?;
+                info!("🚀 Starting Sentio Processor server on {}://{}", scheme, addr);
+                router.serve_with_shutdown(addr, shutdown).await?;
+            }
+        }
 
+        info!("Sentio Processor server stopped");
         Ok(())
     }
 }
 
+/// Bind a Unix domain socket, replacing a stale socket file left by a previous run
+#[cfg(unix)]
+fn bind_unix_socket(path: &Path) -> Result<tokio_stream::wrappers::UnixListenerStream> {
+    use std::os::unix::fs::FileTypeExt;
+
+    if let Ok(meta) = std::fs::symlink_metadata(path) {
+        if !meta.file_type().is_socket() {
+            return Err(anyhow!("Refusing to replace {}: not a socket", path.display()));
+        }
+        std::fs::remove_file(path)?;
+    }
+    let listener = tokio::net::UnixListener::bind(path)?;
+    Ok(tokio_stream::wrappers::UnixListenerStream::new(listener))
+}
+
+#[cfg(not(unix))]
+fn bind_unix_socket(
+    path: &Path,
+) -> Result<futures::stream::Empty<std::io::Result<tokio::net::TcpStream>>> {
+    Err(anyhow!(
+        "Unix domain sockets are not supported on this platform: {}",
+        path.display()
+    ))
+}
+
+fn non_zero_secs(secs: u64) -> Option<Duration> {
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+/// Resolves when the process receives SIGINT (Ctrl+C) or, on Unix, SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
 impl Default for Server {
     fn default() -> Self {
         Self::new()
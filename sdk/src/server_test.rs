@@ -13,16 +13,16 @@ mod tests {
         
         let handle1 = tokio::spawn(async move {
             // Test concurrent read access - should work without blocking
-            let _chain_ids = pm1.get_all_chain_ids();
-            let _count = pm1.total_processor_count();
+            let _chain_ids = pm1.get_all_chain_ids().await;
+            let _count = pm1.total_processor_count().await;
             sleep(Duration::from_millis(10)).await;
             "task1_done"
         });
         
         let handle2 = tokio::spawn(async move {
             // Test concurrent read access - should work without blocking
-            let _chain_ids = pm2.get_all_chain_ids();
-            let _count = pm2.total_processor_count();
+            let _chain_ids = pm2.get_all_chain_ids().await;
+            let _count = pm2.total_processor_count().await;
             sleep(Duration::from_millis(10)).await;
             "task2_done"
         });
@@ -44,9 +44,9 @@ mod tests {
             let pm = pm.clone();
             async move {
                 // Simulate concurrent plugin manager operations
-                let _chain_ids = pm.get_all_chain_ids();
+                let _chain_ids = pm.get_all_chain_ids().await;
                 sleep(Duration::from_millis(20)).await;
-                let _count = pm.total_processor_count();
+                let _count = pm.total_processor_count().await;
                 std::time::Instant::now()
             }
         });
@@ -56,8 +56,8 @@ mod tests {
             async move {
                 sleep(Duration::from_millis(10)).await;
                 // These operations should not block each other with DashMap
-                let _chain_ids = pm.get_all_chain_ids();
-                let _count = pm.total_processor_count();
+                let _chain_ids = pm.get_all_chain_ids().await;
+                let _count = pm.total_processor_count().await;
                 std::time::Instant::now()
             }
         });
@@ -107,4 +107,147 @@ mod tests {
         // assert!(result.is_err());
         // assert!(result.unwrap_err().to_string().contains("No plugin registered"));*/
     }
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/tls")
+            .join(name)
+    }
+
+    async fn check_health(
+        channel: tonic::transport::Channel,
+    ) -> Result<tonic_health::pb::health_check_response::ServingStatus, tonic::Status> {
+        let mut client = tonic_health::pb::health_client::HealthClient::new(channel);
+        let response = client
+            .check(tonic_health::pb::HealthCheckRequest {
+                service: String::new(),
+            })
+            .await?;
+        Ok(response.into_inner().status())
+    }
+
+    fn spawn_server(
+        config: crate::ServerConfig,
+    ) -> (
+        tokio::sync::oneshot::Sender<()>,
+        tokio::task::JoinHandle<anyhow::Result<()>>,
+    ) {
+        let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+        let handle = tokio::spawn(Server::new().serve_with_shutdown(config, async move {
+            let _ = stop_rx.await;
+        }));
+        (stop_tx, handle)
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix_socket_listener() {
+        use tonic::transport::{Endpoint, Uri};
+
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("processor.sock");
+        let config = crate::ServerConfig {
+            unix_socket: Some(socket.clone()),
+            shutdown_timeout: 1,
+            ..Default::default()
+        };
+        let (stop, handle) = spawn_server(config);
+
+        let mut channel = None;
+        for _ in 0..50 {
+            let path = socket.clone();
+            let connected = Endpoint::try_from("http://[::]:50051")
+                .unwrap()
+                .connect_with_connector(tower::service_fn(move |_: Uri| {
+                    let path = path.clone();
+                    async move {
+                        let stream = tokio::net::UnixStream::connect(path).await?;
+                        Ok::<_, std::io::Error>(hyper_util::rt::TokioIo::new(stream))
+                    }
+                }))
+                .await;
+            if let Ok(c) = connected {
+                channel = Some(c);
+                break;
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+
+        let status = check_health(channel.expect("server did not bind the socket"))
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            tonic_health::pb::health_check_response::ServingStatus::NotServing
+        );
+
+        stop.send(()).unwrap();
+        handle.await.unwrap().unwrap();
+        assert!(!socket.exists(), "socket file is removed on shutdown");
+    }
+
+    #[tokio::test]
+    async fn test_mtls_listener() {
+        use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
+
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let config = crate::ServerConfig {
+            host: "127.0.0.1".to_string(),
+            port,
+            shutdown_timeout: 1,
+            tls: crate::config::TlsSettings {
+                cert: Some(fixture("server.pem")),
+                key: Some(fixture("server.key")),
+                client_ca: Some(fixture("ca.pem")),
+            },
+            ..Default::default()
+        };
+        let (stop, handle) = spawn_server(config);
+
+        let ca = Certificate::from_pem(std::fs::read(fixture("ca.pem")).unwrap());
+        let identity = Identity::from_pem(
+            std::fs::read(fixture("client.pem")).unwrap(),
+            std::fs::read(fixture("client.key")).unwrap(),
+        );
+        let endpoint = |tls: ClientTlsConfig| {
+            Endpoint::from_shared(format!("https://127.0.0.1:{}", port))
+                .unwrap()
+                .tls_config(tls.domain_name("localhost"))
+                .unwrap()
+        };
+
+        let mut channel = None;
+        for _ in 0..50 {
+            let tls = ClientTlsConfig::new()
+                .ca_certificate(ca.clone())
+                .identity(identity.clone());
+            if let Ok(c) = endpoint(tls).connect().await {
+                channel = Some(c);
+                break;
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+        let status = check_health(channel.expect("server did not accept TLS connection"))
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            tonic_health::pb::health_check_response::ServingStatus::NotServing
+        );
+
+        // Without a client certificate the handshake or the first request is rejected
+        let anonymous = ClientTlsConfig::new().ca_certificate(ca.clone());
+        let rejected = match endpoint(anonymous).connect().await {
+            Ok(channel) => check_health(channel).await.is_err(),
+            Err(_) => true,
+        };
+        assert!(rejected, "client without certificate must be rejected");
+
+        stop.send(()).unwrap();
+        handle.await.unwrap().unwrap();
+    }
 }
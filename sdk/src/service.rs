@@ -1,21 +1,111 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use anyhow::Result;
+use tokio::sync::{watch, Notify};
 use tonic::{Request, Response, Status};
-use tracing::{debug, error, info};
+use tonic_health::server::HealthReporter;
+use tonic_health::ServingStatus;
+use tracing::{debug, error, info, warn};
 
+use crate::core::{EventTrackerOptions, MetricError, MetricOptions, SentioEvent};
 use crate::core::plugin_manager::PluginManager;
+use crate::recording::StreamRecorder;
 use crate::processor::{
-    processor_v3_server::ProcessorV3,
+    processor_v3_server::{ProcessorV3, ProcessorV3Server},
     ConfigureHandlersResponse,
     ProcessConfigRequest, ProcessConfigResponse,
     ProcessStreamRequest, ProcessStreamResponseV3,
     StartRequest, UpdateTemplatesRequest,
 };
 
+/// Stream of responses sent back to the driver on a bindings stream
+pub type ResponseStream = std::pin::Pin<
+    Box<dyn tokio_stream::Stream<Item = Result<ProcessStreamResponseV3, Status>> + Send>,
+>;
+
+/// Shutdown phases observed by running binding streams
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownPhase {
+    /// Accepting and processing bindings
+    Running,
+    /// New bindings are rejected, in-flight bindings are allowed to finish
+    Draining,
+    /// Streams are closed and pending DB requests are failed
+    Closed,
+}
+
+/// Tracks in-flight bindings and the shutdown phase shared by all streams of a service
+pub(crate) struct Lifecycle {
+    phase: watch::Sender<ShutdownPhase>,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+impl Lifecycle {
+    fn new() -> Self {
+        let (phase, _) = watch::channel(ShutdownPhase::Running);
+        Self {
+            phase,
+            in_flight: AtomicUsize::new(0),
+            idle: Notify::new(),
+        }
+    }
+
+    fn phase(&self) -> ShutdownPhase {
+        *self.phase.borrow()
+    }
+
+    fn subscribe(&self) -> watch::Receiver<ShutdownPhase> {
+        self.phase.subscribe()
+    }
+
+    fn set_phase(&self, phase: ShutdownPhase) {
+        self.phase.send_replace(phase);
+    }
+
+    fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Mark a binding as started; the returned guard marks it done when dropped
+    fn binding_started(self: &Arc<Self>) -> InFlightGuard {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard { lifecycle: self.clone() }
+    }
+
+    /// Wait until no binding is in flight
+    async fn wait_idle(&self) {
+        loop {
+            let notified = self.idle.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.in_flight() == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+struct InFlightGuard {
+    lifecycle: Arc<Lifecycle>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.lifecycle.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.lifecycle.idle.notify_waiters();
+        }
+    }
+}
+
 pub struct ProcessorService {
     pub plugin_manager: Arc<PluginManager>,
     execution_config: crate::processor::ExecutionConfig,
+    health_reporter: Option<HealthReporter>,
+    lifecycle: Arc<Lifecycle>,
+    recording_dir: Option<std::path::PathBuf>,
 }
 
 impl Default for ProcessorService {
@@ -29,6 +119,9 @@ impl Clone for ProcessorService {
         Self {
             plugin_manager: Arc::clone(&self.plugin_manager),
             execution_config: self.execution_config.clone(),
+            health_reporter: self.health_reporter.clone(),
+            lifecycle: Arc::clone(&self.lifecycle),
+            recording_dir: self.recording_dir.clone(),
         }
     }
 }
@@ -44,14 +137,33 @@ impl ProcessorService {
             rpc_retry_times: 3,
             eth_abi_decoder_config: None,
         };
-        Self { plugin_manager: Arc::new(PluginManager::default()), execution_config }
+        Self::new_with_plugin_and_config(Arc::new(PluginManager::default()), execution_config)
     }
 
     pub fn new_with_plugin_and_config(
         plugin_manager: Arc<PluginManager>,
         execution_config: crate::processor::ExecutionConfig,
     ) -> Self {
-        Self { plugin_manager, execution_config }
+        Self {
+            plugin_manager,
+            execution_config,
+            health_reporter: None,
+            lifecycle: Arc::new(Lifecycle::new()),
+            recording_dir: None,
+        }
+    }
+
+    /// Report serving status through the given gRPC health reporter.
+    /// The service is marked SERVING once `get_config` has been answered.
+    pub fn with_health_reporter(mut self, reporter: HealthReporter) -> Self {
+        self.health_reporter = Some(reporter);
+        self
+    }
+
+    /// Record every bindings stream to a new file in `dir` (see [`crate::recording`])
+    pub fn with_recording(mut self, dir: impl Into<std::path::PathBuf>) -> Self {
+        self.recording_dir = Some(dir.into());
+        self
     }
 
     pub fn register_processor<T, P>(&self, processor: T)
@@ -59,10 +171,12 @@ impl ProcessorService {
         T: crate::core::BaseProcessor + 'static,
         P: crate::core::plugin::PluginRegister<T> + crate::core::plugin::FullPlugin + Default + 'static,
     {
+        // A processor that cannot be registered would silently never run, so fail at bind time
         self.plugin_manager
             .with_plugin_mut::<P, _, _>(|plugin| {
-                let _ = plugin.register_processor(processor);
-            });
+                plugin.register_processor(processor);
+            })
+            .expect("Failed to register processor");
     }
 
     /// Set the global GraphQL schema that should be returned in get_config
@@ -70,9 +184,95 @@ impl ProcessorService {
         self.plugin_manager.set_gql_schema(schema);
     }
 
+    /// Declare a counter to be reported in get_config
+    pub fn declare_counter(&self, name: &str, options: MetricOptions) -> Result<(), MetricError> {
+        self.plugin_manager.metric_registry().declare_counter(name, options)
+    }
+
+    /// Declare a gauge to be reported in get_config
+    pub fn declare_gauge(&self, name: &str, options: MetricOptions) -> Result<(), MetricError> {
+        self.plugin_manager.metric_registry().declare_gauge(name, options)
+    }
+
+    /// Declare the schema of a typed event to be reported in get_config
+    pub fn declare_event<T: SentioEvent>(&self) {
+        self.plugin_manager.event_log_registry().declare::<T>();
+    }
+
+    /// Declare an export delivered to `channel`, reported in get_config
+    pub fn declare_export(&self, name: &str, channel: &str) {
+        self.plugin_manager.export_registry().declare(name, channel);
+    }
+
+    /// Declare an event tracker to be reported in get_config
+    pub fn declare_event_tracker(&self, name: &str, options: EventTrackerOptions) {
+        self.plugin_manager.event_tracker_registry().declare(name, options);
+    }
+
+    /// Current shutdown phase of this service
+    pub fn shutdown_phase(&self) -> ShutdownPhase {
+        self.lifecycle.phase()
+    }
+
+    /// Number of bindings currently being processed across all streams
+    pub fn in_flight_bindings(&self) -> usize {
+        self.lifecycle.in_flight()
+    }
+
+    async fn set_serving_status(&self, status: ServingStatus) {
+        if let Some(reporter) = &self.health_reporter {
+            reporter.set_service_status("", status).await;
+            reporter
+                .set_service_status(
+                    <ProcessorV3Server<ProcessorService> as tonic::server::NamedService>::NAME,
+                    status,
+                )
+                .await;
+        }
+    }
+
+    /// Gracefully shut down: stop accepting bindings, wait up to `drain_timeout` for
+    /// in-flight bindings to finish, then close all streams and fail pending DB requests.
+    pub async fn shutdown(&self, drain_timeout: Duration) {
+        self.set_serving_status(ServingStatus::NotServing).await;
+        self.lifecycle.set_phase(ShutdownPhase::Draining);
+        info!(
+            "Draining {} in-flight binding(s) (timeout {}s)",
+            self.lifecycle.in_flight(),
+            drain_timeout.as_secs()
+        );
+
+        if tokio::time::timeout(drain_timeout, self.lifecycle.wait_idle())
+            .await
+            .is_err()
+        {
+            warn!(
+                "Drain timeout reached with {} binding(s) still in flight",
+                self.lifecycle.in_flight()
+            );
+        }
+
+        self.lifecycle.set_phase(ShutdownPhase::Closed);
+        info!("Processor service shut down");
+    }
+
     // No setter for execution_config to keep it immutable after service start.
 }
 
+fn error_response(process_id: i32, error: String) -> ProcessStreamResponseV3 {
+    let err_result = crate::processor::ProcessResult {
+        states: Some(crate::processor::StateResult {
+            config_updated: false,
+            error: Some(error),
+        }),
+        ..Default::default()
+    };
+    ProcessStreamResponseV3 {
+        process_id,
+        value: Some(crate::processor::process_stream_response_v3::Value::Result(err_result)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,6 +290,200 @@ mod tests {
         let db_schema = resp.db_schema.expect("expected db_schema to be set");
         assert!(db_schema.gql_schema.contains("TestEntity"));
     }
+
+    async fn health_status(reporter: &HealthReporter) -> i32 {
+        use tonic_health::pb::health_server::Health;
+        use tonic_health::pb::HealthCheckRequest;
+        use tonic_health::server::HealthService;
+
+        let health = HealthService::from_health_reporter(reporter.clone());
+        let req = Request::new(HealthCheckRequest {
+            service: <ProcessorV3Server<ProcessorService> as tonic::server::NamedService>::NAME
+                .to_string(),
+        });
+        health.check(req).await.unwrap().into_inner().status
+    }
+
+    #[tokio::test]
+    async fn get_config_includes_declared_metrics() {
+        let service = ProcessorService::new();
+        service
+            .declare_counter(
+                "transfers",
+                MetricOptions {
+                    unit: Some("tx".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        service.declare_gauge("price", MetricOptions::default()).unwrap();
+
+        let resp = service
+            .get_config(Request::new(ProcessConfigRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        let metrics: Vec<_> = resp
+            .metric_configs
+            .iter()
+            .map(|config| (config.name.as_str(), config.r#type()))
+            .collect();
+        assert_eq!(
+            metrics,
+            vec![
+                ("price", crate::processor::MetricType::Gauge),
+                ("transfers", crate::processor::MetricType::Counter),
+            ]
+        );
+        assert_eq!(resp.metric_configs[1].unit, "tx");
+    }
+
+    #[tokio::test]
+    async fn get_config_includes_declared_exports() {
+        let service = ProcessorService::new();
+        service.declare_export("transfers", "warehouse");
+
+        let resp = service
+            .get_config(Request::new(ProcessConfigRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(
+            resp.export_configs,
+            vec![crate::processor::ExportConfig {
+                name: "transfers".to_string(),
+                channel: "warehouse".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn get_config_includes_declared_event_trackers() {
+        let service = ProcessorService::new();
+        service.declare_event_tracker(
+            "swap",
+            EventTrackerOptions {
+                distinct_aggregation_by_days: vec![7],
+                ..Default::default()
+            },
+        );
+
+        let resp = service
+            .get_config(Request::new(ProcessConfigRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(resp.event_tracking_configs.len(), 1);
+        assert_eq!(resp.event_tracking_configs[0].event_name, "swap");
+        assert!(resp.event_tracking_configs[0].unique);
+        assert_eq!(resp.event_tracking_configs[0].distinct_aggregation_by_days, vec![7]);
+    }
+
+    #[tokio::test]
+    async fn get_config_includes_declared_event_logs() {
+        use crate::processor::event_log_config::{BasicFieldType, field};
+
+        #[derive(crate::SentioEvent)]
+        #[event(name = "Swap")]
+        struct SwapLog {
+            #[event(coin = "ETH")]
+            amount_in: crate::BigDecimal,
+            trader: String,
+        }
+
+        let service = ProcessorService::new();
+        service.declare_event::<SwapLog>();
+
+        let resp = service
+            .get_config(Request::new(ProcessConfigRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(resp.event_log_configs.len(), 1);
+        let config = &resp.event_log_configs[0];
+        assert_eq!(config.name, "Swap");
+        assert_eq!(
+            config.fields[0].r#type,
+            Some(field::Type::CoinType(crate::CoinId::Eth as i32))
+        );
+        assert_eq!(
+            config.fields[1].r#type,
+            Some(field::Type::BasicType(BasicFieldType::String as i32))
+        );
+    }
+
+    #[tokio::test]
+    async fn get_config_marks_service_serving() {
+        use tonic_health::pb::health_check_response::ServingStatus as PbStatus;
+
+        let reporter = HealthReporter::new();
+        let service = ProcessorService::new().with_health_reporter(reporter.clone());
+        service.set_serving_status(ServingStatus::NotServing).await;
+        assert_eq!(health_status(&reporter).await, PbStatus::NotServing as i32);
+
+        service
+            .get_config(Request::new(ProcessConfigRequest {}))
+            .await
+            .unwrap();
+        assert_eq!(health_status(&reporter).await, PbStatus::Serving as i32);
+
+        service.shutdown(Duration::from_millis(10)).await;
+        assert_eq!(health_status(&reporter).await, PbStatus::NotServing as i32);
+    }
+
+    #[tokio::test]
+    async fn shutdown_drains_in_flight_bindings() {
+        let service = ProcessorService::new();
+        let guard = service.lifecycle.binding_started();
+        assert_eq!(service.in_flight_bindings(), 1);
+
+        let shutdown = {
+            let service = service.clone();
+            tokio::spawn(async move { service.shutdown(Duration::from_secs(5)).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(service.shutdown_phase(), ShutdownPhase::Draining);
+
+        drop(guard);
+        shutdown.await.unwrap();
+        assert_eq!(service.in_flight_bindings(), 0);
+        assert_eq!(service.shutdown_phase(), ShutdownPhase::Closed);
+    }
+
+    #[tokio::test]
+    async fn shutdown_closes_after_drain_timeout() {
+        let service = ProcessorService::new();
+        let _stuck = service.lifecycle.binding_started();
+
+        service.shutdown(Duration::from_millis(20)).await;
+        assert_eq!(service.shutdown_phase(), ShutdownPhase::Closed);
+        assert_eq!(service.in_flight_bindings(), 1);
+    }
+
+    #[tokio::test]
+    async fn fail_pending_resolves_waiting_db_requests() {
+        use crate::core::{RuntimeContext, RUNTIME_CONTEXT};
+        use crate::entity::store::backend::Backend;
+        use crate::entity::store::StorageBackend;
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(8);
+        let backend = Arc::new(Backend::remote());
+        let ctx = RuntimeContext::new_with_empty_metadata(tx, 1, backend.clone());
+
+        let pending = {
+            let backend = backend.clone();
+            tokio::spawn(RUNTIME_CONTEXT.scope(ctx, async move { backend.get("Transfer", "1").await }))
+        };
+        // Wait for the DB request to reach the (absent) driver
+        rx.recv()
+            .await
+            .expect("db request should be sent")
+            .expect("db request should be valid");
+
+        backend.fail_pending("processor is shutting down");
+        let err = pending.await.unwrap().expect_err("pending request should fail");
+        assert!(err.to_string().contains("shutting down"));
+    }
 }
 
 #[tonic::async_trait]
@@ -116,7 +510,7 @@ impl ProcessorV3 for ProcessorService {
         };
 
         // Configure for all chains/processors
-        self.plugin_manager.configure_all_plugins(&mut handler_config);
+        self.plugin_manager.configure_all_plugins(&mut handler_config).await;
 
         let mut response = ProcessConfigResponse {
             config: None,
@@ -124,10 +518,10 @@ impl ProcessorV3 for ProcessorService {
             contract_configs: handler_config.contract_configs,
             template_instances: vec![],
             account_configs: handler_config.account_configs,
-            metric_configs: vec![],
-            event_tracking_configs: vec![],
-            export_configs: vec![],
-            event_log_configs: vec![],
+            metric_configs: self.plugin_manager.metric_registry().metric_configs(),
+            event_tracking_configs: self.plugin_manager.event_tracker_registry().configs(),
+            export_configs: self.plugin_manager.export_registry().configs(),
+            event_log_configs: self.plugin_manager.event_log_registry().event_log_configs(),
             db_schema: None,
         };
 
@@ -137,6 +531,11 @@ impl ProcessorV3 for ProcessorService {
         }
 
         info!("get_config assembled {} contract configs", response.contract_configs.len());
+
+        // Processor is configured; report it as ready unless we are already shutting down
+        if self.lifecycle.phase() == ShutdownPhase::Running {
+            self.set_serving_status(ServingStatus::Serving).await;
+        }
         Ok(Response::new(response))
     }
 
@@ -153,42 +552,93 @@ impl ProcessorV3 for ProcessorService {
         Ok(Response::new(()))
     }
 
-    type ProcessBindingsStreamStream = std::pin::Pin<
-        Box<dyn tokio_stream::Stream<Item = Result<ProcessStreamResponseV3, Status>> + Send>,
-    >;
+    type ProcessBindingsStreamStream = ResponseStream;
 
     async fn process_bindings_stream(
         &self,
         request: Request<tonic::Streaming<ProcessStreamRequest>>,
     ) -> Result<Response<Self::ProcessBindingsStreamStream>, Status> {
-        use crate::processor::process_stream_request;
-        use tokio_stream::{wrappers::ReceiverStream, StreamExt};
-        // Allocate an id for this bindings stream and mark open for benchmarking
-        let stream_id = crate::core::benchmark::new_stream_id();
-        crate::core::benchmark::on_stream_open(stream_id);
-        let mut received_start = Instant::now();
         debug!(
             "Starting process_bindings_stream from client: {:?}",
             request.remote_addr()
         );
+        // Allocate an id for this bindings stream, shared by its recording
+        let stream_id = crate::core::benchmark::new_stream_id();
+        let recorder = self.recording_dir.as_ref().and_then(|dir| {
+            match StreamRecorder::create_in(dir, stream_id) {
+                Ok(recorder) => {
+                    info!("Recording bindings stream to {}", recorder.path().display());
+                    Some(Arc::new(recorder))
+                }
+                Err(e) => {
+                    warn!("Bindings stream will not be recorded: {:#}", e);
+                    None
+                }
+            }
+        });
+        Ok(Response::new(self.open_stream(
+            request.into_inner(),
+            stream_id,
+            recorder,
+        )))
+    }
+}
+
+impl ProcessorService {
+    /// Run a bindings stream session over `inbound` and return the stream of responses.
+    /// When a recorder is given, every request and response is written to it.
+    pub(crate) fn open_stream<S>(
+        &self,
+        mut inbound_stream: S,
+        stream_id: i32,
+        recorder: Option<Arc<StreamRecorder>>,
+    ) -> ResponseStream
+    where
+        S: tokio_stream::Stream<Item = Result<ProcessStreamRequest, Status>>
+            + Send
+            + Unpin
+            + 'static,
+    {
+        use crate::processor::process_stream_request;
+        use tokio_stream::{wrappers::ReceiverStream, StreamExt};
+        // Mark the stream open for benchmarking
+        crate::core::benchmark::on_stream_open(stream_id);
+        let mut received_start = Instant::now();
         info!("Starting bindings stream processing");
 
-        let mut inbound_stream = request.into_inner();
         let (tx, rx) = tokio::sync::mpsc::channel(1000);
 
         // Clone the plugin manager Arc for sharing between tasks
         let plugin_manager = self.plugin_manager.clone();
         // Snapshot timeout to avoid capturing self in spawned task
         let timeout_secs_snapshot = (self.execution_config.process_binding_timeout as u64).max(1);
+        let lifecycle = self.lifecycle.clone();
+        let mut shutdown_rx = self.lifecycle.subscribe();
+        let request_recorder = recorder.clone();
 
         tokio::spawn(async move {
             // new session
             let db_backend =
                 Arc::new(crate::entity::store::backend::Backend::remote());
-            while let Some(stream_request) = inbound_stream.next().await {
+            loop {
+                let stream_request = tokio::select! {
+                    next = inbound_stream.next() => match next {
+                        Some(stream_request) => stream_request,
+                        None => break,
+                    },
+                    _ = shutdown_rx.wait_for(|phase| *phase == ShutdownPhase::Closed) => {
+                        // Unblock handlers still waiting on the driver before closing the stream
+                        db_backend.fail_pending("processor is shutting down");
+                        debug!("Closing bindings stream due to shutdown");
+                        break;
+                    }
+                };
                 crate::core::benchmark::record_receive_time(received_start.elapsed());
                 match stream_request {
                     Ok(req) => {
+                        if let Some(recorder) = &request_recorder {
+                            recorder.record_request(&req);
+                        }
                         debug!(
                             "Received stream request with process_id: {}",
                             req.process_id
@@ -200,15 +650,31 @@ impl ProcessorV3 for ProcessorService {
                         if let Some(value) = req.value {
                             match value {
                                 process_stream_request::Value::Binding(binding) => {
+                                    if lifecycle.phase() != ShutdownPhase::Running {
+                                        warn!(
+                                            "Rejecting binding for chain '{}': processor is shutting down",
+                                            binding.chain_id
+                                        );
+                                        let response = error_response(
+                                            process_id,
+                                            "processor is shutting down".to_string(),
+                                        );
+                                        if let Err(e) = tx_clone.send(Ok(response)).await {
+                                            error!("Failed to send response: {}", e);
+                                        }
+                                        continue;
+                                    }
                                     debug!("Processing binding for chain_id: {}", binding.chain_id);
                                     let pm = plugin_manager.clone();
                                     let db = db_backend.clone();
                                     let tx_resp = tx_clone.clone();
                                     let timeout_secs = timeout_secs_snapshot;
+                                    let in_flight = lifecycle.binding_started();
                                     // Track handler concurrency within this stream
                                     crate::core::benchmark::on_binding_spawn(stream_id);
                                     // Spawn per-binding processing so the stream keeps receiving next requests
                                     tokio::spawn(async move {
+                                        let _in_flight = in_flight;
                                         let runtime_context = crate::core::RuntimeContext::new_with_empty_metadata(tx_resp.clone(), process_id, db.clone());
                                         let start = std::time::Instant::now();
                                         let setup_time = start.elapsed();
@@ -230,30 +696,14 @@ impl ProcessorV3 for ProcessorService {
                                                     "Failed to process binding for chain '{}': {}",
                                                     binding.chain_id, e
                                                 );
-                                                let mut err_result = crate::processor::ProcessResult::default();
-                                                err_result.states = Some(crate::processor::StateResult {
-                                                    config_updated: false,
-                                                    error: Some(e.to_string()),
-                                                });
-                                                ProcessStreamResponseV3 {
-                                                    process_id,
-                                                    value: Some(crate::processor::process_stream_response_v3::Value::Result(err_result)),
-                                                }
+                                                error_response(process_id, e.to_string())
                                             }
                                             Err(_elapsed) => {
                                                 error!(
                                                     "Processing binding timed out for chain '{}' after {}s",
                                                     binding.chain_id, timeout_secs
                                                 );
-                                                let mut err_result = crate::processor::ProcessResult::default();
-                                                err_result.states = Some(crate::processor::StateResult {
-                                                    config_updated: false,
-                                                    error: Some(format!("user processor timeout after {}s", timeout_secs)),
-                                                });
-                                                ProcessStreamResponseV3 {
-                                                    process_id,
-                                                    value: Some(crate::processor::process_stream_response_v3::Value::Result(err_result)),
-                                                }
+                                                error_response(process_id, format!("user processor timeout after {}s", timeout_secs))
                                             }
                                         };
                                         let process_time = process_start.elapsed();
@@ -303,6 +753,16 @@ impl ProcessorV3 for ProcessorService {
         });
 
         let response_stream = ReceiverStream::new(rx);
-        Ok(Response::new(Box::pin(response_stream)))
+        match recorder {
+            Some(recorder) => Box::pin(futures::StreamExt::inspect(
+                response_stream,
+                move |response| {
+                    if let Ok(response) = response {
+                        recorder.record_response(response);
+                    }
+                },
+            )),
+            None => Box::pin(response_stream),
+        }
     }
 }
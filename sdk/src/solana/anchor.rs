@@ -0,0 +1,376 @@
+//! Instruction decoding driven by an Anchor IDL
+//!
+//! Anchor programs prefix instruction data with an 8 byte discriminator followed
+//! by the borsh encoded arguments. [`AnchorInstructionDecoder`] reads both from
+//! the IDL at runtime and produces JSON arguments keyed by the IDL argument names.
+//!
+//! Integer types up to 64 bits decode to JSON numbers, 128 bit integers to decimal
+//! strings, public keys to base58 strings and `bytes` to `0x` prefixed hex.
+
+use crate::solana::{encode_base58, DecodedInstruction, InstructionDecoder, RawInstruction};
+use anyhow::{anyhow, bail, Result};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Anchor IDL, only the parts needed to decode instructions
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AnchorIdl {
+    pub address: String,
+    pub metadata: AnchorIdlMetadata,
+    pub instructions: Vec<AnchorIdlInstruction>,
+    pub types: Vec<AnchorIdlTypeDef>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AnchorIdlMetadata {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AnchorIdlInstruction {
+    pub name: String,
+    pub discriminator: Vec<u8>,
+    pub args: Vec<AnchorIdlField>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnchorIdlField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: AnchorIdlType,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnchorIdlTypeDef {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_def: AnchorIdlTypeDefBody,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum AnchorIdlTypeDefBody {
+    Struct {
+        #[serde(default)]
+        fields: AnchorIdlFields,
+    },
+    Enum {
+        variants: Vec<AnchorIdlVariant>,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnchorIdlVariant {
+    pub name: String,
+    #[serde(default)]
+    pub fields: AnchorIdlFields,
+}
+
+/// Fields of a struct or enum variant, named or positional
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum AnchorIdlFields {
+    Named(Vec<AnchorIdlField>),
+    Tuple(Vec<AnchorIdlType>),
+}
+
+impl Default for AnchorIdlFields {
+    fn default() -> Self {
+        AnchorIdlFields::Named(Vec::new())
+    }
+}
+
+/// A type reference as written in the IDL
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum AnchorIdlType {
+    /// Primitive types: `bool`, `u8` .. `u128`, `i8` .. `i128`, `f32`, `f64`,
+    /// `string`, `bytes` and `pubkey`
+    Primitive(String),
+    Vec { vec: Box<AnchorIdlType> },
+    Option { option: Box<AnchorIdlType> },
+    Array { array: (Box<AnchorIdlType>, usize) },
+    Defined { defined: AnchorIdlDefined },
+}
+
+/// Reference to a user defined type, `{"name": ..}` or a bare name in older IDLs
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum AnchorIdlDefined {
+    Named { name: String },
+    Legacy(String),
+}
+
+impl AnchorIdlDefined {
+    pub fn name(&self) -> &str {
+        match self {
+            AnchorIdlDefined::Named { name } => name,
+            AnchorIdlDefined::Legacy(name) => name,
+        }
+    }
+}
+
+impl AnchorIdl {
+    /// Parse an IDL from its JSON form
+    pub fn from_json(idl: &str) -> Result<Self> {
+        serde_json::from_str(idl).map_err(|e| anyhow!("Failed to parse Anchor IDL: {}", e))
+    }
+
+    /// Program name from the IDL metadata
+    pub fn name(&self) -> &str {
+        &self.metadata.name
+    }
+}
+
+/// [`InstructionDecoder`] for Anchor programs
+///
+/// Requires an IDL generated by Anchor 0.30 or later, which carries the
+/// instruction discriminators.
+#[derive(Debug, Clone)]
+pub struct AnchorInstructionDecoder {
+    instructions: HashMap<[u8; 8], AnchorIdlInstruction>,
+    types: HashMap<String, AnchorIdlTypeDefBody>,
+}
+
+impl AnchorInstructionDecoder {
+    pub fn new(idl: AnchorIdl) -> Result<Self> {
+        let mut instructions = HashMap::new();
+        for instruction in idl.instructions {
+            let discriminator: [u8; 8] = instruction.discriminator.as_slice().try_into().map_err(|_| {
+                anyhow!(
+                    "Instruction {} has no 8 byte discriminator, an Anchor 0.30+ IDL is required",
+                    instruction.name
+                )
+            })?;
+            instructions.insert(discriminator, instruction);
+        }
+        let types = idl
+            .types
+            .into_iter()
+            .map(|type_def| (type_def.name, type_def.type_def))
+            .collect();
+        Ok(Self { instructions, types })
+    }
+
+    /// Build a decoder from the JSON form of an IDL
+    pub fn from_idl(idl: &str) -> Result<Self> {
+        Self::new(AnchorIdl::from_json(idl)?)
+    }
+
+    /// Decode raw instruction data, `Ok(None)` if the discriminator is unknown
+    pub fn decode(&self, data: &[u8]) -> Result<Option<DecodedInstruction>> {
+        let Some(discriminator) = data.get(..8) else {
+            return Ok(None);
+        };
+        let Some(instruction) = self.instructions.get(discriminator) else {
+            return Ok(None);
+        };
+
+        let mut reader = BorshReader::new(&data[8..]);
+        let mut args = Map::new();
+        for arg in &instruction.args {
+            let value = self
+                .read(&mut reader, &arg.field_type)
+                .map_err(|e| anyhow!("Failed to decode {}.{}: {}", instruction.name, arg.name, e))?;
+            args.insert(arg.name.clone(), value);
+        }
+        Ok(Some(DecodedInstruction {
+            name: instruction.name.clone(),
+            args: Value::Object(args),
+        }))
+    }
+
+    fn read(&self, reader: &mut BorshReader, idl_type: &AnchorIdlType) -> Result<Value> {
+        Ok(match idl_type {
+            AnchorIdlType::Primitive(name) => reader.read_primitive(name)?,
+            AnchorIdlType::Vec { vec } => {
+                let len = reader.read_u32()? as usize;
+                Value::Array((0..len).map(|_| self.read(reader, vec)).collect::<Result<_>>()?)
+            }
+            AnchorIdlType::Option { option } => match reader.read_u8()? {
+                0 => Value::Null,
+                _ => self.read(reader, option)?,
+            },
+            AnchorIdlType::Array { array: (item, len) } => {
+                Value::Array((0..*len).map(|_| self.read(reader, item)).collect::<Result<_>>()?)
+            }
+            AnchorIdlType::Defined { defined } => {
+                let type_def = self
+                    .types
+                    .get(defined.name())
+                    .ok_or_else(|| anyhow!("Unknown type {}", defined.name()))?;
+                match type_def {
+                    AnchorIdlTypeDefBody::Struct { fields } => self.read_fields(reader, fields)?,
+                    AnchorIdlTypeDefBody::Enum { variants } => {
+                        let index = reader.read_u8()? as usize;
+                        let variant = variants
+                            .get(index)
+                            .ok_or_else(|| anyhow!("Invalid variant {} of {}", index, defined.name()))?;
+                        let mut map = Map::new();
+                        map.insert(variant.name.clone(), self.read_fields(reader, &variant.fields)?);
+                        Value::Object(map)
+                    }
+                }
+            }
+        })
+    }
+
+    fn read_fields(&self, reader: &mut BorshReader, fields: &AnchorIdlFields) -> Result<Value> {
+        Ok(match fields {
+            AnchorIdlFields::Named(fields) => {
+                let mut map = Map::new();
+                for field in fields {
+                    map.insert(field.name.clone(), self.read(reader, &field.field_type)?);
+                }
+                Value::Object(map)
+            }
+            AnchorIdlFields::Tuple(types) => {
+                Value::Array(types.iter().map(|t| self.read(reader, t)).collect::<Result<_>>()?)
+            }
+        })
+    }
+}
+
+impl InstructionDecoder for AnchorInstructionDecoder {
+    fn decode_instruction(&self, instruction: &RawInstruction) -> Result<Option<DecodedInstruction>> {
+        self.decode(&instruction.data()?)
+    }
+}
+
+/// Sequential reader of borsh encoded values
+struct BorshReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> BorshReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let bytes = self.take_slice(N)?;
+        Ok(bytes.try_into().expect("slice has length N"))
+    }
+
+    fn take_slice(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.data.len() < len {
+            bail!("Unexpected end of instruction data");
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn read_primitive(&mut self, name: &str) -> Result<Value> {
+        Ok(match name {
+            "bool" => Value::Bool(self.read_u8()? != 0),
+            "u8" => Value::from(self.read_u8()?),
+            "i8" => Value::from(i8::from_le_bytes(self.take()?)),
+            "u16" => Value::from(u16::from_le_bytes(self.take()?)),
+            "i16" => Value::from(i16::from_le_bytes(self.take()?)),
+            "u32" => Value::from(self.read_u32()?),
+            "i32" => Value::from(i32::from_le_bytes(self.take()?)),
+            "u64" => Value::from(u64::from_le_bytes(self.take()?)),
+            "i64" => Value::from(i64::from_le_bytes(self.take()?)),
+            "u128" => Value::String(u128::from_le_bytes(self.take()?).to_string()),
+            "i128" => Value::String(i128::from_le_bytes(self.take()?).to_string()),
+            "f32" => Value::from(f32::from_le_bytes(self.take()?)),
+            "f64" => Value::from(f64::from_le_bytes(self.take()?)),
+            "string" => {
+                let len = self.read_u32()? as usize;
+                let bytes = self.take_slice(len)?;
+                Value::String(String::from_utf8(bytes.to_vec())?)
+            }
+            "bytes" => {
+                let len = self.read_u32()? as usize;
+                Value::String(format!("0x{}", hex::encode(self.take_slice(len)?)))
+            }
+            "pubkey" | "publicKey" => Value::String(encode_base58(&self.take::<32>()?)),
+            other => bail!("Unsupported IDL type {}", other),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COUNTER_IDL: &str = r#"{
+        "address": "Counter111111111111111111111111111111111111",
+        "metadata": {"name": "counter", "version": "0.1.0", "spec": "0.1.0"},
+        "instructions": [
+            {
+                "name": "increment",
+                "discriminator": [11, 18, 104, 9, 104, 174, 59, 33],
+                "accounts": [{"name": "counter", "writable": true}],
+                "args": [
+                    {"name": "amount", "type": "u64"},
+                    {"name": "memo", "type": {"option": "string"}},
+                    {"name": "mode", "type": {"defined": {"name": "Mode"}}}
+                ]
+            }
+        ],
+        "types": [
+            {"name": "Mode", "type": {"kind": "enum", "variants": [
+                {"name": "Fast"},
+                {"name": "Capped", "fields": [{"name": "limit", "type": "u128"}]}
+            ]}}
+        ]
+    }"#;
+
+    fn increment_data() -> Vec<u8> {
+        let mut data = vec![11, 18, 104, 9, 104, 174, 59, 33];
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(b"hi");
+        data.push(1);
+        data.extend_from_slice(&7u128.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_decode_instruction() {
+        let decoder = AnchorInstructionDecoder::from_idl(COUNTER_IDL).unwrap();
+        let decoded = decoder.decode(&increment_data()).unwrap().unwrap();
+        assert_eq!(decoded.name, "increment");
+        assert_eq!(
+            decoded.args,
+            serde_json::json!({"amount": 42, "memo": "hi", "mode": {"Capped": {"limit": "7"}}})
+        );
+
+        let instruction = RawInstruction {
+            instruction_data: encode_base58(&increment_data()),
+            ..Default::default()
+        };
+        assert!(decoder.decode_instruction(&instruction).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_unknown_and_truncated_instructions() {
+        let decoder = AnchorInstructionDecoder::from_idl(COUNTER_IDL).unwrap();
+        assert!(decoder.decode(&[0u8; 16]).unwrap().is_none());
+        assert!(decoder.decode(&[1, 2]).unwrap().is_none());
+
+        let data = increment_data();
+        assert!(decoder.decode(&data[..12]).is_err());
+    }
+
+    #[test]
+    fn test_idl_without_discriminator_is_rejected() {
+        let idl = r#"{"instructions": [{"name": "initialize", "accounts": [], "args": []}]}"#;
+        assert!(AnchorInstructionDecoder::from_idl(idl).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+//! Code generator for Anchor IDLs
+//!
+//! For every `idls/<program>.json` under the source directory a `<program>.rs`
+//! module is generated containing the embedded IDL, a `decoder()` constructor and,
+//! per instruction, a marker type for `configure_instruction` plus a typed
+//! `<Instruction>Args` struct for `SolanaInstruction::parse_args`.
+
+use crate::codegen::{CodeGenerator, CodegenResult};
+use crate::solana::anchor::{AnchorIdl, AnchorIdlInstruction, AnchorIdlType};
+use anyhow::{Context, Result};
+use convert_case::{Case, Casing};
+use rust_codegen::{Field, Scope, Struct, Type};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Generator for Solana instruction bindings from Anchor IDLs
+pub struct AnchorIdlCodeGenerator;
+
+impl AnchorIdlCodeGenerator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn idl_files(&self, src_dir: &Path) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(src_dir.join("idls")) else {
+            return vec![];
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Generate the Rust module for a single IDL
+    pub fn generate_program(&self, idl_json: &str) -> Result<String> {
+        let idl = AnchorIdl::from_json(idl_json)?;
+
+        let mut header = Scope::new();
+        header.raw(&format!("//! Generated Solana instruction bindings: {}", idl.name()));
+        header.raw("// This file is auto-generated. Do not edit manually.");
+        header.raw("");
+
+        let mut scope = Scope::new();
+        scope.import("sentio_sdk::solana", "{AnchorInstructionDecoder, SolanaInstructionMarker}");
+        scope.import("serde", "Deserialize");
+        scope.raw("");
+
+        scope.raw(&format!("pub const PROGRAM_ID: &str = \"{}\";", idl.address));
+        scope.raw("");
+        scope.raw(&format!("pub const IDL: &str = r####\"{}\"####;", idl_json.trim()));
+        scope.raw("");
+        scope.raw("/// Decoder for the instructions of this program");
+        scope.raw("pub fn decoder() -> AnchorInstructionDecoder {");
+        scope.raw("    AnchorInstructionDecoder::from_idl(IDL).expect(\"embedded IDL is valid\")");
+        scope.raw("}");
+        scope.raw("");
+
+        for instruction in &idl.instructions {
+            self.generate_instruction(&mut scope, instruction);
+        }
+
+        Ok(format!("{}{}", header.to_string(), scope.to_string()))
+    }
+
+    /// Generate the marker type and argument struct of an instruction
+    fn generate_instruction(&self, scope: &mut Scope, instruction: &AnchorIdlInstruction) {
+        let type_name = instruction.name.to_case(Case::Pascal);
+
+        let mut marker = Struct::new(&type_name);
+        marker.vis("pub");
+        marker.doc(&format!("Marker for the `{}` instruction", instruction.name));
+        scope.push_struct(marker);
+        scope.raw("");
+        scope.raw(&format!(
+            "impl SolanaInstructionMarker for {} {{\n    fn instruction_name() -> &'static str {{\n        \"{}\"\n    }}\n}}",
+            type_name, instruction.name
+        ));
+        scope.raw("");
+
+        let mut args = Struct::new(&format!("{}Args", type_name));
+        args.vis("pub");
+        args.doc(&format!("Arguments of the `{}` instruction", instruction.name));
+        args.derive("Debug").derive("Clone").derive("Deserialize");
+        for arg in &instruction.args {
+            let field_name = arg.name.to_case(Case::Snake);
+            let mut annotation = Vec::new();
+            if field_name != arg.name {
+                annotation.push(format!("#[serde(rename = \"{}\")]", arg.name));
+            }
+            args.push_field(Field {
+                name: format!("pub {}", field_name),
+                ty: Type::new(&self.rust_type(&arg.field_type)),
+                documentation: vec![],
+                annotation,
+            });
+        }
+        scope.push_struct(args);
+        scope.raw("");
+    }
+
+    /// Rust type of a decoded IDL value, see [`crate::solana::anchor`] for the JSON forms
+    fn rust_type(&self, idl_type: &AnchorIdlType) -> String {
+        match idl_type {
+            AnchorIdlType::Primitive(name) => match name.as_str() {
+                "bool" | "u8" | "i8" | "u16" | "i16" | "u32" | "i32" | "u64" | "i64" | "f32"
+                | "f64" => name.clone(),
+                _ => "String".to_string(),
+            },
+            AnchorIdlType::Vec { vec } => format!("Vec<{}>", self.rust_type(vec)),
+            AnchorIdlType::Option { option } => format!("Option<{}>", self.rust_type(option)),
+            AnchorIdlType::Array { array: (item, _) } => format!("Vec<{}>", self.rust_type(item)),
+            // User defined types are kept as JSON
+            AnchorIdlType::Defined { .. } => "serde_json::Value".to_string(),
+        }
+    }
+}
+
+impl Default for AnchorIdlCodeGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CodeGenerator for AnchorIdlCodeGenerator {
+    fn generator_name(&self) -> &str {
+        "anchor-idl"
+    }
+
+    fn should_generate(&self, src_dir: &Path) -> bool {
+        !self.idl_files(src_dir).is_empty()
+    }
+
+    fn generate(&self, src_dir: &Path, dst_dir: &Path) -> Result<CodegenResult> {
+        fs::create_dir_all(dst_dir)
+            .with_context(|| format!("Failed to create output directory: {}", dst_dir.display()))?;
+
+        let mut generated_files = Vec::new();
+        for idl_path in self.idl_files(src_dir) {
+            let idl_json = fs::read_to_string(&idl_path)
+                .with_context(|| format!("Failed to read IDL file: {}", idl_path.display()))?;
+            let code = self
+                .generate_program(&idl_json)
+                .with_context(|| format!("Failed to generate bindings for {}", idl_path.display()))?;
+
+            let stem = idl_path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or_default()
+                .to_case(Case::Snake);
+            let output_path = dst_dir.join(format!("{}.rs", stem));
+            fs::write(&output_path, code)
+                .with_context(|| format!("Failed to write {}", output_path.display()))?;
+            generated_files.push(output_path);
+        }
+
+        Ok(CodegenResult {
+            generator_name: self.generator_name().to_string(),
+            message: format!("Generated bindings for {} Anchor programs", generated_files.len()),
+            files_generated: generated_files,
+            success: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDL: &str = r#"{
+        "address": "Counter111111111111111111111111111111111111",
+        "metadata": {"name": "counter"},
+        "instructions": [{
+            "name": "set_limit",
+            "discriminator": [1, 2, 3, 4, 5, 6, 7, 8],
+            "accounts": [],
+            "args": [
+                {"name": "newLimit", "type": "u128"},
+                {"name": "owners", "type": {"vec": "pubkey"}},
+                {"name": "flag", "type": {"option": "bool"}}
+            ]
+        }]
+    }"#;
+
+    #[test]
+    fn test_generate_program() {
+        let code = AnchorIdlCodeGenerator::new().generate_program(IDL).unwrap();
+        assert!(code.contains("pub struct SetLimit;"));
+        assert!(code.contains("impl SolanaInstructionMarker for SetLimit"));
+        assert!(code.contains("\"set_limit\""));
+        assert!(code.contains("pub struct SetLimitArgs"));
+        assert!(code.contains("#[serde(rename = \"newLimit\")]"));
+        assert!(code.contains("pub new_limit: String"));
+        assert!(code.contains("pub owners: Vec<String>"));
+        assert!(code.contains("pub flag: Option<bool>"));
+        assert!(code.contains("pub const PROGRAM_ID: &str = \"Counter111111111111111111111111111111111111\";"));
+    }
+
+    #[test]
+    fn test_generate_from_idls_dir() {
+        let src = tempfile::tempdir().unwrap();
+        let dst = tempfile::tempdir().unwrap();
+        let generator = AnchorIdlCodeGenerator::new();
+        assert!(!generator.should_generate(src.path()));
+
+        fs::create_dir(src.path().join("idls")).unwrap();
+        fs::write(src.path().join("idls").join("counter.json"), IDL).unwrap();
+        assert!(generator.should_generate(src.path()));
+
+        let result = generator.generate(src.path(), dst.path()).unwrap();
+        assert!(result.success);
+        assert_eq!(result.files_generated, vec![dst.path().join("counter.rs")]);
+        assert!(dst.path().join("counter.rs").exists());
+    }
+}
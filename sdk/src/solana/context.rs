@@ -0,0 +1,48 @@
+use crate::core::{Context, BaseContext, StateCollector};
+
+/// Context passed to Solana handlers
+#[derive(Clone)]
+pub struct SolanaContext {
+    base_context: BaseContext,
+    state_collector: Option<StateCollector>,
+    slot: u64,
+    program_id: String,
+}
+
+impl SolanaContext {
+    /// Create a new SolanaContext for an instruction of `program_id` processed in `slot`
+    pub fn new(slot: u64, program_id: impl Into<String>) -> Self {
+        Self {
+            base_context: BaseContext::new(),
+            state_collector: None,
+            slot,
+            program_id: program_id.into(),
+        }
+    }
+
+    /// Enable state collection for this context
+    pub fn with_state_collector(mut self, state_collector: StateCollector) -> Self {
+        self.state_collector = Some(state_collector);
+        self
+    }
+
+    /// Slot the instruction was processed in
+    pub fn slot(&self) -> u64 {
+        self.slot
+    }
+
+    /// Program the instruction was sent to
+    pub fn program_id(&self) -> &str {
+        &self.program_id
+    }
+}
+
+impl Context for SolanaContext {
+    fn base_context(&mut self) -> &mut BaseContext {
+        &mut self.base_context
+    }
+
+    fn state_collector(&self) -> Option<&StateCollector> {
+        self.state_collector.as_ref()
+    }
+}
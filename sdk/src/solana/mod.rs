@@ -0,0 +1,13 @@
+pub mod solana_processor;
+pub mod solana_plugin;
+pub mod context;
+pub mod anchor;
+pub mod codegen;
+mod solana_types;
+
+pub use solana_types::*;
+
+pub use solana_processor::{SolanaProcessor, SolanaBindOptions};
+pub use solana_plugin::SolanaPlugin;
+pub use anchor::AnchorInstructionDecoder;
+pub use codegen::AnchorIdlCodeGenerator;
@@ -0,0 +1,393 @@
+use crate::core::conversions::struct_to_json;
+use crate::core::plugin::FullPlugin;
+use crate::core::{
+    AsyncPluginProcessor, BaseProcessor, ContractConfigBuilder, MetaData, Plugin, PluginRegister,
+    binding_value, dispatch_handler,
+};
+use crate::processor::{HandlerType, data};
+use crate::solana::context::SolanaContext;
+use crate::solana::solana_processor::SolanaProcessorImpl;
+use crate::solana::{RawInstruction, SolanaInstruction};
+use crate::{ConfigureHandlersResponse, InstructionHandlerConfig, Timestamp};
+use tracing::debug;
+
+/// Plugin for Solana instruction processors
+///
+/// Instructions are not bound to handler ids: the driver sends every instruction
+/// of a configured program, each processor of that program decodes it and the
+/// handlers registered for the decoded instruction name are invoked.
+#[derive(Default)]
+pub struct SolanaPlugin {
+    processors: Vec<Box<SolanaProcessorImpl>>,
+}
+
+impl Plugin for SolanaPlugin {
+    fn name(&self) -> &str {
+        "solana-plugin"
+    }
+
+    fn handler_types(&self) -> &'static [HandlerType] {
+        &[HandlerType::SolInstruction]
+    }
+
+    fn processor_count(&self) -> usize {
+        self.processors.len()
+    }
+
+    fn chain_ids(&self) -> Vec<String> {
+        self.processors
+            .iter()
+            .map(|p| p.chain_id().to_string())
+            .collect()
+    }
+
+    fn configure(&mut self, config: &mut ConfigureHandlersResponse) {
+        debug!("Configuring SolanaPlugin handlers for all chains");
+
+        for processor in &self.processors {
+            let options = &processor.options;
+            let mut builder = ContractConfigBuilder::new(
+                processor.chain_id(),
+                options.program_id.clone(),
+                processor.name(),
+            )
+            .start_block(options.start_slot.unwrap_or_default())
+            .end_block(options.end_slot.unwrap_or_default());
+
+            builder.config_mut().instruction_config = Some(InstructionHandlerConfig {
+                inner_instruction: options.inner_instruction,
+                parsed_instruction: options.parsed_instruction,
+                raw_data_instruction: options.raw_data_instruction,
+            });
+
+            config.contract_configs.push(builder.build());
+        }
+    }
+}
+
+impl SolanaPlugin {
+    fn metadata(processor: &SolanaProcessorImpl, chain_id: &str, slot: u64) -> MetaData {
+        MetaData {
+            address: processor.options.program_id.clone(),
+            contract_name: processor.name().to_string(),
+            chain_id: chain_id.to_string(),
+            block_number: slot,
+            // Instruction bindings carry no block time
+            timestamp: Timestamp::from(prost_types::Timestamp::default()),
+            transaction_hash: String::new(),
+            transaction_index: 0,
+            log_index: 0,
+            base_labels: Default::default(),
+        }
+    }
+
+    async fn process_instruction(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        let sol_instruction = match binding_value(data)? {
+            data::Value::SolInstruction(sol_instruction) => sol_instruction,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Expected Solana instruction data but got different type"
+                ));
+            }
+        };
+        let raw = RawInstruction {
+            program_id: sol_instruction.program_account_id.clone(),
+            instruction_data: sol_instruction.instruction_data.clone(),
+            accounts: sol_instruction.accounts.clone(),
+            parsed: sol_instruction.parsed.as_ref().map(struct_to_json),
+            slot: sol_instruction.slot,
+        };
+
+        let mut result = crate::ProcessResult::default();
+        let processors = self.processors.iter().filter(|processor| {
+            processor.chain_id() == data.chain_id && processor.options.program_id == raw.program_id
+        });
+        for processor in processors {
+            let decoded = processor.processor.decode_instruction(&raw).map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to decode instruction of program {} in processor {}: {}",
+                    raw.program_id,
+                    processor.name(),
+                    e
+                )
+            })?;
+            let Some(decoded) = decoded else {
+                debug!(
+                    "Processor {} skipped an unrecognized instruction",
+                    processor.name()
+                );
+                continue;
+            };
+
+            let handlers = processor
+                .instruction_handlers
+                .iter()
+                .filter(|handler| handler.instruction_name == decoded.name);
+            for handler in handlers {
+                let instruction = SolanaInstruction {
+                    name: decoded.name.clone(),
+                    args: decoded.args.clone(),
+                    program_id: raw.program_id.clone(),
+                    accounts: raw.accounts.clone(),
+                    instruction_data: raw.instruction_data.clone(),
+                };
+                let handler_result = dispatch_handler(
+                    Self::metadata(processor, &data.chain_id, raw.slot),
+                    |collector| {
+                        SolanaContext::new(raw.slot, raw.program_id.clone())
+                            .with_state_collector(collector)
+                    },
+                    |ctx| handler.handler.handle_instruction(instruction, ctx),
+                )
+                .await;
+                result = result.merge(handler_result);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[tonic::async_trait]
+impl AsyncPluginProcessor for SolanaPlugin {
+    async fn process_binding(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        debug!(
+            "SolanaPlugin processing binding for chain_id: {}",
+            data.chain_id
+        );
+
+        let handler_type = HandlerType::try_from(data.handler_type)?;
+        match handler_type {
+            HandlerType::SolInstruction => self.process_instruction(data).await,
+            _ => Err(anyhow::anyhow!(
+                "Unsupported handler type: {:?}",
+                handler_type
+            )),
+        }
+    }
+}
+
+impl FullPlugin for SolanaPlugin {}
+
+impl PluginRegister<SolanaProcessorImpl> for SolanaPlugin {
+    fn register_processor(&mut self, processor: SolanaProcessorImpl) -> &mut SolanaProcessorImpl {
+        debug!(
+            "Registering processor: {} (chain_id: {})",
+            processor.name(),
+            processor.chain_id()
+        );
+
+        self.processors.push(Box::new(processor));
+        self.processors.last_mut().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Context;
+    use crate::solana::{
+        DecodedInstruction, InstructionDecoder, SOLANA_MAINNET, SolanaInstructionHandler,
+        SolanaInstructionMarker, SolanaProcessor, encode_base58,
+    };
+    use crate::testing::{TestProcessorServer, TestResult};
+
+    const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+    const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+    /// Decodes System program transfers: u32 instruction index 2, then u64 lamports
+    struct SystemProcessor;
+
+    impl InstructionDecoder for SystemProcessor {
+        fn decode_instruction(
+            &self,
+            instruction: &RawInstruction,
+        ) -> anyhow::Result<Option<DecodedInstruction>> {
+            let data = instruction.data()?;
+            if data.len() != 12 || data[..4] != 2u32.to_le_bytes() {
+                return Ok(None);
+            }
+            let lamports = u64::from_le_bytes(data[4..].try_into()?);
+            Ok(Some(DecodedInstruction {
+                name: "transfer".to_string(),
+                args: serde_json::json!({ "lamports": lamports }),
+            }))
+        }
+    }
+
+    impl SolanaProcessor for SystemProcessor {
+        fn program_id(&self) -> &str {
+            SYSTEM_PROGRAM
+        }
+
+        fn name(&self) -> &str {
+            "system"
+        }
+    }
+
+    /// Relies on the node parsed form through the default decoder
+    struct TokenProcessor;
+
+    impl InstructionDecoder for TokenProcessor {}
+
+    impl SolanaProcessor for TokenProcessor {
+        fn program_id(&self) -> &str {
+            TOKEN_PROGRAM
+        }
+
+        fn name(&self) -> &str {
+            "token"
+        }
+    }
+
+    struct Transfer;
+
+    impl SolanaInstructionMarker for Transfer {
+        fn instruction_name() -> &'static str {
+            "transfer"
+        }
+    }
+
+    #[crate::async_trait]
+    impl SolanaInstructionHandler<Transfer> for SystemProcessor {
+        async fn on_instruction(&self, instruction: SolanaInstruction, mut ctx: SolanaContext) {
+            assert_eq!(ctx.program_id(), SYSTEM_PROGRAM);
+            let lamports = instruction.args["lamports"].as_u64().unwrap();
+            ctx.base_context()
+                .gauge("lamports")
+                .record(lamports as f64, None)
+                .await
+                .ok();
+        }
+    }
+
+    #[crate::async_trait]
+    impl SolanaInstructionHandler<Transfer> for TokenProcessor {
+        async fn on_instruction(&self, instruction: SolanaInstruction, mut ctx: SolanaContext) {
+            let amount: f64 = instruction.args["amount"].as_str().unwrap().parse().unwrap();
+            ctx.base_context()
+                .gauge("token_amount")
+                .record(amount, None)
+                .await
+                .ok();
+        }
+    }
+
+    fn system_transfer(lamports: u64, slot: u64) -> RawInstruction {
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&lamports.to_le_bytes());
+        RawInstruction {
+            program_id: SYSTEM_PROGRAM.to_string(),
+            instruction_data: encode_base58(&data),
+            accounts: vec!["from".to_string(), "to".to_string()],
+            parsed: None,
+            slot,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_solana_plugin_configure_and_dispatch() {
+        let mut server = TestProcessorServer::new();
+        SystemProcessor
+            .configure_instruction::<Transfer>()
+            .from_slot(100)
+            .bind(&server);
+        TokenProcessor
+            .configure_instruction::<Transfer>()
+            .with_parsed_instructions()
+            .bind(&server);
+        server.start().await.unwrap();
+
+        let config = server.get_config().await;
+        assert_eq!(config.contract_configs.len(), 2);
+        let system_config = &config.contract_configs[0];
+        assert_eq!(system_config.start_block, 100);
+        assert_eq!(
+            system_config.instruction_config,
+            Some(InstructionHandlerConfig {
+                inner_instruction: false,
+                parsed_instruction: false,
+                raw_data_instruction: true,
+            })
+        );
+        assert!(
+            config.contract_configs[1]
+                .instruction_config
+                .as_ref()
+                .unwrap()
+                .parsed_instruction
+        );
+
+        let facet = server.solana();
+        let result = facet.test_instruction(system_transfer(5_000, 123), None).await;
+        assert_eq!(result.first_gauge_value("lamports"), Some(5_000.0));
+        assert_eq!(result.gauges[0].metadata.block_number, Some(123));
+        assert_eq!(result.gauges[0].metadata.contract_name.as_deref(), Some("system"));
+
+        // Unrecognized instructions are skipped
+        let mut create_account = system_transfer(5_000, 124);
+        create_account.instruction_data = encode_base58(&0u32.to_le_bytes());
+        let result = facet.test_instruction(create_account, None).await;
+        assert!(result.gauges.is_empty());
+
+        let token_transfer = RawInstruction {
+            program_id: TOKEN_PROGRAM.to_string(),
+            parsed: Some(serde_json::json!({
+                "type": "transfer",
+                "info": {"amount": "250", "source": "a", "destination": "b"}
+            })),
+            slot: 125,
+            ..Default::default()
+        };
+        let result = facet.test_instruction(token_transfer, None).await;
+        assert_eq!(result.first_gauge_value("token_amount"), Some(250.0));
+        assert!(result.first_gauge_value("lamports").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_solana_plugin_dispatches_without_handler_ids() {
+        let mut server = TestProcessorServer::new();
+        SystemProcessor
+            .configure_instruction::<Transfer>()
+            .bind(&server);
+        server.start().await.unwrap();
+
+        // The driver binds instructions by program, never by handler id
+        let binding = |program_id: &str| {
+            let mut data = 2u32.to_le_bytes().to_vec();
+            data.extend_from_slice(&7_000u64.to_le_bytes());
+            crate::DataBinding {
+                data: Some(crate::Data {
+                    value: Some(data::Value::SolInstruction(data::SolInstruction {
+                        instruction_data: encode_base58(&data),
+                        slot: 200,
+                        program_account_id: program_id.to_string(),
+                        accounts: vec![],
+                        parsed: None,
+                    })),
+                }),
+                handler_type: HandlerType::SolInstruction as i32,
+                handler_ids: vec![],
+                chain_id: SOLANA_MAINNET.to_string(),
+            }
+        };
+
+        let mut result = TestResult::new();
+        server
+            .process_databinding(&binding(SYSTEM_PROGRAM), &mut result)
+            .await;
+        assert_eq!(result.first_gauge_value("lamports"), Some(7_000.0));
+
+        let mut result = TestResult::new();
+        server
+            .process_databinding(&binding(TOKEN_PROGRAM), &mut result)
+            .await;
+        assert!(result.gauges.is_empty());
+    }
+}
@@ -0,0 +1,219 @@
+use crate::core::BaseProcessor;
+use crate::solana::context::SolanaContext;
+use crate::solana::{
+    InstructionDecoder, SolanaInstruction, SolanaInstructionHandler, SolanaInstructionMarker,
+    SolanaPlugin, SOLANA_MAINNET,
+};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct SolanaBindOptions {
+    /// Program id
+    pub program_id: String,
+    /// Optional, if not set, then use solana mainnet
+    pub network: Option<String>,
+    /// Optional, override default processor name
+    pub name: Option<String>,
+    pub start_slot: Option<u64>,
+    pub end_slot: Option<u64>,
+    /// Also process instructions invoked through CPI
+    pub inner_instruction: bool,
+    /// Ask the driver for the node parsed form of instructions
+    pub parsed_instruction: bool,
+    /// Ask the driver for the raw instruction data
+    pub raw_data_instruction: bool,
+}
+
+impl SolanaBindOptions {
+    pub fn new(program_id: impl Into<String>) -> Self {
+        Self {
+            program_id: program_id.into(),
+            network: None,
+            name: None,
+            start_slot: None,
+            end_slot: None,
+            inner_instruction: false,
+            parsed_instruction: false,
+            raw_data_instruction: true,
+        }
+    }
+
+    pub fn with_network(mut self, network: impl Into<String>) -> Self {
+        self.network = Some(network.into());
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn from_slot(mut self, slot: u64) -> Self {
+        self.start_slot = Some(slot);
+        self
+    }
+
+    pub fn to_slot(mut self, slot: u64) -> Self {
+        self.end_slot = Some(slot);
+        self
+    }
+
+    pub fn with_inner_instructions(mut self) -> Self {
+        self.inner_instruction = true;
+        self
+    }
+
+    pub fn with_parsed_instructions(mut self) -> Self {
+        self.parsed_instruction = true;
+        self
+    }
+}
+
+/// A configurable Solana processor that can register per-instruction handlers
+pub struct ConfigurableSolanaProcessor<P: SolanaProcessor> {
+    processor: Arc<P>,
+    options: SolanaBindOptions,
+    instruction_handlers: Vec<InstructionHandler>,
+}
+
+impl<P: SolanaProcessor> ConfigurableSolanaProcessor<P> {
+    /// Create a new configurable processor
+    pub fn new(processor: P) -> Self {
+        let options = SolanaBindOptions::new(processor.program_id())
+            .with_network(processor.chain_id())
+            .with_name(processor.name());
+        Self {
+            processor: Arc::new(processor),
+            options,
+            instruction_handlers: Vec::new(),
+        }
+    }
+
+    /// Handle instructions named `T` once decoded by the processor
+    pub fn configure_instruction<T: SolanaInstructionMarker>(mut self) -> Self
+    where
+        P: SolanaInstructionHandler<T>,
+    {
+        let handler: Arc<dyn TypeErasedSolanaInstructionHandler> =
+            Arc::new((Arc::clone(&self.processor), PhantomData::<T>));
+        self.instruction_handlers.push(InstructionHandler {
+            handler,
+            instruction_name: T::instruction_name().to_string(),
+        });
+        self
+    }
+
+    /// Start processing from `slot`
+    pub fn from_slot(mut self, slot: u64) -> Self {
+        self.options = self.options.from_slot(slot);
+        self
+    }
+
+    /// Stop processing after `slot`
+    pub fn to_slot(mut self, slot: u64) -> Self {
+        self.options = self.options.to_slot(slot);
+        self
+    }
+
+    /// Also process instructions invoked through CPI
+    pub fn with_inner_instructions(mut self) -> Self {
+        self.options = self.options.with_inner_instructions();
+        self
+    }
+
+    /// Ask the driver for the node parsed form of instructions, used by the
+    /// default [`InstructionDecoder`] implementation
+    pub fn with_parsed_instructions(mut self) -> Self {
+        self.options = self.options.with_parsed_instructions();
+        self
+    }
+
+    /// Bind this configured processor to a server
+    pub fn bind<S: crate::BindableServer>(self, server: &S) {
+        let processor_impl = SolanaProcessorImpl {
+            options: self.options,
+            instruction_handlers: self.instruction_handlers,
+            processor: self.processor,
+        };
+
+        server.register_processor::<SolanaProcessorImpl, SolanaPlugin>(processor_impl);
+    }
+}
+
+/// Core trait that all Solana processors must implement
+///
+/// The processor decodes instruction data of its program through
+/// [`InstructionDecoder`], and decoded instructions are dispatched by name.
+pub trait SolanaProcessor: InstructionDecoder + Send + Sync + 'static {
+    /// Get the program id this processor handles
+    fn program_id(&self) -> &str;
+
+    /// Get the network, defaults to Solana mainnet
+    fn chain_id(&self) -> &str {
+        SOLANA_MAINNET
+    }
+
+    /// Get the processor name
+    fn name(&self) -> &str;
+
+    fn configure_instruction<T: SolanaInstructionMarker>(self) -> ConfigurableSolanaProcessor<Self>
+    where
+        Self: Sized + SolanaInstructionHandler<T>,
+    {
+        ConfigurableSolanaProcessor::new(self).configure_instruction::<T>()
+    }
+}
+
+// Type-erased handler so processors with different markers can share storage
+pub trait TypeErasedSolanaInstructionHandler: Send + Sync {
+    fn handle_instruction(
+        &self,
+        instruction: SolanaInstruction,
+        ctx: SolanaContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+}
+
+impl<H, T> TypeErasedSolanaInstructionHandler for (H, PhantomData<T>)
+where
+    H: SolanaInstructionHandler<T>,
+    T: SolanaInstructionMarker,
+{
+    fn handle_instruction(
+        &self,
+        instruction: SolanaInstruction,
+        ctx: SolanaContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(self.0.on_instruction(instruction, ctx))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct InstructionHandler {
+    pub(crate) handler: Arc<dyn TypeErasedSolanaInstructionHandler>,
+    pub(crate) instruction_name: String,
+}
+
+// Internal storage for processor data - used by the plugin system
+#[derive(Clone)]
+pub(crate) struct SolanaProcessorImpl {
+    pub(crate) options: SolanaBindOptions,
+    pub(crate) instruction_handlers: Vec<InstructionHandler>,
+    pub(crate) processor: Arc<dyn SolanaProcessor>,
+}
+
+impl BaseProcessor for SolanaProcessorImpl {
+    fn chain_id(&self) -> &str {
+        self.options.network.as_deref().unwrap_or(SOLANA_MAINNET)
+    }
+
+    fn name(&self) -> &str {
+        self.options.name.as_deref().unwrap_or("solana-processor")
+    }
+
+    fn handler_count(&self) -> usize {
+        self.instruction_handlers.len()
+    }
+}
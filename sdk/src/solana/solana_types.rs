@@ -0,0 +1,200 @@
+use crate::solana::context::SolanaContext;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Chain id of Solana mainnet
+pub const SOLANA_MAINNET: &str = "sol_mainnet";
+/// Chain id of Solana devnet
+pub const SOLANA_DEVNET: &str = "sol_devnet";
+/// Chain id of Solana testnet
+pub const SOLANA_TESTNET: &str = "sol_testnet";
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Decode a base58 string, the encoding Solana uses for instruction data and keys
+pub fn decode_base58(input: &str) -> anyhow::Result<Vec<u8>> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(input.len());
+    for c in input.bytes() {
+        let mut carry = BASE58_ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or_else(|| anyhow::anyhow!("Invalid base58 character '{}'", c as char))?
+            as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    // Each leading '1' encodes a leading zero byte
+    let leading_zeros = input.bytes().take_while(|&c| c == b'1').count();
+    bytes.extend(std::iter::repeat_n(0, leading_zeros));
+    bytes.reverse();
+    Ok(bytes)
+}
+
+/// Encode bytes as base58
+pub fn encode_base58(input: &[u8]) -> String {
+    let mut digits: Vec<u8> = Vec::with_capacity(input.len() * 2);
+    for &byte in input {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let leading_zeros = input.iter().take_while(|&&b| b == 0).count();
+    std::iter::repeat_n(b'1', leading_zeros)
+        .chain(digits.iter().rev().map(|&d| BASE58_ALPHABET[d as usize]))
+        .map(char::from)
+        .collect()
+}
+
+/// An instruction as delivered by the Sentio driver, before decoding
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RawInstruction {
+    /// Program the instruction was sent to
+    pub program_id: String,
+    /// Base58 encoded instruction data
+    pub instruction_data: String,
+    /// Account keys passed to the instruction, in order
+    pub accounts: Vec<String>,
+    /// Instruction as parsed by the RPC node, only set for programs with a
+    /// built-in parser (e.g. SPL Token) when parsed instructions are enabled
+    pub parsed: Option<Value>,
+    pub slot: u64,
+}
+
+impl RawInstruction {
+    /// Instruction data as bytes
+    pub fn data(&self) -> anyhow::Result<Vec<u8>> {
+        decode_base58(&self.instruction_data)
+    }
+}
+
+/// Result of decoding a [`RawInstruction`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DecodedInstruction {
+    /// Instruction name, matched against [`SolanaInstructionMarker::instruction_name`]
+    pub name: String,
+    /// Decoded instruction arguments
+    pub args: Value,
+}
+
+/// Decodes instruction data of a program into named instructions
+///
+/// The default implementation reads the node parsed form (`{"type": ..., "info": ...}`),
+/// which is enough for programs like SPL Token. Programs with their own encoding
+/// implement this by hand or delegate to an
+/// [`AnchorInstructionDecoder`](crate::solana::AnchorInstructionDecoder).
+pub trait InstructionDecoder: Send + Sync {
+    /// Decode an instruction, `Ok(None)` if it is not recognized
+    fn decode_instruction(&self, instruction: &RawInstruction) -> anyhow::Result<Option<DecodedInstruction>> {
+        let Some(parsed) = &instruction.parsed else {
+            return Ok(None);
+        };
+        let Some(name) = parsed.get("type").and_then(Value::as_str) else {
+            return Ok(None);
+        };
+        Ok(Some(DecodedInstruction {
+            name: name.to_string(),
+            args: parsed.get("info").cloned().unwrap_or(Value::Null),
+        }))
+    }
+}
+
+/// A decoded instruction handled by a [`SolanaInstructionHandler`]
+#[derive(Debug, Clone)]
+pub struct SolanaInstruction {
+    pub name: String,
+    pub args: Value,
+    pub program_id: String,
+    pub accounts: Vec<String>,
+    /// Base58 encoded instruction data
+    pub instruction_data: String,
+}
+
+impl SolanaInstruction {
+    /// Decode `args` into a user defined type
+    pub fn parse_args<T: DeserializeOwned>(&self) -> anyhow::Result<T> {
+        serde_json::from_value(self.args.clone())
+            .map_err(|e| anyhow::anyhow!("Failed to decode instruction {}: {}", self.name, e))
+    }
+}
+
+/// Marker for an instruction handled by a [`SolanaInstructionHandler`]
+pub trait SolanaInstructionMarker: Send + Sync + 'static {
+    /// Instruction name as produced by the processor's decoder, e.g. `transfer`
+    fn instruction_name() -> &'static str;
+}
+
+#[crate::async_trait]
+pub trait SolanaInstructionHandler<T: SolanaInstructionMarker>: Send + Sync + 'static {
+    async fn on_instruction(&self, instruction: SolanaInstruction, ctx: SolanaContext);
+}
+
+// Implementation for Arc<H> so a shared processor can be registered for several markers
+#[crate::async_trait]
+impl<H, T> SolanaInstructionHandler<T> for std::sync::Arc<H>
+where
+    H: SolanaInstructionHandler<T>,
+    T: SolanaInstructionMarker,
+{
+    async fn on_instruction(&self, instruction: SolanaInstruction, ctx: SolanaContext) {
+        self.as_ref().on_instruction(instruction, ctx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::conversions::{json_to_struct, struct_to_json};
+
+    #[test]
+    fn test_base58_round_trip() {
+        // Transfer instruction of the System program: u32 index 2, u64 lamports 1_000_000
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&1_000_000u64.to_le_bytes());
+        let encoded = encode_base58(&data);
+        assert_eq!(decode_base58(&encoded).unwrap(), data);
+
+        assert_eq!(encode_base58(&[0, 0, 1]), "112");
+        assert_eq!(decode_base58("112").unwrap(), vec![0, 0, 1]);
+        assert_eq!(
+            decode_base58("11111111111111111111111111111111").unwrap(),
+            vec![0u8; 32]
+        );
+        assert!(decode_base58("0OIl").is_err());
+    }
+
+    #[test]
+    fn test_default_decoder_uses_parsed_instruction() {
+        struct TokenDecoder;
+        impl InstructionDecoder for TokenDecoder {}
+
+        let parsed = serde_json::json!({
+            "type": "transfer",
+            "info": {"amount": "100", "source": "A", "destination": "B"}
+        });
+        let instruction = RawInstruction {
+            parsed: Some(struct_to_json(&json_to_struct(&parsed).unwrap())),
+            ..Default::default()
+        };
+        let decoded = TokenDecoder.decode_instruction(&instruction).unwrap().unwrap();
+        assert_eq!(decoded.name, "transfer");
+        assert_eq!(decoded.args["amount"], "100");
+
+        let unparsed = RawInstruction::default();
+        assert!(TokenDecoder.decode_instruction(&unparsed).unwrap().is_none());
+    }
+}
@@ -0,0 +1,60 @@
+use crate::Timestamp;
+use crate::core::{BaseContext, Context, StateCollector};
+
+/// Context passed to Starknet handlers
+#[derive(Clone)]
+pub struct StarknetContext {
+    base_context: BaseContext,
+    state_collector: Option<StateCollector>,
+    block_number: u64,
+    transaction_hash: String,
+    timestamp: Timestamp,
+}
+
+impl StarknetContext {
+    /// Create a new StarknetContext for a transaction included in block `block_number`
+    pub fn new(
+        block_number: u64,
+        transaction_hash: impl Into<String>,
+        timestamp: Timestamp,
+    ) -> Self {
+        Self {
+            base_context: BaseContext::new(),
+            state_collector: None,
+            block_number,
+            transaction_hash: transaction_hash.into(),
+            timestamp,
+        }
+    }
+
+    /// Enable state collection for this context
+    pub fn with_state_collector(mut self, state_collector: StateCollector) -> Self {
+        self.state_collector = Some(state_collector);
+        self
+    }
+
+    /// Number of the block being processed
+    pub fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    /// Hash of the transaction emitting the event
+    pub fn transaction_hash(&self) -> &str {
+        &self.transaction_hash
+    }
+
+    /// Block timestamp
+    pub fn timestamp(&self) -> &Timestamp {
+        &self.timestamp
+    }
+}
+
+impl Context for StarknetContext {
+    fn base_context(&mut self) -> &mut BaseContext {
+        &mut self.base_context
+    }
+
+    fn state_collector(&self) -> Option<&StateCollector> {
+        self.state_collector.as_ref()
+    }
+}
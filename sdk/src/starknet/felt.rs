@@ -0,0 +1,230 @@
+//! Helpers for decoding Starknet field elements
+//!
+//! Event keys and data are lists of felts encoded as hex strings. Cairo types
+//! wider than a felt span several entries, e.g. `u256` is `[low, high]` and
+//! `ByteArray` is `[len, ...words, pending_word, pending_word_len]`.
+
+use crate::BigInt;
+use anyhow::{Result, anyhow};
+use num_bigint::Sign;
+
+/// Parse a felt from its hex (`0x` prefixed) or decimal representation
+pub fn parse_felt(value: &str) -> Result<BigInt> {
+    let parsed = match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some("") => Some(BigInt::default()),
+        Some(hex) => BigInt::parse_bytes(hex.as_bytes(), 16),
+        None => BigInt::parse_bytes(value.as_bytes(), 10),
+    };
+    match parsed {
+        Some(felt) if felt.sign() != Sign::Minus && felt < felt_prime() => Ok(felt),
+        _ => Err(anyhow!("Invalid felt: {}", value)),
+    }
+}
+
+/// The Starknet field prime, 2^251 + 17 * 2^192 + 1
+pub fn felt_prime() -> BigInt {
+    (BigInt::from(1) << 251) + (BigInt::from(17) << 192) + 1
+}
+
+/// Canonical hex form of a felt: lowercase, `0x` prefixed, without leading zeros
+pub fn normalize_felt(value: &str) -> String {
+    match parse_felt(value) {
+        Ok(felt) => format!("{:#x}", felt),
+        Err(_) => value.to_lowercase(),
+    }
+}
+
+/// Hex form of a contract address, zero padded to 64 digits
+pub fn felt_to_address(value: &str) -> Result<String> {
+    Ok(format!("0x{:064x}", parse_felt(value)?))
+}
+
+pub fn felt_to_u64(value: &str) -> Result<u64> {
+    u64::try_from(parse_felt(value)?).map_err(|_| anyhow!("Felt {} overflows u64", value))
+}
+
+pub fn felt_to_u128(value: &str) -> Result<u128> {
+    u128::try_from(parse_felt(value)?).map_err(|_| anyhow!("Felt {} overflows u128", value))
+}
+
+/// Decode a Cairo signed integer, negative values are stored as `prime - |value|`
+pub fn felt_to_i128(value: &str) -> Result<i128> {
+    let felt = parse_felt(value)?;
+    let signed = if felt > felt_prime() / 2 {
+        felt - felt_prime()
+    } else {
+        felt
+    };
+    i128::try_from(signed).map_err(|_| anyhow!("Felt {} overflows i128", value))
+}
+
+pub fn felt_to_bool(value: &str) -> Result<bool> {
+    match felt_to_u64(value)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        other => Err(anyhow!("Felt {} is not a bool", other)),
+    }
+}
+
+/// Combine the `[low, high]` halves of a Cairo `u256`
+pub fn u256_from_felts(low: &str, high: &str) -> Result<BigInt> {
+    Ok((BigInt::from(felt_to_u128(high)?) << 128) + felt_to_u128(low)?)
+}
+
+/// Decode a Cairo short string, up to 31 ASCII characters packed into one felt
+pub fn felt_to_short_string(value: &str) -> Result<String> {
+    let (_, bytes) = parse_felt(value)?.to_bytes_be();
+    let bytes: Vec<u8> = bytes.into_iter().skip_while(|b| *b == 0).collect();
+    String::from_utf8(bytes).map_err(|e| anyhow!("Felt {} is not a short string: {}", value, e))
+}
+
+/// Selector of an event or function: `starknet_keccak(name)`, the keccak256 hash
+/// truncated to 250 bits
+pub fn get_selector_from_name(name: &str) -> String {
+    let hash = alloy::primitives::keccak256(name.as_bytes());
+    let selector =
+        BigInt::from_bytes_be(Sign::Plus, hash.as_slice()) & ((BigInt::from(1) << 250) - 1);
+    format!("{:#x}", selector)
+}
+
+/// Sequential reader over a list of felts, as used by Cairo serialization
+#[derive(Debug, Clone)]
+pub struct FeltReader<'a> {
+    felts: &'a [String],
+    position: usize,
+}
+
+impl<'a> FeltReader<'a> {
+    pub fn new(felts: &'a [String]) -> Self {
+        Self { felts, position: 0 }
+    }
+
+    /// Number of felts not read yet
+    pub fn remaining(&self) -> usize {
+        self.felts.len() - self.position
+    }
+
+    pub fn next_raw(&mut self) -> Result<&'a str> {
+        let felt = self
+            .felts
+            .get(self.position)
+            .ok_or_else(|| anyhow!("Unexpected end of felts at position {}", self.position))?;
+        self.position += 1;
+        Ok(felt)
+    }
+
+    pub fn next_felt(&mut self) -> Result<BigInt> {
+        parse_felt(self.next_raw()?)
+    }
+
+    pub fn next_u64(&mut self) -> Result<u64> {
+        felt_to_u64(self.next_raw()?)
+    }
+
+    pub fn next_u128(&mut self) -> Result<u128> {
+        felt_to_u128(self.next_raw()?)
+    }
+
+    pub fn next_i128(&mut self) -> Result<i128> {
+        felt_to_i128(self.next_raw()?)
+    }
+
+    pub fn next_bool(&mut self) -> Result<bool> {
+        felt_to_bool(self.next_raw()?)
+    }
+
+    pub fn next_u256(&mut self) -> Result<BigInt> {
+        let low = self.next_raw()?;
+        let high = self.next_raw()?;
+        u256_from_felts(low, high)
+    }
+
+    pub fn next_address(&mut self) -> Result<String> {
+        felt_to_address(self.next_raw()?)
+    }
+
+    pub fn next_short_string(&mut self) -> Result<String> {
+        felt_to_short_string(self.next_raw()?)
+    }
+
+    /// Read a `ByteArray`: full 31-byte words followed by a pending word and its length
+    pub fn next_byte_array(&mut self) -> Result<String> {
+        let words = self.next_u64()?;
+        let mut bytes = Vec::new();
+        for _ in 0..words {
+            let (_, word) = self.next_felt()?.to_bytes_be();
+            bytes.extend(std::iter::repeat_n(0, 31usize.saturating_sub(word.len())));
+            bytes.extend(word);
+        }
+        let (_, pending) = self.next_felt()?.to_bytes_be();
+        let pending_len = self.next_u64()? as usize;
+        bytes.extend(std::iter::repeat_n(
+            0,
+            pending_len.saturating_sub(pending.len()),
+        ));
+        bytes.extend(pending.into_iter().rev().take(pending_len).rev());
+        String::from_utf8(bytes).map_err(|e| anyhow!("Invalid ByteArray: {}", e))
+    }
+
+    /// Read a length prefixed array, decoding each element with `read`
+    pub fn next_array<T>(
+        &mut self,
+        mut read: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let len = self.next_u64()?;
+        (0..len).map(|_| read(self)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_felt_conversions() {
+        assert_eq!(felt_to_u64("0x2a").unwrap(), 42);
+        assert_eq!(felt_to_u128("42").unwrap(), 42);
+        assert_eq!(normalize_felt("0x00AbC"), "0xabc");
+        assert_eq!(
+            felt_to_address("0x49d").unwrap(),
+            "0x000000000000000000000000000000000000000000000000000000000000049d"
+        );
+        let minus_one = format!("{:#x}", felt_prime() - 1);
+        assert_eq!(felt_to_i128(&minus_one).unwrap(), -1);
+        assert_eq!(felt_to_short_string("0x455448").unwrap(), "ETH");
+        assert!(felt_to_bool("0x2").is_err());
+        assert!(parse_felt(&format!("{:#x}", felt_prime())).is_err());
+        assert_eq!(
+            u256_from_felts("0x1", "0x1").unwrap(),
+            (BigInt::from(1) << 128) + 1
+        );
+    }
+
+    #[test]
+    fn test_selector() {
+        assert_eq!(
+            get_selector_from_name("Transfer"),
+            "0x99cd8bde557814842a3121e8ddfd433a539b8c9f14bf31ebf108d12e6196e9"
+        );
+    }
+
+    #[test]
+    fn test_reader() {
+        let felts: Vec<String> = [
+            "0x3e8", "0x0", "0x1", "0x2", "0x7", "0x8", "0x0", "0x6869", "0x2",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let mut reader = FeltReader::new(&felts);
+        assert_eq!(reader.next_u256().unwrap(), BigInt::from(1000));
+        assert!(reader.next_bool().unwrap());
+        assert_eq!(reader.next_array(|r| r.next_u64()).unwrap(), vec![7, 8]);
+        assert_eq!(reader.next_byte_array().unwrap(), "hi");
+        assert_eq!(reader.remaining(), 0);
+        assert!(reader.next_raw().is_err());
+    }
+}
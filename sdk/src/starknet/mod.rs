@@ -0,0 +1,11 @@
+pub mod context;
+pub mod felt;
+pub mod starknet_plugin;
+pub mod starknet_processor;
+mod starknet_types;
+
+pub use starknet_types::*;
+
+pub use felt::FeltReader;
+pub use starknet_plugin::StarknetPlugin;
+pub use starknet_processor::{StarknetBindOptions, StarknetHandlerOptions, StarknetProcessor};
@@ -0,0 +1,297 @@
+use crate::core::conversions::struct_to_json;
+use crate::core::plugin::FullPlugin;
+use crate::core::{
+    AsyncPluginProcessor, BaseProcessor, ContractConfigBuilder, HandlerRegister, MetaData, Plugin,
+    PluginRegister, binding_value, dispatch_handler,
+};
+use crate::processor::{HandlerType, data};
+use crate::starknet::StarknetEvent;
+use crate::starknet::context::StarknetContext;
+use crate::starknet::felt::normalize_felt;
+use crate::starknet::starknet_processor::StarknetProcessorImpl;
+use crate::{
+    ConfigureHandlersResponse, StarknetEventFilter, StarknetEventHandlerConfig, Timestamp,
+};
+use tracing::debug;
+
+#[derive(Default)]
+pub struct StarknetPlugin {
+    handler_register: HandlerRegister<HandlerType>,
+    processors: Vec<Box<StarknetProcessorImpl>>,
+}
+
+impl Plugin for StarknetPlugin {
+    fn name(&self) -> &str {
+        "starknet-plugin"
+    }
+
+    fn handler_types(&self) -> &'static [HandlerType] {
+        &[HandlerType::StarknetEvent]
+    }
+
+    fn processor_count(&self) -> usize {
+        self.processors.len()
+    }
+
+    fn chain_ids(&self) -> Vec<String> {
+        self.processors
+            .iter()
+            .map(|p| p.chain_id().to_string())
+            .collect()
+    }
+
+    fn configure(&mut self, config: &mut ConfigureHandlersResponse) {
+        debug!("Configuring StarknetPlugin handlers for all chains");
+
+        for (processor_idx, processor) in self.processors.iter().enumerate() {
+            let chain_id = processor.chain_id();
+
+            let mut builder = ContractConfigBuilder::new(
+                chain_id,
+                processor.options.address.clone(),
+                processor.name(),
+            )
+            .start_block(processor.options.start_block.unwrap_or_default())
+            .end_block(processor.options.end_block.unwrap_or_default());
+
+            for (handler_idx, handler) in processor.event_handlers.iter().enumerate() {
+                let handler_id = self.handler_register.register(
+                    chain_id,
+                    HandlerType::StarknetEvent,
+                    processor_idx,
+                    handler_idx,
+                );
+                builder
+                    .config_mut()
+                    .starknet_event_configs
+                    .push(StarknetEventHandlerConfig {
+                        filters: vec![StarknetEventFilter {
+                            address: processor.options.address.clone(),
+                            keys: vec![handler.selector.clone()],
+                        }],
+                        handler_id,
+                        handler_name: handler.options.name.clone().unwrap_or_default(),
+                    });
+            }
+
+            config.contract_configs.push(builder.build());
+        }
+    }
+}
+
+impl StarknetPlugin {
+    async fn process_event(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        let starknet_event = match binding_value(data)? {
+            data::Value::StarknetEvents(starknet_event) => starknet_event,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Expected Starknet event data but got different type"
+                ));
+            }
+        };
+        let event: StarknetEvent = match &starknet_event.result {
+            Some(result) => serde_json::from_value(struct_to_json(result))
+                .map_err(|e| anyhow::anyhow!("Failed to parse Starknet event: {}", e))?,
+            None => return Err(anyhow::anyhow!("Starknet event binding has no result")),
+        };
+        let timestamp = Timestamp::from(starknet_event.timestamp.unwrap_or_default());
+        let block_number = event.block_number();
+
+        let mut result = crate::ProcessResult::default();
+        for &handler_id in &data.handler_ids {
+            let (processor, handler_idx) = self
+                .handler_register
+                .find_processor(&self.processors, &data.chain_id, handler_id)?;
+            let handler = processor.event_handlers.get(handler_idx).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Event handler index {} not found in processor {}",
+                    handler_idx,
+                    processor.name()
+                )
+            })?;
+
+            let metadata = MetaData {
+                address: processor.options.address.clone(),
+                contract_name: processor.name().to_string(),
+                chain_id: data.chain_id.clone(),
+                block_number,
+                timestamp: timestamp.clone(),
+                transaction_hash: event.transaction_hash.clone(),
+                transaction_index: 0,
+                log_index: event.event_index as i32,
+                base_labels: Default::default(),
+            };
+            let handler_result = dispatch_handler(
+                metadata,
+                |collector| {
+                    StarknetContext::new(
+                        block_number,
+                        event.transaction_hash.clone(),
+                        timestamp.clone(),
+                    )
+                    .with_state_collector(collector)
+                },
+                |ctx| handler.handler.handle_event(event.clone(), ctx),
+            )
+            .await;
+            result = result.merge(handler_result);
+        }
+
+        Ok(result)
+    }
+}
+
+/// Whether `event` matches an event filter: same contract, and a selector listed in `keys`
+pub(crate) fn matches_event_filter(filter: &StarknetEventFilter, event: &StarknetEvent) -> bool {
+    normalize_felt(&filter.address) == normalize_felt(&event.from_address)
+        && (filter.keys.is_empty()
+            || event.selector().is_some_and(|selector| {
+                filter
+                    .keys
+                    .iter()
+                    .any(|key| normalize_felt(key) == selector)
+            }))
+}
+
+#[tonic::async_trait]
+impl AsyncPluginProcessor for StarknetPlugin {
+    async fn process_binding(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        debug!(
+            "StarknetPlugin processing binding for chain_id: {}, handler_ids: {:?}",
+            data.chain_id, data.handler_ids
+        );
+
+        let handler_type = HandlerType::try_from(data.handler_type)?;
+        match handler_type {
+            HandlerType::StarknetEvent => self.process_event(data).await,
+            _ => Err(anyhow::anyhow!(
+                "Unsupported handler type: {:?}",
+                handler_type
+            )),
+        }
+    }
+}
+
+impl FullPlugin for StarknetPlugin {}
+
+impl PluginRegister<StarknetProcessorImpl> for StarknetPlugin {
+    fn register_processor(
+        &mut self,
+        processor: StarknetProcessorImpl,
+    ) -> &mut StarknetProcessorImpl {
+        debug!(
+            "Registering processor: {} (chain_id: {})",
+            processor.name(),
+            processor.chain_id()
+        );
+
+        self.processors.push(Box::new(processor));
+        self.processors.last_mut().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Context;
+    use crate::starknet::{StarknetEventHandler, StarknetEventMarker, StarknetProcessor};
+    use crate::testing::TestProcessorServer;
+
+    const ETH_TOKEN: &str = "0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7";
+
+    struct TokenProcessor;
+
+    impl StarknetProcessor for TokenProcessor {
+        fn address(&self) -> &str {
+            ETH_TOKEN
+        }
+
+        fn name(&self) -> &str {
+            "eth_token"
+        }
+    }
+
+    struct Transfer;
+
+    impl StarknetEventMarker for Transfer {
+        fn event_name() -> &'static str {
+            "Transfer"
+        }
+    }
+
+    struct Approval;
+
+    impl StarknetEventMarker for Approval {
+        fn event_name() -> &'static str {
+            "Approval"
+        }
+    }
+
+    #[crate::async_trait]
+    impl StarknetEventHandler<Transfer> for TokenProcessor {
+        async fn on_event(&self, event: StarknetEvent, mut ctx: StarknetContext) {
+            let mut data = event.data_reader();
+            let _from = data.next_address().unwrap();
+            let _to = data.next_address().unwrap();
+            let amount = data.next_u256().unwrap();
+            ctx.base_context()
+                .counter("transferred")
+                .add(amount.to_string().parse::<f64>().unwrap(), None)
+                .await
+                .ok();
+        }
+    }
+
+    #[crate::async_trait]
+    impl StarknetEventHandler<Approval> for TokenProcessor {
+        async fn on_event(&self, _event: StarknetEvent, mut ctx: StarknetContext) {
+            ctx.base_context()
+                .counter("approvals")
+                .add(1.0, None)
+                .await
+                .ok();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_starknet_plugin_configure_and_dispatch() {
+        let mut server = TestProcessorServer::new();
+        TokenProcessor
+            .configure_event::<Transfer>(None)
+            .configure_event::<Approval>(None)
+            .from_block(600_000)
+            .bind(&server);
+        server.start().await.unwrap();
+
+        let config = server.get_config().await;
+        assert_eq!(config.contract_configs.len(), 1);
+        let contract_config = &config.contract_configs[0];
+        assert_eq!(contract_config.start_block, 600_000);
+        assert_eq!(contract_config.starknet_event_configs.len(), 2);
+        assert_eq!(
+            contract_config.starknet_event_configs[0].filters[0].keys,
+            vec![Transfer::selector()]
+        );
+
+        let facet = server.starknet();
+        let transfer = StarknetEvent::new(ETH_TOKEN, "Transfer")
+            .with_data(["0x1", "0x2", "0x3e8", "0x0"])
+            .with_block_number(650_000)
+            .with_transaction_hash("0x7a");
+        let other_contract =
+            StarknetEvent::new("0x1234", "Transfer").with_data(["0x1", "0x2", "0x5", "0x0"]);
+        let unknown = StarknetEvent::new(ETH_TOKEN, "Unknown");
+        let result = facet
+            .test_events(vec![transfer, other_contract, unknown], None)
+            .await;
+        assert_eq!(result.counters.len(), 1);
+        assert_eq!(result.first_counter_value("transferred"), Some(1000.0));
+        assert_eq!(result.counters[0].metadata.block_number, Some(650_000));
+    }
+}
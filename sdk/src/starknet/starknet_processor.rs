@@ -0,0 +1,204 @@
+use crate::core::BaseProcessor;
+use crate::starknet::context::StarknetContext;
+use crate::starknet::{
+    STARKNET_MAINNET, StarknetEvent, StarknetEventHandler, StarknetEventMarker, StarknetPlugin,
+};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct StarknetBindOptions {
+    /// Contract address
+    pub address: String,
+    /// Optional, if not set, then use starknet mainnet
+    pub network: Option<String>,
+    /// Optional, override default processor name
+    pub name: Option<String>,
+    pub start_block: Option<u64>,
+    pub end_block: Option<u64>,
+}
+
+impl StarknetBindOptions {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            network: None,
+            name: None,
+            start_block: None,
+            end_block: None,
+        }
+    }
+
+    pub fn with_network(mut self, network: impl Into<String>) -> Self {
+        self.network = Some(network.into());
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn from_block(mut self, block: u64) -> Self {
+        self.start_block = Some(block);
+        self
+    }
+
+    pub fn to_block(mut self, block: u64) -> Self {
+        self.end_block = Some(block);
+        self
+    }
+}
+
+/// Options for a single Starknet handler
+#[derive(Clone, Default)]
+pub struct StarknetHandlerOptions {
+    pub name: Option<String>,
+}
+
+impl StarknetHandlerOptions {
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+}
+
+/// A configurable Starknet processor that can register event handlers
+pub struct ConfigurableStarknetProcessor<P: StarknetProcessor> {
+    processor: Arc<P>,
+    options: StarknetBindOptions,
+    event_handlers: Vec<EventHandler>,
+}
+
+impl<P: StarknetProcessor> ConfigurableStarknetProcessor<P> {
+    /// Create a new configurable processor
+    pub fn new(processor: P) -> Self {
+        let options = StarknetBindOptions::new(processor.address())
+            .with_network(processor.chain_id())
+            .with_name(processor.name());
+        Self {
+            processor: Arc::new(processor),
+            options,
+            event_handlers: Vec::new(),
+        }
+    }
+
+    /// Handle events `T` emitted by the processor's contract
+    pub fn configure_event<T: StarknetEventMarker>(
+        mut self,
+        options: Option<StarknetHandlerOptions>,
+    ) -> Self
+    where
+        P: StarknetEventHandler<T>,
+    {
+        let handler: Arc<dyn TypeErasedStarknetEventHandler> =
+            Arc::new((Arc::clone(&self.processor), PhantomData::<T>));
+        self.event_handlers.push(EventHandler {
+            handler,
+            selector: T::selector(),
+            options: options.unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Start processing from `block`
+    pub fn from_block(mut self, block: u64) -> Self {
+        self.options = self.options.from_block(block);
+        self
+    }
+
+    /// Stop processing after `block`
+    pub fn to_block(mut self, block: u64) -> Self {
+        self.options = self.options.to_block(block);
+        self
+    }
+
+    /// Bind this configured processor to a server
+    pub fn bind<S: crate::BindableServer>(self, server: &S) {
+        let processor_impl = StarknetProcessorImpl {
+            options: self.options,
+            event_handlers: self.event_handlers,
+            _processor: self.processor,
+        };
+
+        server.register_processor::<StarknetProcessorImpl, StarknetPlugin>(processor_impl);
+    }
+}
+
+/// Core trait that all Starknet processors must implement
+pub trait StarknetProcessor: Send + Sync + 'static {
+    /// Get the contract address this processor handles
+    fn address(&self) -> &str;
+
+    /// Get the network, defaults to Starknet mainnet
+    fn chain_id(&self) -> &str {
+        STARKNET_MAINNET
+    }
+
+    /// Get the processor name
+    fn name(&self) -> &str;
+
+    fn configure_event<T: StarknetEventMarker>(
+        self,
+        options: Option<StarknetHandlerOptions>,
+    ) -> ConfigurableStarknetProcessor<Self>
+    where
+        Self: Sized + StarknetEventHandler<T>,
+    {
+        ConfigurableStarknetProcessor::new(self).configure_event::<T>(options)
+    }
+}
+
+// Type-erased handler so processors with different markers can share storage
+pub trait TypeErasedStarknetEventHandler: Send + Sync {
+    fn handle_event(
+        &self,
+        event: StarknetEvent,
+        ctx: StarknetContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+}
+
+impl<H, T> TypeErasedStarknetEventHandler for (H, PhantomData<T>)
+where
+    H: StarknetEventHandler<T>,
+    T: StarknetEventMarker,
+{
+    fn handle_event(
+        &self,
+        event: StarknetEvent,
+        ctx: StarknetContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(self.0.on_event(event, ctx))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct EventHandler {
+    pub(crate) handler: Arc<dyn TypeErasedStarknetEventHandler>,
+    pub(crate) selector: String,
+    pub(crate) options: StarknetHandlerOptions,
+}
+
+// Internal storage for processor data - used by the plugin system
+#[derive(Clone)]
+pub(crate) struct StarknetProcessorImpl {
+    pub(crate) options: StarknetBindOptions,
+    pub(crate) event_handlers: Vec<EventHandler>,
+    pub(crate) _processor: Arc<dyn StarknetProcessor>,
+}
+
+impl BaseProcessor for StarknetProcessorImpl {
+    fn chain_id(&self) -> &str {
+        self.options.network.as_deref().unwrap_or(STARKNET_MAINNET)
+    }
+
+    fn name(&self) -> &str {
+        self.options.name.as_deref().unwrap_or("starknet-processor")
+    }
+
+    fn handler_count(&self) -> usize {
+        self.event_handlers.len()
+    }
+}
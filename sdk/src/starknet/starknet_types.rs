@@ -0,0 +1,131 @@
+use crate::starknet::context::StarknetContext;
+use crate::starknet::felt::{FeltReader, get_selector_from_name, normalize_felt};
+use serde::{Deserialize, Serialize};
+
+/// Chain id of Starknet mainnet
+pub const STARKNET_MAINNET: &str = "starknet_mainnet";
+/// Chain id of Starknet sepolia
+pub const STARKNET_SEPOLIA: &str = "starknet_sepolia";
+
+/// An event emitted by a Starknet contract, in the format of the Starknet RPC
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StarknetEvent {
+    pub from_address: String,
+    /// Event selector followed by the `#[key]` members of the event
+    pub keys: Vec<String>,
+    /// Remaining members of the event
+    pub data: Vec<String>,
+    pub block_hash: Option<String>,
+    pub block_number: Option<u64>,
+    pub transaction_hash: String,
+    /// Index of the event within its transaction
+    pub event_index: u32,
+}
+
+impl StarknetEvent {
+    /// Event named `event_name` emitted by `from_address`
+    pub fn new(from_address: impl Into<String>, event_name: &str) -> Self {
+        Self {
+            from_address: from_address.into(),
+            keys: vec![get_selector_from_name(event_name)],
+            ..Default::default()
+        }
+    }
+
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.keys.push(key.into());
+        self
+    }
+
+    pub fn with_data<S: Into<String>>(mut self, data: impl IntoIterator<Item = S>) -> Self {
+        self.data.extend(data.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn with_block_number(mut self, block_number: u64) -> Self {
+        self.block_number = Some(block_number);
+        self
+    }
+
+    pub fn with_transaction_hash(mut self, transaction_hash: impl Into<String>) -> Self {
+        self.transaction_hash = transaction_hash.into();
+        self
+    }
+
+    /// Selector of the event, normalized
+    pub fn selector(&self) -> Option<String> {
+        self.keys.first().map(|key| normalize_felt(key))
+    }
+
+    pub fn block_number(&self) -> u64 {
+        self.block_number.unwrap_or_default()
+    }
+
+    /// Reader over the `#[key]` members, skipping the selector
+    pub fn keys_reader(&self) -> FeltReader<'_> {
+        FeltReader::new(self.keys.get(1..).unwrap_or_default())
+    }
+
+    /// Reader over the data members
+    pub fn data_reader(&self) -> FeltReader<'_> {
+        FeltReader::new(&self.data)
+    }
+}
+
+/// Marker for a Cairo event handled by a [`StarknetEventHandler`]
+pub trait StarknetEventMarker: Send + Sync + 'static {
+    /// Event name, e.g. `Transfer`
+    fn event_name() -> &'static str;
+
+    /// Selector matched against the first event key
+    fn selector() -> String {
+        get_selector_from_name(Self::event_name())
+    }
+}
+
+#[crate::async_trait]
+pub trait StarknetEventHandler<T: StarknetEventMarker>: Send + Sync + 'static {
+    async fn on_event(&self, event: StarknetEvent, ctx: StarknetContext);
+}
+
+// Implementation for Arc<H> so a shared processor can be registered for several markers
+#[crate::async_trait]
+impl<H, T> StarknetEventHandler<T> for std::sync::Arc<H>
+where
+    H: StarknetEventHandler<T>,
+    T: StarknetEventMarker,
+{
+    async fn on_event(&self, event: StarknetEvent, ctx: StarknetContext) {
+        self.as_ref().on_event(event, ctx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_event() {
+        let raw = r#"{
+            "from_address": "0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7",
+            "keys": ["0x0099cd8bde557814842a3121e8ddfd433a539b8c9f14bf31ebf108d12e6196e9"],
+            "data": ["0x1", "0x2", "0x3e8", "0x0"],
+            "block_hash": "0xb10c",
+            "block_number": 650000,
+            "transaction_hash": "0x7a"
+        }"#;
+        let event: StarknetEvent = serde_json::from_str(raw).unwrap();
+        assert_eq!(event.selector(), Some(get_selector_from_name("Transfer")));
+        assert_eq!(event.block_number(), 650000);
+
+        let mut data = event.data_reader();
+        assert_eq!(
+            data.next_address().unwrap(),
+            "0x0000000000000000000000000000000000000000000000000000000000000001"
+        );
+        data.next_raw().unwrap();
+        assert_eq!(data.next_u256().unwrap(), crate::BigInt::from(1000));
+        assert_eq!(event.keys_reader().remaining(), 0);
+    }
+}
@@ -0,0 +1,56 @@
+use crate::core::{Context, BaseContext, StateCollector};
+use crate::Timestamp;
+
+/// Context passed to Sui handlers
+#[derive(Clone)]
+pub struct SuiContext {
+    base_context: BaseContext,
+    state_collector: Option<StateCollector>,
+    checkpoint: u64,
+    transaction_digest: String,
+    timestamp: Timestamp,
+}
+
+impl SuiContext {
+    /// Create a new SuiContext for a transaction included in `checkpoint`
+    pub fn new(checkpoint: u64, transaction_digest: impl Into<String>, timestamp: Timestamp) -> Self {
+        Self {
+            base_context: BaseContext::new(),
+            state_collector: None,
+            checkpoint,
+            transaction_digest: transaction_digest.into(),
+            timestamp,
+        }
+    }
+
+    /// Enable state collection for this context
+    pub fn with_state_collector(mut self, state_collector: StateCollector) -> Self {
+        self.state_collector = Some(state_collector);
+        self
+    }
+
+    /// Checkpoint sequence number
+    pub fn checkpoint(&self) -> u64 {
+        self.checkpoint
+    }
+
+    /// Digest of the transaction being processed
+    pub fn transaction_digest(&self) -> &str {
+        &self.transaction_digest
+    }
+
+    /// Checkpoint timestamp
+    pub fn timestamp(&self) -> &Timestamp {
+        &self.timestamp
+    }
+}
+
+impl Context for SuiContext {
+    fn base_context(&mut self) -> &mut BaseContext {
+        &mut self.base_context
+    }
+
+    fn state_collector(&self) -> Option<&StateCollector> {
+        self.state_collector.as_ref()
+    }
+}
@@ -0,0 +1,9 @@
+pub mod sui_processor;
+pub mod sui_plugin;
+pub mod context;
+mod sui_types;
+
+pub use sui_types::*;
+
+pub use sui_processor::{SuiProcessor, SuiHandlerOptions, SuiBindOptions};
+pub use sui_plugin::SuiPlugin;
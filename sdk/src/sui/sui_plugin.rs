@@ -0,0 +1,587 @@
+use crate::core::plugin::FullPlugin;
+use crate::core::{
+    AsyncPluginProcessor, BaseProcessor, ContractConfigBuilder, HandlerRegister, MetaData, Plugin,
+    PluginRegister, binding_value, dispatch_handler,
+};
+use crate::processor::{HandlerType, data};
+use crate::sui::context::SuiContext;
+use crate::sui::sui_processor::SuiProcessorImpl;
+use crate::sui::{
+    SuiCall, SuiEvent, SuiObjectChange, SuiTransaction, normalize_sui_address, split_move_type,
+};
+use crate::{
+    ConfigureHandlersResponse, MoveCallFilter, MoveCallHandlerConfig, MoveEventFilter,
+    MoveEventHandlerConfig, MoveResourceChangeConfig, Timestamp,
+};
+use tracing::debug;
+
+#[derive(Default)]
+pub struct SuiPlugin {
+    handler_register: HandlerRegister<HandlerType>,
+    processors: Vec<Box<SuiProcessorImpl>>,
+}
+
+impl Plugin for SuiPlugin {
+    fn name(&self) -> &str {
+        "sui-plugin"
+    }
+
+    fn handler_types(&self) -> &'static [HandlerType] {
+        &[
+            HandlerType::SuiEvent,
+            HandlerType::SuiCall,
+            HandlerType::SuiObjectChange,
+        ]
+    }
+
+    fn processor_count(&self) -> usize {
+        self.processors.len()
+    }
+
+    fn chain_ids(&self) -> Vec<String> {
+        self.processors
+            .iter()
+            .map(|p| p.chain_id().to_string())
+            .collect()
+    }
+
+    fn configure(&mut self, config: &mut ConfigureHandlersResponse) {
+        debug!("Configuring SuiPlugin handlers for all chains");
+
+        for (processor_idx, processor) in self.processors.iter().enumerate() {
+            let chain_id = processor.chain_id();
+            let address = &processor.options.address;
+
+            let mut builder =
+                ContractConfigBuilder::new(chain_id, address.clone(), processor.name());
+            if let Some(checkpoint) = processor.options.start_checkpoint {
+                builder = builder.start_block(checkpoint);
+            }
+            if let Some(checkpoint) = processor.options.end_checkpoint {
+                builder = builder.end_block(checkpoint);
+            }
+
+            for (handler_idx, handler) in processor.event_handlers.iter().enumerate() {
+                let handler_id = self.handler_register.register(
+                    chain_id,
+                    HandlerType::SuiEvent,
+                    processor_idx,
+                    handler_idx,
+                );
+                builder
+                    .config_mut()
+                    .move_event_configs
+                    .push(MoveEventHandlerConfig {
+                        filters: vec![MoveEventFilter {
+                            r#type: handler.event_type.clone(),
+                            account: address.clone(),
+                            event_account: String::new(),
+                        }],
+                        handler_id,
+                        fetch_config: handler.options.fetch_config.clone(),
+                        handler_name: handler.options.name.clone().unwrap_or_default(),
+                    });
+            }
+
+            for (handler_idx, handler) in processor.call_handlers.iter().enumerate() {
+                let handler_id = self.handler_register.register(
+                    chain_id,
+                    HandlerType::SuiCall,
+                    processor_idx,
+                    handler_idx,
+                );
+                builder
+                    .config_mut()
+                    .move_call_configs
+                    .push(MoveCallHandlerConfig {
+                        filters: vec![MoveCallFilter {
+                            function: format!(
+                                "{}::{}",
+                                normalize_sui_address(address),
+                                handler.function
+                            ),
+                            ..Default::default()
+                        }],
+                        handler_id,
+                        fetch_config: handler.options.fetch_config.clone(),
+                        handler_name: handler.options.name.clone().unwrap_or_default(),
+                    });
+            }
+
+            for (handler_idx, handler) in processor.object_change_handlers.iter().enumerate() {
+                let handler_id = self.handler_register.register(
+                    chain_id,
+                    HandlerType::SuiObjectChange,
+                    processor_idx,
+                    handler_idx,
+                );
+                builder
+                    .config_mut()
+                    .move_resource_change_configs
+                    .push(MoveResourceChangeConfig {
+                        r#type: handler.object_type.clone(),
+                        include_deleted: handler.options.include_deleted,
+                        handler_id,
+                        handler_name: handler.options.name.clone().unwrap_or_default(),
+                    });
+            }
+
+            config.contract_configs.push(builder.build());
+        }
+    }
+}
+
+impl SuiPlugin {
+    fn metadata(
+        processor: &SuiProcessorImpl,
+        chain_id: &str,
+        checkpoint: u64,
+        timestamp: &Timestamp,
+        transaction_digest: &str,
+        event_seq: i32,
+    ) -> MetaData {
+        MetaData {
+            address: processor.options.address.clone(),
+            contract_name: processor.name().to_string(),
+            chain_id: chain_id.to_string(),
+            block_number: checkpoint,
+            timestamp: timestamp.clone(),
+            transaction_hash: transaction_digest.to_string(),
+            transaction_index: 0,
+            log_index: event_seq,
+            base_labels: Default::default(),
+        }
+    }
+
+    async fn process_event(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        let sui_event = match binding_value(data)? {
+            data::Value::SuiEvent(sui_event) => sui_event,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Expected Sui event data but got different type"
+                ));
+            }
+        };
+        let event: SuiEvent = serde_json::from_str(&sui_event.raw_event)
+            .map_err(|e| anyhow::anyhow!("Failed to parse Sui event: {}", e))?;
+        let transaction = parse_transaction(&sui_event.raw_transaction)?;
+        let digest = if event.id.tx_digest.is_empty() {
+            transaction.digest.clone()
+        } else {
+            event.id.tx_digest.clone()
+        };
+        let timestamp = Timestamp::from(sui_event.timestamp.unwrap_or_default());
+
+        let mut result = crate::ProcessResult::default();
+        for &handler_id in &data.handler_ids {
+            let (processor, handler_idx) = self
+                .handler_register
+                .find_processor(&self.processors, &data.chain_id, handler_id)?;
+            let handler = processor.event_handlers.get(handler_idx).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Event handler index {} not found in processor {}",
+                    handler_idx,
+                    processor.name()
+                )
+            })?;
+
+            let metadata = Self::metadata(
+                processor,
+                &data.chain_id,
+                sui_event.slot,
+                &timestamp,
+                &digest,
+                event.event_seq(),
+            );
+            let handler_result = dispatch_handler(
+                metadata,
+                |collector| {
+                    SuiContext::new(sui_event.slot, digest.clone(), timestamp.clone())
+                        .with_state_collector(collector)
+                },
+                |ctx| handler.handler.handle_event(event.clone(), ctx),
+            )
+            .await;
+            result = result.merge(handler_result);
+        }
+
+        Ok(result)
+    }
+
+    async fn process_call(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        let sui_call = match binding_value(data)? {
+            data::Value::SuiCall(sui_call) => sui_call,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Expected Sui call data but got different type"
+                ));
+            }
+        };
+        let transaction = parse_transaction(&sui_call.raw_transaction)?;
+        let timestamp = Timestamp::from(sui_call.timestamp.unwrap_or_default());
+        let move_calls = transaction.move_calls();
+
+        let mut result = crate::ProcessResult::default();
+        for &handler_id in &data.handler_ids {
+            let (processor, handler_idx) = self
+                .handler_register
+                .find_processor(&self.processors, &data.chain_id, handler_id)?;
+            let handler = processor.call_handlers.get(handler_idx).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Call handler index {} not found in processor {}",
+                    handler_idx,
+                    processor.name()
+                )
+            })?;
+
+            let package = normalize_sui_address(&processor.options.address);
+            let matched = move_calls.iter().filter(|call| {
+                normalize_sui_address(&call.package) == package
+                    && call.function_name() == handler.function
+            });
+            for call in matched {
+                let metadata = Self::metadata(
+                    processor,
+                    &data.chain_id,
+                    sui_call.slot,
+                    &timestamp,
+                    &transaction.digest,
+                    0,
+                );
+                let call = SuiCall {
+                    call: call.clone(),
+                    transaction: transaction.clone(),
+                };
+                let handler_result = dispatch_handler(
+                    metadata,
+                    |collector| {
+                        SuiContext::new(
+                            sui_call.slot,
+                            transaction.digest.clone(),
+                            timestamp.clone(),
+                        )
+                        .with_state_collector(collector)
+                    },
+                    |ctx| handler.handler.handle_call(call, ctx),
+                )
+                .await;
+                result = result.merge(handler_result);
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn process_object_change(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        let object_change = match binding_value(data)? {
+            data::Value::SuiObjectChange(object_change) => object_change,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Expected Sui object change data but got different type"
+                ));
+            }
+        };
+        let changes = object_change
+            .raw_changes
+            .iter()
+            .map(|raw| serde_json::from_str::<SuiObjectChange>(raw))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("Failed to parse Sui object change: {}", e))?;
+        let timestamp = Timestamp::from(object_change.timestamp.unwrap_or_default());
+
+        let mut result = crate::ProcessResult::default();
+        for &handler_id in &data.handler_ids {
+            let (processor, handler_idx) = self
+                .handler_register
+                .find_processor(&self.processors, &data.chain_id, handler_id)?;
+            let handler = processor
+                .object_change_handlers
+                .get(handler_idx)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Object change handler index {} not found in processor {}",
+                        handler_idx,
+                        processor.name()
+                    )
+                })?;
+
+            let matched: Vec<SuiObjectChange> = changes
+                .iter()
+                .filter(|change| handler.options.include_deleted || change.change_type != "deleted")
+                .filter(|change| matches_object_type(&change.object_type, &handler.object_type))
+                .cloned()
+                .collect();
+            if matched.is_empty() {
+                continue;
+            }
+
+            let metadata = Self::metadata(
+                processor,
+                &data.chain_id,
+                object_change.slot,
+                &timestamp,
+                &object_change.tx_digest,
+                0,
+            );
+            let handler_result = dispatch_handler(
+                metadata,
+                |collector| {
+                    SuiContext::new(
+                        object_change.slot,
+                        object_change.tx_digest.clone(),
+                        timestamp.clone(),
+                    )
+                    .with_state_collector(collector)
+                },
+                |ctx| handler.handler.handle_object_change(matched, ctx),
+            )
+            .await;
+            result = result.merge(handler_result);
+        }
+
+        Ok(result)
+    }
+}
+
+fn parse_transaction(raw_transaction: &str) -> anyhow::Result<SuiTransaction> {
+    if raw_transaction.is_empty() {
+        return Ok(SuiTransaction::default());
+    }
+    serde_json::from_str(raw_transaction)
+        .map_err(|e| anyhow::anyhow!("Failed to parse Sui transaction: {}", e))
+}
+
+/// Whether `object_type` matches a handler's type filter. A filter without type
+/// arguments matches every instantiation of the type.
+pub(crate) fn matches_object_type(object_type: &str, filter: &str) -> bool {
+    let (Some((address, name)), Some((filter_address, filter_name))) =
+        (split_move_type(object_type), split_move_type(filter))
+    else {
+        return object_type == filter;
+    };
+    if address != filter_address || name != filter_name {
+        return false;
+    }
+    match filter.split_once('<') {
+        Some((_, filter_args)) => object_type
+            .split_once('<')
+            .is_some_and(|(_, args)| args.replace(' ', "") == filter_args.replace(' ', "")),
+        None => true,
+    }
+}
+
+#[tonic::async_trait]
+impl AsyncPluginProcessor for SuiPlugin {
+    async fn process_binding(
+        &self,
+        data: &crate::DataBinding,
+    ) -> anyhow::Result<crate::ProcessResult> {
+        debug!(
+            "SuiPlugin processing binding for chain_id: {}, handler_ids: {:?}",
+            data.chain_id, data.handler_ids
+        );
+
+        let handler_type = HandlerType::try_from(data.handler_type)?;
+        match handler_type {
+            HandlerType::SuiEvent => self.process_event(data).await,
+            HandlerType::SuiCall => self.process_call(data).await,
+            HandlerType::SuiObjectChange => self.process_object_change(data).await,
+            _ => Err(anyhow::anyhow!(
+                "Unsupported handler type: {:?}",
+                handler_type
+            )),
+        }
+    }
+}
+
+impl FullPlugin for SuiPlugin {}
+
+impl PluginRegister<SuiProcessorImpl> for SuiPlugin {
+    fn register_processor(&mut self, processor: SuiProcessorImpl) -> &mut SuiProcessorImpl {
+        debug!(
+            "Registering processor: {} (chain_id: {})",
+            processor.name(),
+            processor.chain_id()
+        );
+
+        self.processors.push(Box::new(processor));
+        self.processors.last_mut().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Context;
+    use crate::sui::{
+        SuiCallHandler, SuiCallMarker, SuiEventHandler, SuiEventMarker, SuiEventId,
+        SuiMoveCall, SuiObjectChangeHandler, SuiObjectTypeMarker, SuiProcessor,
+    };
+    use crate::testing::TestProcessorServer;
+
+    const PACKAGE: &str = "0x1eabed72c53feb3805120a081dc15963c204dc8d091542592abaf7a35689b2fb";
+
+    struct PoolProcessor;
+
+    impl SuiProcessor for PoolProcessor {
+        fn address(&self) -> &str {
+            PACKAGE
+        }
+
+        fn name(&self) -> &str {
+            "pool"
+        }
+    }
+
+    struct SwapEvent;
+    struct SwapCall;
+    struct PoolObject;
+
+    impl SuiEventMarker for SwapEvent {
+        fn event_type() -> &'static str {
+            "pool::SwapEvent"
+        }
+    }
+
+    impl SuiCallMarker for SwapCall {
+        fn function() -> &'static str {
+            "pool::swap"
+        }
+    }
+
+    impl SuiObjectTypeMarker for PoolObject {
+        fn object_type() -> &'static str {
+            "0x1eabed72c53feb3805120a081dc15963c204dc8d091542592abaf7a35689b2fb::pool::Pool"
+        }
+    }
+
+    #[crate::async_trait]
+    impl SuiEventHandler<SwapEvent> for PoolProcessor {
+        async fn on_event(&self, event: SuiEvent, mut ctx: SuiContext) {
+            assert_eq!(ctx.transaction_digest(), "digest-1");
+            let amount: f64 = event.parsed_json["amount_in"].as_str().unwrap().parse().unwrap();
+            ctx.base_context().gauge("swap_amount").record(amount, None).await.ok();
+        }
+    }
+
+    #[crate::async_trait]
+    impl SuiCallHandler<SwapCall> for PoolProcessor {
+        async fn on_call(&self, call: SuiCall, mut ctx: SuiContext) {
+            assert_eq!(call.call.type_arguments, vec!["0x2::sui::SUI"]);
+            assert_eq!(call.transaction.sender(), Some("0xabc"));
+            ctx.base_context().counter("swap_calls").add(1.0, None).await.ok();
+        }
+    }
+
+    #[crate::async_trait]
+    impl SuiObjectChangeHandler<PoolObject> for PoolProcessor {
+        async fn on_object_change(&self, changes: Vec<SuiObjectChange>, mut ctx: SuiContext) {
+            ctx.base_context()
+                .counter("pool_changes")
+                .add(changes.len() as f64, None)
+                .await
+                .ok();
+        }
+    }
+
+    fn swap_transaction() -> SuiTransaction {
+        SuiTransaction::new("digest-1")
+            .with_sender("0xabc")
+            .with_checkpoint(77)
+            .with_move_call(SuiMoveCall {
+                package: PACKAGE.to_string(),
+                module: "pool".to_string(),
+                function: "swap".to_string(),
+                type_arguments: vec!["0x2::sui::SUI".to_string()],
+                arguments: vec![],
+            })
+            .with_event(SuiEvent {
+                id: SuiEventId {
+                    tx_digest: "digest-1".to_string(),
+                    event_seq: "0".to_string(),
+                },
+                package_id: PACKAGE.to_string(),
+                transaction_module: "pool".to_string(),
+                sender: "0xabc".to_string(),
+                event_type: format!("{}::pool::SwapEvent<0x2::sui::SUI>", PACKAGE),
+                parsed_json: serde_json::json!({ "amount_in": "250" }),
+                timestamp_ms: None,
+            })
+    }
+
+    #[tokio::test]
+    async fn test_sui_plugin_configure_and_dispatch() {
+        let mut server = TestProcessorServer::new();
+        PoolProcessor
+            .configure_event::<SwapEvent>(None)
+            .configure_call::<SwapCall>(None)
+            .configure_object_change::<PoolObject>(None)
+            .from_checkpoint(10)
+            .bind(&server);
+        server.start().await.unwrap();
+
+        let config = server.get_config().await;
+        assert_eq!(config.contract_configs.len(), 1);
+        let contract_config = &config.contract_configs[0];
+        assert_eq!(contract_config.start_block, 10);
+        assert_eq!(contract_config.contract.as_ref().unwrap().chain_id, "sui_mainnet");
+        assert_eq!(contract_config.move_event_configs[0].filters[0].r#type, "pool::SwapEvent");
+        assert_eq!(contract_config.move_event_configs[0].filters[0].account, PACKAGE);
+        assert_eq!(
+            contract_config.move_call_configs[0].filters[0].function,
+            format!("{}::pool::swap", normalize_sui_address(PACKAGE))
+        );
+        assert_eq!(contract_config.move_resource_change_configs.len(), 1);
+
+        let facet = server.sui();
+        let result = facet.test_transaction(swap_transaction(), None).await;
+        assert_eq!(result.first_counter_value("swap_calls"), Some(1.0));
+        assert_eq!(result.first_gauge_value("swap_amount"), Some(250.0));
+        assert_eq!(result.gauges[0].metadata.block_number, Some(77));
+        assert_eq!(result.gauges[0].metadata.contract_name.as_deref(), Some("pool"));
+
+        // Events on another network are not dispatched
+        let result = facet.test_transaction(swap_transaction(), Some("sui_testnet")).await;
+        assert!(result.counters.is_empty() && result.gauges.is_empty());
+
+        let changes = vec![
+            SuiObjectChange {
+                change_type: "mutated".to_string(),
+                object_type: format!("{}::pool::Pool<0x2::sui::SUI>", PACKAGE),
+                ..Default::default()
+            },
+            SuiObjectChange {
+                change_type: "deleted".to_string(),
+                object_type: format!("{}::pool::Pool<0x2::sui::SUI>", PACKAGE),
+                ..Default::default()
+            },
+            SuiObjectChange {
+                change_type: "created".to_string(),
+                object_type: "0x2::coin::Coin<0x2::sui::SUI>".to_string(),
+                ..Default::default()
+            },
+        ];
+        let result = facet.test_object_changes(changes, "digest-2", None).await;
+        assert_eq!(result.first_counter_value("pool_changes"), Some(1.0));
+    }
+
+    #[test]
+    fn test_matches_object_type() {
+        assert!(matches_object_type("0x2::coin::Coin<0x2::sui::SUI>", "0x2::coin::Coin"));
+        assert!(matches_object_type(
+            "0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin<0x2::sui::SUI>",
+            "0x2::coin::Coin<0x2::sui::SUI>"
+        ));
+        assert!(!matches_object_type("0x2::coin::Coin<0x2::sui::SUI>", "0x2::coin::Coin<0x3::usdc::USDC>"));
+        assert!(!matches_object_type("0x2::coin::TreasuryCap", "0x2::coin::Coin"));
+    }
+}
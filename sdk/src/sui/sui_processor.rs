@@ -0,0 +1,335 @@
+use crate::core::BaseProcessor;
+use crate::sui::context::SuiContext;
+use crate::sui::{
+    SuiCall, SuiCallHandler, SuiCallMarker, SuiEvent, SuiEventHandler, SuiEventMarker,
+    SuiObjectChange, SuiObjectChangeHandler, SuiObjectTypeMarker, SuiPlugin, SUI_MAINNET,
+};
+use crate::MoveFetchConfig;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct SuiBindOptions {
+    /// Package address
+    pub address: String,
+    /// Optional, if not set, then use sui mainnet
+    pub network: Option<String>,
+    /// Optional, override default processor name
+    pub name: Option<String>,
+    pub start_checkpoint: Option<u64>,
+    pub end_checkpoint: Option<u64>,
+}
+
+impl SuiBindOptions {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            network: None,
+            name: None,
+            start_checkpoint: None,
+            end_checkpoint: None,
+        }
+    }
+
+    pub fn with_network(mut self, network: impl Into<String>) -> Self {
+        self.network = Some(network.into());
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn from_checkpoint(mut self, checkpoint: u64) -> Self {
+        self.start_checkpoint = Some(checkpoint);
+        self
+    }
+
+    pub fn to_checkpoint(mut self, checkpoint: u64) -> Self {
+        self.end_checkpoint = Some(checkpoint);
+        self
+    }
+}
+
+/// Options for a single Sui handler
+#[derive(Clone, Default)]
+pub struct SuiHandlerOptions {
+    pub fetch_config: Option<MoveFetchConfig>,
+    /// Include object deletions, only used by object change handlers
+    pub include_deleted: bool,
+    pub name: Option<String>,
+}
+
+impl SuiHandlerOptions {
+    pub fn with_fetch_config(mut self, fetch_config: MoveFetchConfig) -> Self {
+        self.fetch_config = Some(fetch_config);
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn include_deleted(mut self) -> Self {
+        self.include_deleted = true;
+        self
+    }
+}
+
+/// A configurable Sui processor that can register event, call and object change handlers
+pub struct ConfigurableSuiProcessor<P: SuiProcessor> {
+    processor: Arc<P>,
+    options: SuiBindOptions,
+    event_handlers: Vec<EventHandler>,
+    call_handlers: Vec<CallHandler>,
+    object_change_handlers: Vec<ObjectChangeHandler>,
+}
+
+impl<P: SuiProcessor> ConfigurableSuiProcessor<P> {
+    /// Create a new configurable processor
+    pub fn new(processor: P) -> Self {
+        let options = SuiBindOptions::new(processor.address())
+            .with_network(processor.chain_id())
+            .with_name(processor.name());
+        Self {
+            processor: Arc::new(processor),
+            options,
+            event_handlers: Vec::new(),
+            call_handlers: Vec::new(),
+            object_change_handlers: Vec::new(),
+        }
+    }
+
+    /// Handle Move events of type `T` emitted by the processor's package
+    pub fn configure_event<T: SuiEventMarker>(mut self, options: Option<SuiHandlerOptions>) -> Self
+    where
+        P: SuiEventHandler<T>,
+    {
+        let handler: Arc<dyn TypeErasedSuiEventHandler> =
+            Arc::new((Arc::clone(&self.processor), PhantomData::<T>));
+        self.event_handlers.push(EventHandler {
+            handler,
+            event_type: T::event_type().to_string(),
+            options: options.unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Handle calls to the entry function `T` of the processor's package
+    pub fn configure_call<T: SuiCallMarker>(mut self, options: Option<SuiHandlerOptions>) -> Self
+    where
+        P: SuiCallHandler<T>,
+    {
+        let handler: Arc<dyn TypeErasedSuiCallHandler> =
+            Arc::new((Arc::clone(&self.processor), PhantomData::<T>));
+        self.call_handlers.push(CallHandler {
+            handler,
+            function: T::function().to_string(),
+            options: options.unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Handle changes to objects of type `T`
+    pub fn configure_object_change<T: SuiObjectTypeMarker>(
+        mut self,
+        options: Option<SuiHandlerOptions>,
+    ) -> Self
+    where
+        P: SuiObjectChangeHandler<T>,
+    {
+        let handler: Arc<dyn TypeErasedSuiObjectChangeHandler> =
+            Arc::new((Arc::clone(&self.processor), PhantomData::<T>));
+        self.object_change_handlers.push(ObjectChangeHandler {
+            handler,
+            object_type: T::object_type().to_string(),
+            options: options.unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Start processing from `checkpoint`
+    pub fn from_checkpoint(mut self, checkpoint: u64) -> Self {
+        self.options = self.options.from_checkpoint(checkpoint);
+        self
+    }
+
+    /// Stop processing after `checkpoint`
+    pub fn to_checkpoint(mut self, checkpoint: u64) -> Self {
+        self.options = self.options.to_checkpoint(checkpoint);
+        self
+    }
+
+    /// Bind this configured processor to a server
+    pub fn bind<S: crate::BindableServer>(self, server: &S) {
+        let processor_impl = SuiProcessorImpl {
+            options: self.options,
+            event_handlers: self.event_handlers,
+            call_handlers: self.call_handlers,
+            object_change_handlers: self.object_change_handlers,
+            _processor: self.processor,
+        };
+
+        server.register_processor::<SuiProcessorImpl, SuiPlugin>(processor_impl);
+    }
+}
+
+/// Core trait that all Sui processors must implement
+pub trait SuiProcessor: Send + Sync + 'static {
+    /// Get the package address this processor handles
+    fn address(&self) -> &str;
+
+    /// Get the network, defaults to Sui mainnet
+    fn chain_id(&self) -> &str {
+        SUI_MAINNET
+    }
+
+    /// Get the processor name
+    fn name(&self) -> &str;
+
+    fn configure_event<T: SuiEventMarker>(
+        self,
+        options: Option<SuiHandlerOptions>,
+    ) -> ConfigurableSuiProcessor<Self>
+    where
+        Self: Sized + SuiEventHandler<T>,
+    {
+        ConfigurableSuiProcessor::new(self).configure_event::<T>(options)
+    }
+
+    fn configure_call<T: SuiCallMarker>(
+        self,
+        options: Option<SuiHandlerOptions>,
+    ) -> ConfigurableSuiProcessor<Self>
+    where
+        Self: Sized + SuiCallHandler<T>,
+    {
+        ConfigurableSuiProcessor::new(self).configure_call::<T>(options)
+    }
+
+    fn configure_object_change<T: SuiObjectTypeMarker>(
+        self,
+        options: Option<SuiHandlerOptions>,
+    ) -> ConfigurableSuiProcessor<Self>
+    where
+        Self: Sized + SuiObjectChangeHandler<T>,
+    {
+        ConfigurableSuiProcessor::new(self).configure_object_change::<T>(options)
+    }
+}
+
+// Type-erased handlers so processors with different markers can share storage
+pub trait TypeErasedSuiEventHandler: Send + Sync {
+    fn handle_event(
+        &self,
+        event: SuiEvent,
+        ctx: SuiContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+}
+
+pub trait TypeErasedSuiCallHandler: Send + Sync {
+    fn handle_call(
+        &self,
+        call: SuiCall,
+        ctx: SuiContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+}
+
+pub trait TypeErasedSuiObjectChangeHandler: Send + Sync {
+    fn handle_object_change(
+        &self,
+        changes: Vec<SuiObjectChange>,
+        ctx: SuiContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+}
+
+impl<H, T> TypeErasedSuiEventHandler for (H, PhantomData<T>)
+where
+    H: SuiEventHandler<T>,
+    T: SuiEventMarker,
+{
+    fn handle_event(
+        &self,
+        event: SuiEvent,
+        ctx: SuiContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(self.0.on_event(event, ctx))
+    }
+}
+
+impl<H, T> TypeErasedSuiCallHandler for (H, PhantomData<T>)
+where
+    H: SuiCallHandler<T>,
+    T: SuiCallMarker,
+{
+    fn handle_call(
+        &self,
+        call: SuiCall,
+        ctx: SuiContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(self.0.on_call(call, ctx))
+    }
+}
+
+impl<H, T> TypeErasedSuiObjectChangeHandler for (H, PhantomData<T>)
+where
+    H: SuiObjectChangeHandler<T>,
+    T: SuiObjectTypeMarker,
+{
+    fn handle_object_change(
+        &self,
+        changes: Vec<SuiObjectChange>,
+        ctx: SuiContext,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(self.0.on_object_change(changes, ctx))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct EventHandler {
+    pub(crate) handler: Arc<dyn TypeErasedSuiEventHandler>,
+    pub(crate) event_type: String,
+    pub(crate) options: SuiHandlerOptions,
+}
+
+#[derive(Clone)]
+pub(crate) struct CallHandler {
+    pub(crate) handler: Arc<dyn TypeErasedSuiCallHandler>,
+    pub(crate) function: String,
+    pub(crate) options: SuiHandlerOptions,
+}
+
+#[derive(Clone)]
+pub(crate) struct ObjectChangeHandler {
+    pub(crate) handler: Arc<dyn TypeErasedSuiObjectChangeHandler>,
+    pub(crate) object_type: String,
+    pub(crate) options: SuiHandlerOptions,
+}
+
+// Internal storage for processor data - used by the plugin system
+#[derive(Clone)]
+pub(crate) struct SuiProcessorImpl {
+    pub(crate) options: SuiBindOptions,
+    pub(crate) event_handlers: Vec<EventHandler>,
+    pub(crate) call_handlers: Vec<CallHandler>,
+    pub(crate) object_change_handlers: Vec<ObjectChangeHandler>,
+    pub(crate) _processor: Arc<dyn SuiProcessor>,
+}
+
+impl BaseProcessor for SuiProcessorImpl {
+    fn chain_id(&self) -> &str {
+        self.options.network.as_deref().unwrap_or(SUI_MAINNET)
+    }
+
+    fn name(&self) -> &str {
+        self.options.name.as_deref().unwrap_or("sui-processor")
+    }
+
+    fn handler_count(&self) -> usize {
+        self.event_handlers.len() + self.call_handlers.len() + self.object_change_handlers.len()
+    }
+}
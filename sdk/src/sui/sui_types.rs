@@ -0,0 +1,343 @@
+use crate::sui::context::SuiContext;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Chain id of Sui mainnet
+pub const SUI_MAINNET: &str = "sui_mainnet";
+/// Chain id of Sui testnet
+pub const SUI_TESTNET: &str = "sui_testnet";
+
+/// Normalize a Sui address to lowercase, `0x`-prefixed, 64 hex characters
+pub fn normalize_sui_address(address: &str) -> String {
+    let hex = address.trim().trim_start_matches("0x").to_lowercase();
+    format!("0x{:0>64}", hex)
+}
+
+/// Split a fully qualified Move type (`0x2::coin::Coin<0x2::sui::SUI>`) into the
+/// normalized package address and the type without address or type arguments
+/// (`coin::Coin`)
+pub fn split_move_type(move_type: &str) -> Option<(String, String)> {
+    let base = move_type.split('<').next()?;
+    let (address, rest) = base.split_once("::")?;
+    Some((normalize_sui_address(address), rest.to_string()))
+}
+
+/// Identifier of an event inside a transaction
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiEventId {
+    pub tx_digest: String,
+    pub event_seq: String,
+}
+
+/// A Move event as returned by the Sui JSON-RPC API
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SuiEvent {
+    pub id: SuiEventId,
+    pub package_id: String,
+    pub transaction_module: String,
+    pub sender: String,
+    /// Fully qualified event type, e.g. `0x2::coin::CoinEvent<0x2::sui::SUI>`
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub parsed_json: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp_ms: Option<String>,
+}
+
+impl SuiEvent {
+    /// Decode `parsed_json` into a user defined type
+    pub fn parse_json<T: DeserializeOwned>(&self) -> anyhow::Result<T> {
+        serde_json::from_value(self.parsed_json.clone())
+            .map_err(|e| anyhow::anyhow!("Failed to decode event {}: {}", self.event_type, e))
+    }
+
+    /// Sequence number of the event inside its transaction
+    pub fn event_seq(&self) -> i32 {
+        self.id.event_seq.parse().unwrap_or_default()
+    }
+}
+
+/// A `MoveCall` command of a programmable transaction
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SuiMoveCall {
+    pub package: String,
+    pub module: String,
+    pub function: String,
+    pub type_arguments: Vec<String>,
+    pub arguments: Vec<Value>,
+}
+
+impl SuiMoveCall {
+    /// `module::function`, the form used by call markers
+    pub fn function_name(&self) -> String {
+        format!("{}::{}", self.module, self.function)
+    }
+
+    /// `package::module::function` with the normalized package address, the form used in call filters
+    pub fn qualified_function_name(&self) -> String {
+        format!("{}::{}", normalize_sui_address(&self.package), self.function_name())
+    }
+}
+
+/// A transaction block as returned by the Sui JSON-RPC API
+///
+/// Only the commonly used fields are typed; everything else is kept as JSON in
+/// `transaction`, `effects` and `object_changes`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SuiTransaction {
+    pub digest: String,
+    pub transaction: Value,
+    pub effects: Value,
+    pub events: Vec<SuiEvent>,
+    pub object_changes: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp_ms: Option<String>,
+}
+
+impl SuiTransaction {
+    pub fn new(digest: impl Into<String>) -> Self {
+        Self {
+            digest: digest.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sender of the transaction
+    pub fn sender(&self) -> Option<&str> {
+        self.transaction.pointer("/data/sender")?.as_str()
+    }
+
+    /// Checkpoint the transaction was included in
+    pub fn checkpoint(&self) -> Option<u64> {
+        self.checkpoint.as_deref()?.parse().ok()
+    }
+
+    /// All `MoveCall` commands of the transaction, in order
+    pub fn move_calls(&self) -> Vec<SuiMoveCall> {
+        self.transaction
+            .pointer("/data/transaction/transactions")
+            .and_then(Value::as_array)
+            .map(|commands| {
+                commands
+                    .iter()
+                    .filter_map(|command| command.get("MoveCall"))
+                    .filter_map(|call| serde_json::from_value(call.clone()).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Append a `MoveCall` command, mainly useful for building test transactions
+    pub fn with_move_call(mut self, call: SuiMoveCall) -> Self {
+        let call = serde_json::json!({ "MoveCall": call });
+        let sender = self.sender().map(str::to_string);
+        match self
+            .transaction
+            .pointer_mut("/data/transaction/transactions")
+            .and_then(Value::as_array_mut)
+        {
+            Some(commands) => commands.push(call),
+            None => {
+                self.transaction = serde_json::json!({
+                    "data": {
+                        "sender": sender,
+                        "transaction": {
+                            "kind": "ProgrammableTransaction",
+                            "transactions": [call],
+                        },
+                    },
+                });
+            }
+        }
+        self
+    }
+
+    pub fn with_sender(mut self, sender: impl Into<String>) -> Self {
+        if !self.transaction.is_object() {
+            self.transaction = serde_json::json!({ "data": {} });
+        }
+        if let Some(data) = self.transaction.pointer_mut("/data").and_then(Value::as_object_mut) {
+            data.insert("sender".to_string(), Value::String(sender.into()));
+        }
+        self
+    }
+
+    pub fn with_event(mut self, event: SuiEvent) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    pub fn with_checkpoint(mut self, checkpoint: u64) -> Self {
+        self.checkpoint = Some(checkpoint.to_string());
+        self
+    }
+}
+
+/// A single entry of a transaction's object changes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SuiObjectChange {
+    /// `created`, `mutated`, `deleted`, `wrapped`, `transferred` or `published`
+    #[serde(rename = "type")]
+    pub change_type: String,
+    pub sender: String,
+    pub owner: Value,
+    pub object_type: String,
+    pub object_id: String,
+    pub version: String,
+    pub digest: String,
+}
+
+/// A Move call handled by a [`SuiCallHandler`], with the transaction it belongs to
+#[derive(Debug, Clone)]
+pub struct SuiCall {
+    pub call: SuiMoveCall,
+    pub transaction: SuiTransaction,
+}
+
+/// Marker for a Move event type handled by a [`SuiEventHandler`]
+pub trait SuiEventMarker: Send + Sync + 'static {
+    /// Event type without the package address, e.g. `pool::SwapEvent`
+    fn event_type() -> &'static str;
+}
+
+/// Marker for an entry function handled by a [`SuiCallHandler`]
+pub trait SuiCallMarker: Send + Sync + 'static {
+    /// Function without the package address, e.g. `pool::swap`
+    fn function() -> &'static str;
+}
+
+/// Marker for an object type handled by a [`SuiObjectChangeHandler`]
+pub trait SuiObjectTypeMarker: Send + Sync + 'static {
+    /// Fully qualified object type, e.g. `0x2::coin::Coin<0x2::sui::SUI>`
+    fn object_type() -> &'static str;
+}
+
+#[crate::async_trait]
+pub trait SuiEventHandler<T: SuiEventMarker>: Send + Sync + 'static {
+    async fn on_event(&self, event: SuiEvent, ctx: SuiContext);
+}
+
+#[crate::async_trait]
+pub trait SuiCallHandler<T: SuiCallMarker>: Send + Sync + 'static {
+    async fn on_call(&self, call: SuiCall, ctx: SuiContext);
+}
+
+#[crate::async_trait]
+pub trait SuiObjectChangeHandler<T: SuiObjectTypeMarker>: Send + Sync + 'static {
+    async fn on_object_change(&self, changes: Vec<SuiObjectChange>, ctx: SuiContext);
+}
+
+// Implementations for Arc<H> so a shared processor can be registered for several markers
+#[crate::async_trait]
+impl<H, T> SuiEventHandler<T> for std::sync::Arc<H>
+where
+    H: SuiEventHandler<T>,
+    T: SuiEventMarker,
+{
+    async fn on_event(&self, event: SuiEvent, ctx: SuiContext) {
+        self.as_ref().on_event(event, ctx).await
+    }
+}
+
+#[crate::async_trait]
+impl<H, T> SuiCallHandler<T> for std::sync::Arc<H>
+where
+    H: SuiCallHandler<T>,
+    T: SuiCallMarker,
+{
+    async fn on_call(&self, call: SuiCall, ctx: SuiContext) {
+        self.as_ref().on_call(call, ctx).await
+    }
+}
+
+#[crate::async_trait]
+impl<H, T> SuiObjectChangeHandler<T> for std::sync::Arc<H>
+where
+    H: SuiObjectChangeHandler<T>,
+    T: SuiObjectTypeMarker,
+{
+    async fn on_object_change(&self, changes: Vec<SuiObjectChange>, ctx: SuiContext) {
+        self.as_ref().on_object_change(changes, ctx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_event_and_transaction() {
+        let raw_event = r#"{
+            "id": {"txDigest": "9XfG", "eventSeq": "2"},
+            "packageId": "0x2",
+            "transactionModule": "pool",
+            "sender": "0xabc",
+            "type": "0x2::pool::SwapEvent<0x2::sui::SUI>",
+            "parsedJson": {"amount_in": "10", "a_to_b": true},
+            "bcs": "ignored",
+            "timestampMs": "1700000000000"
+        }"#;
+        let event: SuiEvent = serde_json::from_str(raw_event).unwrap();
+        assert_eq!(event.event_seq(), 2);
+        assert_eq!(event.id.tx_digest, "9XfG");
+
+        #[derive(Deserialize)]
+        struct Swap {
+            amount_in: String,
+            a_to_b: bool,
+        }
+        let swap: Swap = event.parse_json().unwrap();
+        assert_eq!(swap.amount_in, "10");
+        assert!(swap.a_to_b);
+
+        let (address, ty) = split_move_type(&event.event_type).unwrap();
+        assert_eq!(address, normalize_sui_address("0x2"));
+        assert_eq!(ty, "pool::SwapEvent");
+
+        let raw_transaction = r#"{
+            "digest": "9XfG",
+            "transaction": {"data": {"sender": "0xabc", "transaction": {
+                "kind": "ProgrammableTransaction",
+                "transactions": [
+                    {"SplitCoins": ["GasCoin", [{"Input": 0}]]},
+                    {"MoveCall": {"package": "0x2", "module": "pool", "function": "swap",
+                                  "type_arguments": ["0x2::sui::SUI"], "arguments": [{"Input": 1}]}}
+                ]
+            }}},
+            "checkpoint": "123"
+        }"#;
+        let transaction: SuiTransaction = serde_json::from_str(raw_transaction).unwrap();
+        assert_eq!(transaction.sender(), Some("0xabc"));
+        assert_eq!(transaction.checkpoint(), Some(123));
+        let calls = transaction.move_calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].function_name(), "pool::swap");
+        assert_eq!(calls[0].type_arguments, vec!["0x2::sui::SUI"]);
+    }
+
+    #[test]
+    fn test_build_transaction() {
+        let transaction = SuiTransaction::new("digest")
+            .with_sender("0x1")
+            .with_move_call(SuiMoveCall {
+                package: "0x2".to_string(),
+                module: "pool".to_string(),
+                function: "swap".to_string(),
+                ..Default::default()
+            });
+        assert_eq!(transaction.sender(), Some("0x1"));
+        assert_eq!(transaction.move_calls()[0].function, "swap");
+
+        let round_trip: SuiTransaction =
+            serde_json::from_str(&serde_json::to_string(&transaction).unwrap()).unwrap();
+        assert_eq!(round_trip, transaction);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::aptos::{
+    matches_aptos_type, normalize_aptos_address, split_aptos_type, AptosEvent, AptosResource,
+    AptosTransaction, APTOS_MAINNET,
+};
+use crate::testing::TestResult;
+use crate::{data, Data, DataBinding, HandlerType};
+
+/// Aptos testing facet for simulating transactions, events and resource changes
+///
+/// Bindings are matched against the processor configuration the same way the
+/// Sentio driver does, so only handlers whose filters match are invoked.
+pub struct AptosTestFacet {
+    server: crate::testing::TestProcessorServer,
+}
+
+impl AptosTestFacet {
+    pub fn new(server: crate::testing::TestProcessorServer) -> Self {
+        Self { server }
+    }
+
+    /// Test a single event, wrapped in a transaction carrying only that event
+    pub async fn test_event(&self, event: AptosEvent, version: u64, network: Option<&str>) -> TestResult {
+        let transaction = AptosTransaction::new(version, "").with_event(event);
+        self.test_transaction(transaction, network).await
+    }
+
+    /// Test a transaction: its entry function call first, then each of its events
+    pub async fn test_transaction(&self, transaction: AptosTransaction, network: Option<&str>) -> TestResult {
+        self.test_transactions(vec![transaction], network).await
+    }
+
+    /// Test multiple transactions in order
+    pub async fn test_transactions(
+        &self,
+        transactions: Vec<AptosTransaction>,
+        network: Option<&str>,
+    ) -> TestResult {
+        let chain_id = network.unwrap_or(APTOS_MAINNET);
+        let mut test_result = TestResult::new();
+
+        for transaction in transactions {
+            let raw_transaction = serde_json::to_string(&transaction).unwrap_or_default();
+
+            let call_handler_ids = self.call_handler_ids(&transaction, chain_id).await;
+            if !call_handler_ids.is_empty() {
+                let binding = DataBinding {
+                    data: Some(Data {
+                        value: Some(data::Value::AptCall(data::AptCall {
+                            raw_transaction: raw_transaction.clone(),
+                            ..Default::default()
+                        })),
+                    }),
+                    handler_type: HandlerType::AptCall as i32,
+                    handler_ids: call_handler_ids,
+                    chain_id: chain_id.to_string(),
+                };
+                self.server.process_databinding(&binding, &mut test_result).await;
+            }
+
+            for (event_index, event) in transaction.events.iter().enumerate() {
+                let handler_ids = self.event_handler_ids(event, chain_id).await;
+                if handler_ids.is_empty() {
+                    continue;
+                }
+                let binding = DataBinding {
+                    data: Some(Data {
+                        value: Some(data::Value::AptEvent(data::AptEvent {
+                            raw_event: serde_json::to_string(event).unwrap_or_default(),
+                            event_index: event_index as i32,
+                            raw_transaction: raw_transaction.clone(),
+                            ..Default::default()
+                        })),
+                    }),
+                    handler_type: HandlerType::AptEvent as i32,
+                    handler_ids,
+                    chain_id: chain_id.to_string(),
+                };
+                self.server.process_databinding(&binding, &mut test_result).await;
+            }
+        }
+
+        test_result
+    }
+
+    /// Test the resources of `account` as of ledger `version`
+    pub async fn test_resources(
+        &self,
+        account: &str,
+        resources: Vec<AptosResource>,
+        version: u64,
+        timestamp_micros: u64,
+        network: Option<&str>,
+    ) -> TestResult {
+        let chain_id = network.unwrap_or(APTOS_MAINNET);
+        let mut test_result = TestResult::new();
+
+        let handler_ids = self.resource_handler_ids(account, &resources, chain_id).await;
+        if handler_ids.is_empty() {
+            return test_result;
+        }
+
+        let binding = DataBinding {
+            data: Some(Data {
+                value: Some(data::Value::AptResource(data::AptResource {
+                    version: version as i64,
+                    timestamp_micros: timestamp_micros as i64,
+                    raw_resources: resources
+                        .iter()
+                        .map(|resource| serde_json::to_string(resource).unwrap_or_default())
+                        .collect(),
+                    ..Default::default()
+                })),
+            }),
+            handler_type: HandlerType::AptResource as i32,
+            handler_ids,
+            chain_id: chain_id.to_string(),
+        };
+        self.server.process_databinding(&binding, &mut test_result).await;
+
+        test_result
+    }
+
+    /// Handler ids of event handlers whose filter matches the event type
+    async fn event_handler_ids(&self, event: &AptosEvent, chain_id: &str) -> Vec<i32> {
+        let Some((address, event_type)) = split_aptos_type(&event.event_type) else {
+            return vec![];
+        };
+        let config = self.server.get_config().await;
+
+        config
+            .contract_configs
+            .iter()
+            .filter(|c| c.contract.as_ref().is_some_and(|contract| contract.chain_id == chain_id))
+            .flat_map(|c| c.move_event_configs.iter())
+            .filter(|event_config| {
+                event_config.filters.iter().any(|filter| {
+                    normalize_aptos_address(&filter.account) == address && filter.r#type == event_type
+                })
+            })
+            .map(|event_config| event_config.handler_id)
+            .collect()
+    }
+
+    /// Handler ids of call handlers matching the transaction's entry function
+    async fn call_handler_ids(&self, transaction: &AptosTransaction, chain_id: &str) -> Vec<i32> {
+        let Some(payload) = &transaction.payload else {
+            return vec![];
+        };
+        let config = self.server.get_config().await;
+
+        let mut handler_ids = Vec::new();
+        for contract_config in &config.contract_configs {
+            let Some(contract) = &contract_config.contract else {
+                continue;
+            };
+            if contract.chain_id != chain_id {
+                continue;
+            }
+            for call_config in &contract_config.move_call_configs {
+                let matched = call_config
+                    .filters
+                    .iter()
+                    .any(|filter| matches_aptos_type(&payload.function, &filter.function));
+                if matched {
+                    handler_ids.push(call_config.handler_id);
+                }
+            }
+        }
+        handler_ids
+    }
+
+    /// Handler ids of resource change handlers of `account` matching any of the resources
+    async fn resource_handler_ids(&self, account: &str, resources: &[AptosResource], chain_id: &str) -> Vec<i32> {
+        let account = normalize_aptos_address(account);
+        let config = self.server.get_config().await;
+
+        config
+            .account_configs
+            .iter()
+            .filter(|c| c.chain_id == chain_id && normalize_aptos_address(&c.address) == account)
+            .flat_map(|c| c.move_resource_change_configs.iter())
+            .filter(|change_config| {
+                resources
+                    .iter()
+                    .any(|resource| matches_aptos_type(&resource.resource_type, &change_config.r#type))
+            })
+            .map(|change_config| change_config.handler_id)
+            .collect()
+    }
+}
+
+impl Default for AptosTestFacet {
+    fn default() -> Self {
+        Self::new(crate::testing::TestProcessorServer::new())
+    }
+}
@@ -0,0 +1,118 @@
+use crate::btc::{BtcBlock, BtcTransaction, BtcTransactionFilter, BTC_MAINNET};
+use crate::core::conversions::json_to_struct;
+use crate::testing::TestResult;
+use crate::{data, Data, DataBinding, HandlerType};
+
+/// Bitcoin testing facet for simulating transactions and blocks
+///
+/// Transactions are matched against the configured filters the same way the
+/// Sentio driver does, so only handlers whose filters match are invoked.
+pub struct BtcTestFacet {
+    server: crate::testing::TestProcessorServer,
+}
+
+impl BtcTestFacet {
+    pub fn new(server: crate::testing::TestProcessorServer) -> Self {
+        Self { server }
+    }
+
+    /// Test a transaction against every matching transaction handler
+    pub async fn test_transaction(&self, transaction: BtcTransaction, network: Option<&str>) -> TestResult {
+        self.test_transactions(vec![transaction], network).await
+    }
+
+    /// Test multiple transactions in order
+    pub async fn test_transactions(
+        &self,
+        transactions: Vec<BtcTransaction>,
+        network: Option<&str>,
+    ) -> TestResult {
+        let chain_id = network.unwrap_or(BTC_MAINNET);
+        let mut test_result = TestResult::new();
+
+        for transaction in transactions {
+            let handler_ids = self.transaction_handler_ids(&transaction, chain_id).await;
+            if handler_ids.is_empty() {
+                continue;
+            }
+
+            let binding = DataBinding {
+                data: Some(Data {
+                    value: Some(data::Value::BtcTransaction(data::BtcTransaction {
+                        transaction: json_to_struct(&serde_json::to_value(&transaction).unwrap_or_default()),
+                        timestamp: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
+                    })),
+                }),
+                handler_type: HandlerType::BtcTransaction as i32,
+                handler_ids,
+                chain_id: chain_id.to_string(),
+            };
+            self.server.process_databinding(&binding, &mut test_result).await;
+        }
+
+        test_result
+    }
+
+    /// Test a block against every block interval handler
+    pub async fn test_block(&self, block: BtcBlock, network: Option<&str>) -> TestResult {
+        let chain_id = network.unwrap_or(BTC_MAINNET);
+        let mut test_result = TestResult::new();
+
+        let handler_ids = self.block_handler_ids(chain_id).await;
+        if handler_ids.is_empty() {
+            return test_result;
+        }
+
+        let binding = DataBinding {
+            data: Some(Data {
+                value: Some(data::Value::BtcBlock(data::BtcBlock {
+                    block: json_to_struct(&serde_json::to_value(&block).unwrap_or_default()),
+                    timestamp: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
+                })),
+            }),
+            handler_type: HandlerType::BtcBlock as i32,
+            handler_ids,
+            chain_id: chain_id.to_string(),
+        };
+        self.server.process_databinding(&binding, &mut test_result).await;
+
+        test_result
+    }
+
+    /// Handler ids of transaction handlers with any filter matching `transaction`
+    async fn transaction_handler_ids(&self, transaction: &BtcTransaction, chain_id: &str) -> Vec<i32> {
+        let config = self.server.get_config().await;
+
+        config
+            .contract_configs
+            .iter()
+            .filter(|c| c.contract.as_ref().is_some_and(|contract| contract.chain_id == chain_id))
+            .flat_map(|c| c.btc_transaction_configs.iter())
+            .filter(|transaction_config| {
+                transaction_config
+                    .filters
+                    .iter()
+                    .any(|filter| BtcTransactionFilter::from_proto(filter).matches(transaction))
+            })
+            .map(|transaction_config| transaction_config.handler_id)
+            .collect()
+    }
+
+    async fn block_handler_ids(&self, chain_id: &str) -> Vec<i32> {
+        let config = self.server.get_config().await;
+
+        config
+            .contract_configs
+            .iter()
+            .filter(|c| c.contract.as_ref().is_some_and(|contract| contract.chain_id == chain_id))
+            .flat_map(|c| c.interval_configs.iter())
+            .map(|interval_config| interval_config.handler_id)
+            .collect()
+    }
+}
+
+impl Default for BtcTestFacet {
+    fn default() -> Self {
+        Self::new(crate::testing::TestProcessorServer::new())
+    }
+}
@@ -0,0 +1,80 @@
+use crate::core::conversions::json_to_struct;
+use crate::cosmos::{CosmosTransaction, INJECTIVE_MAINNET};
+use crate::testing::TestResult;
+use crate::{data, Data, DataBinding, HandlerType};
+
+/// Cosmos testing facet for simulating transactions and their events
+///
+/// Transactions are only sent to handlers whose log filters match at least one
+/// of their events, as the Sentio driver does.
+pub struct CosmosTestFacet {
+    server: crate::testing::TestProcessorServer,
+}
+
+impl CosmosTestFacet {
+    pub fn new(server: crate::testing::TestProcessorServer) -> Self {
+        Self { server }
+    }
+
+    /// Test a single transaction
+    pub async fn test_transaction(&self, transaction: CosmosTransaction, network: Option<&str>) -> TestResult {
+        self.test_transactions(vec![transaction], network).await
+    }
+
+    /// Test multiple transactions in order
+    pub async fn test_transactions(
+        &self,
+        transactions: Vec<CosmosTransaction>,
+        network: Option<&str>,
+    ) -> TestResult {
+        let chain_id = network.unwrap_or(INJECTIVE_MAINNET);
+        let mut test_result = TestResult::new();
+
+        for transaction in transactions {
+            let handler_ids = self.log_handler_ids(&transaction, chain_id).await;
+            if handler_ids.is_empty() {
+                continue;
+            }
+
+            let binding = DataBinding {
+                data: Some(Data {
+                    value: Some(data::Value::CosmosCall(data::CosmosCall {
+                        transaction: json_to_struct(&serde_json::to_value(&transaction).unwrap_or_default()),
+                        timestamp: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
+                    })),
+                }),
+                handler_type: HandlerType::CosmosCall as i32,
+                handler_ids,
+                chain_id: chain_id.to_string(),
+            };
+            self.server.process_databinding(&binding, &mut test_result).await;
+        }
+
+        test_result
+    }
+
+    /// Handler ids of log handlers with a filter matching any event of `transaction`
+    async fn log_handler_ids(&self, transaction: &CosmosTransaction, chain_id: &str) -> Vec<i32> {
+        let config = self.server.get_config().await;
+        let events = transaction.message_events();
+
+        config
+            .contract_configs
+            .iter()
+            .filter(|c| c.contract.as_ref().is_some_and(|contract| contract.chain_id == chain_id))
+            .flat_map(|c| c.cosmos_log_configs.iter())
+            .filter(|log_config| {
+                events.iter().any(|(_, event)| {
+                    log_config.log_filters.iter().any(|filter| event.matches_filter(filter))
+                })
+            })
+            .map(|log_config| log_config.handler_id)
+            .collect()
+    }
+}
+
+impl Default for CosmosTestFacet {
+    fn default() -> Self {
+        Self::new(crate::testing::TestProcessorServer::new())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::AttributeValue;
+use crate::core::{AttributeValue, NumberValue};
 use crate::eth::EthHandlerType;
 use crate::{data, Data};
 use crate::{DataBinding, HandlerType};
@@ -204,12 +204,14 @@ impl EthTestFacet {
     // pub async fn test_traces(&self, traces: Vec<Trace>, chain_id: Option<u64>) -> TestResult
 }
 
-/// Result of a test operation containing metrics and events
+/// Result of a test operation containing metrics, events, exports and tracked events
 #[derive(Debug, Clone, Default)]
 pub struct TestResult {
     pub counters: Vec<CounterResult>,
     pub gauges: Vec<GaugeResult>,
     pub events: Vec<EventResult>,
+    pub exports: Vec<ExportRecord>,
+    pub tracked_events: Vec<TrackedEventResult>,
     pub db: Arc<crate::testing::MemoryDatabase>,
 }
 
@@ -226,6 +228,14 @@ impl TestResult {
             .map(|c| c.value)
     }
 
+    /// Get the exact first counter value by name
+    pub fn first_counter_exact_value(&self, name: &str) -> Option<&NumberValue> {
+        self.counters
+            .iter()
+            .find(|c| c.name == name)
+            .map(|c| &c.exact_value)
+    }
+
     /// Get the first gauge value by name
     pub fn first_gauge_value(&self, name: &str) -> Option<f64> {
         self.gauges
@@ -234,18 +244,43 @@ impl TestResult {
             .map(|g| g.value)
     }
     
+    /// Get the exact first gauge value by name
+    pub fn first_gauge_exact_value(&self, name: &str) -> Option<&NumberValue> {
+        self.gauges
+            .iter()
+            .find(|g| g.name == name)
+            .map(|g| &g.exact_value)
+    }
+
     /// Get the first event by name
     pub fn first_event(&self, name: &str) -> Option<&EventResult> {
         self.events
             .iter()
             .find(|e| e.name == name)
     }
+
+    /// Get the first export by name
+    pub fn first_export(&self, name: &str) -> Option<&ExportRecord> {
+        self.exports
+            .iter()
+            .find(|e| e.name == name)
+    }
+
+    /// Get the first tracked event by tracker name
+    pub fn first_tracked_event(&self, name: &str) -> Option<&TrackedEventResult> {
+        self.tracked_events
+            .iter()
+            .find(|e| e.name == name)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct CounterResult {
     pub name: String,
+    /// Recorded value as `f64`, approximate for big integers and decimals
     pub value: f64,
+    /// Recorded value without loss of precision
+    pub exact_value: NumberValue,
     pub labels: HashMap<String, String>,
     pub metadata: TestMetadata,
 }
@@ -253,7 +288,10 @@ pub struct CounterResult {
 #[derive(Debug, Clone)]
 pub struct GaugeResult {
     pub name: String,
+    /// Recorded value as `f64`, approximate for big integers and decimals
     pub value: f64,
+    /// Recorded value without loss of precision
+    pub exact_value: NumberValue,
     pub labels: HashMap<String, String>,
     pub metadata: TestMetadata,
 }
@@ -265,6 +303,22 @@ pub struct EventResult {
     pub metadata: TestMetadata,
 }
 
+#[derive(Debug, Clone)]
+pub struct ExportRecord {
+    pub name: String,
+    /// Payload parsed back from its JSON serialization
+    pub payload: serde_json::Value,
+    pub metadata: TestMetadata,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrackedEventResult {
+    pub name: String,
+    pub distinct_id: String,
+    pub attributes: HashMap<String, AttributeValue>,
+    pub metadata: TestMetadata,
+}
+
 #[derive(Debug, Clone)]
 pub struct TestMetadata {
     pub contract_name: Option<String>,
@@ -0,0 +1,155 @@
+use crate::core::conversions::json_to_struct;
+use crate::fuel::fuel_plugin::matches_receipt_config;
+use crate::fuel::{FuelBlock, FuelTransaction, FUEL_MAINNET};
+use crate::testing::TestResult;
+use crate::{data, Data, DataBinding, HandlerType};
+
+/// Fuel testing facet for simulating transactions, receipts and blocks
+///
+/// Bindings are matched against the processor configuration the same way the
+/// Sentio driver does, so only handlers whose filters match are invoked.
+pub struct FuelTestFacet {
+    server: crate::testing::TestProcessorServer,
+}
+
+impl FuelTestFacet {
+    pub fn new(server: crate::testing::TestProcessorServer) -> Self {
+        Self { server }
+    }
+
+    /// Test a transaction: transaction handlers first, then each matching receipt
+    pub async fn test_transaction(&self, transaction: FuelTransaction, network: Option<&str>) -> TestResult {
+        self.test_transactions(vec![transaction], network).await
+    }
+
+    /// Test multiple transactions in order
+    pub async fn test_transactions(
+        &self,
+        transactions: Vec<FuelTransaction>,
+        network: Option<&str>,
+    ) -> TestResult {
+        let chain_id = network.unwrap_or(FUEL_MAINNET);
+        let mut test_result = TestResult::new();
+
+        for transaction in transactions {
+            let raw_transaction = json_to_struct(&serde_json::to_value(&transaction).unwrap_or_default());
+            let timestamp = Some(prost_types::Timestamp::from(std::time::SystemTime::now()));
+
+            let transaction_handler_ids = self.transaction_handler_ids(chain_id).await;
+            if !transaction_handler_ids.is_empty() {
+                let binding = DataBinding {
+                    data: Some(Data {
+                        value: Some(data::Value::FuelTransaction(data::FuelTransaction {
+                            transaction: raw_transaction.clone(),
+                            timestamp,
+                        })),
+                    }),
+                    handler_type: HandlerType::FuelTransaction as i32,
+                    handler_ids: transaction_handler_ids,
+                    chain_id: chain_id.to_string(),
+                };
+                self.server.process_databinding(&binding, &mut test_result).await;
+            }
+
+            for receipt_index in 0..transaction.receipts().len() {
+                let handler_ids = self.receipt_handler_ids(&transaction, receipt_index, chain_id).await;
+                if handler_ids.is_empty() {
+                    continue;
+                }
+                let binding = DataBinding {
+                    data: Some(Data {
+                        value: Some(data::Value::FuelLog(data::FuelReceipt {
+                            transaction: raw_transaction.clone(),
+                            timestamp,
+                            receipt_index: receipt_index as i64,
+                        })),
+                    }),
+                    handler_type: HandlerType::FuelReceipt as i32,
+                    handler_ids,
+                    chain_id: chain_id.to_string(),
+                };
+                self.server.process_databinding(&binding, &mut test_result).await;
+            }
+        }
+
+        test_result
+    }
+
+    /// Test a block against every block interval handler
+    pub async fn test_block(&self, block: FuelBlock, network: Option<&str>) -> TestResult {
+        let chain_id = network.unwrap_or(FUEL_MAINNET);
+        let mut test_result = TestResult::new();
+
+        let handler_ids = self.block_handler_ids(chain_id).await;
+        if handler_ids.is_empty() {
+            return test_result;
+        }
+
+        let binding = DataBinding {
+            data: Some(Data {
+                value: Some(data::Value::FuelBlock(data::FuelBlock {
+                    block: json_to_struct(&serde_json::to_value(&block).unwrap_or_default()),
+                    timestamp: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
+                })),
+            }),
+            handler_type: HandlerType::FuelBlock as i32,
+            handler_ids,
+            chain_id: chain_id.to_string(),
+        };
+        self.server.process_databinding(&binding, &mut test_result).await;
+
+        test_result
+    }
+
+    async fn transaction_handler_ids(&self, chain_id: &str) -> Vec<i32> {
+        let config = self.server.get_config().await;
+
+        config
+            .contract_configs
+            .iter()
+            .filter(|c| c.contract.as_ref().is_some_and(|contract| contract.chain_id == chain_id))
+            .flat_map(|c| c.fuel_transaction_configs.iter())
+            .map(|transaction_config| transaction_config.handler_id)
+            .collect()
+    }
+
+    /// Handler ids of receipt handlers matching the receipt at `receipt_index`
+    async fn receipt_handler_ids(&self, transaction: &FuelTransaction, receipt_index: usize, chain_id: &str) -> Vec<i32> {
+        let receipt = &transaction.receipts()[receipt_index];
+        let config = self.server.get_config().await;
+
+        let mut handler_ids = Vec::new();
+        for contract_config in &config.contract_configs {
+            let Some(contract) = &contract_config.contract else {
+                continue;
+            };
+            if contract.chain_id != chain_id {
+                continue;
+            }
+            for receipt_config in &contract_config.fuel_receipt_configs {
+                if matches_receipt_config(receipt_config, &contract.address, receipt) {
+                    handler_ids.push(receipt_config.handler_id);
+                }
+            }
+        }
+        handler_ids
+    }
+
+    async fn block_handler_ids(&self, chain_id: &str) -> Vec<i32> {
+        let config = self.server.get_config().await;
+
+        config
+            .contract_configs
+            .iter()
+            .filter(|c| c.contract.as_ref().is_some_and(|contract| contract.chain_id == chain_id))
+            .flat_map(|c| c.interval_configs.iter())
+            .map(|interval_config| interval_config.handler_id)
+            .collect()
+    }
+}
+
+impl Default for FuelTestFacet {
+    fn default() -> Self {
+        Self::new(crate::testing::TestProcessorServer::new())
+    }
+}
@@ -3,10 +3,20 @@
 //! Each facet provides testing utilities for a specific blockchain ecosystem,
 //! allowing simulation of blockchain events, transactions, and blocks for testing processors.
 
+pub mod aptos_facet;
+pub mod btc_facet;
+pub mod cosmos_facet;
 pub mod eth_facet;
-// TODO: Add other chain facets as needed:
-// pub mod aptos_facet;
-// pub mod sui_facet; 
-// pub mod solana_facet;
+pub mod fuel_facet;
+pub mod solana_facet;
+pub mod starknet_facet;
+pub mod sui_facet;
 
-pub use eth_facet::*;
\ No newline at end of file
+pub use aptos_facet::*;
+pub use btc_facet::*;
+pub use cosmos_facet::*;
+pub use eth_facet::*;
+pub use fuel_facet::*;
+pub use solana_facet::*;
+pub use starknet_facet::*;
+pub use sui_facet::*;
\ No newline at end of file
@@ -0,0 +1,73 @@
+use crate::core::conversions::json_to_struct;
+use crate::solana::{RawInstruction, SOLANA_MAINNET};
+use crate::testing::TestResult;
+use crate::{data, Data, DataBinding, HandlerType};
+
+/// Solana testing facet for simulating instructions
+///
+/// Like the Sentio driver, only instructions of configured programs are sent
+/// to the processors.
+pub struct SolanaTestFacet {
+    server: crate::testing::TestProcessorServer,
+}
+
+impl SolanaTestFacet {
+    pub fn new(server: crate::testing::TestProcessorServer) -> Self {
+        Self { server }
+    }
+
+    /// Test a single instruction
+    pub async fn test_instruction(&self, instruction: RawInstruction, network: Option<&str>) -> TestResult {
+        self.test_instructions(vec![instruction], network).await
+    }
+
+    /// Test multiple instructions in order
+    pub async fn test_instructions(
+        &self,
+        instructions: Vec<RawInstruction>,
+        network: Option<&str>,
+    ) -> TestResult {
+        let chain_id = network.unwrap_or(SOLANA_MAINNET);
+        let mut test_result = TestResult::new();
+
+        for instruction in instructions {
+            if !self.is_configured(&instruction.program_id, chain_id).await {
+                continue;
+            }
+            let binding = DataBinding {
+                data: Some(Data {
+                    value: Some(data::Value::SolInstruction(data::SolInstruction {
+                        instruction_data: instruction.instruction_data,
+                        slot: instruction.slot,
+                        program_account_id: instruction.program_id,
+                        accounts: instruction.accounts,
+                        parsed: instruction.parsed.as_ref().and_then(json_to_struct),
+                    })),
+                }),
+                handler_type: HandlerType::SolInstruction as i32,
+                handler_ids: vec![],
+                chain_id: chain_id.to_string(),
+            };
+            self.server.process_databinding(&binding, &mut test_result).await;
+        }
+
+        test_result
+    }
+
+    /// Whether a processor is configured for `program_id` on `chain_id`
+    async fn is_configured(&self, program_id: &str, chain_id: &str) -> bool {
+        let config = self.server.get_config().await;
+        config.contract_configs.iter().any(|c| {
+            c.instruction_config.is_some()
+                && c.contract
+                    .as_ref()
+                    .is_some_and(|contract| contract.chain_id == chain_id && contract.address == program_id)
+        })
+    }
+}
+
+impl Default for SolanaTestFacet {
+    fn default() -> Self {
+        Self::new(crate::testing::TestProcessorServer::new())
+    }
+}
@@ -0,0 +1,72 @@
+use crate::core::conversions::json_to_struct;
+use crate::starknet::starknet_plugin::matches_event_filter;
+use crate::starknet::{StarknetEvent, STARKNET_MAINNET};
+use crate::testing::TestResult;
+use crate::{data, Data, DataBinding, HandlerType};
+
+/// Starknet testing facet for simulating contract events
+///
+/// Events are matched against the configured address and selector filters the
+/// same way the Sentio driver does, so only matching handlers are invoked.
+pub struct StarknetTestFacet {
+    server: crate::testing::TestProcessorServer,
+}
+
+impl StarknetTestFacet {
+    pub fn new(server: crate::testing::TestProcessorServer) -> Self {
+        Self { server }
+    }
+
+    /// Test a single event
+    pub async fn test_event(&self, event: StarknetEvent, network: Option<&str>) -> TestResult {
+        self.test_events(vec![event], network).await
+    }
+
+    /// Test multiple events in order
+    pub async fn test_events(&self, events: Vec<StarknetEvent>, network: Option<&str>) -> TestResult {
+        let chain_id = network.unwrap_or(STARKNET_MAINNET);
+        let mut test_result = TestResult::new();
+
+        for event in events {
+            let handler_ids = self.event_handler_ids(&event, chain_id).await;
+            if handler_ids.is_empty() {
+                continue;
+            }
+
+            let binding = DataBinding {
+                data: Some(Data {
+                    value: Some(data::Value::StarknetEvents(data::StarknetEvent {
+                        result: json_to_struct(&serde_json::to_value(&event).unwrap_or_default()),
+                        timestamp: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
+                    })),
+                }),
+                handler_type: HandlerType::StarknetEvent as i32,
+                handler_ids,
+                chain_id: chain_id.to_string(),
+            };
+            self.server.process_databinding(&binding, &mut test_result).await;
+        }
+
+        test_result
+    }
+
+    /// Handler ids of event handlers with any filter matching `event`
+    async fn event_handler_ids(&self, event: &StarknetEvent, chain_id: &str) -> Vec<i32> {
+        let config = self.server.get_config().await;
+
+        config
+            .contract_configs
+            .iter()
+            .filter(|c| c.contract.as_ref().is_some_and(|contract| contract.chain_id == chain_id))
+            .flat_map(|c| c.starknet_event_configs.iter())
+            .filter(|event_config| event_config.filters.iter().any(|filter| matches_event_filter(filter, event)))
+            .map(|event_config| event_config.handler_id)
+            .collect()
+    }
+}
+
+impl Default for StarknetTestFacet {
+    fn default() -> Self {
+        Self::new(crate::testing::TestProcessorServer::new())
+    }
+}
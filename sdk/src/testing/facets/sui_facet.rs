@@ -0,0 +1,215 @@
+use crate::sui::sui_plugin::matches_object_type;
+use crate::sui::{
+    normalize_sui_address, split_move_type, SuiEvent, SuiObjectChange, SuiTransaction, SUI_MAINNET,
+};
+use crate::testing::TestResult;
+use crate::{data, Data, DataBinding, HandlerType};
+
+/// Sui testing facet for simulating events, transactions and object changes
+///
+/// Bindings are matched against the processor configuration the same way the
+/// Sentio driver does, so only handlers whose filters match are invoked.
+pub struct SuiTestFacet {
+    server: crate::testing::TestProcessorServer,
+}
+
+impl SuiTestFacet {
+    pub fn new(server: crate::testing::TestProcessorServer) -> Self {
+        Self { server }
+    }
+
+    /// Test a single event, wrapped in a transaction carrying only that event
+    pub async fn test_event(&self, event: SuiEvent, network: Option<&str>) -> TestResult {
+        let transaction = SuiTransaction::new(event.id.tx_digest.clone()).with_event(event);
+        self.test_transaction(transaction, network).await
+    }
+
+    /// Test a transaction: its entry function calls first, then each of its events
+    pub async fn test_transaction(&self, transaction: SuiTransaction, network: Option<&str>) -> TestResult {
+        self.test_transactions(vec![transaction], network).await
+    }
+
+    /// Test multiple transactions in order
+    pub async fn test_transactions(
+        &self,
+        transactions: Vec<SuiTransaction>,
+        network: Option<&str>,
+    ) -> TestResult {
+        let chain_id = network.unwrap_or(SUI_MAINNET);
+        let mut test_result = TestResult::new();
+
+        for transaction in transactions {
+            let raw_transaction = serde_json::to_string(&transaction).unwrap_or_default();
+            let timestamp = Some(transaction_timestamp(&transaction));
+            let slot = transaction.checkpoint().unwrap_or_default();
+
+            let call_handler_ids = self.call_handler_ids(&transaction, chain_id).await;
+            if !call_handler_ids.is_empty() {
+                let binding = DataBinding {
+                    data: Some(Data {
+                        value: Some(data::Value::SuiCall(data::SuiCall {
+                            raw_transaction: raw_transaction.clone(),
+                            timestamp,
+                            slot,
+                            ..Default::default()
+                        })),
+                    }),
+                    handler_type: HandlerType::SuiCall as i32,
+                    handler_ids: call_handler_ids,
+                    chain_id: chain_id.to_string(),
+                };
+                self.server.process_databinding(&binding, &mut test_result).await;
+            }
+
+            for event in &transaction.events {
+                let handler_ids = self.event_handler_ids(event, chain_id).await;
+                if handler_ids.is_empty() {
+                    continue;
+                }
+                let binding = DataBinding {
+                    data: Some(Data {
+                        value: Some(data::Value::SuiEvent(data::SuiEvent {
+                            raw_event: serde_json::to_string(event).unwrap_or_default(),
+                            raw_transaction: raw_transaction.clone(),
+                            timestamp,
+                            slot,
+                            ..Default::default()
+                        })),
+                    }),
+                    handler_type: HandlerType::SuiEvent as i32,
+                    handler_ids,
+                    chain_id: chain_id.to_string(),
+                };
+                self.server.process_databinding(&binding, &mut test_result).await;
+            }
+        }
+
+        test_result
+    }
+
+    /// Test the object changes of a single transaction
+    pub async fn test_object_changes(
+        &self,
+        changes: Vec<SuiObjectChange>,
+        tx_digest: &str,
+        network: Option<&str>,
+    ) -> TestResult {
+        let chain_id = network.unwrap_or(SUI_MAINNET);
+        let mut test_result = TestResult::new();
+
+        let handler_ids = self.object_change_handler_ids(&changes, chain_id).await;
+        if handler_ids.is_empty() {
+            return test_result;
+        }
+
+        let binding = DataBinding {
+            data: Some(Data {
+                value: Some(data::Value::SuiObjectChange(data::SuiObjectChange {
+                    raw_changes: changes
+                        .iter()
+                        .map(|change| serde_json::to_string(change).unwrap_or_default())
+                        .collect(),
+                    timestamp: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
+                    tx_digest: tx_digest.to_string(),
+                    slot: 0,
+                    ..Default::default()
+                })),
+            }),
+            handler_type: HandlerType::SuiObjectChange as i32,
+            handler_ids,
+            chain_id: chain_id.to_string(),
+        };
+        self.server.process_databinding(&binding, &mut test_result).await;
+
+        test_result
+    }
+
+    /// Handler ids of event handlers whose filter matches the event type
+    async fn event_handler_ids(&self, event: &SuiEvent, chain_id: &str) -> Vec<i32> {
+        let Some((address, event_type)) = split_move_type(&event.event_type) else {
+            return vec![];
+        };
+        let config = self.server.get_config().await;
+
+        config
+            .contract_configs
+            .iter()
+            .filter(|c| c.contract.as_ref().is_some_and(|contract| contract.chain_id == chain_id))
+            .flat_map(|c| c.move_event_configs.iter())
+            .filter(|event_config| {
+                event_config.filters.iter().any(|filter| {
+                    normalize_sui_address(&filter.account) == address && filter.r#type == event_type
+                })
+            })
+            .map(|event_config| event_config.handler_id)
+            .collect()
+    }
+
+    /// Handler ids of call handlers matching one of the transaction's Move calls
+    async fn call_handler_ids(&self, transaction: &SuiTransaction, chain_id: &str) -> Vec<i32> {
+        let move_calls = transaction.move_calls();
+        if move_calls.is_empty() {
+            return vec![];
+        }
+        let config = self.server.get_config().await;
+
+        let mut handler_ids = Vec::new();
+        for contract_config in &config.contract_configs {
+            let Some(contract) = &contract_config.contract else {
+                continue;
+            };
+            if contract.chain_id != chain_id {
+                continue;
+            }
+            for call_config in &contract_config.move_call_configs {
+                let matched = call_config.filters.iter().any(|filter| {
+                    move_calls
+                        .iter()
+                        .any(|call| call.qualified_function_name() == filter.function)
+                });
+                if matched {
+                    handler_ids.push(call_config.handler_id);
+                }
+            }
+        }
+        handler_ids
+    }
+
+    /// Handler ids of object change handlers whose type matches any of the changes
+    async fn object_change_handler_ids(&self, changes: &[SuiObjectChange], chain_id: &str) -> Vec<i32> {
+        let config = self.server.get_config().await;
+
+        config
+            .contract_configs
+            .iter()
+            .filter(|c| c.contract.as_ref().is_some_and(|contract| contract.chain_id == chain_id))
+            .flat_map(|c| c.move_resource_change_configs.iter())
+            .filter(|change_config| {
+                changes.iter().any(|change| {
+                    (change_config.include_deleted || change.change_type != "deleted")
+                        && matches_object_type(&change.object_type, &change_config.r#type)
+                })
+            })
+            .map(|change_config| change_config.handler_id)
+            .collect()
+    }
+}
+
+fn transaction_timestamp(transaction: &SuiTransaction) -> prost_types::Timestamp {
+    let millis = transaction
+        .timestamp_ms
+        .as_deref()
+        .and_then(|ms| ms.parse::<u64>().ok());
+    match millis {
+        Some(ms) => prost_types::Timestamp::from(
+            std::time::UNIX_EPOCH + std::time::Duration::from_millis(ms),
+        ),
+        None => prost_types::Timestamp::from(std::time::SystemTime::now()),
+    }
+}
+
+impl Default for SuiTestFacet {
+    fn default() -> Self {
+        Self::new(crate::testing::TestProcessorServer::new())
+    }
+}
@@ -1,11 +1,15 @@
 // In-memory database implementation for testing
 use crate::db_request::DbFilter;
-use crate::entity::store::backend::StorageBackend;
+use crate::entity::store::backend::{ListResponse, StorageBackend};
 use crate::entity::store::store::StoreImpl;
-use crate::common::RichStruct;
+use crate::entity::{BigDecimal, BigInt, FromRichValue, ToRichValue};
+use crate::common::{RichStruct, RichValue, rich_value};
+use crate::processor::EntityUpdateData;
+use crate::processor::entity_update_data::Operator;
 use crate::{db_response, processor::Entity};
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use async_trait::async_trait;
+use std::cmp::Ordering;
 use std::sync::Arc;
 use dashmap::DashMap;
 
@@ -108,34 +112,51 @@ impl StorageBackend for MemoryDatabase {
         Ok(())
     }
 
+    /// The cursor is the offset of the page in the table, ordered by ID
     async fn list(
         &self,
         table: &str,
-        _filters: Vec<DbFilter>,
-        _cursor: String,
+        filters: Vec<DbFilter>,
+        cursor: String,
         page_size: Option<u32>,
-    ) -> Result<Option<db_response::Value>> {
-        if let Some(table_data) = self.data.get(table) {
-            let mut entities: Vec<Entity> = table_data.iter().map(|entry| entry.value().clone()).collect();
-            
-            // Sort by entity ID for consistent ordering
-            entities.sort_by(|a, b| a.entity.cmp(&b.entity));
-            
-            // Apply page size limit
-            if let Some(limit) = page_size {
-                entities.truncate(limit as usize);
-            }
-            
-            let entity_list = crate::processor::EntityList { entities };
-            return Ok(Some(db_response::Value::EntityList(entity_list)));
-        }
-        
-        // Return empty list for non-existent tables
-        Ok(Some(db_response::Value::EntityList(
-            crate::processor::EntityList {
-                entities: vec![],
-            }
-        )))
+    ) -> Result<ListResponse> {
+        let offset = if cursor.is_empty() {
+            0
+        } else {
+            cursor
+                .parse::<usize>()
+                .map_err(|_| anyhow!("Invalid cursor: {}", cursor))?
+        };
+
+        let mut entities: Vec<Entity> = self
+            .data
+            .get(table)
+            .map(|table_data| {
+                table_data
+                    .iter()
+                    .filter(|entry| {
+                        filters
+                            .iter()
+                            .all(|filter| matches_filter(entry.value(), filter))
+                    })
+                    .map(|entry| entry.value().clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Sort by entity ID for consistent ordering
+        entities.sort_by(|a, b| a.entity.cmp(&b.entity));
+
+        let total = entities.len();
+        let end = page_size.map_or(total, |limit| total.min(offset + limit as usize));
+        let entities: Vec<Entity> = entities.into_iter().take(end).skip(offset).collect();
+
+        Ok(ListResponse {
+            value: Some(db_response::Value::EntityList(
+                crate::processor::EntityList { entities },
+            )),
+            next_cursor: (end < total).then(|| end.to_string()),
+        })
     }
 
     async fn upsert(&self, tables: Vec<String>, ids: Vec<String>, entity_data: Vec<RichStruct>) -> Result<()> {
@@ -156,6 +177,286 @@ impl StorageBackend for MemoryDatabase {
         
         Ok(())
     }
+
+    /// Every update is applied under the entity's lock, and not at all if one of its fields fails
+    async fn update(
+        &self,
+        tables: Vec<String>,
+        ids: Vec<String>,
+        entity_data: Vec<EntityUpdateData>,
+    ) -> Result<()> {
+        for ((table, id), update) in tables.into_iter().zip(ids).zip(entity_data) {
+            let table_data = self.data.get(&table);
+            let mut entity = table_data
+                .as_ref()
+                .and_then(|table_data| table_data.get_mut(&id))
+                .ok_or_else(|| anyhow!("Cannot update missing entity {} {}", table, id))?;
+
+            let mut values = Vec::with_capacity(update.fields.len());
+            for (field, update_value) in update.fields {
+                let op = update_value.op();
+                let value = update_value
+                    .value
+                    .and_then(|value| value.value)
+                    .unwrap_or(rich_value::Value::NullValue(0));
+                let value = match op {
+                    Operator::Set => value,
+                    _ => apply_numeric(field_value(&entity, &field), &value, op).map_err(|e| {
+                        anyhow!("Cannot update {} of {} {}: {}", field, table, id, e)
+                    })?,
+                };
+                values.push((field, value));
+            }
+
+            let data = entity.data.get_or_insert_with(Default::default);
+            for (field, value) in values {
+                data.fields.insert(field, RichValue { value: Some(value) });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Value of `field` in `entity`, `None` when missing or null
+fn field_value<'a>(entity: &'a Entity, field: &str) -> Option<&'a rich_value::Value> {
+    entity
+        .data
+        .as_ref()
+        .and_then(|data| data.fields.get(field))
+        .and_then(|value| value.value.as_ref())
+        .filter(|value| !matches!(value, rich_value::Value::NullValue(_)))
+}
+
+/// Apply `ADD` or `MULTIPLY` to the current value of a field, a missing value counts as zero
+///
+/// Integers stay integers unless they overflow `i64`, otherwise the result has the type
+/// of the widest operand.
+fn apply_numeric(
+    current: Option<&rich_value::Value>,
+    operand: &rich_value::Value,
+    op: Operator,
+) -> Result<rich_value::Value> {
+    use rich_value::Value;
+
+    let zero = match operand {
+        Value::IntValue(_) => Value::IntValue(0),
+        _ => Value::Int64Value(0),
+    };
+    let current = current.unwrap_or(&zero);
+    let is_int = |value: &Value| matches!(value, Value::IntValue(_) | Value::Int64Value(_));
+    let is_float = |value: &Value| matches!(value, Value::FloatValue(_));
+
+    if is_int(current) && is_int(operand) {
+        let (a, b) = (parse_as::<i64>(current), parse_as::<i64>(operand));
+        let result = a.zip(b).and_then(|(a, b)| match op {
+            Operator::Add => a.checked_add(b),
+            _ => a.checked_mul(b),
+        });
+        if let Some(result) = result {
+            return Ok(match (current, i32::try_from(result)) {
+                (Value::IntValue(_), Ok(result)) => Value::IntValue(result),
+                _ => Value::Int64Value(result),
+            });
+        }
+    }
+
+    let result = if (is_float(current) || is_float(operand))
+        && let (Some(a), Some(b)) = (as_f64(current), as_f64(operand))
+    {
+        numeric_op(a, b, op).to_rich_value()?
+    } else if [current, operand]
+        .into_iter()
+        .all(|value| is_int(value) || matches!(value, Value::BigintValue(_)))
+        && let (Some(a), Some(b)) = (parse_as::<BigInt>(current), parse_as::<BigInt>(operand))
+    {
+        numeric_op(a, b, op).to_rich_value()?
+    } else if let (Some(a), Some(b)) = (as_decimal(current), as_decimal(operand)) {
+        numeric_op(a, b, op).to_rich_value()?
+    } else {
+        return Err(anyhow!("{} only applies to numbers", op.as_str_name()));
+    };
+    result
+        .value
+        .ok_or_else(|| anyhow!("Empty {} result", op.as_str_name()))
+}
+
+fn numeric_op<N: std::ops::Add<Output = N> + std::ops::Mul<Output = N>>(
+    a: N,
+    b: N,
+    op: Operator,
+) -> N {
+    match op {
+        Operator::Add => a + b,
+        _ => a * b,
+    }
+}
+
+fn as_f64(value: &rich_value::Value) -> Option<f64> {
+    match value {
+        rich_value::Value::FloatValue(f) => Some(*f),
+        value => parse_as::<i64>(value).map(|i| i as f64),
+    }
+}
+
+/// Compare two values, numbers of different types are compared as decimals
+///
+/// Returns `None` for values that cannot be compared.
+fn compare_values(a: &rich_value::Value, b: &rich_value::Value) -> Option<Ordering> {
+    use rich_value::Value;
+
+    match (a, b) {
+        (Value::IntValue(_) | Value::Int64Value(_), Value::IntValue(_) | Value::Int64Value(_)) => {
+            compare_as::<i64>(a, b)
+        }
+        (Value::FloatValue(a), Value::FloatValue(b)) => Some(a.total_cmp(b)),
+        (Value::BoolValue(a), Value::BoolValue(b)) => Some(a.cmp(b)),
+        (Value::StringValue(a), Value::StringValue(b)) => Some(a.cmp(b)),
+        (Value::BytesValue(a), Value::BytesValue(b)) => Some(a.cmp(b)),
+        (Value::TimestampValue(a), Value::TimestampValue(b)) => {
+            Some((a.seconds, a.nanos).cmp(&(b.seconds, b.nanos)))
+        }
+        (Value::BigintValue(_), Value::BigintValue(_)) => compare_as::<BigInt>(a, b),
+        (Value::BigdecimalValue(_), Value::BigdecimalValue(_)) => compare_as::<BigDecimal>(a, b),
+        _ => Some(as_decimal(a)?.cmp(&as_decimal(b)?)),
+    }
+}
+
+fn compare_as<T: FromRichValue + Ord>(
+    a: &rich_value::Value,
+    b: &rich_value::Value,
+) -> Option<Ordering> {
+    Some(parse_as::<T>(a)?.cmp(&parse_as::<T>(b)?))
+}
+
+fn parse_as<T: FromRichValue>(value: &rich_value::Value) -> Option<T> {
+    T::from_rich_value(&RichValue {
+        value: Some(value.clone()),
+    })
+    .ok()
+}
+
+fn as_decimal(value: &rich_value::Value) -> Option<BigDecimal> {
+    use rich_value::Value;
+
+    match value {
+        Value::IntValue(_) | Value::Int64Value(_) => parse_as::<i64>(value).map(BigDecimal::from),
+        Value::FloatValue(f) => BigDecimal::try_from(*f).ok(),
+        Value::BigintValue(_) => parse_as::<BigInt>(value).map(|i| BigDecimal::new(i, 0)),
+        Value::BigdecimalValue(_) => parse_as::<BigDecimal>(value),
+        _ => None,
+    }
+}
+
+fn values_equal(a: Option<&rich_value::Value>, b: Option<&rich_value::Value>) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(rich_value::Value::ListValue(a)), Some(rich_value::Value::ListValue(b))) => {
+            a.values.len() == b.values.len()
+                && a.values
+                    .iter()
+                    .zip(&b.values)
+                    .all(|(a, b)| values_equal(a.value.as_ref(), b.value.as_ref()))
+        }
+        // IDs may be stored as integers and filtered as strings
+        (Some(rich_value::Value::StringValue(s)), Some(int))
+        | (Some(int), Some(rich_value::Value::StringValue(s)))
+            if matches!(
+                int,
+                rich_value::Value::IntValue(_) | rich_value::Value::Int64Value(_)
+            ) =>
+        {
+            s.parse::<i64>().ok() == parse_as::<i64>(int)
+        }
+        (Some(a), Some(b)) => compare_values(a, b) == Some(Ordering::Equal),
+        _ => false,
+    }
+}
+
+fn list_values(value: Option<&rich_value::Value>) -> &[RichValue] {
+    match value {
+        Some(rich_value::Value::ListValue(list)) => &list.values,
+        _ => &[],
+    }
+}
+
+/// Evaluate a filter the way the remote store does
+fn matches_filter(entity: &Entity, filter: &DbFilter) -> bool {
+    use crate::db_request::DbOperator;
+
+    let field = field_value(entity, &filter.field);
+    let value = filter
+        .value
+        .as_ref()
+        .and_then(|list| list.values.first())
+        .and_then(|value| value.value.as_ref())
+        .filter(|value| !matches!(value, rich_value::Value::NullValue(_)));
+    let ordering = || compare_values(field?, value?);
+    let contains = |list: &[RichValue], item: Option<&rich_value::Value>| {
+        list.iter().any(|v| values_equal(v.value.as_ref(), item))
+    };
+    let like = || match (field, value) {
+        (
+            Some(rich_value::Value::StringValue(text)),
+            Some(rich_value::Value::StringValue(pattern)),
+        ) => Some(like_matches(text, pattern)),
+        _ => None,
+    };
+
+    match DbOperator::try_from(filter.op) {
+        Ok(DbOperator::Eq) => values_equal(field, value),
+        Ok(DbOperator::Ne) => !values_equal(field, value),
+        Ok(DbOperator::Gt) => ordering().is_some_and(Ordering::is_gt),
+        Ok(DbOperator::Ge) => ordering().is_some_and(Ordering::is_ge),
+        Ok(DbOperator::Lt) => ordering().is_some_and(Ordering::is_lt),
+        Ok(DbOperator::Le) => ordering().is_some_and(Ordering::is_le),
+        Ok(DbOperator::In) => contains(list_values(value), field),
+        Ok(DbOperator::NotIn) => !contains(list_values(value), field),
+        Ok(DbOperator::Like) => like() == Some(true),
+        Ok(DbOperator::NotLike) => like() == Some(false),
+        Ok(DbOperator::HasAll) => list_values(value)
+            .iter()
+            .all(|v| contains(list_values(field), v.value.as_ref())),
+        Ok(DbOperator::HasAny) => list_values(value)
+            .iter()
+            .any(|v| contains(list_values(field), v.value.as_ref())),
+        Err(_) => false,
+    }
+}
+
+/// SQL LIKE matching, `%` matches any sequence of characters and `_` a single one
+fn like_matches(text: &str, pattern: &str) -> bool {
+    let text: Vec<char> = text.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let (mut t, mut p) = (0, 0);
+    // Position after the last `%` and the text position it currently matches up to
+    let mut backtrack = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('%') => {
+                p += 1;
+                backtrack = Some((p, t));
+            }
+            Some('_') => {
+                p += 1;
+                t += 1;
+            }
+            Some(c) if *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((after_wildcard, matched)) => {
+                    p = after_wildcard;
+                    t = matched + 1;
+                    backtrack = Some((after_wildcard, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '%')
 }
 
 /// Test wrapper that makes MemoryDatabase compatible with RemoteBackend usage patterns
@@ -195,13 +496,24 @@ impl StorageBackend for TestBackend {
         filters: Vec<DbFilter>,
         cursor: String,
         page_size: Option<u32>,
-    ) -> Result<Option<db_response::Value>> {
-        self.memory_db.list(table, filters, cursor, page_size).await
+    ) -> Result<ListResponse> {
+        self.memory_db
+            .list(table, filters, cursor, page_size)
+            .await
     }
 
     async fn upsert(&self, table: Vec<String>, id: Vec<String>, data: Vec<RichStruct>) -> Result<()> {
         self.memory_db.upsert(table, id, data).await
     }
+
+    async fn update(
+        &self,
+        table: Vec<String>,
+        id: Vec<String>,
+        data: Vec<EntityUpdateData>,
+    ) -> Result<()> {
+        self.memory_db.update(table, id, data).await
+    }
 }
 
 /// Test store type alias for use in testing contexts
@@ -1,9 +1,9 @@
 use std::collections::HashMap;
-use crate::testing::{TestEnvironment, EthTestFacet, MemoryDatabase, TestResult, TestMetadata, CounterResult, GaugeResult, EventResult};
-use crate::core::{AttributeValue, PluginManager, RuntimeContext};
-use std::sync::Arc;
+use crate::testing::{TestEnvironment, AptosTestFacet, BtcTestFacet, CosmosTestFacet, EthTestFacet, FuelTestFacet, SolanaTestFacet, StarknetTestFacet, SuiTestFacet, MemoryDatabase, TestResult, TestMetadata, CounterResult, GaugeResult, EventResult, ExportRecord, TrackedEventResult};
+use crate::core::{AttributeValue, EventTrackerOptions, MetricError, MetricOptions, NumberValue, PluginManager, RuntimeContext, SentioEvent};
+use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
-use crate::{ConfigureHandlersResponse, DataBinding};
+use crate::{ConfigureHandlersResponse, DataBinding, EventLogConfig, EventTrackingConfig, ExportConfig, MetricConfig};
 use crate::entity::store::backend::Backend;
 use crate::eth::EthHandlerType;
 use crate::timeseries_result::TimeseriesType;
@@ -36,7 +36,8 @@ pub struct TestProcessorServer {
     
     /// Plugin manager for coordinating processors (public for facet access)
     pub(crate) plugin_manager: Arc<PluginManager>,
-    config: Option<ConfigureHandlersResponse>
+    /// Config cached by `start`, refreshed when a binding updates it
+    config: Mutex<Option<ConfigureHandlersResponse>>,
 }
 
 impl TestProcessorServer {
@@ -50,13 +51,35 @@ impl TestProcessorServer {
 
 
         match self.plugin_manager.process(data_binding, runtime_context).await {
-            Ok(_process_result) => {
+            Ok(process_result) => {
+                // Like the driver, re-fetch the config after processors were bound at runtime
+                let config_updated = process_result
+                    .states
+                    .as_ref()
+                    .is_some_and(|states| states.config_updated);
+                if config_updated {
+                    let config = self.configure().await;
+                    let mut cached = self.config.lock().unwrap();
+                    if cached.is_some() {
+                        *cached = Some(config);
+                    }
+                }
+
                 // Processing succeeded, collect any messages from the channel
                 while let Ok(msg) = rx.try_recv() {
                     if let Ok(response) = msg {
-                        self.collect_results_from_channel_response(response, test_result);
+                        test_result.collect_response(response);
                     }
                 }
+                for ts_result in process_result.timeseries_result {
+                    test_result.collect_timeseries_result(ts_result);
+                }
+                for export_result in process_result.exports {
+                    test_result.collect_export_result(export_result);
+                }
+                for event_tracking_result in process_result.events {
+                    test_result.collect_event_tracking_result(event_tracking_result);
+                }
                 
                 // 6. Update test_result with the shared database that contains the processing results
                 test_result.db = self.db.clone();
@@ -69,19 +92,17 @@ impl TestProcessorServer {
             }
         }
     }
+}
 
+impl TestResult {
     /// Collect results from a single channel response
-    fn collect_results_from_channel_response(
-        &self,
-        response: crate::ProcessStreamResponseV3,
-        test_result: &mut TestResult
-    ) {
+    pub(crate) fn collect_response(&mut self, response: crate::ProcessStreamResponseV3) {
         if let Some(value) = response.value {
             match value {
                 crate::processor::process_stream_response_v3::Value::TsRequest(ts_request) => {
                     // Process timeseries data (counters and gauges)
                     for ts_data in ts_request.data {
-                        self.process_timeseries_result(ts_data,  test_result);
+                        self.collect_timeseries_result(ts_data);
                     }
                 }
                 // TODO: Handle event logs and other request types when the correct protobuf types are identified
@@ -92,12 +113,53 @@ impl TestProcessorServer {
         }
     }
 
+    /// Process a single export result
+    pub(crate) fn collect_export_result(&mut self, export_result: crate::ExportResult) {
+        let metadata = TestMetadata {
+            contract_name: export_result.metadata.as_ref().map(|m| m.contract_name.clone()),
+            block_number: export_result.metadata.as_ref().map(|m| m.block_number),
+            handler_type: EthHandlerType::Event,
+        };
+
+        let name = export_result.metadata.as_ref()
+            .map(|m| m.name.clone())
+            .unwrap_or_default();
+
+        // Exporter payloads are always JSON, keep anything else as a plain string
+        let payload = serde_json::from_str(&export_result.payload)
+            .unwrap_or(serde_json::Value::String(export_result.payload));
+
+        self.exports.push(ExportRecord { name, payload, metadata });
+    }
+
+    /// Process a single event tracking result
+    pub(crate) fn collect_event_tracking_result(&mut self, result: crate::EventTrackingResult) {
+        let metadata = TestMetadata {
+            contract_name: result.metadata.as_ref().map(|m| m.contract_name.clone()),
+            block_number: result.metadata.as_ref().map(|m| m.block_number),
+            handler_type: EthHandlerType::Event,
+        };
+
+        let name = result.metadata.as_ref()
+            .map(|m| m.name.clone())
+            .unwrap_or_default();
+
+        let attributes = result.attributes2.map(|attributes| {
+            attributes.fields.iter()
+                .filter_map(|(key, value)| Some((key.clone(), AttributeValue::try_from(value).ok()?)))
+                .collect()
+        }).unwrap_or_default();
+
+        self.tracked_events.push(TrackedEventResult {
+            name,
+            distinct_id: result.distinct_entity_id,
+            attributes,
+            metadata,
+        });
+    }
+
     /// Process a single timeseries result (counter or gauge)
-    fn process_timeseries_result(
-        &self,
-        ts_result: crate::TimeseriesResult,
-        test_result: &mut TestResult
-    ) {
+    pub(crate) fn collect_timeseries_result(&mut self, ts_result: crate::TimeseriesResult) {
         let metadata = TestMetadata {
             contract_name: ts_result.metadata.as_ref().map(|m| m.contract_name.clone()),
             block_number: ts_result.metadata.as_ref().map(|m| m.block_number),
@@ -116,53 +178,41 @@ impl TestProcessorServer {
         let metric_type = TimeseriesType::try_from(ts_result.r#type)
             .unwrap_or(TimeseriesType::Counter);
 
-        // Extract value from data field (which is a RichStruct)
-        let value = if let Some(ref data) = ts_result.data {
-            // Try to extract a numeric value from the RichStruct fields
-            data.fields.get("value")
-                .and_then(|v| match &v.value {
-                    Some(value_type) => match value_type {
-                        crate::common::rich_value::Value::FloatValue(f) => Some(*f),
-                        crate::common::rich_value::Value::IntValue(i) => Some(*i as f64),
-                        _ => None,
-                    },
-                    None => None,
-                })
-                .unwrap_or(0.0)
-        } else {
-            0.0
-        };
+        // Extract the exact value from the data field (which is a RichStruct)
+        let exact_value = ts_result.data.as_ref()
+            .and_then(|data| data.fields.get("value"))
+            .and_then(NumberValue::from_rich_value)
+            .unwrap_or(NumberValue::Float(0.0));
+        let value = exact_value.to_f64();
 
         match metric_type {
             TimeseriesType::Counter => {
-                test_result.counters.push(CounterResult {
+                self.counters.push(CounterResult {
                     name,
                     value,
+                    exact_value,
                     labels,
                     metadata,
                 });
             }
             TimeseriesType::Gauge => {
-                test_result.gauges.push(GaugeResult {
+                self.gauges.push(GaugeResult {
                     name,
                     value,
+                    exact_value,
                     labels,
                     metadata,
                 });
             }
             TimeseriesType::Event => {
                 // Process event logs
-                self.process_event_log(ts_result, test_result);
+                self.collect_event_log(ts_result);
             }
         }
     }
 
     /// Process an event log from TimeseriesResult
-    fn process_event_log(
-        &self,
-        ts_result: crate::TimeseriesResult,
-        test_result: &mut TestResult
-    ) {
+    fn collect_event_log(&mut self, ts_result: crate::TimeseriesResult) {
         let metadata = TestMetadata {
             contract_name: ts_result.metadata.as_ref().map(|m| m.contract_name.clone()),
             block_number: ts_result.metadata.as_ref().map(|m| m.block_number),
@@ -195,16 +245,12 @@ impl TestProcessorServer {
             ("unknown_event".to_string(), HashMap::new())
         };
 
-        test_result.events.push(EventResult {
+        self.events.push(EventResult {
             name: event_name,
             attributes,
             metadata,
         });
     }
-
-}
-
-impl TestProcessorServer {
 }
 
 impl Default for TestProcessorServer {
@@ -229,7 +275,7 @@ impl TestProcessorServer {
             db: Arc::new(MemoryDatabase::new()),
             environment,
             plugin_manager,
-            config: None,
+            config: Mutex::new(None),
         }
     }
     
@@ -238,16 +284,101 @@ impl TestProcessorServer {
         EthTestFacet::new(self)
     }
 
+    /// Create an Aptos testing facet by consuming this server
+    pub fn aptos(self) -> AptosTestFacet {
+        AptosTestFacet::new(self)
+    }
+
+    /// Create a Bitcoin testing facet by consuming this server
+    pub fn btc(self) -> BtcTestFacet {
+        BtcTestFacet::new(self)
+    }
+
+    /// Create a Cosmos testing facet by consuming this server
+    pub fn cosmos(self) -> CosmosTestFacet {
+        CosmosTestFacet::new(self)
+    }
+
+    /// Create a Fuel testing facet by consuming this server
+    pub fn fuel(self) -> FuelTestFacet {
+        FuelTestFacet::new(self)
+    }
+
+    /// Create a Solana testing facet by consuming this server
+    pub fn solana(self) -> SolanaTestFacet {
+        SolanaTestFacet::new(self)
+    }
+
+    /// Create a Starknet testing facet by consuming this server
+    pub fn starknet(self) -> StarknetTestFacet {
+        StarknetTestFacet::new(self)
+    }
+
+    /// Create a Sui testing facet by consuming this server
+    pub fn sui(self) -> SuiTestFacet {
+        SuiTestFacet::new(self)
+    }
+
     pub async fn start(&mut self) -> anyhow::Result<()> {
-        self.config = Some(self.get_config().await);
+        let config = self.get_config().await;
+        *self.config.lock().unwrap() = Some(config);
         Ok(())
     }
 
     /// Get processor configuration for debugging
     pub async fn get_config(&self) -> ConfigureHandlersResponse {
-        if self.config.is_some() {
-            return self.config.as_ref().unwrap().clone();
+        if let Some(config) = self.config.lock().unwrap().as_ref() {
+            return config.clone();
         }
+        self.configure().await
+    }
+
+    /// Declare a counter; recording undeclared metrics then fails
+    pub fn declare_counter(&self, name: &str, options: MetricOptions) -> Result<(), MetricError> {
+        self.plugin_manager.metric_registry().declare_counter(name, options)
+    }
+
+    /// Declare a gauge; recording undeclared metrics then fails
+    pub fn declare_gauge(&self, name: &str, options: MetricOptions) -> Result<(), MetricError> {
+        self.plugin_manager.metric_registry().declare_gauge(name, options)
+    }
+
+    /// Metric configs reported in get_config
+    pub fn metric_configs(&self) -> Vec<MetricConfig> {
+        self.plugin_manager.metric_registry().metric_configs()
+    }
+
+    /// Declare the schema of a typed event
+    pub fn declare_event<T: SentioEvent>(&self) {
+        self.plugin_manager.event_log_registry().declare::<T>();
+    }
+
+    /// Event log configs reported in get_config
+    pub fn event_log_configs(&self) -> Vec<EventLogConfig> {
+        self.plugin_manager.event_log_registry().event_log_configs()
+    }
+
+    /// Declare an export; emitting to an undeclared export then fails
+    pub fn declare_export(&self, name: &str, channel: &str) {
+        self.plugin_manager.export_registry().declare(name, channel);
+    }
+
+    /// Export configs reported in get_config
+    pub fn export_configs(&self) -> Vec<ExportConfig> {
+        self.plugin_manager.export_registry().configs()
+    }
+
+    /// Declare an event tracker; tracking undeclared events then fails
+    pub fn declare_event_tracker(&self, name: &str, options: EventTrackerOptions) {
+        self.plugin_manager.event_tracker_registry().declare(name, options);
+    }
+
+    /// Event tracking configs reported in get_config
+    pub fn event_tracking_configs(&self) -> Vec<EventTrackingConfig> {
+        self.plugin_manager.event_tracker_registry().configs()
+    }
+
+    async fn configure(&self) -> ConfigureHandlersResponse {
         // Get the configuration from all registered plugins
         let mut config_response = ConfigureHandlersResponse {
             contract_configs: vec![],
@@ -255,7 +386,7 @@ impl TestProcessorServer {
         };
         
         // Get configuration from plugin manager
-        self.plugin_manager.configure_all_plugins(&mut config_response);
+        self.plugin_manager.configure_all_plugins(&mut config_response).await;
    
        config_response
     }
@@ -271,9 +402,11 @@ impl crate::BindableServer for TestProcessorServer {
         let plugin_manager_arc = self.plugin_manager.clone();
         
         futures::executor::block_on(async move {
-            plugin_manager_arc.with_plugin_mut::<P, _, _>(|plugin| {
-                plugin.register_processor(processor);
-            });
+            plugin_manager_arc
+                .with_plugin_mut::<P, _, _>(|plugin| {
+                    plugin.register_processor(processor);
+                })
+                .expect("Failed to register processor");
         });
     }
 }